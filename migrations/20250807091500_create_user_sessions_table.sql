@@ -0,0 +1,16 @@
+-- migrations/YYYY..._create_user_sessions_table.sql
+
+-- Metadane aktywnych sesji (urządzenie, IP, ostatnia aktywność).
+-- Same dane sesji trzyma tower_sessions, tu mamy tylko to, co pokazujemy w "Bezpieczeństwo".
+-- `id` to publiczny identyfikator wiersza - prawdziwego ID sesji nigdy nie wysyłamy do przeglądarki.
+CREATE TABLE user_sessions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    session_id TEXT UNIQUE NOT NULL,
+    erika_id UUID NOT NULL REFERENCES erikas(id) ON DELETE CASCADE,
+    user_agent TEXT,
+    ip_address VARCHAR(64),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_seen_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_user_sessions_erika_id ON user_sessions(erika_id);
@@ -0,0 +1,7 @@
+-- migrations/YYYY..._case_insensitive_unique_erikas.sql
+
+-- Logowanie szuka po LOWER(username), więc "Ala" i "ala" muszą być tym samym kontem.
+-- Zwykłe UNIQUE rozróżnia wielkość liter - dokładamy unikalne indeksy na LOWER().
+-- Jeśli w bazie są już kolidujące konta, migracja się nie powiedzie i trzeba je najpierw scalić.
+CREATE UNIQUE INDEX erikas_username_lower_key ON erikas (LOWER(username));
+CREATE UNIQUE INDEX erikas_email_lower_key ON erikas (LOWER(email));
@@ -0,0 +1,30 @@
+-- migrations/YYYY..._create_audit_events_table.sql
+
+-- Trwały dziennik działań administracyjnych: kto, co, komu, z jakiego IP i kiedy.
+-- `actor_id` i `target_id` celowo bez kluczy obcych - wpis ma przetrwać usunięcie konta.
+CREATE TABLE audit_events (
+    id BIGSERIAL PRIMARY KEY,
+    actor_id UUID,
+    actor_username VARCHAR(50) NOT NULL,
+    action VARCHAR(64) NOT NULL,
+    target_type VARCHAR(32) NOT NULL,
+    target_id UUID,
+    changes JSONB NOT NULL DEFAULT '{}'::jsonb,
+    ip_address VARCHAR(64),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_audit_events_created_at ON audit_events(created_at DESC);
+CREATE INDEX idx_audit_events_target ON audit_events(target_type, target_id);
+CREATE INDEX idx_audit_events_actor_id ON audit_events(actor_id);
+
+-- Dziennik jest tylko do dopisywania: blokujemy UPDATE i DELETE na poziomie bazy.
+CREATE FUNCTION audit_events_append_only() RETURNS trigger AS $$
+BEGIN
+    RAISE EXCEPTION 'audit_events jest tylko do dopisywania';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER audit_events_no_update_delete
+    BEFORE UPDATE OR DELETE ON audit_events
+    FOR EACH ROW EXECUTE FUNCTION audit_events_append_only();
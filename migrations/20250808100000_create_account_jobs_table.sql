@@ -0,0 +1,21 @@
+-- migrations/YYYY..._create_account_jobs_table.sql
+
+-- Zadania w tle dla wniosków RODO: eksport danych i usunięcie konta.
+-- Po usunięciu konta `erika_id` staje się NULL, a wpis zostaje jako ślad wykonania wniosku.
+CREATE TYPE account_job_kind AS ENUM ('Export', 'Deletion');
+CREATE TYPE account_job_status AS ENUM ('Pending', 'Running', 'Done', 'Failed');
+
+CREATE TABLE account_jobs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    erika_id UUID REFERENCES erikas(id) ON DELETE SET NULL,
+    kind account_job_kind NOT NULL,
+    status account_job_status NOT NULL DEFAULT 'Pending',
+    -- Ścieżka do gotowego archiwum ZIP (tylko eksport), poza katalogiem `uploads`
+    file_path VARCHAR(255),
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    finished_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_account_jobs_erika_id ON account_jobs(erika_id);
+CREATE INDEX idx_account_jobs_pending ON account_jobs(created_at) WHERE status = 'Pending';
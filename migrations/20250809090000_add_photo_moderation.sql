@@ -0,0 +1,16 @@
+-- migrations/YYYY..._add_photo_moderation.sql
+
+-- Każde nowe zdjęcie czeka na akceptację moderatora, zanim pojawi się publicznie
+CREATE TYPE photo_status AS ENUM ('Pending', 'Approved', 'Rejected');
+
+ALTER TABLE photos
+    ADD COLUMN status photo_status NOT NULL DEFAULT 'Pending',
+    ADD COLUMN rejection_reason TEXT,
+    ADD COLUMN moderated_by UUID REFERENCES erikas(id) ON DELETE SET NULL,
+    ADD COLUMN moderated_at TIMESTAMPTZ;
+
+-- Zdjęcia sprzed wprowadzenia moderacji były już publiczne
+UPDATE photos SET status = 'Approved', moderated_at = NOW();
+
+-- Kolejka moderacji czyta tylko oczekujące zdjęcia, od najstarszych
+CREATE INDEX photos_pending_idx ON photos (created_at) WHERE status = 'Pending';
@@ -0,0 +1,14 @@
+-- migrations/YYYY..._account_status_lifecycle.sql
+
+-- Zamiast jednokierunkowej flagi `is_approved` konto ma status z pełnym cyklem życia.
+-- Powód ostatniej zmiany widzi modelka w swoim panelu, historia zmian jest w audit_events.
+CREATE TYPE account_status AS ENUM ('Pending', 'Approved', 'Rejected', 'Suspended', 'Banned');
+
+ALTER TABLE erikas
+    ADD COLUMN status account_status NOT NULL DEFAULT 'Pending',
+    ADD COLUMN status_reason TEXT,
+    ADD COLUMN status_changed_at TIMESTAMPTZ;
+
+UPDATE erikas SET status = 'Approved' WHERE is_approved;
+
+ALTER TABLE erikas DROP COLUMN is_approved;
@@ -0,0 +1,26 @@
+-- migrations/YYYY..._create_identity_verifications.sql
+
+-- Weryfikacja tożsamości i pełnoletności modelek (KYC).
+-- Pliki leżą w katalogu prywatnym, tu trzymamy tylko ścieżki na dysku.
+CREATE TYPE verification_status AS ENUM ('Pending', 'Verified', 'Rejected');
+
+CREATE TABLE identity_verifications (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    erika_id UUID NOT NULL REFERENCES erikas(id) ON DELETE CASCADE,
+    status verification_status NOT NULL DEFAULT 'Pending',
+    date_of_birth DATE NOT NULL,
+    document_front_path TEXT NOT NULL,
+    document_back_path TEXT,
+    selfie_path TEXT NOT NULL,
+    rejection_reason TEXT,
+    reviewed_by UUID REFERENCES erikas(id) ON DELETE SET NULL,
+    reviewed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Najwyżej jedno zgłoszenie w toku lub zaakceptowane na konto
+CREATE UNIQUE INDEX identity_verifications_open_key
+    ON identity_verifications (erika_id) WHERE status IN ('Pending', 'Verified');
+
+CREATE INDEX identity_verifications_pending_idx
+    ON identity_verifications (created_at) WHERE status = 'Pending';
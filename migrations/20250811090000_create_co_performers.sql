@@ -0,0 +1,28 @@
+-- migrations/YYYY..._create_co_performers.sql
+
+-- Osoby współwystępujące w galeriach (model release). Dla każdej trzymamy
+-- podpisaną zgodę i dokument potwierdzający wiek - pliki leżą w katalogu
+-- prywatnym, tu są tylko ścieżki na dysku.
+CREATE TABLE co_performers (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    gallery_id UUID NOT NULL REFERENCES galleries(id) ON DELETE CASCADE,
+    legal_name TEXT NOT NULL,
+    date_of_birth DATE NOT NULL,
+    id_document_path TEXT NOT NULL,
+    release_path TEXT NOT NULL,
+    status verification_status NOT NULL DEFAULT 'Pending',
+    rejection_reason TEXT,
+    reviewed_by UUID REFERENCES erikas(id) ON DELETE SET NULL,
+    reviewed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX co_performers_gallery_id_idx ON co_performers (gallery_id);
+
+CREATE INDEX co_performers_pending_idx
+    ON co_performers (created_at) WHERE status = 'Pending';
+
+-- Galeria jest publiczna dopiero po opublikowaniu. Istniejące galerie
+-- były dotąd widoczne od razu, więc traktujemy je jako opublikowane.
+ALTER TABLE galleries ADD COLUMN published_at TIMESTAMPTZ;
+UPDATE galleries SET published_at = created_at;
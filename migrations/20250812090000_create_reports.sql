@@ -0,0 +1,47 @@
+-- migrations/YYYY..._create_reports.sql
+
+-- Zgłoszenia od odwiedzających (notice and action, DSA art. 16) i decyzje
+-- moderacyjne z uzasadnieniem dla modelki (DSA art. 17).
+CREATE TYPE report_target AS ENUM ('Profile', 'Gallery');
+CREATE TYPE report_reason AS ENUM (
+    'IllegalContent', 'Minor', 'NonConsensual', 'Copyright', 'Impersonation', 'Spam', 'Other'
+);
+CREATE TYPE report_status AS ENUM ('Open', 'Actioned', 'Dismissed');
+CREATE TYPE moderation_action AS ENUM ('NoAction', 'HideContent', 'SuspendCreator');
+
+-- Jedna decyzja zamyka wszystkie otwarte zgłoszenia dotyczące tej samej treści
+CREATE TABLE moderation_decisions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    erika_id UUID NOT NULL REFERENCES erikas(id) ON DELETE CASCADE,
+    target_kind report_target NOT NULL,
+    target_id UUID NOT NULL,
+    action moderation_action NOT NULL,
+    ground report_reason NOT NULL,
+    statement TEXT NOT NULL,
+    decided_by UUID REFERENCES erikas(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX moderation_decisions_erika_id_idx ON moderation_decisions (erika_id, created_at);
+
+-- `erika_id` to konto, którego dotyczy zgłoszenie (właścicielka profilu lub galerii)
+CREATE TABLE reports (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    target_kind report_target NOT NULL,
+    target_id UUID NOT NULL,
+    erika_id UUID NOT NULL REFERENCES erikas(id) ON DELETE CASCADE,
+    reason report_reason NOT NULL,
+    details TEXT NOT NULL,
+    reporter_id UUID REFERENCES erikas(id) ON DELETE SET NULL,
+    reporter_email TEXT,
+    ip_address TEXT,
+    status report_status NOT NULL DEFAULT 'Open',
+    decision_id UUID REFERENCES moderation_decisions(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX reports_open_idx ON reports (created_at) WHERE status = 'Open';
+CREATE INDEX reports_target_idx ON reports (target_kind, target_id);
+
+-- Galeria ukryta decyzją moderacji nie może zostać ponownie opublikowana przez właścicielkę
+ALTER TABLE galleries ADD COLUMN blocked_at TIMESTAMPTZ;
@@ -0,0 +1,40 @@
+-- migrations/YYYY..._categories_table.sql
+
+-- Kategorie galerii jako dane zarządzane przez admina zamiast typu ENUM.
+-- Zmiana listy nie wymaga już migracji ani ponownej kompilacji.
+CREATE TABLE categories (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    slug TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL UNIQUE,
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Wartości typu `gallery_category` (mogą występować w istniejących galeriach)
+-- oraz kategorie, które aplikacja oferowała w formularzu, choć baza ich nie znała.
+INSERT INTO categories (slug, name, sort_order) VALUES
+    ('piersi', 'Piersi', 10),
+    ('tylek', 'Tyłek', 20),
+    ('stopy', 'Stopy', 30),
+    ('nogi', 'Nogi', 40),
+    ('twarz', 'Twarz', 50),
+    ('bielizna', 'Bielizna', 60),
+    ('cale-cialo', 'Całe Ciało', 70),
+    ('artystyczne', 'Artystyczne', 80),
+    ('cipka', 'Cipka', 90),
+    ('otwieram-cipke-dla-ciebie', 'Otwieram Cipkę dla Ciebie', 100),
+    ('analne', 'Analne', 110),
+    ('zabawy-wibratorem', 'Zabawy wibratorem', 120),
+    ('orgazm', 'Orgazm', 130);
+
+ALTER TABLE galleries ADD COLUMN category_id UUID REFERENCES categories(id);
+
+UPDATE galleries g SET category_id = c.id
+FROM categories c WHERE c.name = g.name::TEXT;
+
+ALTER TABLE galleries ALTER COLUMN category_id SET NOT NULL;
+ALTER TABLE galleries DROP COLUMN name;
+DROP TYPE gallery_category;
+
+CREATE INDEX galleries_category_id_idx ON galleries (category_id);
@@ -0,0 +1,14 @@
+-- migrations/YYYY..._create_password_resets.sql
+
+-- Jednorazowe linki do ustawienia nowego hasła (wydawane z CLI admina).
+-- Trzymamy tylko skrót tokenu - wyciek tabeli nie pozwala przejąć konta.
+CREATE TABLE password_resets (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    erika_id UUID NOT NULL REFERENCES erikas(id) ON DELETE CASCADE,
+    token_hash BYTEA NOT NULL UNIQUE,
+    expires_at TIMESTAMPTZ NOT NULL,
+    used_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX password_resets_erika_id_idx ON password_resets (erika_id);
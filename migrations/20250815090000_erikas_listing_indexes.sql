@@ -0,0 +1,5 @@
+-- migrations/YYYY..._erikas_listing_indexes.sql
+
+-- Sortowanie i stronicowanie listy kont w panelu admina po dacie rejestracji i statusie.
+CREATE INDEX erikas_created_at_id_idx ON erikas (created_at, id);
+CREATE INDEX erikas_status_username_idx ON erikas (status, username, id);
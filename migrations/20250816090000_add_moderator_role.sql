@@ -0,0 +1,5 @@
+-- migrations/YYYY..._add_moderator_role.sql
+
+-- Moderator: konto zespołu z wybranymi uprawnieniami, bez pełnych praw admina.
+-- Macierz uprawnień ról jest w kodzie (models/permission.rs).
+ALTER TYPE user_role ADD VALUE IF NOT EXISTS 'Moderator';
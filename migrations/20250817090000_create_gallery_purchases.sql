@@ -0,0 +1,17 @@
+-- migrations/YYYY..._create_gallery_purchases.sql
+
+-- Księga sprzedaży galerii. Wiersz zapisuje integracja płatności po potwierdzeniu
+-- transakcji; prowizja serwisu jest zapamiętywana w chwili sprzedaży.
+-- Po usunięciu galerii lub konta wpis zostaje (rozliczenia), traci tylko powiązanie.
+CREATE TABLE gallery_purchases (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    gallery_id UUID REFERENCES galleries(id) ON DELETE SET NULL,
+    erika_id UUID REFERENCES erikas(id) ON DELETE SET NULL,
+    buyer_id UUID REFERENCES erikas(id) ON DELETE SET NULL,
+    amount_pln NUMERIC(10, 2) NOT NULL CHECK (amount_pln >= 0),
+    commission_pln NUMERIC(10, 2) NOT NULL CHECK (commission_pln >= 0 AND commission_pln <= amount_pln),
+    paid_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX gallery_purchases_paid_at_idx ON gallery_purchases (paid_at);
+CREATE INDEX gallery_purchases_gallery_id_idx ON gallery_purchases (gallery_id, paid_at);
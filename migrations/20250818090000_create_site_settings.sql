@@ -0,0 +1,9 @@
+-- migrations/YYYY..._create_site_settings.sql
+
+-- Ustawienia serwisu edytowane w panelu admina (klucz -> wartość tekstowa).
+-- Brakujący klucz oznacza wartość domyślną z models/site_settings.rs.
+CREATE TABLE site_settings (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,31 @@
+-- migrations/YYYY..._create_announcements_and_notices.sql
+
+-- Ogłoszenia serwisu: pasek na górze strony dla wybranej grupy odbiorców
+-- w zadanym przedziale czasu. Zamknięcie paska zapamiętuje przeglądarka.
+CREATE TYPE announcement_audience AS ENUM ('Creators', 'Fans', 'Everyone');
+
+CREATE TABLE announcements (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    message TEXT NOT NULL,
+    audience announcement_audience NOT NULL,
+    starts_at TIMESTAMPTZ NOT NULL,
+    ends_at TIMESTAMPTZ,
+    created_by UUID REFERENCES erikas(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (ends_at IS NULL OR ends_at > starts_at)
+);
+
+CREATE INDEX announcements_active_idx ON announcements (starts_at, ends_at);
+
+-- Wiadomości od administracji do jednej modelki; `read_at` to potwierdzenie odczytu
+CREATE TABLE admin_notices (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    erika_id UUID NOT NULL REFERENCES erikas(id) ON DELETE CASCADE,
+    sender_id UUID REFERENCES erikas(id) ON DELETE SET NULL,
+    subject TEXT NOT NULL,
+    body TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    read_at TIMESTAMPTZ
+);
+
+CREATE INDEX admin_notices_erika_id_idx ON admin_notices (erika_id, created_at);
@@ -0,0 +1,12 @@
+-- migrations/YYYY..._add_presence_to_erikas.sql
+
+-- Obecność liczona z sygnałów panelu kamerki zamiast ręcznego przełącznika.
+-- `is_online` zostaje jako wynik: świeży sygnał i brak "pokazuj mnie jako offline".
+ALTER TABLE erikas
+    ADD COLUMN last_seen_at TIMESTAMPTZ,
+    ADD COLUMN appear_offline BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Dotychczasowy ręczny status nie ma pokrycia w sygnałach
+UPDATE erikas SET is_online = FALSE;
+
+CREATE INDEX erikas_online_idx ON erikas (last_seen_at) WHERE is_online;
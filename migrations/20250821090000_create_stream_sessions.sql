@@ -0,0 +1,24 @@
+-- migrations/YYYY..._create_stream_sessions.sql
+
+-- Transmisje modelek: zaplanowane ("na żywo w piątek 21:00") i historia
+-- przeprowadzonych. Transmisja bez `scheduled_at` to start bez zapowiedzi.
+CREATE TABLE stream_sessions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    erika_id UUID NOT NULL REFERENCES erikas(id) ON DELETE CASCADE,
+    title TEXT NOT NULL,
+    scheduled_at TIMESTAMPTZ,
+    planned_minutes INT NOT NULL DEFAULT 60 CHECK (planned_minutes > 0),
+    started_at TIMESTAMPTZ,
+    ended_at TIMESTAMPTZ,
+    peak_viewers INT NOT NULL DEFAULT 0 CHECK (peak_viewers >= 0),
+    earnings_pln NUMERIC(10, 2) NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (scheduled_at IS NOT NULL OR started_at IS NOT NULL),
+    CHECK (ended_at IS NULL OR (started_at IS NOT NULL AND ended_at >= started_at))
+);
+
+CREATE INDEX stream_sessions_erika_id_idx ON stream_sessions (erika_id, scheduled_at);
+
+-- Modelka może mieć naraz tylko jedną trwającą transmisję
+CREATE UNIQUE INDEX stream_sessions_one_live_idx ON stream_sessions (erika_id)
+    WHERE started_at IS NOT NULL AND ended_at IS NULL;
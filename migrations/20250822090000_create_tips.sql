@@ -0,0 +1,47 @@
+-- migrations/YYYY..._create_tips.sql
+
+-- Przedpłacone saldo widza, z którego płaci napiwki. Doładowuje je integracja
+-- płatności po potwierdzeniu wpłaty (albo operator przez `make_admin credit`).
+CREATE TABLE wallets (
+    erika_id UUID PRIMARY KEY REFERENCES erikas(id) ON DELETE CASCADE,
+    balance_pln NUMERIC(10, 2) NOT NULL DEFAULT 0 CHECK (balance_pln >= 0),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Menu napiwków modelki: nazwane akcje z ceną ("taniec - 20 PLN")
+CREATE TABLE tip_menu_items (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    erika_id UUID NOT NULL REFERENCES erikas(id) ON DELETE CASCADE,
+    label TEXT NOT NULL,
+    price_pln NUMERIC(10, 2) NOT NULL CHECK (price_pln > 0),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX tip_menu_items_erika_id_idx ON tip_menu_items (erika_id, price_pln);
+
+-- Cel zbiórki. Postęp to suma napiwków od `created_at`, więc nowy cel
+-- (zastępujący poprzedni) liczy się od zera.
+CREATE TABLE tip_goals (
+    erika_id UUID PRIMARY KEY REFERENCES erikas(id) ON DELETE CASCADE,
+    title TEXT NOT NULL,
+    target_pln NUMERIC(10, 2) NOT NULL CHECK (target_pln > 0),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Księga napiwków. Jak w `gallery_purchases`: prowizja serwisu jest zapamiętywana
+-- w chwili wpłaty, a po usunięciu konta wpis zostaje (rozliczenia).
+-- `label` to kopia nazwy pozycji z menu; NULL oznacza dowolną kwotę.
+CREATE TABLE tips (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    erika_id UUID REFERENCES erikas(id) ON DELETE SET NULL,
+    tipper_id UUID REFERENCES erikas(id) ON DELETE SET NULL,
+    stream_session_id UUID REFERENCES stream_sessions(id) ON DELETE SET NULL,
+    label TEXT,
+    message TEXT,
+    amount_pln NUMERIC(10, 2) NOT NULL CHECK (amount_pln > 0),
+    commission_pln NUMERIC(10, 2) NOT NULL CHECK (commission_pln >= 0 AND commission_pln <= amount_pln),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX tips_erika_id_idx ON tips (erika_id, created_at);
+CREATE INDEX tips_stream_session_id_idx ON tips (stream_session_id, created_at);
@@ -0,0 +1,44 @@
+-- migrations/YYYY..._create_private_shows.sql
+
+-- Pokazy prywatne jeden na jeden, płatne za minutę z salda widza.
+-- Zaakceptowany pokaz to transmisja (`stream_sessions`) oznaczona jako prywatna.
+ALTER TABLE stream_sessions ADD COLUMN is_private BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Stawka modelki za minutę. Brak wiersza oznacza, że nie przyjmuje próśb.
+CREATE TABLE private_show_rates (
+    erika_id UUID PRIMARY KEY REFERENCES erikas(id) ON DELETE CASCADE,
+    rate_pln NUMERIC(10, 2) NOT NULL CHECK (rate_pln > 0),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TYPE private_show_status AS ENUM ('Requested', 'Declined', 'Cancelled', 'Active', 'Ended');
+CREATE TYPE private_show_end_reason AS ENUM ('Creator', 'Viewer', 'Funds', 'ViewerGone', 'CreatorOffline');
+
+-- Księga pokazów. Minuta jest płatna z góry, na jej początku; prowizję serwisu
+-- liczymy od całej kwoty przy zakończeniu. Po usunięciu konta wpis zostaje.
+CREATE TABLE private_shows (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    erika_id UUID REFERENCES erikas(id) ON DELETE SET NULL,
+    viewer_id UUID REFERENCES erikas(id) ON DELETE SET NULL,
+    stream_session_id UUID REFERENCES stream_sessions(id) ON DELETE SET NULL,
+    status private_show_status NOT NULL DEFAULT 'Requested',
+    rate_pln NUMERIC(10, 2) NOT NULL CHECK (rate_pln > 0),
+    minutes_billed INT NOT NULL DEFAULT 0,
+    billed_pln NUMERIC(10, 2) NOT NULL DEFAULT 0,
+    commission_pln NUMERIC(10, 2) NOT NULL DEFAULT 0,
+    end_reason private_show_end_reason,
+    viewer_seen_at TIMESTAMPTZ,
+    requested_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    started_at TIMESTAMPTZ,
+    ended_at TIMESTAMPTZ,
+    CHECK (commission_pln >= 0 AND commission_pln <= billed_pln)
+);
+
+CREATE INDEX private_shows_erika_id_idx ON private_shows (erika_id, requested_at);
+
+-- Widz może mieć naraz tylko jedną otwartą prośbę albo trwający pokaz
+CREATE UNIQUE INDEX private_shows_one_open_per_viewer_idx ON private_shows (viewer_id)
+    WHERE status IN ('Requested', 'Active');
+
+-- Pętla naliczania przegląda tylko trwające pokazy
+CREATE INDEX private_shows_active_idx ON private_shows (started_at) WHERE status = 'Active';
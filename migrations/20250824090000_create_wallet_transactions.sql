@@ -0,0 +1,32 @@
+-- migrations/YYYY..._create_wallet_transactions.sql
+
+-- Dziennik salda: każda zmiana `wallets.balance_pln` ma tu wpis zapisany w tej
+-- samej transakcji. Kwota jest ze znakiem (+ wpłata, - obciążenie), a
+-- `balance_after_pln` to saldo po operacji. Po usunięciu konta wpisy zostają.
+CREATE TYPE wallet_transaction_kind AS ENUM ('Credit', 'Tip', 'PrivateShow');
+
+CREATE TABLE wallet_transactions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    erika_id UUID REFERENCES erikas(id) ON DELETE SET NULL,
+    kind wallet_transaction_kind NOT NULL,
+    amount_pln NUMERIC(10, 2) NOT NULL CHECK (amount_pln <> 0),
+    balance_after_pln NUMERIC(10, 2) NOT NULL CHECK (balance_after_pln >= 0),
+    tip_id UUID REFERENCES tips(id) ON DELETE SET NULL,
+    private_show_id UUID REFERENCES private_shows(id) ON DELETE SET NULL,
+    note TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX wallet_transactions_erika_id_idx ON wallet_transactions (erika_id, created_at);
+
+-- Dotychczasowe salda nie mają historii - zaczynamy ją od wpisu otwarcia
+INSERT INTO wallet_transactions (erika_id, kind, amount_pln, balance_after_pln, note)
+SELECT erika_id, 'Credit', balance_pln, balance_pln, 'Saldo otwarcia dziennika'
+FROM wallets
+WHERE balance_pln > 0;
+
+-- Saldo to pieniądze widza: konta z portfelem nie da się usunąć kaskadą.
+-- Pusty portfel usuwa jawnie proces usuwania konta, dodatni blokuje wniosek.
+ALTER TABLE wallets DROP CONSTRAINT wallets_erika_id_fkey;
+ALTER TABLE wallets ADD CONSTRAINT wallets_erika_id_fkey
+    FOREIGN KEY (erika_id) REFERENCES erikas(id) ON DELETE RESTRICT;
@@ -0,0 +1,7 @@
+-- migrations/YYYY..._add_photo_reports.sql
+
+-- Zgłaszać można też pojedyncze zdjęcie. Ukrycie zdjęcia decyzją moderacji
+-- ustawia `hidden_at` - plik przestaje być wydawany publicznie.
+ALTER TYPE report_target ADD VALUE 'Photo';
+
+ALTER TABLE photos ADD COLUMN hidden_at TIMESTAMPTZ;
@@ -0,0 +1,27 @@
+-- migrations/YYYY..._restrict_deleting_financial_records.sql
+
+-- Rekordy finansowe zostają po usunięciu konta (obowiązek archiwizacji), ale
+-- bez danych osobowych. Odpina je jawnie `Erika::delete`, razem z treścią
+-- wiadomości przy napiwkach - baza nie robi już tego po cichu przez
+-- ON DELETE SET NULL, więc pominięta tabela zablokuje usunięcie konta.
+ALTER TABLE gallery_purchases
+    DROP CONSTRAINT gallery_purchases_erika_id_fkey,
+    DROP CONSTRAINT gallery_purchases_buyer_id_fkey,
+    ADD CONSTRAINT gallery_purchases_erika_id_fkey FOREIGN KEY (erika_id) REFERENCES erikas(id),
+    ADD CONSTRAINT gallery_purchases_buyer_id_fkey FOREIGN KEY (buyer_id) REFERENCES erikas(id);
+
+ALTER TABLE tips
+    DROP CONSTRAINT tips_erika_id_fkey,
+    DROP CONSTRAINT tips_tipper_id_fkey,
+    ADD CONSTRAINT tips_erika_id_fkey FOREIGN KEY (erika_id) REFERENCES erikas(id),
+    ADD CONSTRAINT tips_tipper_id_fkey FOREIGN KEY (tipper_id) REFERENCES erikas(id);
+
+ALTER TABLE private_shows
+    DROP CONSTRAINT private_shows_erika_id_fkey,
+    DROP CONSTRAINT private_shows_viewer_id_fkey,
+    ADD CONSTRAINT private_shows_erika_id_fkey FOREIGN KEY (erika_id) REFERENCES erikas(id),
+    ADD CONSTRAINT private_shows_viewer_id_fkey FOREIGN KEY (viewer_id) REFERENCES erikas(id);
+
+ALTER TABLE wallet_transactions
+    DROP CONSTRAINT wallet_transactions_erika_id_fkey,
+    ADD CONSTRAINT wallet_transactions_erika_id_fkey FOREIGN KEY (erika_id) REFERENCES erikas(id);
@@ -0,0 +1,16 @@
+-- migrations/YYYY..._retain_identity_verifications.sql
+
+-- Dokumenty weryfikacji tożsamości przechowujemy po usunięciu konta przez
+-- okres retencji (dowód pełnoletności). `Erika::delete` odpina je od konta
+-- i oznacza jako zastrzeżone - panel admina ich nie pokazuje ani nie wydaje
+-- plików. Po `retain_until` wiersz i pliki usuwa zadanie `retention`.
+ALTER TABLE identity_verifications
+    ADD COLUMN restricted_at TIMESTAMPTZ,
+    ADD COLUMN retain_until TIMESTAMPTZ,
+    ALTER COLUMN erika_id DROP NOT NULL,
+    DROP CONSTRAINT identity_verifications_erika_id_fkey,
+    ADD CONSTRAINT identity_verifications_erika_id_fkey FOREIGN KEY (erika_id) REFERENCES erikas(id),
+    ADD CHECK ((erika_id IS NULL) = (restricted_at IS NOT NULL));
+
+CREATE INDEX identity_verifications_retain_until_idx
+    ON identity_verifications (retain_until) WHERE retain_until IS NOT NULL;
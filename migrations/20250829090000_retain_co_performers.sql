@@ -0,0 +1,15 @@
+-- migrations/YYYY..._retain_co_performers.sql
+
+-- Zgody osób współwystępujących przechowujemy po usunięciu konta tak jak
+-- dokumenty weryfikacji: `Erika::delete` odpina je od galerii i zastrzega
+-- do `retain_until`, potem wiersz i pliki usuwa zadanie `retention`.
+ALTER TABLE co_performers
+    ADD COLUMN restricted_at TIMESTAMPTZ,
+    ADD COLUMN retain_until TIMESTAMPTZ,
+    ALTER COLUMN gallery_id DROP NOT NULL,
+    DROP CONSTRAINT co_performers_gallery_id_fkey,
+    ADD CONSTRAINT co_performers_gallery_id_fkey FOREIGN KEY (gallery_id) REFERENCES galleries(id),
+    ADD CHECK ((gallery_id IS NULL) = (restricted_at IS NOT NULL));
+
+CREATE INDEX co_performers_retain_until_idx
+    ON co_performers (retain_until) WHERE retain_until IS NOT NULL;
@@ -0,0 +1,22 @@
+-- migrations/YYYY..._allow_audit_redaction.sql
+
+-- Dziennik zostaje tylko do dopisywania, z jednym udokumentowanym wyjątkiem:
+-- przy usunięciu konta wartości jego danych osobowych we wpisach o edycji profilu
+-- zastępujemy znacznikiem (`AuditEvent::redact_profile_values`). Zmienić można
+-- wtedy wyłącznie kolumnę `changes` i tylko w transakcji, która ustawiła
+-- `audit.redaction`. Kto, co, komu i kiedy zmienił - zostaje.
+CREATE OR REPLACE FUNCTION audit_events_append_only() RETURNS trigger AS $$
+BEGIN
+    IF TG_OP = 'UPDATE'
+       AND current_setting('audit.redaction', true) = 'on'
+       AND (NEW.id, NEW.actor_id, NEW.actor_username, NEW.action, NEW.target_type,
+            NEW.target_id, NEW.ip_address, NEW.created_at)
+           IS NOT DISTINCT FROM
+           (OLD.id, OLD.actor_id, OLD.actor_username, OLD.action, OLD.target_type,
+            OLD.target_id, OLD.ip_address, OLD.created_at)
+    THEN
+        RETURN NEW;
+    END IF;
+    RAISE EXCEPTION 'audit_events jest tylko do dopisywania';
+END;
+$$ LANGUAGE plpgsql;
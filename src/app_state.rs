@@ -1,6 +1,55 @@
+use crate::events::CreatorEvent;
+use erika_system::models::site_settings::SiteSettings;
 use sqlx::PgPool;
+use std::sync::{Arc, RwLock};
+use tokio::sync::broadcast;
+use tower_sessions_sqlx_store::PostgresStore;
+use tracing::debug;
 
 #[derive(Clone)]
 pub struct AppState {
     pub db: PgPool,
+    // Magazyn sesji - potrzebny, żeby móc zdalnie unieważniać sesje
+    pub sessions: PostgresStore,
+    // Ustawienia serwisu wczytane przy starcie i podmieniane po zapisie w panelu.
+    // Przy kilku instancjach aplikacji pozostałe zobaczą zmianę dopiero po restarcie.
+    pub settings: Arc<RwLock<SiteSettings>>,
+    // Zmiany na liście modelek dla stron otwartych przez SSE (tylko ta instancja)
+    pub creator_events: broadcast::Sender<CreatorEvent>,
+}
+
+impl AppState {
+    /// Kopia bieżących ustawień.
+    pub fn settings(&self) -> SiteSettings {
+        self.settings
+            .read()
+            .expect("Blokada ustawień zatruta")
+            .clone()
+    }
+
+    pub fn replace_settings(&self, settings: SiteSettings) {
+        *self.settings.write().expect("Blokada ustawień zatruta") = settings;
+    }
+
+    /// Publikuje zmianę listy modelek. Brak słuchaczy to nie błąd.
+    pub fn publish(&self, event: CreatorEvent) {
+        match event {
+            CreatorEvent::Presence {
+                erika_id,
+                is_online,
+            } => {
+                debug!("Obecność {}: online={}", erika_id, is_online)
+            }
+            CreatorEvent::Listing { erika_id, listed } => {
+                debug!("Lista modelek {}: widoczna={}", erika_id, listed)
+            }
+            CreatorEvent::Tip { erika_id, tip_id } => {
+                debug!("Napiwek {} dla {}", tip_id, erika_id)
+            }
+            CreatorEvent::PrivateShow { erika_id, show_id } => {
+                debug!("Pokaz prywatny {} modelki {}", show_id, erika_id)
+            }
+        }
+        let _ = self.creator_events.send(event);
+    }
 }
@@ -0,0 +1,602 @@
+// src/auth.rs
+
+// Ekstraktory uwierzytelniania. Zamiast w każdym handlerze ręcznie czytać
+// `erika_id` z sesji, handler deklaruje, kogo potrzebuje:
+// `CurrentErika`, `CurrentStaff` albo `OptionalViewer`.
+
+use crate::{
+    app_state::AppState,
+    errors::AppError,
+    models::{
+        audit::{AuditAction, AuditEvent, NewAuditEvent},
+        erika::{AccountStatus, Erika, ErikaAuth},
+        gallery::Gallery,
+        permission::{self, Permission},
+        photo::Photo,
+        user_session::UserSession,
+    },
+};
+use axum::{
+    extract::{ConnectInfo, FromRef, FromRequestParts},
+    http::{HeaderMap, HeaderValue, header, request::Parts},
+    response::{IntoResponse, Redirect, Response},
+};
+use sqlx::PgPool;
+use std::{
+    convert::Infallible,
+    env,
+    net::{IpAddr, SocketAddr},
+    str::FromStr,
+    sync::OnceLock,
+};
+use tower_sessions::{Session, SessionStore, session::Id};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Klucz w sesji, pod którym trzymamy ID zalogowanego konta.
+pub const SESSION_KEY: &str = "erika_id";
+
+/// Klucz z "odciskiem" uprawnień (rola + status konta) z chwili wydania sesji.
+const PRIVILEGES_KEY: &str = "privileges";
+
+/// Klucz z ID konta, które admin ogląda w podglądzie ("zobacz jako").
+const IMPERSONATED_KEY: &str = "impersonated_id";
+
+/// Adresy reverse proxy (po przecinku), którym wierzymy w X-Forwarded-For.
+/// Bez tej zmiennej nagłówek jest ignorowany - każdy klient mógłby go podrobić.
+pub const TRUSTED_PROXIES_VAR: &str = "TRUSTED_PROXIES";
+
+static TRUSTED_PROXIES: OnceLock<Vec<IpAddr>> = OnceLock::new();
+
+fn trusted_proxies() -> &'static [IpAddr] {
+    TRUSTED_PROXIES.get_or_init(|| {
+        env::var(TRUSTED_PROXIES_VAR)
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|ip| !ip.is_empty())
+            .map(|ip| {
+                ip.parse().unwrap_or_else(|_| {
+                    panic!("{} zawiera niepoprawny adres '{}'", TRUSTED_PROXIES_VAR, ip)
+                })
+            })
+            .collect()
+    })
+}
+
+/// Adres klienta za zaufanym proxy: idziemy od prawej strony X-Forwarded-For
+/// (wpisy dopisane przez nasze proxy) i bierzemy pierwszy obcy adres.
+/// Lewą część nagłówka ustawia klient, więc nie można jej wierzyć.
+fn forwarded_client(header: &str, trusted: &[IpAddr]) -> Option<IpAddr> {
+    let mut last = None;
+    for hop in header.split(',').rev() {
+        let ip = hop.trim().parse::<IpAddr>().ok()?;
+        if !trusted.contains(&ip) {
+            return Some(ip);
+        }
+        last = Some(ip);
+    }
+    last
+}
+
+/// Po ilu dniach bez aktywności sesja wygasa.
+pub const SESSION_INACTIVITY_DAYS: i64 = 1;
+
+pub const ROLE_ADMIN: &str = "Admin";
+pub const ROLE_ERIKA: &str = "Erika";
+
+/// Czy konto ma panel modelki (admin też może go mieć).
+pub fn has_creator_panel(erika: &ErikaAuth) -> bool {
+    erika.role == ROLE_ERIKA || erika.role == ROLE_ADMIN
+}
+
+/// Strona startowa po zalogowaniu: moderator bez panelu modelki trafia do panelu admina.
+pub fn home_path(erika: &ErikaAuth) -> &'static str {
+    if !has_creator_panel(erika) && erika.is_staff() {
+        "/admin"
+    } else {
+        "/panel"
+    }
+}
+
+/// Zalogowana modelka (lub admin, który też może mieć swój panel).
+pub struct CurrentErika(pub ErikaAuth);
+
+/// Zalogowany członek zespołu (admin albo moderator). Konkretne uprawnienia
+/// sprawdza `middleware::require_permission` na poziomie tras.
+pub struct CurrentStaff(pub ErikaAuth);
+
+/// Dowolne zalogowane konto, niezależnie od roli (także fan).
+pub struct CurrentAccount(pub ErikaAuth);
+
+/// Dowolny odwiedzający - zalogowany lub nie.
+pub struct OptionalViewer(pub Option<ErikaAuth>);
+
+impl CurrentErika {
+    pub fn id(&self) -> Uuid {
+        self.0.id
+    }
+
+    /// Zwraca błąd, jeśli konto nie jest zaakceptowane (czeka, odrzucone lub zawieszone).
+    pub fn ensure_approved(&self) -> Result<(), AppError> {
+        if self.0.status == AccountStatus::Approved {
+            Ok(())
+        } else {
+            Err(AppError::Forbidden)
+        }
+    }
+
+    /// Zwraca galerię tylko jeśli należy do zalogowanej Eriki.
+    /// Cudza galeria daje 404, żeby nie zdradzać, że w ogóle istnieje.
+    pub async fn gallery(&self, gallery_id: Uuid, db: &PgPool) -> Result<Gallery, AppError> {
+        Gallery::find_by_id_and_erika_id(gallery_id, self.0.id, db)
+            .await?
+            .ok_or(AppError::NotFound)
+    }
+
+    /// Zwraca zdjęcie tylko jeśli leży we wskazanej galerii zalogowanej Eriki.
+    pub async fn photo(
+        &self,
+        gallery_id: Uuid,
+        photo_id: Uuid,
+        db: &PgPool,
+    ) -> Result<(Gallery, Photo), AppError> {
+        let gallery = self.gallery(gallery_id, db).await?;
+        let photo = Photo::find_by_id(photo_id, db)
+            .await?
+            .filter(|photo| photo.gallery_id == gallery.id)
+            .ok_or(AppError::NotFound)?;
+        Ok((gallery, photo))
+    }
+}
+
+impl CurrentStaff {
+    pub fn id(&self) -> Uuid {
+        self.0.id
+    }
+
+    pub fn can(&self, permission: Permission) -> bool {
+        self.0.can(permission)
+    }
+
+    /// Zapisuje działanie tej osoby w dzienniku audytu.
+    pub async fn audit(
+        &self,
+        action: AuditAction,
+        target_type: &str,
+        target_id: Option<Uuid>,
+        changes: serde_json::Value,
+        client: &ClientInfo,
+        db: &PgPool,
+    ) -> Result<(), AppError> {
+        AuditEvent::record(
+            NewAuditEvent {
+                actor_id: Some(self.0.id),
+                actor_username: &self.0.username,
+                action,
+                target_type,
+                target_id,
+                changes,
+                ip_address: client.ip.as_deref(),
+            },
+            db,
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+/// Aktywny podgląd konta: admin zalogowany w tej sesji i konto, które ogląda.
+/// Middleware `impersonation` dokłada go do żądania - od tej chwili ekstraktory
+/// zwracają oglądane konto zamiast admina.
+#[derive(Clone)]
+pub struct Impersonation {
+    pub admin: ErikaAuth,
+    pub target: ErikaAuth,
+}
+
+/// Czy to konto można oglądać w podglądzie. Konta zespołu nie, żeby podgląd
+/// nie dawał cudzych uprawnień.
+pub fn can_be_impersonated(role: &str) -> bool {
+    permission::permissions_for(role).is_empty()
+}
+
+/// Przełącza bieżącą sesję admina w podgląd wskazanego konta.
+pub async fn start_impersonation(session: &Session, target_id: Uuid) -> Result<(), AppError> {
+    session
+        .insert(IMPERSONATED_KEY, target_id)
+        .await
+        .map_err(|_| AppError::InternalServerError)
+}
+
+/// Kończy podgląd - sesja znów należy do admina.
+pub async fn end_impersonation(session: &Session) -> Result<(), AppError> {
+    session
+        .remove::<Uuid>(IMPERSONATED_KEY)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    Ok(())
+}
+
+/// Adres IP i przeglądarka, z których przyszło żądanie.
+#[derive(Clone, Default)]
+pub struct ClientInfo {
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+impl ClientInfo {
+    fn from_parts(parts: &Parts) -> Self {
+        let peer = parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip());
+
+        // Za reverse proxy prawdziwy adres jest w X-Forwarded-For - ale tylko
+        // wtedy, gdy połączenie przyszło od skonfigurowanego proxy
+        let trusted = trusted_proxies();
+        let forwarded = peer
+            .filter(|peer| trusted.contains(peer))
+            .and_then(|_| parts.headers.get("x-forwarded-for"))
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| forwarded_client(value, trusted));
+
+        let ip = forwarded.or(peer).map(|ip| ip.to_string());
+
+        let user_agent = parts
+            .headers
+            .get(header::USER_AGENT)
+            .and_then(|value| value.to_str().ok())
+            .map(|ua| ua.chars().take(512).collect());
+
+        ClientInfo { ip, user_agent }
+    }
+}
+
+impl<S> FromRequestParts<S> for ClientInfo
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(ClientInfo::from_parts(parts))
+    }
+}
+
+/// Loguje konto w bieżącej sesji. ID sesji jest zawsze wymieniane,
+/// żeby nikt nie mógł podrzucić ofierze znanego sobie ciasteczka.
+pub async fn start_session(
+    session: &Session,
+    erika: &ErikaAuth,
+    client: &ClientInfo,
+    db: &PgPool,
+) -> Result<(), AppError> {
+    session
+        .cycle_id()
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    session
+        .insert(SESSION_KEY, erika.id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    session
+        .insert(PRIVILEGES_KEY, erika.privileges())
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    // Zapisujemy od razu, żeby znać nowe ID sesji
+    session
+        .save()
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    let session_id = session.id().ok_or(AppError::InternalServerError)?;
+    UserSession::touch(
+        &session_id.to_string(),
+        erika.id,
+        client.user_agent.as_deref(),
+        client.ip.as_deref(),
+        db,
+    )
+    .await?;
+    Ok(())
+}
+
+/// Wymienia ID bieżącej sesji bez wylogowania (po zmianie uprawnień).
+pub async fn rotate_session(session: &Session, db: &PgPool) -> Result<(), AppError> {
+    let old_id = session.id();
+    session
+        .cycle_id()
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    session
+        .save()
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    if let (Some(old_id), Some(new_id)) = (old_id, session.id()) {
+        UserSession::rename(&old_id.to_string(), &new_id.to_string(), db).await?;
+    }
+    Ok(())
+}
+
+/// Wylogowuje bieżącą sesję i usuwa jej metadane.
+pub async fn end_session(session: &Session, db: &PgPool) -> Result<(), AppError> {
+    if let Some(id) = session.id() {
+        UserSession::delete_by_session_id(&id.to_string(), db).await?;
+    }
+    session
+        .flush()
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    Ok(())
+}
+
+/// Zdalnie unieważnia wskazane sesje - usuwa je z magazynu sesji,
+/// więc następne żądanie z takim ciasteczkiem jest już anonimowe.
+pub async fn revoke_sessions(state: &AppState, sessions: &[UserSession]) -> Result<(), AppError> {
+    for user_session in sessions {
+        match Id::from_str(&user_session.session_id) {
+            Ok(id) => {
+                if let Err(e) = state.sessions.delete(&id).await {
+                    warn!("Nie udało się usunąć sesji {}: {}", user_session.id, e);
+                    return Err(AppError::InternalServerError);
+                }
+            }
+            Err(_) => warn!("Niepoprawne ID sesji w user_sessions: {}", user_session.id),
+        }
+        UserSession::delete_by_session_id(&user_session.session_id, &state.db).await?;
+        info!(
+            "Unieważniono sesję {} konta {}",
+            user_session.id, user_session.erika_id
+        );
+    }
+    Ok(())
+}
+
+/// Wylogowuje wszystkie sesje danego konta (np. wymuszone przez admina).
+pub async fn revoke_all_sessions(state: &AppState, erika_id: Uuid) -> Result<usize, AppError> {
+    let sessions = UserSession::find_all_except(erika_id, None, &state.db).await?;
+    revoke_sessions(state, &sessions).await?;
+    Ok(sessions.len())
+}
+
+/// Odpowiedź dla niezalogowanego: przeglądarka trafia na `/login`,
+/// HTMX dostaje nagłówek `HX-Redirect`, a reszta zwykłe 401.
+fn login_required(headers: &HeaderMap) -> Response {
+    if headers.contains_key("hx-request") {
+        return (
+            [("hx-redirect", HeaderValue::from_static("/login"))],
+            AppError::Unauthorized,
+        )
+            .into_response();
+    }
+
+    let wants_html = headers
+        .get(header::ACCEPT)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|accept| accept.contains("text/html"));
+
+    if wants_html {
+        Redirect::to("/login").into_response()
+    } else {
+        AppError::Unauthorized.into_response()
+    }
+}
+
+/// Konto wczytane już w tym żądaniu - middleware i handler nie czytają go dwa razy.
+#[derive(Clone)]
+struct LoadedViewer(Option<ErikaAuth>);
+
+/// Konto, w którego imieniu obsługujemy żądanie: w podglądzie oglądane konto,
+/// w pozostałych przypadkach konto zalogowane w sesji.
+async fn load_viewer(parts: &mut Parts, state: &AppState) -> Result<Option<ErikaAuth>, Response> {
+    if let Some(impersonation) = parts.extensions.get::<Impersonation>() {
+        return Ok(Some(impersonation.target.clone()));
+    }
+    if let Some(LoadedViewer(viewer)) = parts.extensions.get::<LoadedViewer>() {
+        return Ok(viewer.clone());
+    }
+    let viewer = load_account(parts, state).await?;
+    parts.extensions.insert(LoadedViewer(viewer.clone()));
+    Ok(viewer)
+}
+
+/// Podgląd zapisany w sesji, o ile nadal jest ważny. Gdy admin stracił
+/// uprawnienie albo oglądane konto zniknęło lub trafiło do zespołu,
+/// podgląd kończy się po cichu.
+pub async fn load_impersonation(
+    parts: &mut Parts,
+    state: &AppState,
+) -> Result<Option<Impersonation>, Response> {
+    let session = Session::from_request_parts(parts, state)
+        .await
+        .map_err(|_| AppError::InternalServerError.into_response())?;
+
+    let Some(target_id) = session.get::<Uuid>(IMPERSONATED_KEY).await.unwrap_or(None) else {
+        return Ok(None);
+    };
+
+    let admin = load_account(parts, state)
+        .await?
+        .filter(|admin| admin.can(Permission::Impersonate));
+    let impersonation = match admin {
+        Some(admin) => Erika::find_by_id_for_auth(target_id, &state.db)
+            .await
+            .map_err(|e| AppError::from(e).into_response())?
+            .filter(|target| can_be_impersonated(&target.role))
+            .map(|target| Impersonation { admin, target }),
+        None => None,
+    };
+
+    if impersonation.is_none() {
+        warn!("Porzucono nieważny podgląd konta {}", target_id);
+        let _ = session.remove::<Uuid>(IMPERSONATED_KEY).await;
+    }
+    Ok(impersonation)
+}
+
+/// Wczytuje konto zalogowane w bieżącej sesji (jeśli jest).
+async fn load_account(parts: &mut Parts, state: &AppState) -> Result<Option<ErikaAuth>, Response> {
+    let session = Session::from_request_parts(parts, state)
+        .await
+        .map_err(|_| AppError::InternalServerError.into_response())?;
+
+    let Some(erika_id) = session.get::<Uuid>(SESSION_KEY).await.unwrap_or(None) else {
+        return Ok(None);
+    };
+
+    let erika = Erika::find_by_id_for_auth(erika_id, &state.db)
+        .await
+        .map_err(|e| AppError::from(e).into_response())?;
+
+    // Konto usunięte w trakcie trwania sesji - sprzątamy martwą sesję.
+    let Some(erika) = erika else {
+        let _ = session.remove::<Uuid>(SESSION_KEY).await;
+        return Ok(None);
+    };
+
+    // Zbanowane konto traci sesję przy najbliższym żądaniu
+    if !erika.status.can_log_in() {
+        if let Err(e) = end_session(&session, &state.db).await {
+            warn!(
+                "Nie udało się zakończyć sesji zbanowanego konta {}: {:?}",
+                erika.id, e
+            );
+        }
+        return Ok(None);
+    }
+
+    // Rola lub status konta zmieniły się od wydania sesji - wymieniamy jej ID
+    let privileges = erika.privileges();
+    let issued_with = session.get::<String>(PRIVILEGES_KEY).await.unwrap_or(None);
+    if issued_with.as_deref() != Some(privileges.as_str()) {
+        if let Err(e) = rotate_session(&session, &state.db).await {
+            warn!("Nie udało się wymienić ID sesji dla {}: {:?}", erika.id, e);
+        }
+        let _ = session.insert(PRIVILEGES_KEY, privileges).await;
+    }
+
+    // Odświeżamy "ostatnio widziana" na liście sesji w "Bezpieczeństwo"
+    if let Some(session_id) = session.id() {
+        let client = ClientInfo::from_parts(parts);
+        if let Err(e) = UserSession::touch(
+            &session_id.to_string(),
+            erika.id,
+            client.user_agent.as_deref(),
+            client.ip.as_deref(),
+            &state.db,
+        )
+        .await
+        {
+            warn!("Nie udało się odświeżyć sesji {}: {}", erika.id, e);
+        }
+    }
+
+    Ok(Some(erika))
+}
+
+impl<S> FromRequestParts<S> for OptionalViewer
+where
+    AppState: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let state = AppState::from_ref(state);
+        Ok(OptionalViewer(load_viewer(parts, &state).await?))
+    }
+}
+
+impl<S> FromRequestParts<S> for CurrentAccount
+where
+    AppState: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let state = AppState::from_ref(state);
+        let account = load_viewer(parts, &state)
+            .await?
+            .ok_or_else(|| login_required(&parts.headers))?;
+        Ok(CurrentAccount(account))
+    }
+}
+
+impl<S> FromRequestParts<S> for CurrentErika
+where
+    AppState: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let state = AppState::from_ref(state);
+        let erika = load_viewer(parts, &state)
+            .await?
+            .ok_or_else(|| login_required(&parts.headers))?;
+
+        if !has_creator_panel(&erika) {
+            return Err(AppError::Forbidden.into_response());
+        }
+
+        Ok(CurrentErika(erika))
+    }
+}
+
+impl<S> FromRequestParts<S> for CurrentStaff
+where
+    AppState: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let state = AppState::from_ref(state);
+        let erika = load_viewer(parts, &state)
+            .await?
+            .ok_or_else(|| login_required(&parts.headers))?;
+
+        if !erika.is_staff() {
+            return Err(AppError::Forbidden.into_response());
+        }
+
+        Ok(CurrentStaff(erika))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(value: &str) -> IpAddr {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn forwarded_client_skips_trusted_hops_from_the_right() {
+        let trusted = [ip("10.0.0.1"), ip("10.0.0.2")];
+        assert_eq!(
+            forwarded_client("203.0.113.7", &trusted),
+            Some(ip("203.0.113.7"))
+        );
+        // Klient dopisał fałszywy adres z lewej - bierzemy ten, który widziało nasze proxy
+        assert_eq!(
+            forwarded_client("1.1.1.1, 203.0.113.7, 10.0.0.2", &trusted),
+            Some(ip("203.0.113.7"))
+        );
+        assert_eq!(
+            forwarded_client("10.0.0.2, 10.0.0.1", &trusted),
+            Some(ip("10.0.0.2"))
+        );
+    }
+
+    #[test]
+    fn forwarded_client_rejects_garbage() {
+        let trusted = [ip("10.0.0.1")];
+        assert_eq!(forwarded_client("nie-ip", &trusted), None);
+        assert_eq!(forwarded_client("", &trusted), None);
+    }
+}
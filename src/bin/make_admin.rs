@@ -1,37 +1,520 @@
 // src/bin/make_admin.rs
 
+// Narzędzie administracyjne do obsługi kont z wiersza poleceń.
+// Korzysta z tych samych modeli co serwer, a każda zmiana trafia do
+// dziennika audytu jako wykonana przez `cli:<użytkownik systemu>`.
+
+use bigdecimal::BigDecimal;
+use erika_system::models::audit::{AuditAction, AuditEvent, NewAuditEvent};
+use erika_system::models::co_performer::CoPerformer;
+use erika_system::models::erika::{self, AccountStatus, Erika, ErikaQuery};
+use erika_system::models::gallery::Gallery;
+use erika_system::models::password_reset::{PasswordReset, RESET_TTL};
+use erika_system::models::photo::Photo;
+use erika_system::models::report::Report;
+use erika_system::models::user_session::UserSession;
+use erika_system::models::verification::IdentityVerification;
+use erika_system::models::wallet::Wallet;
+use serde_json::json;
+use sqlx::PgPool;
 use sqlx::postgres::PgPoolOptions;
+use std::collections::HashMap;
 use std::env;
+use std::error::Error;
+use std::process;
+use std::str::FromStr;
+use time::OffsetDateTime;
+use time::macros::format_description;
+use uuid::Uuid;
+
+type CliResult = Result<(), Box<dyn Error>>;
+
+const USAGE: &str = "\
+Użycie: make_admin <polecenie> [argumenty]
+
+Polecenia:
+  create-user <nazwa> <email> [--role ROLA]   zakłada konto i wypisuje link do ustawienia hasła
+  set-role <nazwa> <ROLA>                     zmienia rolę (Admin, Erika, Moderator, User), także odbiera admina
+  approve <nazwa> [--reason POWÓD]            akceptuje konto (wymaga potwierdzonej tożsamości)
+  suspend <nazwa> --reason POWÓD              zawiesza konto; powód zobaczy modelka
+  reset-password <nazwa>                      wypisuje jednorazowy link do zmiany hasła
+  credit <nazwa> <kwota> --reason POWÓD       doładowuje saldo napiwków (np. wpłata przelewem)
+  list-users [--status S] [--role R] [--search TEKST] [--limit N]
+  show-user <nazwa>
+  stats
+
+Skrót zgodny ze starszą wersją: `make_admin <nazwa>` = `make_admin set-role <nazwa> Admin`.
+Adres w linkach bierzemy z APP_URL (domyślnie http://localhost:3000).";
+
+/// Argumenty polecenia: pozycyjne oraz opcje w postaci `--nazwa wartość`.
+struct Args {
+    positional: Vec<String>,
+    options: HashMap<String, String>,
+}
+
+impl Args {
+    fn parse(raw: &[String]) -> Result<Self, Box<dyn Error>> {
+        let mut positional = Vec::new();
+        let mut options = HashMap::new();
+        let mut iter = raw.iter();
+        while let Some(arg) = iter.next() {
+            match arg.strip_prefix("--") {
+                Some(name) => {
+                    let value = iter
+                        .next()
+                        .ok_or_else(|| format!("Opcja --{} wymaga wartości.", name))?;
+                    options.insert(name.to_string(), value.clone());
+                }
+                None => positional.push(arg.clone()),
+            }
+        }
+        Ok(Args {
+            positional,
+            options,
+        })
+    }
+
+    fn required(&self, index: usize, name: &str) -> Result<&str, Box<dyn Error>> {
+        self.positional
+            .get(index)
+            .map(String::as_str)
+            .ok_or_else(|| format!("Brak argumentu <{}>.\n\n{}", name, USAGE).into())
+    }
+
+    fn option(&self, name: &str) -> Option<&str> {
+        self.options
+            .get(name)
+            .map(|value| value.trim())
+            .filter(|value| !value.is_empty())
+    }
+}
+
+/// Połączenie z bazą i nazwa operatora zapisywana w dzienniku audytu.
+struct Cli {
+    db: PgPool,
+    operator: String,
+}
+
+impl Cli {
+    async fn audit(
+        &self,
+        action: AuditAction,
+        target_id: Uuid,
+        changes: serde_json::Value,
+    ) -> Result<(), sqlx::Error> {
+        AuditEvent::record(
+            NewAuditEvent {
+                actor_id: None,
+                actor_username: &self.operator,
+                action,
+                target_type: "erika",
+                target_id: Some(target_id),
+                changes,
+                ip_address: None,
+            },
+            &self.db,
+        )
+        .await
+    }
+
+    async fn find_user(&self, username: &str) -> Result<Erika, Box<dyn Error>> {
+        Erika::find_by_username(username, &self.db)
+            .await?
+            .ok_or_else(|| format!("Nie znaleziono użytkownika o nazwie '{}'.", username).into())
+    }
+
+    /// Wydaje token i składa z niego link do strony zmiany hasła.
+    async fn reset_link(&self, erika_id: Uuid) -> Result<String, sqlx::Error> {
+        let token = PasswordReset::issue(erika_id, &self.db).await?;
+        let base = env::var("APP_URL").unwrap_or_else(|_| "http://localhost:3000".into());
+        Ok(format!(
+            "{}/reset-password/{}",
+            base.trim_end_matches('/'),
+            token
+        ))
+    }
+}
 
 #[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
+async fn main() {
     dotenvy::dotenv().expect("Nie znaleziono pliku .env");
+    if let Err(e) = run().await {
+        eprintln!("Błąd: {}", e);
+        process::exit(1);
+    }
+}
+
+async fn run() -> CliResult {
+    let raw: Vec<String> = env::args().skip(1).collect();
+    let Some(command) = raw.first() else {
+        println!("{}", USAGE);
+        return Ok(());
+    };
+    if matches!(command.as_str(), "help" | "-h" | "--help") {
+        println!("{}", USAGE);
+        return Ok(());
+    }
+    let args = Args::parse(&raw[1..])?;
+
     let database_url = env::var("DATABASE_URL").expect("Brak DATABASE_URL");
+    let cli = Cli {
+        db: PgPoolOptions::new().connect(&database_url).await?,
+        operator: format!(
+            "cli:{}",
+            env::var("USER").unwrap_or_else(|_| "unknown".into())
+        ),
+    };
+
+    match command.as_str() {
+        "create-user" => create_user(&cli, &args).await,
+        "set-role" => set_role(&cli, args.required(0, "nazwa")?, args.required(1, "rola")?).await,
+        "approve" => approve(&cli, &args).await,
+        "suspend" => suspend(&cli, &args).await,
+        "reset-password" => reset_password(&cli, &args).await,
+        "credit" => credit(&cli, &args).await,
+        "list-users" => list_users(&cli, &args).await,
+        "show-user" => show_user(&cli, &args).await,
+        "stats" => stats(&cli).await,
+        // Dawne wywołanie `make_admin <nazwa>` nadal nadaje rolę Admina
+        username if raw.len() == 1 && !username.starts_with('-') => {
+            set_role(&cli, username, "Admin").await
+        }
+        other => Err(format!("Nieznane polecenie '{}'.\n\n{}", other, USAGE).into()),
+    }
+}
+
+fn parse_role(role: &str) -> Result<&'static str, Box<dyn Error>> {
+    erika::ROLES
+        .iter()
+        .find(|known| known.eq_ignore_ascii_case(role))
+        .copied()
+        .ok_or_else(|| {
+            format!(
+                "Nieznana rola '{}'. Dostępne: {}.",
+                role,
+                erika::ROLES.join(", ")
+            )
+            .into()
+        })
+}
+
+fn format_datetime(value: OffsetDateTime) -> String {
+    value
+        .format(format_description!(
+            "[year]-[month]-[day] [hour]:[minute] UTC"
+        ))
+        .unwrap_or_default()
+}
+
+async fn create_user(cli: &Cli, args: &Args) -> CliResult {
+    let username = args.required(0, "nazwa")?;
+    let email = args.required(1, "email")?.to_lowercase();
+    let role = parse_role(args.option("role").unwrap_or("Erika"))?;
+    if !email.contains('@') {
+        return Err(format!("'{}' nie wygląda na adres email.", email).into());
+    }
+
+    // Hasła nikt nie zna - właściciel konta ustawia własne z linku
+    let placeholder = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+    let erika_id = match Erika::create(username, &email, &placeholder, &cli.db).await {
+        Ok(id) => id,
+        Err(e)
+            if e.as_database_error()
+                .is_some_and(|db| db.is_unique_violation()) =>
+        {
+            return Err("Konto z tą nazwą użytkownika lub adresem email już istnieje.".into());
+        }
+        Err(e) => return Err(e.into()),
+    };
+    if role != "Erika" {
+        Erika::change_role(erika_id, role, &cli.db).await?;
+    }
+    cli.audit(
+        AuditAction::AccountCreated,
+        erika_id,
+        json!({ "username": username, "email": email, "role": role }),
+    )
+    .await?;
+
+    let link = cli.reset_link(erika_id).await?;
+    cli.audit(AuditAction::PasswordResetIssued, erika_id, json!({}))
+        .await?;
+
+    println!(
+        "Utworzono konto '{}' ({}) z rolą {}.",
+        username, email, role
+    );
+    println!(
+        "Link do ustawienia hasła (ważny {} h):",
+        RESET_TTL.whole_hours()
+    );
+    println!("{}", link);
+    Ok(())
+}
+
+async fn set_role(cli: &Cli, username: &str, role: &str) -> CliResult {
+    let role = parse_role(role)?;
+    let erika = cli.find_user(username).await?;
+    let before = Erika::find_role(erika.id, &cli.db)
+        .await?
+        .ok_or("Konto zniknęło w trakcie operacji.")?;
+    if before == role {
+        println!("Użytkownik '{}' ma już rolę {}.", erika.username, role);
+        return Ok(());
+    }
 
-    // Pobieramy nazwę użytkownika z argumentów wiersza poleceń
-    let args: Vec<String> = env::args().collect();
-    let username = args
-        .get(1)
-        .expect("Podaj nazwę użytkownika, którego chcesz awansować na Admina!");
+    if !Erika::change_role(erika.id, role, &cli.db).await? {
+        return Err("To ostatni administrator - najpierw nadaj rolę Admin innemu kontu.".into());
+    }
+    cli.audit(
+        AuditAction::RoleChanged,
+        erika.id,
+        json!({ "role": { "before": before, "after": role } }),
+    )
+    .await?;
 
-    let pool = PgPoolOptions::new().connect(&database_url).await?;
+    println!(
+        "Sukces! Użytkownik '{}' ma teraz rolę {} (wcześniej {}).",
+        erika.username, role, before
+    );
+    Ok(())
+}
+
+/// Wspólna ścieżka `approve` i `suspend` - te same reguły przejść co w panelu admina.
+async fn change_status(cli: &Cli, username: &str, next: AccountStatus, reason: &str) -> CliResult {
+    let erika = cli.find_user(username).await?;
+    if !erika.status.can_transition_to(next) {
+        return Err(format!(
+            "Z obecnego statusu ({}) nie można przejść do {}.",
+            erika.status.label(),
+            next.label()
+        )
+        .into());
+    }
+    if next == AccountStatus::Approved
+        && !IdentityVerification::has_verified(erika.id, &cli.db).await?
+    {
+        return Err("Nie można zaakceptować konta bez potwierdzonej tożsamości i wieku.".into());
+    }
+    if !Erika::change_status(erika.id, erika.status, next, reason, &cli.db).await? {
+        return Err(
+            "Status konta zmienił się w międzyczasie. Sprawdź go i spróbuj ponownie.".into(),
+        );
+    }
 
-    // Zmieniamy rolę użytkownika w bazie danych
-    let result = sqlx::query!(
-        "UPDATE erikas SET role = 'Admin' WHERE username = $1",
-        username
+    cli.audit(
+        AuditAction::StatusChanged,
+        erika.id,
+        json!({
+            "status": { "before": erika.status.to_string(), "after": next.to_string() },
+            "reason": reason,
+        }),
     )
-    .execute(&pool)
     .await?;
 
-    if result.rows_affected() > 0 {
+    println!(
+        "Status konta '{}': {} -> {}.",
+        erika.username,
+        erika.status.label(),
+        next.label()
+    );
+    Ok(())
+}
+
+async fn approve(cli: &Cli, args: &Args) -> CliResult {
+    let username = args.required(0, "nazwa")?;
+    let reason = args.option("reason").unwrap_or("Konto zaakceptowane.");
+    change_status(cli, username, AccountStatus::Approved, reason).await
+}
+
+async fn suspend(cli: &Cli, args: &Args) -> CliResult {
+    let username = args.required(0, "nazwa")?;
+    let reason = args
+        .option("reason")
+        .ok_or("Podaj powód zawieszenia (--reason) - zobaczy go modelka.")?;
+    change_status(cli, username, AccountStatus::Suspended, reason).await
+}
+
+async fn reset_password(cli: &Cli, args: &Args) -> CliResult {
+    let erika = cli.find_user(args.required(0, "nazwa")?).await?;
+    let link = cli.reset_link(erika.id).await?;
+    cli.audit(AuditAction::PasswordResetIssued, erika.id, json!({}))
+        .await?;
+
+    println!(
+        "Jednorazowy link do zmiany hasła dla '{}' (ważny {} h):",
+        erika.username,
+        RESET_TTL.whole_hours()
+    );
+    println!("{}", link);
+    println!("Wcześniej wydane linki tego konta przestały działać.");
+    Ok(())
+}
+
+async fn list_users(cli: &Cli, args: &Args) -> CliResult {
+    let status = match args.option("status") {
+        Some(value) => Some(AccountStatus::from_str(value).map_err(|_| {
+            format!(
+                "Nieznany status '{}'. Dostępne: Pending, Approved, Rejected, Suspended, Banned.",
+                value
+            )
+        })?),
+        None => None,
+    };
+    let role = args.option("role").map(parse_role).transpose()?;
+    let limit = match args.option("limit") {
+        Some(value) => value
+            .parse::<i64>()
+            .ok()
+            .filter(|limit| *limit > 0)
+            .ok_or("--limit musi być dodatnią liczbą.")?,
+        None => 100,
+    };
+    let query = ErikaQuery {
+        status,
+        role: role.map(str::to_string),
+        search: args.option("search").map(str::to_string),
+    };
+
+    let users = Erika::find_filtered(&query, limit, &cli.db).await?;
+    println!(
+        "{:<30} {:<36} {:<6} {:<10} ZAŁOŻONE",
+        "NAZWA", "EMAIL", "ROLA", "STATUS"
+    );
+    for user in &users {
         println!(
-            "Sukces! Użytkownik '{}' jest teraz Administratorem.",
-            username
+            "{:<30} {:<36} {:<6} {:<10} {}",
+            user.username,
+            user.email,
+            user.role,
+            user.status,
+            format_datetime(user.created_at)
         );
+    }
+    let truncated = if users.len() as i64 == limit {
+        " (lista ucięta - użyj --limit)"
     } else {
-        println!("Błąd: Nie znaleziono użytkownika o nazwie '{}'.", username);
+        ""
+    };
+    println!("Kont na liście: {}{}", users.len(), truncated);
+    Ok(())
+}
+
+async fn credit(cli: &Cli, args: &Args) -> CliResult {
+    let erika = cli.find_user(args.required(0, "nazwa")?).await?;
+    let amount = BigDecimal::from_str(args.required(1, "kwota")?)
+        .ok()
+        .filter(|amount| *amount > BigDecimal::from(0) && amount.fractional_digit_count() <= 2)
+        .ok_or("Kwota musi być dodatnia, z najwyżej dwoma miejscami po przecinku (np. 50.00).")?;
+    let reason = args
+        .option("reason")
+        .ok_or("Podaj powód doładowania (--reason), np. numer przelewu.")?;
+
+    let balance = Wallet::credit(erika.id, &amount, reason, &cli.db).await?;
+    cli.audit(
+        AuditAction::WalletCredited,
+        erika.id,
+        json!({
+            "amount_pln": amount.with_scale(2).to_string(),
+            "balance_pln": balance.with_scale(2).to_string(),
+            "reason": reason,
+        }),
+    )
+    .await?;
+
+    println!(
+        "Doładowano saldo '{}' o {} PLN. Saldo: {} PLN.",
+        erika.username,
+        amount.with_scale(2),
+        balance.with_scale(2)
+    );
+    Ok(())
+}
+
+async fn show_user(cli: &Cli, args: &Args) -> CliResult {
+    let erika = cli.find_user(args.required(0, "nazwa")?).await?;
+    let role = Erika::find_role(erika.id, &cli.db)
+        .await?
+        .unwrap_or_default();
+    let verified = IdentityVerification::has_verified(erika.id, &cli.db).await?;
+    let galleries = Gallery::find_by_erika_id(erika.id, &cli.db).await?;
+    let published = galleries
+        .iter()
+        .filter(|g| g.published_at.is_some())
+        .count();
+    let sessions = UserSession::find_all_except(erika.id, None, &cli.db).await?;
+
+    println!("ID:          {}", erika.id);
+    println!("Nazwa:       {}", erika.username);
+    println!("Email:       {}", erika.email);
+    println!("Rola:        {}", role);
+    println!("Status:      {} ({})", erika.status.label(), erika.status);
+    if let Some(reason) = &erika.status_reason {
+        println!("Powód:       {}", reason);
+    }
+    if let Some(changed_at) = erika.status_changed_at {
+        println!("Zmiana:      {}", format_datetime(changed_at));
+    }
+    println!(
+        "Tożsamość:   {}",
+        if verified {
+            "potwierdzona"
+        } else {
+            "niepotwierdzona"
+        }
+    );
+    println!(
+        "Online:      {}",
+        if erika.is_online { "tak" } else { "nie" }
+    );
+    if erika.appear_offline {
+        println!("Widoczność:  pokazuje się jako offline");
     }
+    if let Some(last_seen_at) = erika.last_seen_at {
+        println!("Aktywność:   {}", format_datetime(last_seen_at));
+    }
+    println!(
+        "Galerie:     {} (opublikowane: {})",
+        galleries.len(),
+        published
+    );
+    println!("Sesje:       {}", sessions.len());
+    println!(
+        "Saldo:       {} PLN",
+        Wallet::balance(erika.id, &cli.db).await?.with_scale(2)
+    );
+    Ok(())
+}
 
+async fn stats(cli: &Cli) -> CliResult {
+    println!("Konta wg statusu:");
+    for (status, count) in Erika::count_by_status(&cli.db).await? {
+        println!("  {:<14} {}", status.label(), count);
+    }
+    println!("Konta wg roli:");
+    for (role, count) in Erika::count_by_role(&cli.db).await? {
+        println!("  {:<14} {}", role, count);
+    }
+    println!("Kolejki moderacji:");
+    println!(
+        "  {:<28} {}",
+        "Weryfikacje tożsamości",
+        IdentityVerification::count_pending(&cli.db).await?
+    );
+    println!(
+        "  {:<28} {}",
+        "Osoby współwystępujące",
+        CoPerformer::find_pending(&cli.db).await?.len()
+    );
+    println!(
+        "  {:<28} {}",
+        "Zdjęcia",
+        Photo::count_pending(&cli.db).await?
+    );
+    println!(
+        "  {:<28} {}",
+        "Otwarte zgłoszenia",
+        Report::count_open(&cli.db).await?
+    );
     Ok(())
 }
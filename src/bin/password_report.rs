@@ -0,0 +1,57 @@
+// src/bin/password_report.rs
+
+// Raport: ile kont ma jeszcze hash hasła ze starszymi parametrami Argon2.
+// Takie hashe są przeliczane automatycznie przy następnym logowaniu.
+
+use erika_system::password;
+use sqlx::postgres::PgPoolOptions;
+use std::collections::BTreeMap;
+use std::env;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    dotenvy::dotenv().expect("Nie znaleziono pliku .env");
+    let database_url = env::var("DATABASE_URL").expect("Brak DATABASE_URL");
+    let verbose = env::args().any(|arg| arg == "--list");
+
+    let pool = PgPoolOptions::new().connect(&database_url).await?;
+    let rows = sqlx::query!("SELECT username, password_hash FROM erikas ORDER BY username")
+        .fetch_all(&pool)
+        .await?;
+
+    let current = password::params();
+    println!(
+        "Aktualne parametry: argon2id v19 m={} t={} p={}",
+        current.m_cost(),
+        current.t_cost(),
+        current.p_cost()
+    );
+
+    let mut by_params: BTreeMap<String, usize> = BTreeMap::new();
+    let mut legacy = Vec::new();
+    for row in &rows {
+        *by_params
+            .entry(password::describe(&row.password_hash))
+            .or_default() += 1;
+        if password::needs_rehash(&row.password_hash) {
+            legacy.push(row.username.as_str());
+        }
+    }
+
+    println!("\nKonta według parametrów hasha:");
+    for (params, count) in &by_params {
+        println!("  {:<40} {}", params, count);
+    }
+    println!(
+        "\nKonta do przeliczenia przy logowaniu: {} z {}",
+        legacy.len(),
+        rows.len()
+    );
+    if verbose {
+        for username in legacy {
+            println!("  {}", username);
+        }
+    }
+
+    Ok(())
+}
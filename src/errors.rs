@@ -3,10 +3,11 @@ use axum::{
     response::{IntoResponse, Response},
 };
 
-#[derive()]
+#[derive(Debug)]
 pub enum AppError {
     InternalServerError,
     Unauthorized,
+    Forbidden,
     NotFound,
 }
 
@@ -21,6 +22,7 @@ impl IntoResponse for AppError {
                 StatusCode::UNAUTHORIZED,
                 "Brak autoryzacji. Musisz być zalogowany.",
             ),
+            AppError::Forbidden => (StatusCode::FORBIDDEN, "Nie masz uprawnień do tej operacji."),
             AppError::NotFound => (StatusCode::NOT_FOUND, "Nie znaleziono zasobu"),
         };
         (status, error_message).into_response()
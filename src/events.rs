@@ -0,0 +1,35 @@
+// src/events.rs
+
+// Zdarzenia na żywo dla odwiedzających. Miejsca, które zmieniają listę modelek
+// (obecność, akceptacja, blokada konta), publikują zdarzenie w kanale
+// z `AppState`, a otwarte strony główne dostają je przez SSE. Tym samym kanałem
+// idą napiwki do czatu transmisji i zmiany w pokazach prywatnych.
+
+use uuid::Uuid;
+
+/// Ile zdarzeń może czekać na wolnego odbiorcę. Odbiorca, który nie nadąży,
+/// dostaje `Lagged` i po prostu odświeża całą listę.
+pub const CHANNEL_CAPACITY: usize = 64;
+
+/// Zmiana widoczna na liście modelek na stronie głównej albo na transmisji.
+#[derive(Debug, Clone, Copy)]
+pub enum CreatorEvent {
+    /// Modelka weszła lub zeszła z trybu online.
+    Presence { erika_id: Uuid, is_online: bool },
+    /// Konto pojawiło się na liście (akceptacja) albo z niej zniknęło.
+    Listing { erika_id: Uuid, listed: bool },
+    /// Widz dał napiwek w czasie transmisji modelki.
+    Tip { erika_id: Uuid, tip_id: Uuid },
+    /// Prośba o pokaz prywatny, jego start, naliczona minuta albo koniec.
+    PrivateShow { erika_id: Uuid, show_id: Uuid },
+}
+
+impl CreatorEvent {
+    /// Czy zdarzenie zmienia siatkę modelek na stronie głównej.
+    pub fn changes_listing(&self) -> bool {
+        !matches!(
+            self,
+            CreatorEvent::Tip { .. } | CreatorEvent::PrivateShow { .. }
+        )
+    }
+}
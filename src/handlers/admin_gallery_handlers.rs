@@ -0,0 +1,238 @@
+// src/handlers/admin_gallery_handlers.rs
+
+// Galerie i zdjęcia dowolnej modelki z perspektywy admina. Korzysta z tych
+// samych fragmentów widoku co panel modelki; każda zmiana trafia do audytu.
+
+use super::gallery_handlers::{
+    UpdateGalleryPayload, category_unavailable_response, delete_photo_dialog,
+    delete_photo_with_file, gallery_details_form, is_selectable_category, render_photo_partial,
+    render_photos_grid,
+};
+use super::layout;
+use crate::auth::{ClientInfo, CurrentStaff};
+use crate::models::audit::{self, AuditAction};
+use crate::models::category::Category;
+use crate::models::erika::Erika;
+use crate::models::gallery::Gallery;
+use crate::models::photo::Photo;
+use crate::{app_state::AppState, errors::AppError};
+use axum::{
+    Form,
+    extract::{Path, State},
+    http::header::HeaderName,
+    response::{Html, IntoResponse, Redirect, Response},
+};
+use serde_json::json;
+use tracing::info;
+use uuid::Uuid;
+
+async fn find_gallery(gallery_id: Uuid, state: &AppState) -> Result<Gallery, AppError> {
+    Gallery::find_by_id(gallery_id, &state.db)
+        .await?
+        .ok_or(AppError::NotFound)
+}
+
+/// Zdjęcie musi należeć do galerii z adresu.
+async fn find_photo(gallery_id: Uuid, photo_id: Uuid, state: &AppState) -> Result<Photo, AppError> {
+    Photo::find_by_id(photo_id, &state.db)
+        .await?
+        .filter(|photo| photo.gallery_id == gallery_id)
+        .ok_or(AppError::NotFound)
+}
+
+fn admin_confirm_url(photo: &Photo) -> String {
+    format!(
+        "/admin/galleries/{}/photos/{}/delete-confirm",
+        photo.gallery_id, photo.id
+    )
+}
+
+fn price_text(gallery: &Gallery) -> Option<String> {
+    gallery
+        .price_pln
+        .as_ref()
+        .map(|p| p.with_scale(2).to_string())
+}
+
+// Szczegóły galerii, zdjęcia i akcje admina
+pub async fn show_gallery(
+    Path(gallery_id): Path<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Html<String>, AppError> {
+    let gallery = find_gallery(gallery_id, &state).await?;
+    let owner = Erika::find_by_id(gallery.erika_id, &state.db)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    let photos = Photo::find_by_gallery_id(gallery_id, &state.db).await?;
+    let categories = Category::find_active(&state.db).await?;
+
+    let content = maud::html! {
+        div class="max-w-4xl mx-auto" {
+            a href=(format!("/admin/erika/{}", owner.id)) class="inline-block mb-6 text-blue-400 hover:text-blue-300 transition-colors" {
+                "← Wróć do konta " (owner.username)
+            }
+            h1 class="text-3xl font-bold text-white mb-2" { "Galeria: " (gallery.name) }
+            p class="text-gray-400 mb-6" {
+                "Właścicielka: " (owner.username) " · utworzona " (layout::format_datetime(gallery.created_at))
+            }
+
+            div class="bg-gray-800 p-6 rounded-lg shadow-lg mb-8 flex flex-col md:flex-row md:justify-between md:items-center gap-4" {
+                div {
+                    @if gallery.blocked_at.is_some() {
+                        p class="text-red-400 font-semibold" { "Ukryta decyzją moderacji" }
+                    } @else if let Some(published_at) = gallery.published_at {
+                        p class="text-green-400 font-semibold" { "Opublikowana " (layout::format_datetime(published_at)) }
+                    } @else {
+                        p class="text-gray-300 font-semibold" { "Szkic" }
+                    }
+                    p class="text-gray-400 text-sm" {
+                        "Cena: " (price_text(&gallery).map(|p| format!("{} PLN", p)).unwrap_or_else(|| "brak".to_string()))
+                        " · zdjęć: " (photos.len())
+                    }
+                }
+                @if gallery.published_at.is_some() {
+                    form action=(format!("/admin/galleries/{}/unpublish", gallery.id)) method="post" {
+                        button type="submit" class="bg-gray-600 hover:bg-gray-700 text-white font-bold py-2 px-4 rounded-md" { "Cofnij publikację" }
+                    }
+                }
+            }
+
+            div class="bg-gray-800 p-6 rounded-lg shadow-lg mb-8" {
+                h2 class="text-xl font-semibold text-white mb-4" { "Szczegóły galerii" }
+                (gallery_details_form(&format!("/admin/galleries/{}", gallery.id), &gallery, &categories))
+            }
+
+            h2 class="text-xl font-semibold text-white mb-4" { "Zdjęcia" }
+            (maud::PreEscaped(render_photos_grid(&photos, admin_confirm_url)))
+        }
+    };
+    Ok(Html(layout::page("Galeria (admin)", content).into_string()))
+}
+
+pub async fn update_gallery(
+    Path(gallery_id): Path<Uuid>,
+    admin: CurrentStaff,
+    client: ClientInfo,
+    State(state): State<AppState>,
+    Form(payload): Form<UpdateGalleryPayload>,
+) -> Result<Response, AppError> {
+    let before = find_gallery(gallery_id, &state).await?;
+    let back = format!("/admin/galleries/{}", gallery_id);
+
+    if !is_selectable_category(payload.category_id, Some(before.category_id), &state).await? {
+        return Ok(category_unavailable_response(&back));
+    }
+
+    Gallery::update_details(
+        gallery_id,
+        payload.category_id,
+        &payload.description,
+        payload.price_pln.clone(),
+        &state.db,
+    )
+    .await?;
+
+    let after = find_gallery(gallery_id, &state).await?;
+    let changes = audit::diff(&[
+        ("category", json!(before.name), json!(after.name)),
+        (
+            "description",
+            json!(before.description),
+            json!(after.description),
+        ),
+        (
+            "price_pln",
+            json!(price_text(&before)),
+            json!(price_text(&after)),
+        ),
+    ]);
+    admin
+        .audit(
+            AuditAction::GalleryUpdated,
+            "gallery",
+            Some(gallery_id),
+            changes,
+            &client,
+            &state.db,
+        )
+        .await?;
+    info!("Admin {} zmienił galerię {}", admin.0.username, gallery_id);
+    Ok(Redirect::to(&back).into_response())
+}
+
+pub async fn unpublish_gallery(
+    Path(gallery_id): Path<Uuid>,
+    admin: CurrentStaff,
+    client: ClientInfo,
+    State(state): State<AppState>,
+) -> Result<Redirect, AppError> {
+    let gallery = find_gallery(gallery_id, &state).await?;
+    if gallery.published_at.is_some() {
+        Gallery::unpublish(gallery.id, &state.db).await?;
+        admin
+            .audit(
+                AuditAction::GalleryUnpublished,
+                "gallery",
+                Some(gallery.id),
+                json!({ "erika_id": gallery.erika_id, "category": gallery.name }),
+                &client,
+                &state.db,
+            )
+            .await?;
+        info!(
+            "Admin {} cofnął publikację galerii {}",
+            admin.0.username, gallery.id
+        );
+    }
+    Ok(Redirect::to(&format!("/admin/galleries/{}", gallery.id)))
+}
+
+// Treść modala z potwierdzeniem usunięcia zdjęcia
+pub async fn confirm_delete_photo(
+    Path((gallery_id, photo_id)): Path<(Uuid, Uuid)>,
+    State(state): State<AppState>,
+) -> Result<Html<String>, AppError> {
+    find_photo(gallery_id, photo_id, &state).await?;
+    let delete_url = format!("/admin/galleries/{}/photos/{}/delete", gallery_id, photo_id);
+    Ok(Html(delete_photo_dialog(&delete_url).into_string()))
+}
+
+pub async fn delete_photo(
+    Path((gallery_id, photo_id)): Path<(Uuid, Uuid)>,
+    admin: CurrentStaff,
+    client: ClientInfo,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, AppError> {
+    let photo = find_photo(gallery_id, photo_id, &state).await?;
+    delete_photo_with_file(&photo, &state.db).await?;
+
+    admin
+        .audit(
+            AuditAction::PhotoDeleted,
+            "photo",
+            Some(photo.id),
+            json!({ "gallery_id": gallery_id, "file_url": photo.file_url, "status": photo.status.to_string() }),
+            &client,
+            &state.db,
+        )
+        .await?;
+    info!("Admin {} usunął zdjęcie {}", admin.0.username, photo.id);
+
+    let photos = Photo::find_by_gallery_id(gallery_id, &state.db).await?;
+    Ok((
+        [(HeaderName::from_static("hx-trigger"), "closeModal")],
+        Html(render_photos_grid(&photos, admin_confirm_url)),
+    ))
+}
+
+// Pojedyncze zdjęcie - używane, gdy admin anuluje usuwanie
+pub async fn get_photo_partial(
+    Path((gallery_id, photo_id)): Path<(Uuid, Uuid)>,
+    State(state): State<AppState>,
+) -> Result<Html<String>, AppError> {
+    let photo = find_photo(gallery_id, photo_id, &state).await?;
+    Ok(Html(render_photo_partial(
+        &admin_confirm_url(&photo),
+        &photo,
+    )))
+}
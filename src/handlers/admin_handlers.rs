@@ -1,64 +1,401 @@
 // src/handlers/admin_handlers.rs
+use crate::auth::{self, ClientInfo, CurrentStaff};
+use crate::events::CreatorEvent;
+use crate::handlers::erika_handlers::{UpdateProfilePayload, form_errors_response};
 use crate::handlers::layout;
+use crate::models::audit::{self, AuditAction, AuditEvent, AuditQuery};
+use crate::models::erika::{
+    AccountStatus, CreatorCursor, CreatorListItem, CreatorQuery, CreatorSort,
+};
 use crate::models::gallery::Gallery;
+use crate::models::permission::Permission;
+use crate::models::photo::Photo;
+use crate::models::report::Report;
+use crate::models::user_session::UserSession;
+use crate::models::verification::IdentityVerification;
+use crate::models::wallet::Wallet;
+use crate::validation::{self, FormErrors, Validate};
 use crate::{app_state::AppState, errors::AppError, models::erika::Erika};
 use axum::Form;
-use axum::extract::Path;
-use axum::response::Redirect;
+use axum::extract::{Path, Query};
+use axum::http::{HeaderMap, header};
+use axum::response::{IntoResponse, Redirect, Response};
 use axum::{extract::State, response::Html};
+use bigdecimal::BigDecimal;
+use maud::Markup;
+use serde::Deserialize;
+use serde_json::json;
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+use time::Date;
+use time::macros::format_description;
 use tracing::info;
 use uuid::Uuid;
 
-// Handler do wyświetlania głównego dashboardu admina
-pub async fn admin_dashboard(State(state): State<AppState>) -> Result<Html<String>, AppError> {
-    // Na razie pobierzmy wszystkie Eriki
-    let erikas = Erika::find_all(&state.db)
-        .await
-        .map_err(|_| AppError::InternalServerError)?;
+/// Ile ostatnich zmian statusu pokazujemy na stronie edycji konta.
+const STATUS_HISTORY_LIMIT: i64 = 20;
+
+/// Ile kont pokazujemy na jednej stronie listy.
+const CREATORS_PAGE_SIZE: i64 = 50;
+
+// Parametry wyszukiwania listy kont - puste pola przychodzą jako puste stringi
+#[derive(Deserialize, Default)]
+pub struct CreatorFilterParams {
+    #[serde(default)]
+    pub q: String,
+    #[serde(default)]
+    pub status: String,
+    #[serde(default)]
+    pub online: String,
+    #[serde(default)]
+    pub from: String,
+    #[serde(default)]
+    pub to: String,
+    #[serde(default)]
+    pub sort: String,
+    #[serde(default)]
+    pub dir: String,
+    #[serde(default)]
+    pub after: String,
+}
+
+impl CreatorFilterParams {
+    fn to_query(&self) -> CreatorQuery {
+        // Niepoprawne wartości ignorujemy, tak jak w dzienniku audytu
+        let date = |value: &str| {
+            Date::parse(value.trim(), format_description!("[year]-[month]-[day]")).ok()
+        };
+        let search = self.q.trim();
 
+        CreatorQuery {
+            search: (!search.is_empty()).then(|| search.to_string()),
+            status: AccountStatus::from_str(&self.status).ok(),
+            online: match self.online.as_str() {
+                "1" => Some(true),
+                "0" => Some(false),
+                _ => None,
+            },
+            registered_from: date(&self.from),
+            registered_to: date(&self.to),
+            sort: CreatorSort::from_slug(&self.sort).unwrap_or_default(),
+            descending: self.dir == "desc",
+            after: CreatorCursor::decode(&self.after),
+        }
+    }
+
+    // Adres listy z bieżącymi filtrami, podanym sortowaniem i kursorem strony
+    fn url(&self, sort: CreatorSort, descending: bool, after: Option<&CreatorCursor>) -> String {
+        let mut url = format!(
+            "/admin/creators?sort={}&dir={}",
+            sort.slug(),
+            if descending { "desc" } else { "asc" }
+        );
+        for (key, value) in [
+            ("q", &self.q),
+            ("status", &self.status),
+            ("online", &self.online),
+            ("from", &self.from),
+            ("to", &self.to),
+        ] {
+            if !value.is_empty() {
+                url.push_str(&format!("&{}={}", key, layout::urlencode(value)));
+            }
+        }
+        if let Some(after) = after {
+            url.push_str(&format!("&after={}", layout::urlencode(&after.encode())));
+        }
+        url
+    }
+}
+
+// Lista kont modelek. Żądania HTMX dostają tylko listę (po zmianie filtrów)
+// albo tylko kolejne wiersze ("Pokaż więcej").
+pub async fn show_creators(
+    Query(params): Query<CreatorFilterParams>,
+    headers: HeaderMap,
+    staff: CurrentStaff,
+    State(state): State<AppState>,
+) -> Result<Response, AppError> {
+    let query = params.to_query();
+    let mut creators = Erika::find_page(&query, CREATORS_PAGE_SIZE + 1, &state.db).await?;
+    // Pobieramy jeden wiersz więcej - jeśli przyszedł, jest następna strona
+    let has_more = creators.len() as i64 > CREATORS_PAGE_SIZE;
+    creators.truncate(CREATORS_PAGE_SIZE as usize);
+    let next_after = creators
+        .last()
+        .filter(|_| has_more)
+        .map(CreatorCursor::from_item);
+
+    // Przy cofaniu w historii HTMX prosi o całą stronę
+    let partial =
+        headers.contains_key("hx-request") && !headers.contains_key("hx-history-restore-request");
+    if partial && query.after.is_some() {
+        let rows = creator_rows(&creators, next_after.as_ref(), &params, &query);
+        return Ok(([(header::VARY, "HX-Request")], Html(rows.into_string())).into_response());
+    }
+
+    // Liczymy tylko dla pierwszej strony - kolejne jej nie pokazują
+    let total = match query.after {
+        None => Some(Erika::count_filtered(&query, &state.db).await?),
+        Some(_) => None,
+    };
+    let list = creator_list(
+        &creators,
+        total,
+        next_after.as_ref(),
+        &params,
+        &query,
+        partial,
+    );
+    if partial {
+        return Ok(([(header::VARY, "HX-Request")], Html(list.into_string())).into_response());
+    }
+
+    let input_class = "px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white focus:outline-none focus:ring-2 focus:ring-blue-500";
+
+    let header = dashboard_header(&staff, &state).await?;
     let content = maud::html! {
-        h1 class="text-3xl font-bold text-white mb-6" { "Panel Administratora" }
+        (header)
 
         h2 class="text-xl font-semibold text-white mb-4" { "Lista Modelek" }
-        div class="bg-gray-800 rounded-lg shadow-lg" {
-            ul {
-                @for erika in erikas {
-                    li class="p-4 border-b border-gray-700 flex justify-between items-center" {
-                        div {
-                            span class="text-white" { (erika.username) " (" (erika.email) ")" }
-                            // Wyświetlanie statusu
-                            @if erika.is_approved {
-                                span class="ml-4 text-xs font-semibold bg-green-500 text-white px-2 py-1 rounded-full" { "Zaakceptowana" }
-                            } @else {
-                                span class="ml-4 text-xs font-semibold bg-yellow-500 text-black px-2 py-1 rounded-full" { "Oczekuje" }
-                            }
+        // Każda zmiana pola odświeża samą listę; bez JS działa zwykły formularz GET
+        form action="/admin/creators" method="get"
+             hx-get="/admin/creators" hx-trigger="submit, input delay:300ms" hx-target="#creator-list"
+             hx-swap="outerHTML" hx-push-url="true" hx-sync="this:replace"
+             class="bg-gray-800 p-4 rounded-lg shadow-lg mb-4 flex flex-wrap gap-4 items-end" {
+            div {
+                label class="block text-gray-300 text-sm font-bold mb-1" { "Szukaj" }
+                input type="search" name="q" value=(params.q) placeholder="nazwa lub email" class=(input_class);
+            }
+            div {
+                label class="block text-gray-300 text-sm font-bold mb-1" { "Status" }
+                select name="status" class=(input_class) {
+                    option value="" { "Wszystkie" }
+                    @for status in AccountStatus::iter() {
+                        option value=(status.to_string()) selected[query.status == Some(status)] { (status.label()) }
+                    }
+                }
+            }
+            div {
+                label class="block text-gray-300 text-sm font-bold mb-1" { "Online" }
+                select name="online" class=(input_class) {
+                    option value="" { "Obojętnie" }
+                    option value="1" selected[query.online == Some(true)] { "Online" }
+                    option value="0" selected[query.online == Some(false)] { "Offline" }
+                }
+            }
+            div {
+                label class="block text-gray-300 text-sm font-bold mb-1" { "Rejestracja od" }
+                input type="date" name="from" value=(params.from) class=(input_class);
+            }
+            div {
+                label class="block text-gray-300 text-sm font-bold mb-1" { "do" }
+                input type="date" name="to" value=(params.to) class=(input_class);
+            }
+            (sort_inputs(&query, false))
+            button type="submit" class="bg-blue-600 hover:bg-blue-700 text-white font-bold py-2 px-4 rounded-md" { "Filtruj" }
+            a href="/admin/creators" class="text-gray-400 hover:text-white py-2" { "Wyczyść" }
+        }
+        (list)
+    };
+    Ok(Html(layout::page("Modelki", content).into_string()).into_response())
+}
+
+// Nagłówek panelu ze skrótami do sekcji, do których rola daje dostęp
+pub async fn dashboard_header(staff: &CurrentStaff, state: &AppState) -> Result<Markup, AppError> {
+    let moderates = staff.can(Permission::ModerateContent);
+    let (pending_photos, open_reports) = if moderates {
+        (
+            Photo::count_pending(&state.db).await?,
+            Report::count_open(&state.db).await?,
+        )
+    } else {
+        (0, 0)
+    };
+    let link = |permission: Permission, href: &'static str, label: &'static str| {
+        maud::html! {
+            @if staff.can(permission) {
+                a href=(href) class="text-blue-400 hover:text-blue-300" { (label) }
+            }
+        }
+    };
+
+    Ok(maud::html! {
+        div class="flex justify-between items-center mb-6" {
+            h1 class="text-3xl font-bold text-white" { a href="/admin" { "Panel Administratora" } }
+            nav class="flex gap-4" {
+                @if moderates {
+                    a href="/admin/moderation" class="text-blue-400 hover:text-blue-300" {
+                        "Moderacja zdjęć"
+                        @if pending_photos > 0 {
+                            span class="ml-1 text-xs font-semibold bg-yellow-500 text-black px-2 py-0.5 rounded-full" { (pending_photos) }
                         }
-                        div class="flex gap-2" {
-                            // Formularz z przyciskiem "Akceptuj", widoczny tylko dla niezaakceptowanych
-                            @if !erika.is_approved {
-                                form action=(format!("/admin/erika/{}/approve", erika.id)) method="post" {
-                                    button type="submit" class="bg-green-600 hover:bg-green-700 text-white font-bold py-1 px-3 rounded-md text-sm" {
-                                        "Akceptuj"
-                                    }
-                                }
-                            }
-                            a href=(format!("/admin/erika/{}", erika.id)) class="bg-blue-600 hover:bg-blue-700 text-white font-bold py-1 px-3 rounded-md text-sm" {
-                                "Edytuj"
-                            }
+                    }
+                    a href="/admin/reports" class="text-blue-400 hover:text-blue-300" {
+                        "Zgłoszenia"
+                        @if open_reports > 0 {
+                            span class="ml-1 text-xs font-semibold bg-red-600 text-white px-2 py-0.5 rounded-full" { (open_reports) }
                         }
                     }
                 }
+                (link(Permission::EditProfiles, "/admin/creators", "Modelki"))
+                (link(Permission::ApproveCreators, "/admin/verifications", "Weryfikacje"))
+                (link(Permission::ApproveCreators, "/admin/co-performers", "Osoby współwystępujące"))
+                (link(Permission::ManageSettings, "/admin/categories", "Kategorie"))
+                (link(Permission::ManageSettings, "/admin/settings", "Ustawienia"))
+                (link(Permission::SendAnnouncements, "/admin/announcements", "Ogłoszenia"))
+                (link(Permission::ManageStaff, "/admin/staff", "Zespół"))
+                (link(Permission::ViewAuditLog, "/admin/audit", "Dziennik audytu"))
             }
         }
+    })
+}
+
+// Ukryte pola sortowania w formularzu filtrów. Po kliknięciu nagłówka kolumny
+// przychodzą z listą jako `hx-swap-oob`, żeby kolejne filtrowanie zachowało sortowanie.
+fn sort_inputs(query: &CreatorQuery, out_of_band: bool) -> Markup {
+    maud::html! {
+        span id="creator-sort" hx-swap-oob=[out_of_band.then_some("true")] {
+            input type="hidden" name="sort" value=(query.sort.slug());
+            input type="hidden" name="dir" value=(if query.descending { "desc" } else { "asc" });
+        }
+    }
+}
+
+// Lista kont z nagłówkami sortowania - podmieniana w całości przez HTMX
+fn creator_list(
+    creators: &[CreatorListItem],
+    total: Option<i64>,
+    next_after: Option<&CreatorCursor>,
+    params: &CreatorFilterParams,
+    query: &CreatorQuery,
+    out_of_band: bool,
+) -> Markup {
+    // Nagłówek kolumny: klik sortuje rosnąco, kolejny klik odwraca kierunek
+    let sort_header = |label: &str, sort: CreatorSort| {
+        let active = query.sort == sort;
+        let descending = active && !query.descending;
+        let url = params.url(sort, descending, None);
+        maud::html! {
+            th class="p-3" {
+                a href=(url) hx-get=(url) hx-target="#creator-list" hx-swap="outerHTML" hx-push-url="true"
+                  class={ "hover:text-white " @if active { "text-white" } } {
+                    (label)
+                    @if active {
+                        @if query.descending { " ↓" } @else { " ↑" }
+                    }
+                }
+            }
+        }
+    };
+
+    maud::html! {
+        div id="creator-list" {
+            @if out_of_band {
+                (sort_inputs(query, true))
+            }
+            @if let Some(total) = total {
+                p class="text-gray-400 text-sm mb-2" { "Znaleziono: " (total) }
+            }
+            div class="bg-gray-800 rounded-lg shadow-lg overflow-x-auto" {
+                table class="w-full text-left text-sm" {
+                    thead class="text-gray-400 border-b border-gray-700" {
+                        tr {
+                            (sort_header("Nazwa", CreatorSort::Username))
+                            th class="p-3" { "Email" }
+                            (sort_header("Status", CreatorSort::Status))
+                            th class="p-3" { "Online" }
+                            (sort_header("Rejestracja", CreatorSort::Registered))
+                            th class="p-3" {}
+                        }
+                    }
+                    tbody {
+                        @if creators.is_empty() {
+                            tr { td colspan="6" class="p-4 text-gray-400" { "Brak kont pasujących do filtrów." } }
+                        }
+                        (creator_rows(creators, next_after, params, query))
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Wiersze listy i - jeśli jest dalsza część - wiersz "Pokaż więcej", który
+// podmienia się na kolejną stronę wierszy
+fn creator_rows(
+    creators: &[CreatorListItem],
+    next_after: Option<&CreatorCursor>,
+    params: &CreatorFilterParams,
+    query: &CreatorQuery,
+) -> Markup {
+    maud::html! {
+        @for erika in creators {
+            tr class="border-b border-gray-700" {
+                td class="p-3 text-white" { (erika.username) }
+                td class="p-3 text-gray-300" { (erika.email) }
+                td class="p-3" { (status_badge(erika.status)) }
+                td class="p-3" {
+                    @if erika.is_online {
+                        span class="text-green-400" { "● online" }
+                    } @else {
+                        span class="text-gray-500" { "offline" }
+                    }
+                }
+                td class="p-3 text-gray-400 whitespace-nowrap" { (layout::format_datetime(erika.created_at)) }
+                td class="p-3 text-right" {
+                    a href=(format!("/admin/erika/{}", erika.id)) class="bg-blue-600 hover:bg-blue-700 text-white font-bold py-1 px-3 rounded-md text-sm" {
+                        "Edytuj"
+                    }
+                }
+            }
+        }
+        @if let Some(after) = next_after {
+            @let url = params.url(query.sort, query.descending, Some(after));
+            tr {
+                td colspan="6" class="p-3 text-center" {
+                    a href=(url) hx-get=(url) hx-target="closest tr" hx-swap="outerHTML"
+                      class="text-blue-400 hover:text-blue-300" { "Pokaż więcej" }
+                }
+            }
+        }
+    }
+}
+
+/// Kolorowa etykieta statusu konta.
+fn status_badge(status: AccountStatus) -> Markup {
+    let class = match status {
+        AccountStatus::Pending => "bg-yellow-500 text-black",
+        AccountStatus::Approved => "bg-green-500 text-white",
+        AccountStatus::Rejected => "bg-gray-500 text-white",
+        AccountStatus::Suspended => "bg-orange-500 text-white",
+        AccountStatus::Banned => "bg-red-600 text-white",
     };
-    Ok(Html(layout::page("Admin", content).into_string()))
+    maud::html! {
+        span class={ "ml-4 text-xs font-semibold px-2 py-1 rounded-full " (class) } { (status.label()) }
+    }
 }
 
 // NOWY HANDLER: Wyświetla stronę edycji profilu konkretnej Eriki
 pub async fn show_edit_erika_form(
     Path(erika_id): Path<Uuid>, // Pobieramy ID z URL
+    admin: CurrentStaff,
     State(state): State<AppState>,
 ) -> Result<Html<String>, AppError> {
+    let page =
+        render_edit_erika_page(erika_id, &admin, None, &FormErrors::default(), &state).await?;
+    Ok(Html(page.into_string()))
+}
+
+// Strona edycji Eriki. `values` to dane wpisane w formularz, który nie przeszedł
+// walidacji - bez nich formularz wypełniamy danymi z bazy.
+async fn render_edit_erika_page(
+    erika_id: Uuid,
+    admin: &CurrentStaff,
+    values: Option<&UpdateProfilePayload>,
+    errors: &FormErrors,
+    state: &AppState,
+) -> Result<Markup, AppError> {
     let erika = Erika::find_by_id(erika_id, &state.db)
         .await
         .map_err(|_| AppError::InternalServerError)?
@@ -68,61 +405,248 @@ pub async fn show_edit_erika_form(
         .await
         .map_err(|_| AppError::InternalServerError)?;
 
+    let sessions =
+        UserSession::find_active_by_erika_id(erika_id, auth::SESSION_INACTIVITY_DAYS, &state.db)
+            .await?;
+
+    let verifications = IdentityVerification::find_by_erika_id(erika_id, &state.db).await?;
+
+    // Konta zespołu nie da się oglądać w podglądzie
+    let can_impersonate = admin.can(Permission::Impersonate)
+        && Erika::find_by_id_for_auth(erika_id, &state.db)
+            .await?
+            .is_some_and(|account| auth::can_be_impersonated(&account.role));
+
+    let balance = if admin.can(Permission::ManagePayouts) {
+        Some(Wallet::balance(erika_id, &state.db).await?)
+    } else {
+        None
+    };
+
+    // Historia zmian statusu pochodzi z dziennika audytu
+    let status_history = AuditEvent::find_filtered(
+        &AuditQuery {
+            action: Some(AuditAction::StatusChanged.to_string()),
+            target_id: Some(erika_id),
+            ..AuditQuery::default()
+        },
+        STATUS_HISTORY_LIMIT,
+        &state.db,
+    )
+    .await?;
+
+    let values = match values {
+        Some(values) => UpdateProfilePayload {
+            username: values.username.clone(),
+            email: values.email.clone(),
+            bio: values.bio.clone(),
+        },
+        None => UpdateProfilePayload {
+            username: erika.username.clone(),
+            email: erika.email.clone(),
+            bio: erika.bio.clone().unwrap_or_default(),
+        },
+    };
+
     let content = maud::html! {
-        a href="/admin" class="inline-block mb-6 text-blue-400 hover:text-blue-300 transition-colors" {
+        a href="/admin/creators" class="inline-block mb-6 text-blue-400 hover:text-blue-300 transition-colors" {
             "← Wróć do listy"
         }
         h1 class="text-3xl font-bold text-white mb-6" { "Edytuj profil: " (erika.username) }
 
+        div class="bg-gray-800 p-6 rounded-lg shadow-lg mb-8" {
+            h2 class="text-xl font-semibold text-white mb-2" { "Status konta" (status_badge(erika.status)) }
+            @if let Some(reason) = &erika.status_reason {
+                p class="text-gray-400 text-sm mb-4" { "Ostatni powód: " (reason) }
+            }
+            p class="text-gray-300 text-sm mb-4" {
+                "Weryfikacja tożsamości: "
+                @match verifications.first() {
+                    Some(v) => {
+                        a href=(format!("/admin/verifications/{}", v.id)) class="text-blue-400 hover:underline" { (v.status.label()) }
+                    }
+                    None => { span class="text-yellow-400" { "brak zgłoszenia" } }
+                }
+            }
+            form action=(format!("/admin/erika/{}/status", erika.id)) method="post" class="space-y-3" {
+                div class="flex flex-wrap gap-4" {
+                    @for (i, next) in erika.status.allowed_transitions().iter().enumerate() {
+                        label class="flex items-center gap-2 text-gray-300" {
+                            input type="radio" name="status" value=(next.to_string()) required checked[i == 0];
+                            (next.action_label())
+                        }
+                    }
+                }
+                (layout::field_error(errors, "status"))
+                textarea name="reason" rows="2" required maxlength=(STATUS_REASON_MAX_LEN)
+                         placeholder="Powód (widoczny dla modelki)"
+                         class="w-full px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white focus:outline-none focus:ring-2 focus:ring-blue-500" {}
+                (layout::field_error(errors, "reason"))
+                button type="submit" class="bg-blue-600 hover:bg-blue-700 text-white font-bold py-2 px-4 rounded-md" { "Zmień status" }
+            }
+            @if !status_history.is_empty() {
+                h3 class="text-white font-semibold mt-6 mb-2" { "Historia" }
+                ul class="text-sm divide-y divide-gray-700" {
+                    @for event in &status_history {
+                        li class="py-2 text-gray-300" {
+                            span class="text-gray-500" { (layout::format_datetime(event.created_at)) " · " (event.actor_username) }
+                            br;
+                            (event.changes["status"]["before"].as_str().unwrap_or("?"))
+                            " → "
+                            (event.changes["status"]["after"].as_str().unwrap_or("?"))
+                            @if let Some(reason) = event.changes["reason"].as_str() {
+                                ": " (reason)
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
         // --- POPRAWKA TUTAJ: Wypełniamy formularz ---
         div class="bg-gray-800 p-6 rounded-lg shadow-lg" {
             form action=(format!("/admin/erika/{}", erika.id)) method="post" {
                 div class="mb-4" {
                     label for="username" class="block text-gray-300 text-sm font-bold mb-2" { "Nazwa użytkownika:" }
-                    input type="text" name="username" value=(erika.username) required
+                    input type="text" name="username" value=(values.username) required
                           class="w-full px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white focus:outline-none focus:ring-2 focus:ring-blue-500";
+                    (layout::field_error(errors, "username"))
                 }
                 div class="mb-4" {
                     label for="email" class="block text-gray-300 text-sm font-bold mb-2" { "Email:" }
-                    input type="email" name="email" value=(erika.email) required
+                    input type="email" name="email" value=(values.email) required
                           class="w-full px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white focus:outline-none focus:ring-2 focus:ring-blue-500";
+                    (layout::field_error(errors, "email"))
                 }
                 div class="mb-6" {
                     label for="bio" class="block text-gray-300 text-sm font-bold mb-2" { "Krótkie bio:" }
                     textarea name="bio" rows="3"
                               class="w-full px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white focus:outline-none focus:ring-2 focus:ring-blue-500" {
-                        (erika.bio.as_deref().unwrap_or(""))
+                        (values.bio)
                     }
+                    (layout::field_error(errors, "bio"))
                 }
                 button type="submit" class="w-full bg-green-600 hover:bg-green-700 text-white font-bold py-2 px-4 rounded-md transition duration-300" { "Zapisz zmiany" }
             }
         }
         // --- KONIEC POPRAWKI ---
 
+        div class="bg-gray-800 p-6 rounded-lg shadow-lg mt-8 flex justify-between items-center" {
+            div {
+                h2 class="text-xl font-semibold text-white" { "Sesje" }
+                p class="text-gray-400" { "Aktywne sesje: " (sessions.len()) }
+            }
+            form action=(format!("/admin/erika/{}/logout-all", erika.id)) method="post" {
+                button type="submit" class="bg-red-600 hover:bg-red-700 text-white font-bold py-2 px-4 rounded-md" {
+                    "Wyloguj ze wszystkich urządzeń"
+                }
+            }
+        }
+
+        @if let Some(balance) = &balance {
+            div class="bg-gray-800 p-6 rounded-lg shadow-lg mt-8" {
+                h2 class="text-xl font-semibold text-white" { "Saldo napiwków" }
+                p class="text-gray-400 mb-4" { "Saldo: " (balance.with_scale(2)) " PLN" }
+                form action=(format!("/admin/erika/{}/wallet/credit", erika.id)) method="post" class="space-y-3" {
+                    input type="text" name="credit_amount" inputmode="decimal" required placeholder="Kwota (PLN)"
+                          class="w-full px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white focus:outline-none focus:ring-2 focus:ring-blue-500";
+                    (layout::field_error(errors, "credit_amount"))
+                    input type="text" name="credit_reason" required maxlength=(STATUS_REASON_MAX_LEN)
+                          placeholder="Powód, np. numer przelewu"
+                          class="w-full px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white focus:outline-none focus:ring-2 focus:ring-blue-500";
+                    (layout::field_error(errors, "credit_reason"))
+                    button type="submit" class="bg-blue-600 hover:bg-blue-700 text-white font-bold py-2 px-4 rounded-md" { "Doładuj saldo" }
+                }
+            }
+        }
+
+        @if admin.can(Permission::SendAnnouncements) {
+            div class="bg-gray-800 p-6 rounded-lg shadow-lg mt-8 flex justify-between items-center gap-4" {
+                div {
+                    h2 class="text-xl font-semibold text-white" { "Wiadomości" }
+                    p class="text-gray-400" { "Wiadomości od administracji z potwierdzeniem odczytu." }
+                }
+                a href=(format!("/admin/erika/{}/notices", erika.id)) class="bg-blue-600 hover:bg-blue-700 text-white font-bold py-2 px-4 rounded-md whitespace-nowrap" {
+                    "Napisz wiadomość"
+                }
+            }
+        }
+
+        @if can_impersonate {
+            div class="bg-gray-800 p-6 rounded-lg shadow-lg mt-8 flex justify-between items-center gap-4" {
+                div {
+                    h2 class="text-xl font-semibold text-white" { "Podgląd konta" }
+                    p class="text-gray-400" {
+                        "Zobacz serwis tak jak ta osoba. Można tylko przeglądać - zmiany, płatności i usuwanie są zablokowane, "
+                        "a każde żądanie trafia do dziennika audytu."
+                    }
+                }
+                form action=(format!("/admin/erika/{}/impersonate", erika.id)) method="post" {
+                    button type="submit" class="bg-purple-600 hover:bg-purple-700 text-white font-bold py-2 px-4 rounded-md whitespace-nowrap" {
+                        "Zobacz jako"
+                    }
+                }
+            }
+        }
+
         h2 class="text-2xl font-semibold text-white mt-8 mb-4" { "Galerie tej modelki" }
         @if galleries.is_empty() {
             p class="text-gray-400" { "Brak galerii." }
         } @else {
             div class="space-y-4" {
                 @for gallery in galleries {
-                    div class="bg-gray-700 p-4 rounded-lg" {
-                        p class="text-white" { (gallery.name.to_string()) }
+                    div class="bg-gray-700 p-4 rounded-lg flex justify-between items-center gap-4" {
+                        div {
+                            p class="text-white font-semibold" { (gallery.name) }
+                            p class="text-gray-400 text-sm" {
+                                @if gallery.blocked_at.is_some() {
+                                    span class="text-red-400" { "Ukryta" }
+                                } @else if gallery.published_at.is_some() {
+                                    span class="text-green-400" { "Opublikowana" }
+                                } @else {
+                                    "Szkic"
+                                }
+                                @if let Some(price) = &gallery.price_pln {
+                                    " · " (price.with_scale(2)) " PLN"
+                                }
+                            }
+                            @if let Some(description) = &gallery.description {
+                                p class="text-gray-300 text-sm mt-1" { (description) }
+                            }
+                        }
+                        a href=(format!("/admin/galleries/{}", gallery.id))
+                          class="bg-blue-600 hover:bg-blue-700 text-white font-bold py-2 px-4 rounded-md shrink-0" { "Zarządzaj" }
                     }
                 }
             }
         }
     };
-    Ok(Html(layout::page("Edytuj Erikę", content).into_string()))
+    Ok(layout::page("Edytuj Erikę", content))
 }
 
 // NOWY HANDLER: Przetwarza formularz edycji
 pub async fn update_erika_by_admin(
     Path(erika_id): Path<Uuid>,
+    admin: CurrentStaff,
+    client: ClientInfo,
     State(state): State<AppState>,
-    Form(payload): Form<crate::handlers::erika_handlers::UpdateProfilePayload>, // Używamy ponownie tej struktury
-) -> Result<Redirect, AppError> {
+    Form(mut payload): Form<UpdateProfilePayload>, // Używamy ponownie tej struktury
+) -> Result<Response, AppError> {
+    // Admin podlega tym samym regułom co sama Erika
+    payload.normalize();
+    if let Err(errors) = payload.validate() {
+        let page =
+            render_edit_erika_page(erika_id, &admin, Some(&payload), &errors, &state).await?;
+        return Ok(form_errors_response(page));
+    }
+
+    // Stan sprzed zmiany - do dziennika audytu
+    let before = Erika::find_by_id(erika_id, &state.db)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
     // Wywołujemy istniejącą logikę aktualizacji, ale bez uploadu avatara
-    Erika::update_profile_details(
+    if let Err(e) = Erika::update_profile_details(
         erika_id,
         &payload.username,
         &payload.email,
@@ -130,21 +654,243 @@ pub async fn update_erika_by_admin(
         &state.db,
     )
     .await
-    .map_err(|_| AppError::InternalServerError)?;
+    {
+        let errors = validation::unique_violation(&e).ok_or(AppError::from(e))?;
+        let page =
+            render_edit_erika_page(erika_id, &admin, Some(&payload), &errors, &state).await?;
+        return Ok(form_errors_response(page));
+    }
+
+    let changes = audit::diff(&[
+        ("username", json!(before.username), json!(payload.username)),
+        ("email", json!(before.email), json!(payload.email)),
+        (
+            "bio",
+            json!(before.bio.unwrap_or_default()),
+            json!(payload.bio),
+        ),
+    ]);
+    admin
+        .audit(
+            AuditAction::ProfileUpdated,
+            "erika",
+            Some(erika_id),
+            changes,
+            &client,
+            &state.db,
+        )
+        .await?;
+
+    info!(
+        "Admin {} ({}) zaktualizował profil dla: {}",
+        admin.0.username,
+        admin.id(),
+        erika_id
+    );
+    Ok(Redirect::to("/admin/creators").into_response())
+}
+
+#[derive(Deserialize, Default)]
+pub struct ChangeStatusPayload {
+    pub status: String,
+    pub reason: String,
+}
+
+/// Powód zmiany statusu widzi modelka w panelu, więc limit jak dla bio.
+const STATUS_REASON_MAX_LEN: usize = 500;
+
+// Zmiana statusu konta (akceptacja, odrzucenie, zawieszenie, ban, przywrócenie)
+pub async fn change_erika_status(
+    Path(erika_id): Path<Uuid>,
+    admin: CurrentStaff,
+    client: ClientInfo,
+    State(state): State<AppState>,
+    Form(payload): Form<ChangeStatusPayload>,
+) -> Result<Response, AppError> {
+    let before = Erika::find_by_id(erika_id, &state.db)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    let reason = payload.reason.trim();
+    let mut errors = FormErrors::default();
+    let next = AccountStatus::from_str(&payload.status).ok();
+    match next {
+        Some(next) if before.status.can_transition_to(next) => {}
+        _ => errors.add(
+            "status",
+            format!(
+                "Z obecnego statusu ({}) nie można przejść do wybranego.",
+                before.status.label()
+            ),
+        ),
+    }
+    if reason.is_empty() {
+        errors.add("reason", "Podaj powód - zobaczy go modelka.");
+    } else if reason.chars().count() > STATUS_REASON_MAX_LEN {
+        errors.add(
+            "reason",
+            format!("Powód może mieć najwyżej {} znaków.", STATUS_REASON_MAX_LEN),
+        );
+    }
+    if next == Some(AccountStatus::Approved)
+        && !IdentityVerification::has_verified(erika_id, &state.db).await?
+    {
+        errors.add(
+            "status",
+            "Nie można zaakceptować konta bez potwierdzonej tożsamości i wieku.",
+        );
+    }
+    let Some(next) = next.filter(|_| errors.is_empty()) else {
+        let page = render_edit_erika_page(erika_id, &admin, None, &errors, &state).await?;
+        return Ok(form_errors_response(page));
+    };
 
-    info!("Admin zaktualizował profil dla: {}", erika_id);
-    Ok(Redirect::to("/admin"))
+    // Status mógł się zmienić, zanim admin kliknął - wtedy nic nie robimy
+    if !Erika::change_status(erika_id, before.status, next, reason, &state.db).await? {
+        let mut errors = FormErrors::default();
+        errors.add(
+            "status",
+            "Status konta zmienił się w międzyczasie. Sprawdź go i spróbuj ponownie.",
+        );
+        let page = render_edit_erika_page(erika_id, &admin, None, &errors, &state).await?;
+        return Ok(form_errors_response(page));
+    }
+
+    state.publish(CreatorEvent::Listing {
+        erika_id,
+        listed: next == AccountStatus::Approved,
+    });
+
+    // Ban odcina wszystkie zalogowane urządzenia od razu
+    if next == AccountStatus::Banned {
+        auth::revoke_all_sessions(&state, erika_id).await?;
+    }
+
+    let mut changes = audit::diff(&[(
+        "status",
+        json!(before.status.to_string()),
+        json!(next.to_string()),
+    )]);
+    changes["reason"] = json!(reason);
+    admin
+        .audit(
+            AuditAction::StatusChanged,
+            "erika",
+            Some(erika_id),
+            changes,
+            &client,
+            &state.db,
+        )
+        .await?;
+
+    info!(
+        "Admin {} ({}) zmienił status {} z {} na {}",
+        admin.0.username,
+        admin.id(),
+        erika_id,
+        before.status,
+        next
+    );
+    Ok(Redirect::to(&format!("/admin/erika/{}", erika_id)).into_response())
 }
 
-// NOWY HANDLER: Akceptuje profil Eriki
-pub async fn approve_erika(
+// Wymusza wylogowanie konta ze wszystkich urządzeń
+pub async fn force_logout_erika(
     Path(erika_id): Path<Uuid>,
+    admin: CurrentStaff,
+    client: ClientInfo,
     State(state): State<AppState>,
 ) -> Result<Redirect, AppError> {
-    Erika::approve(erika_id, &state.db)
-        .await
-        .map_err(|_| AppError::InternalServerError)?;
+    let revoked = auth::revoke_all_sessions(&state, erika_id).await?;
+
+    admin
+        .audit(
+            AuditAction::SessionsRevoked,
+            "erika",
+            Some(erika_id),
+            json!({ "revoked_sessions": revoked }),
+            &client,
+            &state.db,
+        )
+        .await?;
+
+    info!(
+        "Admin {} ({}) wylogował {} sesji konta: {}",
+        admin.0.username,
+        admin.id(),
+        revoked,
+        erika_id
+    );
+    Ok(Redirect::to(&format!("/admin/erika/{}", erika_id)))
+}
+
+#[derive(Deserialize, Default)]
+pub struct CreditWalletPayload {
+    pub credit_amount: String,
+    pub credit_reason: String,
+}
+
+/// Doładowanie salda napiwków, np. po wpłacie przelewem - to samo co
+/// `make_admin credit`, z wpisem w dzienniku audytu.
+pub async fn credit_wallet(
+    Path(erika_id): Path<Uuid>,
+    admin: CurrentStaff,
+    client: ClientInfo,
+    State(state): State<AppState>,
+    Form(payload): Form<CreditWalletPayload>,
+) -> Result<Response, AppError> {
+    let erika = Erika::find_by_id(erika_id, &state.db)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    let reason = payload.credit_reason.trim();
+    let mut errors = FormErrors::default();
+    let amount = validation::parse_pln(payload.credit_amount.trim())
+        .filter(|amount| *amount > BigDecimal::from(0));
+    if amount.is_none() {
+        errors.add(
+            "credit_amount",
+            "Podaj dodatnią kwotę, z najwyżej dwoma miejscami po przecinku.",
+        );
+    }
+    if reason.is_empty() {
+        errors.add(
+            "credit_reason",
+            "Podaj powód doładowania, np. numer przelewu.",
+        );
+    } else if reason.chars().count() > STATUS_REASON_MAX_LEN {
+        errors.add(
+            "credit_reason",
+            format!("Powód może mieć najwyżej {} znaków.", STATUS_REASON_MAX_LEN),
+        );
+    }
+    let Some(amount) = amount.filter(|_| errors.is_empty()) else {
+        let page = render_edit_erika_page(erika_id, &admin, None, &errors, &state).await?;
+        return Ok(form_errors_response(page));
+    };
+
+    let balance = Wallet::credit(erika_id, &amount, reason, &state.db).await?;
+    admin
+        .audit(
+            AuditAction::WalletCredited,
+            "erika",
+            Some(erika_id),
+            json!({
+                "amount_pln": amount.with_scale(2).to_string(),
+                "balance_pln": balance.with_scale(2).to_string(),
+                "reason": reason,
+            }),
+            &client,
+            &state.db,
+        )
+        .await?;
 
-    info!("Admin zaakceptował profil: {}", erika_id);
-    Ok(Redirect::to("/admin"))
+    info!(
+        "Admin {} ({}) doładował saldo {} o {} PLN",
+        admin.0.username,
+        admin.id(),
+        erika.username,
+        amount.with_scale(2)
+    );
+    Ok(Redirect::to(&format!("/admin/erika/{}", erika_id)).into_response())
 }
@@ -0,0 +1,265 @@
+// src/handlers/announcement_handlers.rs
+
+// Ogłoszenia serwisu. Trwające ogłoszenie pokazuje się jako pasek nad treścią
+// każdej strony (middleware `announcements` + `layout::page`).
+
+use super::erika_handlers::form_errors_response;
+use super::layout;
+use crate::auth::{ClientInfo, CurrentStaff};
+use crate::models::announcement::{Announcement, AnnouncementAudience};
+use crate::models::audit::AuditAction;
+use crate::validation::{self, FormErrors, Validate};
+use crate::{app_state::AppState, errors::AppError};
+use axum::{
+    Form,
+    extract::{Path, State},
+    response::{Html, IntoResponse, Redirect, Response},
+};
+use maud::Markup;
+use serde::Deserialize;
+use serde_json::json;
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+use time::OffsetDateTime;
+use tracing::info;
+use uuid::Uuid;
+
+#[derive(Deserialize, Default)]
+pub struct AnnouncementPayload {
+    pub message: String,
+    pub audience: String,
+    /// Pola `datetime-local` w UTC; pusty początek oznacza "od teraz".
+    #[serde(default)]
+    pub starts_at: String,
+    #[serde(default)]
+    pub ends_at: String,
+}
+
+impl Validate for AnnouncementPayload {
+    fn normalize(&mut self) {
+        self.message = self.message.trim().to_string();
+        self.starts_at = self.starts_at.trim().to_string();
+        self.ends_at = self.ends_at.trim().to_string();
+    }
+
+    fn validate(&self) -> Result<(), FormErrors> {
+        let mut errors = FormErrors::default();
+        let message_len = self.message.chars().count();
+        if message_len == 0 || message_len > validation::ANNOUNCEMENT_MAX_LEN {
+            errors.add(
+                "message",
+                format!(
+                    "Treść musi mieć od 1 do {} znaków.",
+                    validation::ANNOUNCEMENT_MAX_LEN
+                ),
+            );
+        }
+        if AnnouncementAudience::from_str(&self.audience).is_err() {
+            errors.add("audience", "Wybierz odbiorców z listy.");
+        }
+        let starts_at = layout::parse_datetime_input(&self.starts_at);
+        if !self.starts_at.is_empty() && starts_at.is_none() {
+            errors.add("starts_at", "Niepoprawna data rozpoczęcia.");
+        }
+        if !self.ends_at.is_empty() {
+            match layout::parse_datetime_input(&self.ends_at) {
+                None => errors.add("ends_at", "Niepoprawna data zakończenia."),
+                Some(ends_at) if starts_at.is_some_and(|starts_at| ends_at <= starts_at) => {
+                    errors.add("ends_at", "Koniec musi być później niż początek.")
+                }
+                Some(_) => {}
+            }
+        }
+        errors.into_result()
+    }
+}
+
+pub async fn show_announcements(State(state): State<AppState>) -> Result<Html<String>, AppError> {
+    let values = AnnouncementPayload {
+        audience: AnnouncementAudience::Everyone.to_string(),
+        ..AnnouncementPayload::default()
+    };
+    let page = render_announcements_page(&values, &FormErrors::default(), &state).await?;
+    Ok(Html(page.into_string()))
+}
+
+async fn render_announcements_page(
+    values: &AnnouncementPayload,
+    errors: &FormErrors,
+    state: &AppState,
+) -> Result<Markup, AppError> {
+    let announcements = Announcement::find_all(&state.db).await?;
+    let now = OffsetDateTime::now_utc();
+    let input_class = "w-full px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white focus:outline-none focus:ring-2 focus:ring-blue-500";
+    let label_class = "block text-gray-300 text-sm font-bold mb-2";
+
+    let content = maud::html! {
+        a href="/admin" class="inline-block mb-6 text-blue-400 hover:text-blue-300 transition-colors" {
+            "← Wróć do panelu"
+        }
+        h1 class="text-3xl font-bold text-white mb-6" { "Ogłoszenia" }
+
+        div class="bg-gray-800 p-6 rounded-lg shadow-lg mb-6" {
+            h2 class="text-xl font-semibold text-white mb-4" { "Nowe ogłoszenie" }
+            form action="/admin/announcements" method="post" class="space-y-4" {
+                div {
+                    label for="message" class=(label_class) { "Treść:" }
+                    textarea id="message" name="message" rows="2" required
+                             maxlength=(validation::ANNOUNCEMENT_MAX_LEN) class=(input_class) { (values.message) }
+                    (layout::field_error(errors, "message"))
+                }
+                div class="grid grid-cols-1 md:grid-cols-3 gap-4" {
+                    div {
+                        label for="audience" class=(label_class) { "Odbiorcy:" }
+                        select id="audience" name="audience" class=(input_class) {
+                            @for audience in AnnouncementAudience::iter() {
+                                option value=(audience.to_string()) selected[values.audience == audience.to_string()] { (audience.label()) }
+                            }
+                        }
+                        (layout::field_error(errors, "audience"))
+                    }
+                    div {
+                        label for="starts_at" class=(label_class) { "Od (UTC, puste - od teraz):" }
+                        input type="datetime-local" id="starts_at" name="starts_at" value=(values.starts_at) class=(input_class);
+                        (layout::field_error(errors, "starts_at"))
+                    }
+                    div {
+                        label for="ends_at" class=(label_class) { "Do (UTC, puste - bez końca):" }
+                        input type="datetime-local" id="ends_at" name="ends_at" value=(values.ends_at) class=(input_class);
+                        (layout::field_error(errors, "ends_at"))
+                    }
+                }
+                button type="submit" class="bg-blue-600 hover:bg-blue-700 text-white font-bold py-2 px-4 rounded-md" { "Opublikuj" }
+            }
+        }
+
+        @if announcements.is_empty() {
+            p class="text-gray-400" { "Brak ogłoszeń." }
+        } @else {
+            div class="bg-gray-800 rounded-lg shadow-lg" {
+                table class="w-full text-left text-sm" {
+                    thead class="text-gray-400 border-b border-gray-700" {
+                        tr {
+                            th class="p-3" { "Treść" }
+                            th class="p-3" { "Odbiorcy" }
+                            th class="p-3" { "Od" }
+                            th class="p-3" { "Do" }
+                            th class="p-3" { "Status" }
+                            th class="p-3" {}
+                        }
+                    }
+                    tbody {
+                        @for announcement in &announcements {
+                            tr class="border-b border-gray-700" {
+                                td class="p-3 text-white whitespace-pre-line" { (announcement.message) }
+                                td class="p-3 text-gray-300" { (announcement.audience.label()) }
+                                td class="p-3 text-gray-300 whitespace-nowrap" { (layout::format_datetime(announcement.starts_at)) }
+                                td class="p-3 text-gray-300 whitespace-nowrap" {
+                                    @match announcement.ends_at {
+                                        Some(ends_at) => (layout::format_datetime(ends_at)),
+                                        None => "-",
+                                    }
+                                }
+                                td class="p-3" {
+                                    @if announcement.starts_at > now {
+                                        span class="text-yellow-400" { "Zaplanowane" }
+                                    } @else if announcement.ends_at.is_some_and(|ends_at| ends_at <= now) {
+                                        span class="text-gray-500" { "Zakończone" }
+                                    } @else {
+                                        span class="text-green-400" { "Trwa" }
+                                    }
+                                }
+                                td class="p-3 text-right" {
+                                    form action=(format!("/admin/announcements/{}/delete", announcement.id)) method="post" {
+                                        button type="submit" class="text-red-400 hover:text-red-300" { "Usuń" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    };
+    Ok(layout::page("Ogłoszenia", content))
+}
+
+pub async fn create_announcement(
+    admin: CurrentStaff,
+    client: ClientInfo,
+    State(state): State<AppState>,
+    Form(mut payload): Form<AnnouncementPayload>,
+) -> Result<Response, AppError> {
+    payload.normalize();
+    if let Err(errors) = payload.validate() {
+        let page = render_announcements_page(&payload, &errors, &state).await?;
+        return Ok(form_errors_response(page));
+    }
+
+    // Wartości są już zwalidowane
+    let audience =
+        AnnouncementAudience::from_str(&payload.audience).unwrap_or(AnnouncementAudience::Everyone);
+    let starts_at =
+        layout::parse_datetime_input(&payload.starts_at).unwrap_or_else(OffsetDateTime::now_utc);
+    let ends_at = layout::parse_datetime_input(&payload.ends_at);
+    let announcement_id = Announcement::create(
+        &payload.message,
+        audience,
+        starts_at,
+        ends_at,
+        admin.id(),
+        &state.db,
+    )
+    .await?;
+
+    admin
+        .audit(
+            AuditAction::AnnouncementCreated,
+            "announcement",
+            Some(announcement_id),
+            json!({
+                "message": payload.message,
+                "audience": audience.to_string(),
+                "starts_at": layout::format_datetime(starts_at),
+                "ends_at": ends_at.map(layout::format_datetime),
+            }),
+            &client,
+            &state.db,
+        )
+        .await?;
+    info!(
+        "Admin {} dodał ogłoszenie {}",
+        admin.0.username, announcement_id
+    );
+    Ok(Redirect::to("/admin/announcements").into_response())
+}
+
+pub async fn delete_announcement(
+    Path(announcement_id): Path<Uuid>,
+    admin: CurrentStaff,
+    client: ClientInfo,
+    State(state): State<AppState>,
+) -> Result<Redirect, AppError> {
+    let announcement = Announcement::find_by_id(announcement_id, &state.db)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    if !Announcement::delete(announcement_id, &state.db).await? {
+        return Err(AppError::NotFound);
+    }
+
+    admin
+        .audit(
+            AuditAction::AnnouncementDeleted,
+            "announcement",
+            Some(announcement_id),
+            json!({ "message": announcement.message, "audience": announcement.audience.to_string() }),
+            &client,
+            &state.db,
+        )
+        .await?;
+    info!(
+        "Admin {} usunął ogłoszenie {}",
+        admin.0.username, announcement_id
+    );
+    Ok(Redirect::to("/admin/announcements"))
+}
@@ -0,0 +1,178 @@
+// src/handlers/audit_handlers.rs
+
+use super::layout;
+use crate::models::audit::{AuditAction, AuditEvent, AuditQuery};
+use crate::{app_state::AppState, errors::AppError};
+use axum::extract::{Query, State};
+use axum::response::Html;
+use chrono::NaiveDate;
+use serde::Deserialize;
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+use uuid::Uuid;
+
+const PAGE_SIZE: i64 = 50;
+
+// Parametry z formularza filtrów - puste pola przychodzą jako puste stringi
+#[derive(Deserialize, Default)]
+pub struct AuditFilterParams {
+    #[serde(default)]
+    pub action: String,
+    #[serde(default)]
+    pub actor: String,
+    #[serde(default)]
+    pub target_id: String,
+    #[serde(default)]
+    pub from: String,
+    #[serde(default)]
+    pub to: String,
+    pub before: Option<i64>,
+}
+
+impl AuditFilterParams {
+    fn to_query(&self) -> AuditQuery {
+        let non_empty = |value: &str| {
+            let value = value.trim();
+            (!value.is_empty()).then(|| value.to_string())
+        };
+        // Niepoprawną datę po prostu ignorujemy zamiast zwracać błąd
+        let date = |value: &str| {
+            NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+                .ok()
+                .map(|d| d.to_string())
+        };
+
+        AuditQuery {
+            action: non_empty(&self.action).filter(|a| AuditAction::from_str(a).is_ok()),
+            actor_username: non_empty(&self.actor),
+            target_id: Uuid::parse_str(self.target_id.trim()).ok(),
+            date_from: date(&self.from),
+            date_to: date(&self.to),
+            before_id: self.before,
+        }
+    }
+
+    // Link do następnej strony z zachowaniem filtrów
+    fn next_page_url(&self, before: i64) -> String {
+        let mut url = format!("/admin/audit?before={}", before);
+        for (key, value) in [
+            ("action", &self.action),
+            ("actor", &self.actor),
+            ("target_id", &self.target_id),
+            ("from", &self.from),
+            ("to", &self.to),
+        ] {
+            if !value.is_empty() {
+                url.push_str(&format!("&{}={}", key, layout::urlencode(value)));
+            }
+        }
+        url
+    }
+}
+
+// Przeglądarka dziennika audytu z filtrami
+pub async fn show_audit_log(
+    Query(params): Query<AuditFilterParams>,
+    State(state): State<AppState>,
+) -> Result<Html<String>, AppError> {
+    let query = params.to_query();
+    let events = AuditEvent::find_filtered(&query, PAGE_SIZE, &state.db).await?;
+    let next_before = (events.len() as i64 == PAGE_SIZE)
+        .then(|| events.last().map(|e| e.id))
+        .flatten();
+
+    let input_class = "px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white focus:outline-none focus:ring-2 focus:ring-blue-500";
+
+    let content = maud::html! {
+        a href="/admin" class="inline-block mb-6 text-blue-400 hover:text-blue-300 transition-colors" {
+            "← Wróć do panelu"
+        }
+        h1 class="text-3xl font-bold text-white mb-6" { "Dziennik audytu" }
+
+        form action="/admin/audit" method="get" class="bg-gray-800 p-4 rounded-lg shadow-lg mb-6 flex flex-wrap gap-4 items-end" {
+            div {
+                label class="block text-gray-300 text-sm font-bold mb-1" { "Działanie" }
+                select name="action" class=(input_class) {
+                    option value="" { "Wszystkie" }
+                    @for action in AuditAction::iter() {
+                        option value=(action.to_string()) selected[params.action == action.to_string()] { (action.label()) }
+                    }
+                }
+            }
+            div {
+                label class="block text-gray-300 text-sm font-bold mb-1" { "Admin" }
+                input type="text" name="actor" value=(params.actor) placeholder="nazwa użytkownika" class=(input_class);
+            }
+            div {
+                label class="block text-gray-300 text-sm font-bold mb-1" { "ID celu" }
+                input type="text" name="target_id" value=(params.target_id) placeholder="UUID" class=(input_class);
+            }
+            div {
+                label class="block text-gray-300 text-sm font-bold mb-1" { "Od" }
+                input type="date" name="from" value=(params.from) class=(input_class);
+            }
+            div {
+                label class="block text-gray-300 text-sm font-bold mb-1" { "Do" }
+                input type="date" name="to" value=(params.to) class=(input_class);
+            }
+            button type="submit" class="bg-blue-600 hover:bg-blue-700 text-white font-bold py-2 px-4 rounded-md" { "Filtruj" }
+            a href="/admin/audit" class="text-gray-400 hover:text-white py-2" { "Wyczyść" }
+        }
+
+        @if events.is_empty() {
+            p class="text-gray-400" { "Brak wpisów pasujących do filtrów." }
+        } @else {
+            div class="bg-gray-800 rounded-lg shadow-lg overflow-x-auto" {
+                table class="w-full text-left text-sm" {
+                    thead class="text-gray-400 border-b border-gray-700" {
+                        tr {
+                            th class="p-3" { "Kiedy" }
+                            th class="p-3" { "Kto" }
+                            th class="p-3" { "Działanie" }
+                            th class="p-3" { "Cel" }
+                            th class="p-3" { "Zmiany" }
+                            th class="p-3" { "IP" }
+                        }
+                    }
+                    tbody {
+                        @for event in &events {
+                            tr class="border-b border-gray-700 align-top" {
+                                td class="p-3 whitespace-nowrap" { (layout::format_datetime(event.created_at)) }
+                                td class="p-3" {
+                                    @if let Some(actor_id) = event.actor_id {
+                                        a href=(format!("/admin/erika/{}", actor_id)) class="text-blue-400 hover:underline" { (event.actor_username) }
+                                    } @else {
+                                        (event.actor_username)
+                                    }
+                                }
+                                td class="p-3" {
+                                    @match AuditAction::from_str(&event.action) {
+                                        Ok(action) => { (action.label()) }
+                                        Err(_) => { (event.action) }
+                                    }
+                                }
+                                td class="p-3" {
+                                    (event.target_type)
+                                    @if let Some(target_id) = event.target_id {
+                                        br;
+                                        a href=(format!("/admin/audit?target_id={}", target_id)) class="text-blue-400 hover:underline text-xs" { (target_id) }
+                                    }
+                                }
+                                td class="p-3" {
+                                    code class="text-xs text-gray-300 break-all" { (event.changes.to_string()) }
+                                }
+                                td class="p-3" { (event.ip_address.as_deref().unwrap_or("—")) }
+                            }
+                        }
+                    }
+                }
+            }
+            @if let Some(before) = next_before {
+                div class="mt-4 text-right" {
+                    a href=(params.next_page_url(before)) class="text-blue-400 hover:text-blue-300" { "Starsze wpisy →" }
+                }
+            }
+        }
+    };
+    Ok(Html(layout::page("Dziennik audytu", content).into_string()))
+}
@@ -0,0 +1,374 @@
+// src/handlers/category_handlers.rs
+
+// Kategorie galerii zarządzane przez admina. Kategorii używanej przez galerie
+// nie usuwamy - można ją wyłączyć, żeby zniknęła z wyboru dla nowych galerii.
+
+use super::erika_handlers::form_errors_response;
+use super::layout;
+use crate::auth::{ClientInfo, CurrentStaff};
+use crate::models::audit::{self, AuditAction};
+use crate::models::category::{Category, CategoryFields};
+use crate::validation::{self, FormErrors, Validate};
+use crate::{app_state::AppState, errors::AppError};
+use axum::extract::Path;
+use axum::{
+    Form,
+    extract::State,
+    http::StatusCode,
+    response::{Html, IntoResponse, Redirect, Response},
+};
+use maud::Markup;
+use serde::Deserialize;
+use serde_json::json;
+use tracing::info;
+use uuid::Uuid;
+
+#[derive(Deserialize, Default)]
+pub struct CategoryPayload {
+    #[serde(default)]
+    pub slug: String,
+    pub name: String,
+    #[serde(default)]
+    pub sort_order: String,
+    pub is_active: Option<String>,
+}
+
+impl Validate for CategoryPayload {
+    fn normalize(&mut self) {
+        self.name = self.name.trim().to_string();
+        self.slug = self.slug.trim().to_lowercase();
+        self.sort_order = self.sort_order.trim().to_string();
+        if self.slug.is_empty() {
+            self.slug = validation::slugify(&self.name);
+        }
+    }
+
+    fn validate(&self) -> Result<(), FormErrors> {
+        let mut errors = FormErrors::default();
+        let name_len = self.name.chars().count();
+        if name_len == 0 {
+            errors.add("name", "Nazwa kategorii jest wymagana.");
+        } else if name_len > validation::CATEGORY_NAME_MAX_LEN {
+            errors.add(
+                "name",
+                format!(
+                    "Nazwa może mieć najwyżej {} znaków.",
+                    validation::CATEGORY_NAME_MAX_LEN
+                ),
+            );
+        }
+        if self.slug.is_empty() || self.slug.len() > validation::CATEGORY_SLUG_MAX_LEN {
+            errors.add(
+                "slug",
+                format!(
+                    "Slug musi mieć od 1 do {} znaków.",
+                    validation::CATEGORY_SLUG_MAX_LEN
+                ),
+            );
+        } else if !self
+            .slug
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+            || self.slug.starts_with('-')
+            || self.slug.ends_with('-')
+        {
+            errors.add(
+                "slug",
+                "Slug może zawierać tylko małe litery (bez polskich znaków), cyfry i myślniki.",
+            );
+        }
+        if self.sort_order.parse::<i32>().is_err() {
+            errors.add("sort_order", "Kolejność musi być liczbą całkowitą.");
+        }
+        errors.into_result()
+    }
+}
+
+impl CategoryPayload {
+    fn from_category(category: &Category) -> Self {
+        CategoryPayload {
+            slug: category.slug.clone(),
+            name: category.name.clone(),
+            sort_order: category.sort_order.to_string(),
+            is_active: category.is_active.then(|| "1".to_string()),
+        }
+    }
+
+    /// Pola po walidacji - `sort_order` jest już sprawdzone jako liczba.
+    fn fields(&self) -> CategoryFields<'_> {
+        CategoryFields {
+            slug: &self.slug,
+            name: &self.name,
+            sort_order: self.sort_order.parse().unwrap_or_default(),
+            is_active: self.is_active.is_some(),
+        }
+    }
+}
+
+fn category_form(
+    action: &str,
+    values: &CategoryPayload,
+    errors: &FormErrors,
+    submit: &str,
+) -> Markup {
+    let input_class = "w-full px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white focus:outline-none focus:ring-2 focus:ring-blue-500";
+    maud::html! {
+        form action=(action) method="post" class="grid grid-cols-1 md:grid-cols-4 gap-4 items-start" {
+            div {
+                label for="name" class="block text-gray-300 text-sm font-bold mb-2" { "Nazwa:" }
+                input type="text" id="name" name="name" value=(values.name) required
+                      maxlength=(validation::CATEGORY_NAME_MAX_LEN) class=(input_class);
+                (layout::field_error(errors, "name"))
+            }
+            div {
+                label for="slug" class="block text-gray-300 text-sm font-bold mb-2" { "Slug (puste - z nazwy):" }
+                input type="text" id="slug" name="slug" value=(values.slug)
+                      maxlength=(validation::CATEGORY_SLUG_MAX_LEN) class=(input_class);
+                (layout::field_error(errors, "slug"))
+            }
+            div {
+                label for="sort_order" class="block text-gray-300 text-sm font-bold mb-2" { "Kolejność:" }
+                input type="number" id="sort_order" name="sort_order" value=(values.sort_order) required class=(input_class);
+                (layout::field_error(errors, "sort_order"))
+            }
+            div class="flex flex-col gap-3 md:pt-7" {
+                label class="flex items-center gap-2 text-gray-300" {
+                    input type="checkbox" name="is_active" value="1" checked[values.is_active.is_some()];
+                    "Aktywna"
+                }
+                button type="submit" class="bg-blue-600 hover:bg-blue-700 text-white font-bold py-2 px-4 rounded-md" { (submit) }
+            }
+        }
+    }
+}
+
+// Lista kategorii z formularzem dodawania
+pub async fn show_categories(State(state): State<AppState>) -> Result<Html<String>, AppError> {
+    let values = CategoryPayload {
+        sort_order: "0".to_string(),
+        is_active: Some("1".to_string()),
+        ..CategoryPayload::default()
+    };
+    let page = render_categories_page(&values, &FormErrors::default(), &state).await?;
+    Ok(Html(page.into_string()))
+}
+
+async fn render_categories_page(
+    values: &CategoryPayload,
+    errors: &FormErrors,
+    state: &AppState,
+) -> Result<Markup, AppError> {
+    let categories = Category::find_all_with_usage(&state.db).await?;
+
+    let content = maud::html! {
+        a href="/admin" class="inline-block mb-6 text-blue-400 hover:text-blue-300 transition-colors" {
+            "← Wróć do panelu"
+        }
+        h1 class="text-3xl font-bold text-white mb-6" { "Kategorie galerii" }
+
+        div class="bg-gray-800 p-6 rounded-lg shadow-lg mb-6" {
+            h2 class="text-xl font-semibold text-white mb-4" { "Dodaj kategorię" }
+            (category_form("/admin/categories", values, errors, "Dodaj"))
+        }
+
+        div class="bg-gray-800 rounded-lg shadow-lg" {
+            table class="w-full text-left text-sm" {
+                thead class="text-gray-400 border-b border-gray-700" {
+                    tr {
+                        th class="p-3" { "Kolejność" }
+                        th class="p-3" { "Nazwa" }
+                        th class="p-3" { "Slug" }
+                        th class="p-3" { "Galerie" }
+                        th class="p-3" { "Status" }
+                        th class="p-3" {}
+                    }
+                }
+                tbody {
+                    @for category in &categories {
+                        tr class="border-b border-gray-700" {
+                            td class="p-3 text-gray-400" { (category.sort_order) }
+                            td class="p-3 text-white" { (category.name) }
+                            td class="p-3 text-gray-300" { code { (category.slug) } }
+                            td class="p-3 text-gray-300" { (category.gallery_count) }
+                            td class="p-3" {
+                                @if category.is_active {
+                                    span class="text-green-400" { "Aktywna" }
+                                } @else {
+                                    span class="text-gray-500" { "Wyłączona" }
+                                }
+                            }
+                            td class="p-3 text-right whitespace-nowrap" {
+                                a href=(format!("/admin/categories/{}", category.id)) class="text-blue-400 hover:underline" { "Edytuj" }
+                                @if category.gallery_count == 0 {
+                                    form action=(format!("/admin/categories/{}/delete", category.id)) method="post" class="inline ml-4" {
+                                        button type="submit" class="text-red-400 hover:text-red-300" { "Usuń" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    };
+    Ok(layout::page("Kategorie", content))
+}
+
+pub async fn create_category(
+    admin: CurrentStaff,
+    client: ClientInfo,
+    State(state): State<AppState>,
+    Form(mut payload): Form<CategoryPayload>,
+) -> Result<Response, AppError> {
+    payload.normalize();
+    if let Err(errors) = payload.validate() {
+        let page = render_categories_page(&payload, &errors, &state).await?;
+        return Ok(form_errors_response(page));
+    }
+
+    let fields = payload.fields();
+    let category_id = match Category::create(&fields, &state.db).await {
+        Ok(id) => id,
+        Err(e) => {
+            let errors = validation::unique_violation(&e).ok_or(AppError::from(e))?;
+            let page = render_categories_page(&payload, &errors, &state).await?;
+            return Ok(form_errors_response(page));
+        }
+    };
+
+    admin
+        .audit(
+            AuditAction::CategoryCreated,
+            "category",
+            Some(category_id),
+            json!({ "slug": fields.slug, "name": fields.name, "sort_order": fields.sort_order, "is_active": fields.is_active }),
+            &client,
+            &state.db,
+        )
+        .await?;
+    info!("Admin {} dodał kategorię {}", admin.0.username, fields.slug);
+    Ok(Redirect::to("/admin/categories").into_response())
+}
+
+// Formularz edycji jednej kategorii
+pub async fn show_edit_category(
+    Path(category_id): Path<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Html<String>, AppError> {
+    let category = Category::find_by_id(category_id, &state.db)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    let values = CategoryPayload::from_category(&category);
+    Ok(Html(
+        render_edit_page(&category, &values, &FormErrors::default()).into_string(),
+    ))
+}
+
+fn render_edit_page(category: &Category, values: &CategoryPayload, errors: &FormErrors) -> Markup {
+    let content = maud::html! {
+        a href="/admin/categories" class="inline-block mb-6 text-blue-400 hover:text-blue-300 transition-colors" {
+            "← Wróć do kategorii"
+        }
+        h1 class="text-3xl font-bold text-white mb-2" { "Edytuj kategorię: " (category.name) }
+        p class="text-gray-400 mb-6" {
+            "Zmiana nazwy będzie widoczna we wszystkich galeriach tej kategorii."
+        }
+        div class="bg-gray-800 p-6 rounded-lg shadow-lg" {
+            (category_form(&format!("/admin/categories/{}", category.id), values, errors, "Zapisz"))
+        }
+    };
+    layout::page("Edytuj kategorię", content)
+}
+
+pub async fn update_category(
+    Path(category_id): Path<Uuid>,
+    admin: CurrentStaff,
+    client: ClientInfo,
+    State(state): State<AppState>,
+    Form(mut payload): Form<CategoryPayload>,
+) -> Result<Response, AppError> {
+    let before = Category::find_by_id(category_id, &state.db)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    payload.normalize();
+    if let Err(errors) = payload.validate() {
+        return Ok(form_errors_response(render_edit_page(
+            &before, &payload, &errors,
+        )));
+    }
+
+    let fields = payload.fields();
+    if let Err(e) = Category::update(category_id, &fields, &state.db).await {
+        let errors = validation::unique_violation(&e).ok_or(AppError::from(e))?;
+        return Ok(form_errors_response(render_edit_page(
+            &before, &payload, &errors,
+        )));
+    }
+
+    let changes = audit::diff(&[
+        ("slug", json!(before.slug), json!(fields.slug)),
+        ("name", json!(before.name), json!(fields.name)),
+        (
+            "sort_order",
+            json!(before.sort_order),
+            json!(fields.sort_order),
+        ),
+        (
+            "is_active",
+            json!(before.is_active),
+            json!(fields.is_active),
+        ),
+    ]);
+    admin
+        .audit(
+            AuditAction::CategoryUpdated,
+            "category",
+            Some(category_id),
+            changes,
+            &client,
+            &state.db,
+        )
+        .await?;
+    info!(
+        "Admin {} zmienił kategorię {}",
+        admin.0.username, fields.slug
+    );
+    Ok(Redirect::to("/admin/categories").into_response())
+}
+
+pub async fn delete_category(
+    Path(category_id): Path<Uuid>,
+    admin: CurrentStaff,
+    client: ClientInfo,
+    State(state): State<AppState>,
+) -> Result<Response, AppError> {
+    let category = Category::find_by_id(category_id, &state.db)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    if !Category::delete_unused(category_id, &state.db).await? {
+        let page = layout::info_page(
+            "Kategorie",
+            "Tej kategorii używają galerie - możesz ją tylko wyłączyć.",
+            Some(("/admin/categories", "Wróć do kategorii")),
+        );
+        return Ok((StatusCode::CONFLICT, Html(page.into_string())).into_response());
+    }
+
+    admin
+        .audit(
+            AuditAction::CategoryDeleted,
+            "category",
+            Some(category_id),
+            json!({ "slug": category.slug, "name": category.name }),
+            &client,
+            &state.db,
+        )
+        .await?;
+    info!(
+        "Admin {} usunął kategorię {}",
+        admin.0.username, category.slug
+    );
+    Ok(Redirect::to("/admin/categories").into_response())
+}
@@ -0,0 +1,56 @@
+// src/handlers/charts.rs
+
+// Proste wykresy SVG renderowane po stronie serwera - bez bibliotek JS.
+// Wymiary są w jednostkach viewBox, SVG skaluje się do szerokości kontenera.
+
+use maud::{Markup, html};
+
+const WIDTH: f64 = 640.0;
+const HEIGHT: f64 = 200.0;
+/// Margines na etykiety osi: lewy (wartości) i dolny (daty).
+const LEFT: f64 = 56.0;
+const BOTTOM: f64 = 20.0;
+
+/// Jeden słupek wykresu: etykieta osi X, wartość i tekst podpowiedzi.
+pub struct ChartPoint {
+    pub label: String,
+    pub value: f64,
+    pub tooltip: String,
+}
+
+/// Wykres słupkowy. Etykiet osi X pokazujemy najwyżej kilka, żeby się nie nakładały.
+pub fn bar_chart(
+    points: &[ChartPoint],
+    color: &str,
+    format_value: impl Fn(f64) -> String,
+) -> Markup {
+    let max = points.iter().map(|p| p.value).fold(0.0, f64::max);
+    // Pusta seria rysuje się jako płaska linia zamiast dzielenia przez zero
+    let scale_max = if max > 0.0 { max } else { 1.0 };
+    let plot_width = WIDTH - LEFT;
+    let plot_height = HEIGHT - BOTTOM;
+    let slot = plot_width / points.len().max(1) as f64;
+    let bar_width = (slot * 0.8).max(1.0);
+    let label_every = points.len().div_ceil(8).max(1);
+
+    html! {
+        svg viewBox=(format!("0 0 {} {}", WIDTH, HEIGHT)) class="w-full h-auto" role="img" {
+            line x1=(LEFT) y1=(plot_height) x2=(WIDTH) y2=(plot_height) stroke="#4b5563" {}
+            line x1=(LEFT) y1="0" x2=(LEFT) y2=(plot_height) stroke="#4b5563" {}
+            text x=(LEFT - 6.0) y="10" text-anchor="end" font-size="10" fill="#9ca3af" { (format_value(max)) }
+            text x=(LEFT - 6.0) y=(plot_height) text-anchor="end" font-size="10" fill="#9ca3af" { (format_value(0.0)) }
+            @for (i, point) in points.iter().enumerate() {
+                @let height = point.value / scale_max * (plot_height - 12.0);
+                @let x = LEFT + i as f64 * slot + (slot - bar_width) / 2.0;
+                rect x=(format!("{:.1}", x)) y=(format!("{:.1}", plot_height - height))
+                     width=(format!("{:.1}", bar_width)) height=(format!("{:.1}", height)) fill=(color) {
+                    title { (point.tooltip) }
+                }
+                @if i % label_every == 0 {
+                    text x=(format!("{:.1}", x + bar_width / 2.0)) y=(HEIGHT - 4.0) text-anchor="middle"
+                         font-size="10" fill="#9ca3af" { (point.label) }
+                }
+            }
+        }
+    }
+}
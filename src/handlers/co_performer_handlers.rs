@@ -0,0 +1,692 @@
+// src/handlers/co_performer_handlers.rs
+
+// Osoby współwystępujące w galeriach (model release). Dla każdej osoby innej
+// niż właścicielka konta trzymamy podpisaną zgodę i dokument potwierdzający
+// wiek. Pliki leżą w katalogu prywatnym - podgląd i eksport ma tylko admin.
+
+use super::erika_handlers::form_errors_response;
+use super::layout;
+use crate::auth::{ClientInfo, CurrentErika, CurrentStaff};
+use crate::models::audit::AuditAction;
+use crate::models::co_performer::{CoPerformer, CoPerformerFile, ComplianceRecord, NewCoPerformer};
+use crate::models::gallery::Gallery;
+use crate::models::verification::{self, MIN_AGE, VerificationStatus};
+use crate::validation::FormErrors;
+use crate::{app_state::AppState, errors::AppError};
+use axum::extract::{Multipart, Path};
+use axum::{
+    Form,
+    body::Bytes,
+    extract::State,
+    http::header,
+    response::{Html, IntoResponse, Redirect, Response},
+};
+use maud::Markup;
+use serde::Deserialize;
+use serde_json::json;
+use std::io::{Cursor, Write};
+use std::path::Path as FsPath;
+use time::format_description::well_known::Rfc3339;
+use time::{Date, OffsetDateTime, macros::format_description};
+use tokio::{fs, task};
+use tracing::{info, warn};
+use uuid::Uuid;
+use zip::{ZipWriter, write::SimpleFileOptions};
+
+/// Katalog na dokumenty i zgody. Celowo poza `uploads`.
+pub const PRIVATE_DIR: &str = "private/co_performers";
+
+/// Limit rozmiaru formularza (skan dokumentu i PDF ze zgodą).
+pub const MAX_UPLOAD_BYTES: usize = 25 * 1024 * 1024;
+
+const LEGAL_NAME_MAX_LEN: usize = 200;
+const REASON_MAX_LEN: usize = 500;
+
+fn file_extension(file_name: &str) -> String {
+    FsPath::new(file_name)
+        .extension()
+        .and_then(|s| s.to_str())
+        .unwrap_or("")
+        .to_lowercase()
+}
+
+fn content_type(path: &str) -> &'static str {
+    match file_extension(path).as_str() {
+        "pdf" => "application/pdf",
+        "png" => "image/png",
+        _ => "image/jpeg",
+    }
+}
+
+// Lista osób współwystępujących w galerii i formularz dodania kolejnej
+pub async fn show_co_performers_page(
+    Path(gallery_id): Path<Uuid>,
+    current: CurrentErika,
+    State(state): State<AppState>,
+) -> Result<Html<String>, AppError> {
+    let gallery = current.gallery(gallery_id, &state.db).await?;
+    let page = render_co_performers_page(&gallery, &FormErrors::default(), &state).await?;
+    Ok(Html(page.into_string()))
+}
+
+async fn render_co_performers_page(
+    gallery: &Gallery,
+    errors: &FormErrors,
+    state: &AppState,
+) -> Result<Markup, AppError> {
+    let co_performers = CoPerformer::find_by_gallery_id(gallery.id, &state.db).await?;
+    let input_class = "w-full px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white focus:outline-none focus:ring-2 focus:ring-blue-500";
+    let file_class = "w-full text-sm text-gray-400 file:mr-4 file:py-2 file:px-4 file:rounded-full file:border-0 file:text-sm file:font-semibold file:bg-blue-600 file:text-white hover:file:bg-blue-700";
+
+    let content = maud::html! {
+        div class="max-w-2xl mx-auto" {
+            a href=(format!("/panel/galleries/{}", gallery.id)) class="inline-block mb-6 text-blue-400 hover:text-blue-300 transition-colors" {
+                "← Wróć do galerii"
+            }
+            h1 class="text-3xl font-bold text-white mb-2" { "Osoby współwystępujące: " (gallery.name) }
+            p class="text-gray-400 mb-6" {
+                "Jeśli w galerii występuje ktoś poza Tobą, dodaj tę osobę wraz ze skanem dokumentu "
+                "tożsamości i podpisaną zgodą (PDF). Galerię można opublikować dopiero, gdy wszystkie "
+                "osoby zostaną zweryfikowane. Dokumenty widzi tylko administrator."
+            }
+
+            @if !co_performers.is_empty() {
+                div class="bg-gray-800 rounded-lg shadow-lg mb-6" {
+                    ul {
+                        @for person in &co_performers {
+                            li class="p-4 border-b border-gray-700 flex justify-between items-center" {
+                                div {
+                                    p class="text-white" { (person.legal_name) }
+                                    p class="text-gray-400 text-sm" {
+                                        (person.status.label()) " · dodano " (layout::format_datetime(person.created_at))
+                                    }
+                                    @if let Some(reason) = &person.rejection_reason {
+                                        p class="text-red-300 text-sm" { "Powód odrzucenia: " (reason) }
+                                    }
+                                }
+                                @if person.status != VerificationStatus::Verified {
+                                    form action=(format!("/panel/galleries/{}/co-performers/{}/delete", gallery.id, person.id)) method="post" {
+                                        button type="submit" class="text-red-400 hover:text-red-300 text-sm" { "Usuń" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            form action=(format!("/panel/galleries/{}/co-performers", gallery.id)) method="post" enctype="multipart/form-data"
+                 class="bg-gray-800 p-6 rounded-lg shadow-lg space-y-5" {
+                h2 class="text-xl font-semibold text-white" { "Dodaj osobę" }
+                div {
+                    label for="legal_name" class="block text-gray-300 text-sm font-bold mb-2" { "Imię i nazwisko (jak w dokumencie):" }
+                    input type="text" id="legal_name" name="legal_name" required maxlength=(LEGAL_NAME_MAX_LEN) class=(input_class);
+                    (layout::field_error(errors, "legal_name"))
+                }
+                div {
+                    label for="date_of_birth" class="block text-gray-300 text-sm font-bold mb-2" { "Data urodzenia:" }
+                    input type="date" id="date_of_birth" name="date_of_birth" required class=(input_class);
+                    (layout::field_error(errors, "date_of_birth"))
+                }
+                div {
+                    label class="block text-gray-300 text-sm font-bold mb-2" { "Dokument tożsamości (PNG, JPG lub PDF):" }
+                    input type="file" name="id_document" required accept="image/png, image/jpeg, application/pdf" class=(file_class);
+                    (layout::field_error(errors, "id_document"))
+                }
+                div {
+                    label class="block text-gray-300 text-sm font-bold mb-2" { "Podpisana zgoda na publikację (PDF):" }
+                    input type="file" name="release" required accept="application/pdf" class=(file_class);
+                    (layout::field_error(errors, "release"))
+                }
+                button type="submit" class="w-full bg-green-600 hover:bg-green-700 text-white font-bold py-2 px-4 rounded-md" {
+                    "Wyślij do weryfikacji"
+                }
+            }
+        }
+    };
+    Ok(layout::page("Osoby współwystępujące", content))
+}
+
+// Dodaje osobę z dokumentami. Opublikowana galeria wraca do szkicu.
+pub async fn add_co_performer(
+    Path(gallery_id): Path<Uuid>,
+    current: CurrentErika,
+    State(state): State<AppState>,
+    mut multipart: Multipart,
+) -> Result<Response, AppError> {
+    let gallery = current.gallery(gallery_id, &state.db).await?;
+
+    let mut legal_name = String::new();
+    let mut date_of_birth = String::new();
+    let mut files: Vec<(CoPerformerFile, String, Bytes)> = Vec::new();
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|_| AppError::InternalServerError)?
+    {
+        let name = field.name().unwrap_or("").to_string();
+        let file_name = field.file_name().unwrap_or("").to_string();
+        let data = field
+            .bytes()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        let kind = match name.as_str() {
+            "legal_name" => {
+                legal_name = String::from_utf8(data.to_vec()).unwrap_or_default();
+                continue;
+            }
+            "date_of_birth" => {
+                date_of_birth = String::from_utf8(data.to_vec()).unwrap_or_default();
+                continue;
+            }
+            "id_document" => CoPerformerFile::IdDocument,
+            "release" => CoPerformerFile::Release,
+            _ => continue,
+        };
+        if !data.is_empty() {
+            files.push((kind, file_extension(&file_name), data));
+        }
+    }
+
+    let mut errors = FormErrors::default();
+    let legal_name = legal_name.trim();
+    if legal_name.is_empty() || legal_name.chars().count() > LEGAL_NAME_MAX_LEN {
+        errors.add(
+            "legal_name",
+            format!(
+                "Podaj imię i nazwisko (najwyżej {} znaków).",
+                LEGAL_NAME_MAX_LEN
+            ),
+        );
+    }
+    let parsed_date = Date::parse(
+        date_of_birth.trim(),
+        format_description!("[year]-[month]-[day]"),
+    )
+    .ok();
+    match parsed_date {
+        None => errors.add("date_of_birth", "Podaj poprawną datę urodzenia."),
+        Some(date) if verification::age_on(date, OffsetDateTime::now_utc().date()) < MIN_AGE => {
+            errors.add(
+                "date_of_birth",
+                format!(
+                    "Osoba współwystępująca musi mieć ukończone {} lat.",
+                    MIN_AGE
+                ),
+            )
+        }
+        Some(_) => {}
+    }
+    match files
+        .iter()
+        .find(|(k, _, _)| *k == CoPerformerFile::IdDocument)
+    {
+        None => errors.add("id_document", "Dokument tożsamości jest wymagany."),
+        Some((_, extension, _))
+            if !matches!(extension.as_str(), "png" | "jpg" | "jpeg" | "pdf") =>
+        {
+            errors.add(
+                "id_document",
+                "Dokument musi być w formacie PNG, JPG lub PDF.",
+            )
+        }
+        Some(_) => {}
+    }
+    match files
+        .iter()
+        .find(|(k, _, _)| *k == CoPerformerFile::Release)
+    {
+        None => errors.add("release", "Podpisana zgoda jest wymagana."),
+        Some((_, extension, data)) if extension != "pdf" || !data.starts_with(b"%PDF") => {
+            errors.add("release", "Zgoda musi być plikiem PDF.")
+        }
+        Some(_) => {}
+    }
+    let Some(date_of_birth) = parsed_date.filter(|_| errors.is_empty()) else {
+        let page = render_co_performers_page(&gallery, &errors, &state).await?;
+        return Ok(form_errors_response(page));
+    };
+
+    // Zapisujemy pliki dopiero po udanej walidacji
+    let dir = format!("{}/{}", PRIVATE_DIR, gallery.id);
+    fs::create_dir_all(&dir)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    let mut paths: Vec<(CoPerformerFile, String)> = Vec::new();
+    for (kind, extension, data) in files {
+        let path = format!("{}/{}_{}.{}", dir, kind.slug(), Uuid::new_v4(), extension);
+        fs::write(&path, &data)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+        paths.push((kind, path));
+    }
+    let path_of = |kind: CoPerformerFile| {
+        paths
+            .iter()
+            .find(|(k, _)| *k == kind)
+            .map(|(_, p)| p.as_str())
+            .unwrap_or_default()
+    };
+
+    let created = CoPerformer::create(
+        NewCoPerformer {
+            gallery_id: gallery.id,
+            legal_name,
+            date_of_birth,
+            id_document_path: path_of(CoPerformerFile::IdDocument),
+            release_path: path_of(CoPerformerFile::Release),
+        },
+        &state.db,
+    )
+    .await;
+
+    if let Err(e) = created {
+        for (_, path) in &paths {
+            let _ = fs::remove_file(path).await;
+        }
+        return Err(e.into());
+    }
+
+    info!(
+        "Erika {} dodała osobę współwystępującą do galerii {}",
+        current.id(),
+        gallery.id
+    );
+    Ok(Redirect::to(&format!("/panel/galleries/{}/co-performers", gallery.id)).into_response())
+}
+
+// Usuwa niezweryfikowaną osobę (np. odrzuconą, żeby dodać ją ponownie)
+pub async fn remove_co_performer(
+    Path((gallery_id, co_performer_id)): Path<(Uuid, Uuid)>,
+    current: CurrentErika,
+    State(state): State<AppState>,
+) -> Result<Redirect, AppError> {
+    let gallery = current.gallery(gallery_id, &state.db).await?;
+    let removed = CoPerformer::delete_unverified(co_performer_id, gallery.id, &state.db)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    for path in [&removed.id_document_path, &removed.release_path] {
+        if let Err(e) = fs::remove_file(path).await {
+            warn!("Nie udało się usunąć pliku {}: {}", path, e);
+        }
+    }
+    Ok(Redirect::to(&format!(
+        "/panel/galleries/{}/co-performers",
+        gallery.id
+    )))
+}
+
+// Kolejka rekordów do sprawdzenia
+pub async fn show_co_performer_queue(
+    State(state): State<AppState>,
+) -> Result<Html<String>, AppError> {
+    let pending = CoPerformer::find_pending(&state.db).await?;
+    let today = OffsetDateTime::now_utc().date();
+
+    let content = maud::html! {
+        a href="/admin" class="inline-block mb-6 text-blue-400 hover:text-blue-300 transition-colors" {
+            "← Wróć do panelu"
+        }
+        div class="flex justify-between items-center mb-6" {
+            h1 class="text-3xl font-bold text-white" { "Osoby współwystępujące" }
+            a href="/admin/co-performers/export" class="bg-gray-700 hover:bg-gray-600 text-white font-bold py-2 px-4 rounded-md text-sm" {
+                "Eksportuj rekordy (ZIP)"
+            }
+        }
+        @if pending.is_empty() {
+            p class="text-gray-400" { "Brak rekordów do sprawdzenia." }
+        } @else {
+            div class="bg-gray-800 rounded-lg shadow-lg" {
+                ul {
+                    @for item in &pending {
+                        li class="p-4 border-b border-gray-700 flex justify-between items-center" {
+                            div {
+                                span class="text-white" { (item.legal_name) }
+                                span class="ml-4 text-gray-400 text-sm" {
+                                    "wiek: " (verification::age_on(item.date_of_birth, today))
+                                    " · galeria " (item.gallery_name) " (" (item.username) ")"
+                                    " · wysłano " (layout::format_datetime(item.created_at))
+                                }
+                            }
+                            a href=(format!("/admin/co-performers/{}", item.id)) class="bg-blue-600 hover:bg-blue-700 text-white font-bold py-1 px-3 rounded-md text-sm" {
+                                "Sprawdź"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    };
+    Ok(Html(
+        layout::page("Osoby współwystępujące", content).into_string(),
+    ))
+}
+
+// Ekran sprawdzenia: dokument i zgoda obok siebie
+pub async fn show_co_performer_review(
+    Path(co_performer_id): Path<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Html<String>, AppError> {
+    let page = render_review_page(co_performer_id, &FormErrors::default(), &state).await?;
+    Ok(Html(page.into_string()))
+}
+
+async fn render_review_page(
+    co_performer_id: Uuid,
+    errors: &FormErrors,
+    state: &AppState,
+) -> Result<Markup, AppError> {
+    let person = CoPerformer::find_by_id(co_performer_id, &state.db)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    let age = person.age();
+    let file_url =
+        |file: CoPerformerFile| format!("/admin/co-performers/{}/files/{}", person.id, file.slug());
+
+    let content = maud::html! {
+        a href="/admin/co-performers" class="inline-block mb-6 text-blue-400 hover:text-blue-300 transition-colors" {
+            "← Wróć do kolejki"
+        }
+        h1 class="text-3xl font-bold text-white mb-2" { "Osoba współwystępująca: " (person.legal_name) }
+        p class="text-gray-300 mb-6" {
+            "Data urodzenia: " strong { (person.date_of_birth.to_string()) }
+            " · wiek: "
+            @if age >= MIN_AGE {
+                strong class="text-green-400" { (age) }
+            } @else {
+                strong class="text-red-400" { (age) " - niepełnoletnia" }
+            }
+            " · status: " (person.status.label())
+        }
+
+        div class="grid grid-cols-1 md:grid-cols-2 gap-4 mb-6" {
+            @for (file, title) in [
+                (CoPerformerFile::IdDocument, "Dokument tożsamości"),
+                (CoPerformerFile::Release, "Podpisana zgoda"),
+            ] {
+                div class="bg-gray-800 rounded-lg p-2" {
+                    p class="text-gray-400 text-sm mb-2" {
+                        (title) " "
+                        a href=(file_url(file)) target="_blank" class="text-blue-400 hover:underline" { "(otwórz)" }
+                    }
+                    @if content_type(person.file_path(file)) == "application/pdf" {
+                        iframe src=(file_url(file)) title=(title) class="w-full h-[32rem] bg-white rounded" {}
+                    } @else {
+                        img src=(file_url(file)) alt=(title) class="w-full max-h-[32rem] object-contain bg-black rounded";
+                    }
+                }
+            }
+        }
+
+        @if person.status == VerificationStatus::Pending {
+            div class="flex flex-col md:flex-row gap-6" {
+                form action=(format!("/admin/co-performers/{}/approve", person.id)) method="post" {
+                    button type="submit" disabled[age < MIN_AGE]
+                           class="bg-green-600 hover:bg-green-700 disabled:opacity-50 text-white font-bold py-2 px-4 rounded-md" {
+                        "Wiek i zgoda potwierdzone"
+                    }
+                    (layout::field_error(errors, "approve"))
+                }
+                form action=(format!("/admin/co-performers/{}/reject", person.id)) method="post" class="flex-1 flex gap-2 items-start" {
+                    div class="flex-1" {
+                        input type="text" name="reason" required maxlength=(REASON_MAX_LEN) placeholder="Powód odrzucenia (widoczny dla modelki)"
+                              class="w-full px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white focus:outline-none focus:ring-2 focus:ring-red-500";
+                        (layout::field_error(errors, "reason"))
+                    }
+                    button type="submit" class="bg-red-600 hover:bg-red-700 text-white font-bold py-2 px-4 rounded-md" { "Odrzuć" }
+                }
+            }
+        } @else if let Some(reviewed_at) = person.reviewed_at {
+            p class="text-gray-400" { "Rozpatrzono: " (layout::format_datetime(reviewed_at)) }
+        }
+    };
+    Ok(layout::page("Osoba współwystępująca", content))
+}
+
+// Podgląd dokumentu lub zgody - tylko dla admina, bez cache w przeglądarce
+pub async fn show_co_performer_file(
+    Path((co_performer_id, slug)): Path<(Uuid, String)>,
+    State(state): State<AppState>,
+) -> Result<Response, AppError> {
+    let file = CoPerformerFile::from_slug(&slug).ok_or(AppError::NotFound)?;
+    let person = CoPerformer::find_by_id(co_performer_id, &state.db)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    let path = person.file_path(file);
+    let bytes = fs::read(path).await.map_err(|e| {
+        warn!("Brak pliku osoby współwystępującej {}: {}", path, e);
+        AppError::NotFound
+    })?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type(path)),
+            (header::CACHE_CONTROL, "no-store"),
+            (header::X_CONTENT_TYPE_OPTIONS, "nosniff"),
+        ],
+        bytes,
+    )
+        .into_response())
+}
+
+#[derive(Deserialize)]
+pub struct RejectCoPerformerPayload {
+    pub reason: String,
+}
+
+pub async fn approve_co_performer(
+    Path(co_performer_id): Path<Uuid>,
+    admin: CurrentStaff,
+    client: ClientInfo,
+    State(state): State<AppState>,
+) -> Result<Response, AppError> {
+    let person = CoPerformer::find_by_id(co_performer_id, &state.db)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    if person.age() < MIN_AGE {
+        let mut errors = FormErrors::default();
+        errors.add("approve", "Osoba niepełnoletnia - rekord trzeba odrzucić.");
+        let page = render_review_page(co_performer_id, &errors, &state).await?;
+        return Ok(form_errors_response(page));
+    }
+
+    if CoPerformer::review(
+        co_performer_id,
+        VerificationStatus::Verified,
+        None,
+        admin.id(),
+        &state.db,
+    )
+    .await?
+    {
+        admin
+            .audit(
+                AuditAction::CoPerformerApproved,
+                "gallery",
+                Some(person.gallery_id),
+                json!({ "co_performer_id": co_performer_id }),
+                &client,
+                &state.db,
+            )
+            .await?;
+        info!(
+            "Admin {} potwierdził osobę współwystępującą w galerii {}",
+            admin.0.username, person.gallery_id
+        );
+    }
+    Ok(Redirect::to("/admin/co-performers").into_response())
+}
+
+pub async fn reject_co_performer(
+    Path(co_performer_id): Path<Uuid>,
+    admin: CurrentStaff,
+    client: ClientInfo,
+    State(state): State<AppState>,
+    Form(payload): Form<RejectCoPerformerPayload>,
+) -> Result<Response, AppError> {
+    let person = CoPerformer::find_by_id(co_performer_id, &state.db)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    let reason = payload.reason.trim();
+    if reason.is_empty() || reason.chars().count() > REASON_MAX_LEN {
+        let mut errors = FormErrors::default();
+        errors.add(
+            "reason",
+            format!("Podaj powód (najwyżej {} znaków).", REASON_MAX_LEN),
+        );
+        let page = render_review_page(co_performer_id, &errors, &state).await?;
+        return Ok(form_errors_response(page));
+    }
+
+    if CoPerformer::review(
+        co_performer_id,
+        VerificationStatus::Rejected,
+        Some(reason),
+        admin.id(),
+        &state.db,
+    )
+    .await?
+    {
+        admin
+            .audit(
+                AuditAction::CoPerformerRejected,
+                "gallery",
+                Some(person.gallery_id),
+                json!({ "co_performer_id": co_performer_id, "reason": reason }),
+                &client,
+                &state.db,
+            )
+            .await?;
+        info!(
+            "Admin {} odrzucił osobę współwystępującą w galerii {}",
+            admin.0.username, person.gallery_id
+        );
+    }
+    Ok(Redirect::to("/admin/co-performers").into_response())
+}
+
+// Eksport wszystkich rekordów dla celów zgodności: CSV z indeksem i pliki
+pub async fn export_compliance_records(
+    admin: CurrentStaff,
+    client: ClientInfo,
+    State(state): State<AppState>,
+) -> Result<Response, AppError> {
+    let records = CoPerformer::find_all_for_export(&state.db).await?;
+    let count = records.len();
+
+    // Pakowanie czyta pliki przez std::io, więc robimy je w osobnym wątku
+    let bytes = task::spawn_blocking(move || build_compliance_zip(&records))
+        .await
+        .map_err(|_| AppError::InternalServerError)?
+        .map_err(|e| {
+            warn!("Nie udało się zbudować eksportu rekordów zgód: {}", e);
+            AppError::InternalServerError
+        })?;
+
+    // Eksport zawiera dokumenty tożsamości, więc każde pobranie zostaje w dzienniku
+    admin
+        .audit(
+            AuditAction::CoPerformersExported,
+            "co_performer",
+            None,
+            json!({ "records": count }),
+            &client,
+            &state.db,
+        )
+        .await?;
+    info!(
+        "Admin {} wyeksportował {} rekordów zgód",
+        admin.0.username, count
+    );
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/zip".to_string()),
+            (header::CACHE_CONTROL, "no-store".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!(
+                    "attachment; filename=\"co-performers-{}.zip\"",
+                    OffsetDateTime::now_utc().date()
+                ),
+            ),
+        ],
+        bytes,
+    )
+        .into_response())
+}
+
+fn format_timestamp(value: OffsetDateTime) -> String {
+    value.format(&Rfc3339).unwrap_or_default()
+}
+
+fn build_compliance_zip(records: &[ComplianceRecord]) -> Result<Vec<u8>, String> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default();
+    let zip_err = |e: zip::result::ZipError| format!("Błąd archiwum: {}", e);
+    let io_err = |e: std::io::Error| format!("Błąd zapisu archiwum: {}", e);
+
+    let mut csv = String::from(
+        "record_id,gallery_id,gallery,owner,legal_name,date_of_birth,status,reviewed_by,reviewed_at,rejection_reason,submitted_at,id_document_file,release_file\r\n",
+    );
+    let mut files: Vec<(&str, String)> = Vec::new();
+    for record in records {
+        let archive_path = |path: &str, slug: &str| {
+            format!("files/{}/{}.{}", record.id, slug, file_extension(path))
+        };
+        let document = archive_path(&record.id_document_path, CoPerformerFile::IdDocument.slug());
+        let release = archive_path(&record.release_path, CoPerformerFile::Release.slug());
+
+        let row = [
+            record.id.to_string(),
+            record.gallery_id.to_string(),
+            record.gallery_name.to_string(),
+            record.owner_username.clone(),
+            record.legal_name.clone(),
+            record.date_of_birth.to_string(),
+            record.status.to_string(),
+            record.reviewed_by.clone().unwrap_or_default(),
+            record.reviewed_at.map(format_timestamp).unwrap_or_default(),
+            record.rejection_reason.clone().unwrap_or_default(),
+            format_timestamp(record.created_at),
+            document.clone(),
+            release.clone(),
+        ];
+        let row: Vec<String> = row.iter().map(|value| layout::csv_field(value)).collect();
+        csv.push_str(&row.join(","));
+        csv.push_str("\r\n");
+
+        files.push((&record.id_document_path, document));
+        files.push((&record.release_path, release));
+    }
+
+    zip.start_file("README.txt", options).map_err(zip_err)?;
+    zip.write_all(
+        "Rekordy osób współwystępujących w galeriach.\n\
+         records.csv - indeks rekordów (status, data urodzenia, osoba sprawdzająca)\n\
+         files/<record_id>/ - dokument tożsamości i podpisana zgoda\n"
+            .as_bytes(),
+    )
+    .map_err(io_err)?;
+    zip.start_file("records.csv", options).map_err(zip_err)?;
+    zip.write_all(csv.as_bytes()).map_err(io_err)?;
+
+    for (path, archive_path) in files {
+        // Brakujący plik nie przerywa eksportu - jest odnotowany w logu
+        match std::fs::read(path) {
+            Ok(bytes) => {
+                zip.start_file(archive_path.as_str(), options)
+                    .map_err(zip_err)?;
+                zip.write_all(&bytes).map_err(io_err)?;
+            }
+            Err(e) => warn!("Pomijam brakujący plik {}: {}", path, e),
+        }
+    }
+
+    let cursor = zip.finish().map_err(zip_err)?;
+    Ok(cursor.into_inner())
+}
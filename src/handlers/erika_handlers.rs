@@ -1,92 +1,203 @@
 // src/handlers/erika_handlers.rs
 
+use crate::auth::{self, ClientInfo, CurrentErika, Impersonation, OptionalViewer};
+use crate::events::CreatorEvent;
+use crate::models::admin_notice::AdminNotice;
+use crate::models::erika::AccountStatus;
 use crate::models::gallery::Gallery;
+use crate::models::photo::Photo;
+use crate::models::stream_session::StreamSession;
+use crate::models::tip::TipMenuItem;
+use crate::models::wallet::Wallet;
+use crate::validation::{self, FormErrors, Validate};
 use crate::{app_state::AppState, errors::AppError, models::erika::Erika};
 use axum::extract::Multipart;
 use axum::extract::Path as AxumPath;
 use axum::{
-    Form,
+    Extension, Form,
     extract::State,
-    response::{Html, IntoResponse, Redirect, Response},
+    http::StatusCode,
+    response::{
+        Html, IntoResponse, Redirect, Response,
+        sse::{Event, KeepAlive, Sse},
+    },
 };
+use erika_system::password;
 use serde::Deserialize;
 use sqlx::types::chrono;
+use std::collections::HashMap;
+use std::convert::Infallible;
 use std::path::Path;
 use tokio::fs;
+use tokio_stream::{Stream, StreamExt, wrappers::BroadcastStream};
 use tower_sessions::Session;
 use tracing::{info, warn};
 use uuid::Uuid;
 
 // Importujemy nasz moduł layoutu
 use super::layout;
+use super::{private_show_handlers, stream_handlers, tip_handlers};
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Default)]
 pub struct RegisterErikaPayload {
     pub username: String,
     pub email: String,
     pub password: String,
 }
 
+impl Validate for RegisterErikaPayload {
+    fn normalize(&mut self) {
+        self.username = self.username.trim().to_string();
+        self.email = self.email.trim().to_lowercase();
+    }
+
+    fn validate(&self) -> Result<(), FormErrors> {
+        let mut errors = FormErrors::default();
+        validation::validate_username(&self.username, &mut errors);
+        validation::validate_email(&self.email, &mut errors);
+        validation::validate_password(&self.password, &self.username, &mut errors);
+        errors.into_result()
+    }
+}
+
 #[derive(Deserialize)]
 pub struct LoginPayload {
     pub username: String,
     pub password: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Default)]
 pub struct UpdateProfilePayload {
     pub username: String,
     pub email: String,
     pub bio: String,
 }
 
+impl Validate for UpdateProfilePayload {
+    fn normalize(&mut self) {
+        self.username = self.username.trim().to_string();
+        self.email = self.email.trim().to_lowercase();
+        self.bio = self.bio.trim().to_string();
+    }
+
+    fn validate(&self) -> Result<(), FormErrors> {
+        let mut errors = FormErrors::default();
+        validation::validate_username(&self.username, &mut errors);
+        validation::validate_email(&self.email, &mut errors);
+        validation::validate_bio(&self.bio, &mut errors);
+        errors.into_result()
+    }
+}
+
 // Handler formularza rejestracji
-pub async fn show_register_form() -> Html<String> {
+pub async fn show_register_form(
+    OptionalViewer(viewer): OptionalViewer,
+    State(state): State<AppState>,
+) -> Response {
+    // Zalogowany użytkownik nie potrzebuje formularza rejestracji
+    if let Some(viewer) = viewer {
+        return Redirect::to(auth::home_path(&viewer)).into_response();
+    }
+    if !state.settings().registration_open {
+        return registration_closed_response();
+    }
+
+    Html(
+        render_register_page(&RegisterErikaPayload::default(), &FormErrors::default())
+            .into_string(),
+    )
+    .into_response()
+}
+
+// Rejestrację można wyłączyć w ustawieniach serwisu
+fn registration_closed_response() -> Response {
+    let page = layout::info_page(
+        "Rejestracja",
+        "Rejestracja nowych kont jest obecnie zamknięta.",
+        Some(("/", "Strona główna")),
+    );
+    (StatusCode::FORBIDDEN, Html(page.into_string())).into_response()
+}
+
+// Formularz rejestracji - przy błędach pokazuje komunikaty i zachowuje wpisane dane (poza hasłem)
+fn render_register_page(values: &RegisterErikaPayload, errors: &FormErrors) -> maud::Markup {
     let content = maud::html! {
         div class="max-w-md mx-auto bg-gray-800 p-8 rounded-lg shadow-lg" {
             h1 class="text-3xl font-bold text-white mb-6 text-center" { "Zarejestruj się" }
             form action="/register" method="post" {
                 div class="mb-4" {
                     label for="username" class="block text-gray-300 text-sm font-bold mb-2" { "Nazwa użytkownika:" }
-                    input type="text" id="username" name="username" required
+                    input type="text" id="username" name="username" value=(values.username) required
+                          maxlength=(validation::USERNAME_MAX_LEN)
                           class="w-full px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white focus:outline-none focus:ring-2 focus:ring-blue-500";
+                    (layout::field_error(errors, "username"))
                 }
                 div class="mb-4" {
                     label for="email" class="block text-gray-300 text-sm font-bold mb-2" { "Email:" }
-                    input type="email" id="email" name="email" required
+                    input type="email" id="email" name="email" value=(values.email) required
                             class="w-full px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white focus:outline-none focus:ring-2 focus:ring-blue-500";
+                    (layout::field_error(errors, "email"))
                 }
                 div class="mb-6" {
                     label for="password" class="block text-gray-300 text-sm font-bold mb-2" { "Hasło:" }
                     input type="password" id="password" name="password" required
+                          minlength=(validation::PASSWORD_MIN_LEN)
                           class="w-full px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white focus:outline-none focus:ring-2 focus:ring-blue-500";
+                    (layout::field_error(errors, "password"))
                 }
                 button type="submit"
                        class="w-full bg-blue-600 hover:bg-blue-700 text-white font-bold py-2 px-4 rounded-md transition duration-300" { "Zarejestruj" }
             }
         }
     };
-    Html(layout::page("Rejestracja", content).into_string())
+    layout::page("Rejestracja", content)
 }
 
 // Handler przetwarzania rejestracji
 pub async fn register_erika(
     State(state): State<AppState>,
-    Form(payload): Form<RegisterErikaPayload>,
-) -> Result<Redirect, AppError> {
+    Form(mut payload): Form<RegisterErikaPayload>,
+) -> Result<Response, AppError> {
+    if !state.settings().registration_open {
+        return Ok(registration_closed_response());
+    }
+    payload.normalize();
+    if let Err(errors) = payload.validate() {
+        return Ok(form_errors_response(render_register_page(
+            &payload, &errors,
+        )));
+    }
+
     // Lepsze UX: Przekierowanie zamiast komunikatu
-    match Erika::create(&payload, &state.db).await {
+    match Erika::create(
+        &payload.username,
+        &payload.email,
+        &payload.password,
+        &state.db,
+    )
+    .await
+    {
         Ok(_) => {
             info!("Zarejestrowano pomyślnie użytkownika: {}", payload.username);
             // Po udanej rejestracji, przekieruj na stronę logowania
-            Ok(Redirect::to("/login"))
+            Ok(Redirect::to("/login").into_response())
         }
-        Err(_) => Err(AppError::InternalServerError),
+        // Zajęta nazwa lub email to błąd formularza, a nie serwera
+        Err(e) => match validation::unique_violation(&e) {
+            Some(errors) => Ok(form_errors_response(render_register_page(
+                &payload, &errors,
+            ))),
+            None => Err(e.into()),
+        },
     }
 }
 
 // Handler formularza logowania (już go zrobiliśmy, ale jest tu dla spójności)
-pub async fn show_login_form() -> Html<String> {
+pub async fn show_login_form(OptionalViewer(viewer): OptionalViewer) -> Response {
+    if let Some(viewer) = viewer {
+        return Redirect::to(auth::home_path(&viewer)).into_response();
+    }
+
     let content = maud::html! {
         div class="max-w-md mx-auto bg-gray-800 p-8 rounded-lg shadow-lg" {
             h1 class="text-3xl font-bold text-white mb-6 text-center" { "Zaloguj się" }
@@ -106,31 +217,71 @@ pub async fn show_login_form() -> Html<String> {
             }
         }
     };
-    Html(layout::page("Logowanie", content).into_string())
+    Html(layout::page("Logowanie", content).into_string()).into_response()
 }
 
 // Handler przetwarzania logowania
 pub async fn login_erika(
     State(state): State<AppState>,
     session: Session,
+    client: ClientInfo,
     Form(payload): Form<LoginPayload>,
 ) -> Result<Response, AppError> {
     // Zmieniamy typ zwracany
     info!("Próba logowania dla użytkownika: {}", payload.username);
 
-    let erika_result = Erika::find_by_username(&payload.username, &state.db).await;
+    let erika = match Erika::find_by_username(&payload.username, &state.db).await {
+        Ok(Some(erika)) => erika
+            .verify_password(&payload.password)
+            .await
+            .then_some(erika),
+        Ok(None) => {
+            // Nieznana nazwa kosztuje tyle samo co błędne hasło
+            Erika::simulate_password_check(&payload.password).await;
+            None
+        }
+        Err(_) => None,
+    };
 
-    match erika_result {
-        Ok(Some(erika)) if erika.verify_password(&payload.password) => {
+    match erika {
+        Some(erika) => {
             info!("Weryfikacja hasła powiodła się.");
-            session
-                .insert("erika_id", erika.id)
-                .await
-                .map_err(|_| AppError::InternalServerError)?;
+            // Zbanowane konto nie dostaje sesji. Powód pokazujemy dopiero
+            // po poprawnym haśle, żeby nie zdradzać statusu cudzego konta.
+            if !erika.status.can_log_in() {
+                warn!("Odrzucono logowanie zbanowanego konta: {}", erika.username);
+                let message = match erika.status_reason.as_deref() {
+                    Some(reason) => format!("Twoje konto zostało zablokowane. Powód: {}", reason),
+                    None => "Twoje konto zostało zablokowane.".to_string(),
+                };
+                let page =
+                    layout::info_page("Konto zablokowane", &message, Some(("/", "Strona główna")));
+                return Ok((StatusCode::FORBIDDEN, Html(page.into_string())).into_response());
+            }
+            if password::needs_rehash(&erika.password_hash) {
+                // Hash ze starymi parametrami - przeliczamy go w tle,
+                // żeby nie wydłużać logowania
+                let (erika, plain, db) =
+                    (erika.clone(), payload.password.clone(), state.db.clone());
+                tokio::spawn(async move {
+                    match erika.rehash_password(&plain, &db).await {
+                        Ok(true) => info!("Zaktualizowano parametry hasha dla {}", erika.username),
+                        Ok(false) => {}
+                        Err(e) => warn!(
+                            "Nie udało się zaktualizować hasha {}: {}",
+                            erika.username, e
+                        ),
+                    }
+                });
+            }
+            let erika_auth = Erika::find_by_id_for_auth(erika.id, &state.db)
+                .await?
+                .ok_or(AppError::InternalServerError)?;
+            auth::start_session(&session, &erika_auth, &client, &state.db).await?;
             // Po udanym logowaniu, przekieruj do panelu
-            Ok(Redirect::to("/panel").into_response())
+            Ok(Redirect::to(auth::home_path(&erika_auth)).into_response())
         }
-        _ => {
+        None => {
             warn!("Logowanie nie powiodło się dla: {}", payload.username);
             // Używamy naszej nowej strony z informacją o błędzie
             let error_page = layout::info_page(
@@ -145,26 +296,32 @@ pub async fn login_erika(
 
 // Handler panelu Eriki
 pub async fn erika_panel(
-    session: Session,
+    current: CurrentErika,
     State(state): State<AppState>,
 ) -> Result<Html<String>, AppError> {
-    let erika_id = match session.get::<Uuid>("erika_id").await {
-        Ok(Some(id)) => id,
-        _ => {
-            let page = layout::info_page(
-                "Brak dostępu",
-                "Musisz się zalogować, aby zobaczyć tę stronę.",
-                Some(("/login", "Przejdź do logowania")),
-            );
-            return Ok(Html(page.into_string()));
-        }
-    };
+    let erika_data = Erika::find_by_id(current.id(), &state.db)
+        .await?
+        .ok_or(AppError::NotFound)?;
 
-    let erika_data = match Erika::find_by_id(erika_id, &state.db).await {
-        Ok(Some(data)) => data,
-        _ => return Err(AppError::InternalServerError),
+    let values = UpdateProfilePayload {
+        username: erika_data.username.clone(),
+        email: erika_data.email.clone(),
+        bio: erika_data.bio.clone().unwrap_or_default(),
     };
+    let unread_notices = AdminNotice::count_unread(current.id(), &state.db).await?;
+    Ok(Html(
+        render_panel(&erika_data, &values, &FormErrors::default(), unread_notices).into_string(),
+    ))
+}
 
+// Panel Eriki z formularzem profilu - `values` to wartości w formularzu,
+// po nieudanej walidacji te wpisane przez użytkownika, a nie z bazy
+fn render_panel(
+    erika_data: &Erika,
+    values: &UpdateProfilePayload,
+    errors: &FormErrors,
+    unread_notices: i64,
+) -> maud::Markup {
     let content = maud::html! {
             div class="max-w-2xl mx-auto bg-gray-800 p-8 rounded-lg shadow-lg" {
                 // --- NOWA SEKCJA: WYŚWIETLANIE AVATARA ---
@@ -180,10 +337,12 @@ pub async fn erika_panel(
                 h1 class="text-3xl font-bold text-white mb-2" { "Witaj w panelu, " (erika_data.username) "!" }
                 p class="text-sm text-gray-400 mb-6" { "Twoje ID: " (erika_data.id) }
 
+                (render_status_notice(erika_data))
+
 
                 // --- NOWY PRZYCISK STATUSU ---
                 div class="my-6" {
-                    (maud::PreEscaped(render_status_button(erika_data.is_online)))
+                    (maud::PreEscaped(render_status_button(erika_data.appear_offline)))
                 }
                 // --- KONIEC ---
 
@@ -198,6 +357,21 @@ pub async fn erika_panel(
                     a href=(format!("/erika/{}", erika_data.username)) target="_blank" class="w-full sm:w-auto inline-block bg-gray-600 hover:bg-gray-700 text-white font-bold py-2 px-4 rounded-md transition duration-300" {
                         "Zobacz profil publiczny"
                     }
+                    a href="/panel/security" class="w-full sm:w-auto inline-block bg-gray-600 hover:bg-gray-700 text-white font-bold py-2 px-4 rounded-md transition duration-300" {
+                        "Bezpieczeństwo"
+                    }
+                    a href="/panel/privacy" class="w-full sm:w-auto inline-block bg-gray-600 hover:bg-gray-700 text-white font-bold py-2 px-4 rounded-md transition duration-300" {
+                        "Prywatność"
+                    }
+                    a href="/panel/notices" class="w-full sm:w-auto inline-block bg-gray-600 hover:bg-gray-700 text-white font-bold py-2 px-4 rounded-md transition duration-300" {
+                        "Decyzje moderacji"
+                    }
+                    a href="/panel/messages" class="w-full sm:w-auto inline-block bg-gray-600 hover:bg-gray-700 text-white font-bold py-2 px-4 rounded-md transition duration-300" {
+                        "Wiadomości"
+                        @if unread_notices > 0 {
+                            span class="ml-2 bg-red-600 text-white text-xs font-semibold px-2 py-0.5 rounded-full" { (unread_notices) }
+                        }
+                    }
                     // Formularz do wylogowania
                     form action="/logout" method="post" class="w-full sm:w-auto" {
                         button type="submit" class="w-full bg-red-600 hover:bg-red-700 text-white font-bold py-2 px-4 rounded-md transition duration-300" {
@@ -214,21 +388,25 @@ pub async fn erika_panel(
                 form action="/panel" method="post" enctype="multipart/form-data" {
                     div class="mb-4" {
                         label for="username" class="block text-gray-300 text-sm font-bold mb-2" { "Nazwa użytkownika:" }
-                        input type="text" name="username" value=(erika_data.username) required
+                        input type="text" name="username" value=(values.username) required
+                              maxlength=(validation::USERNAME_MAX_LEN)
                               class="w-full px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white focus:outline-none focus:ring-2 focus:ring-blue-500";
+                        (layout::field_error(errors, "username"))
                     }
                     div class="mb-6" {
                         label for="email" class="block text-gray-300 text-sm font-bold mb-2" { "Email:" }
-                        input type="email" name="email" value=(erika_data.email) required
+                        input type="email" name="email" value=(values.email) required
                               class="w-full px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white focus:outline-none focus:ring-2 focus:ring-blue-500";
+                        (layout::field_error(errors, "email"))
                     }
 
                     div class="mb-6" {
                         label for="bio" class="block text-gray-300 text-sm font-bold mb-2" { "Krótkie bio:" }
-                        textarea name="bio" rows="3"
+                        textarea name="bio" rows="3" maxlength=(validation::BIO_MAX_LEN)
                                   class="w-full px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white focus:outline-none focus:ring-2 focus:ring-blue-500" {
-                            (erika_data.bio.as_deref().unwrap_or(""))
+                            (values.bio)
                         }
+                        (layout::field_error(errors, "bio"))
                     }
 
                     // --- NOWE POLE: WYBÓR PLIKU ---
@@ -236,6 +414,7 @@ pub async fn erika_panel(
                         label for="avatar" class="block text-gray-300 text-sm font-bold mb-2" { "Zmień zdjęcie profilowe:" }
                         input type="file" id="avatar" name="avatar" accept="image/png, image/jpeg"
                               class="w-full text-sm text-gray-400 file:mr-4 file:py-2 file:px-4 file:rounded-full file:border-0 file:text-sm file:font-semibold file:bg-blue-600 file:text-white hover:file:bg-blue-700";
+                        (layout::field_error(errors, "avatar"))
                     }
 
                     button type="submit"
@@ -243,69 +422,174 @@ pub async fn erika_panel(
                 }
             }
         };
-    Ok(Html(layout::page("Panel Eriki", content).into_string()))
+    layout::page("Panel Eriki", content)
 }
 
 // NOWY handler do aktualizacji profilu (obsługuje pliki)
 pub async fn update_erika_profile(
-    session: Session,
+    current: CurrentErika,
     State(state): State<AppState>,
     mut multipart: Multipart, // Używamy ekstraktora Multipart
-) -> Result<Redirect, AppError> {
-    let erika_id = match session.get::<Uuid>("erika_id").await {
-        Ok(Some(id)) => id,
-        _ => return Err(AppError::InternalServerError),
-    };
+) -> Result<Response, AppError> {
+    let erika_id = current.id();
 
-    let mut username = String::new();
-    let mut email = String::new();
-    let mut bio = String::new(); // <-- DODAJ ZMIENNĄ
-    let mut avatar_url: Option<String> = None;
+    let mut payload = UpdateProfilePayload::default();
+    // Avatar trzymamy w pamięci i zapisujemy dopiero po udanej walidacji
+    let mut avatar: Option<(String, axum::body::Bytes)> = None;
 
     // Przetwarzamy każdą część formularza multipart
-    while let Some(field) = multipart.next_field().await.unwrap() {
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|_| AppError::InternalServerError)?
+    {
         let name = field.name().unwrap_or("").to_string();
         let file_name = field.file_name().unwrap_or("").to_string();
-        let data = field.bytes().await.unwrap();
+        let data = field
+            .bytes()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
 
         match name.as_str() {
-            "username" => username = String::from_utf8(data.to_vec()).unwrap_or_default(),
-            "email" => email = String::from_utf8(data.to_vec()).unwrap_or_default(),
-            "bio" => bio = String::from_utf8(data.to_vec()).unwrap_or_default(),
+            "username" => payload.username = String::from_utf8(data.to_vec()).unwrap_or_default(),
+            "email" => payload.email = String::from_utf8(data.to_vec()).unwrap_or_default(),
+            "bio" => payload.bio = String::from_utf8(data.to_vec()).unwrap_or_default(),
             "avatar" if !data.is_empty() => {
-                // Tworzymy unikalną nazwę pliku, aby uniknąć konfliktów
                 let extension = Path::new(&file_name)
                     .extension()
                     .and_then(|s| s.to_str())
-                    .unwrap_or("jpg");
-                let unique_filename = format!(
-                    "{}_{}.{}",
-                    erika_id,
-                    chrono::Utc::now().timestamp(),
-                    extension
-                );
-                let file_path_str = format!("uploads/{}", unique_filename);
-
-                // Zapisujemy plik na serwerze
-                fs::write(&file_path_str, &data)
-                    .await
-                    .map_err(|_| AppError::InternalServerError)?;
-                info!("Zapisano nowy avatar: {}", file_path_str);
-
-                // Zapisujemy publiczny URL, a nie ścieżkę systemową
-                avatar_url = Some(format!("/{}", file_path_str));
+                    .unwrap_or("jpg")
+                    .to_lowercase();
+                avatar = Some((extension, data));
             }
             _ => {}
         }
     }
 
+    payload.normalize();
+    let mut errors = payload.validate().err().unwrap_or_default();
+    if let Some((extension, _)) = &avatar
+        && !matches!(extension.as_str(), "png" | "jpg" | "jpeg")
+    {
+        errors.add(
+            "avatar",
+            "Zdjęcie profilowe musi być w formacie PNG lub JPG.",
+        );
+    }
+    if !errors.is_empty() {
+        return profile_form_errors(erika_id, &payload, &errors, &state).await;
+    }
+
+    let avatar_url = match avatar {
+        Some((extension, data)) => {
+            // Tworzymy unikalną nazwę pliku, aby uniknąć konfliktów
+            let unique_filename = format!(
+                "{}_{}.{}",
+                erika_id,
+                chrono::Utc::now().timestamp(),
+                extension
+            );
+            let file_path_str = format!("uploads/{}", unique_filename);
+
+            // Zapisujemy plik na serwerze
+            fs::write(&file_path_str, &data)
+                .await
+                .map_err(|_| AppError::InternalServerError)?;
+            info!("Zapisano nowy avatar: {}", file_path_str);
+
+            // Zapisujemy publiczny URL, a nie ścieżkę systemową
+            Some(format!("/{}", file_path_str))
+        }
+        None => None,
+    };
+
     // Wywołujemy zaktualizowaną metodę z modelu
-    Erika::update_profile(erika_id, &username, &email, &bio, avatar_url, &state.db)
-        .await
-        .map_err(|_| AppError::InternalServerError)?;
+    if let Err(e) = Erika::update_profile(
+        erika_id,
+        &payload.username,
+        &payload.email,
+        &payload.bio,
+        avatar_url,
+        &state.db,
+    )
+    .await
+    {
+        return match validation::unique_violation(&e) {
+            Some(errors) => profile_form_errors(erika_id, &payload, &errors, &state).await,
+            None => Err(e.into()),
+        };
+    }
 
     info!("Zaktualizowano profil dla: {}", erika_id);
-    Ok(Redirect::to("/panel"))
+    Ok(Redirect::to("/panel").into_response())
+}
+
+// Ponownie renderuje panel z błędami formularza profilu
+async fn profile_form_errors(
+    erika_id: Uuid,
+    values: &UpdateProfilePayload,
+    errors: &FormErrors,
+    state: &AppState,
+) -> Result<Response, AppError> {
+    let erika_data = Erika::find_by_id(erika_id, &state.db)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    let unread_notices = AdminNotice::count_unread(erika_id, &state.db).await?;
+    Ok(form_errors_response(render_panel(
+        &erika_data,
+        values,
+        errors,
+        unread_notices,
+    )))
+}
+
+/// Wyjaśnienie statusu konta dla modelki - co się stało i co dalej.
+fn render_status_notice(erika: &Erika) -> maud::Markup {
+    let (class, title, explanation) = match erika.status {
+        AccountStatus::Approved => return maud::html! {},
+        AccountStatus::Pending => (
+            "bg-yellow-900/50 border-yellow-600",
+            "Konto czeka na weryfikację",
+            "Administrator sprawdzi Twój profil i dokumenty. Do tego czasu profil i galerie nie są widoczne publicznie.",
+        ),
+        AccountStatus::Rejected => (
+            "bg-red-900/50 border-red-600",
+            "Konto zostało odrzucone",
+            "Popraw profil zgodnie z uwagami poniżej i skontaktuj się z obsługą, aby ponownie poprosić o weryfikację.",
+        ),
+        AccountStatus::Suspended => (
+            "bg-orange-900/50 border-orange-600",
+            "Konto jest zawieszone",
+            "Profil, galerie i kamerka są niedostępne do czasu przywrócenia konta przez administratora.",
+        ),
+        AccountStatus::Banned => (
+            "bg-red-900/50 border-red-600",
+            "Konto jest zablokowane",
+            "Konto zostało trwale zablokowane.",
+        ),
+    };
+    maud::html! {
+        div class={ "border-l-4 p-4 rounded-md mb-6 text-left " (class) } {
+            p class="font-bold text-white" { (title) }
+            p class="text-gray-300 text-sm mt-1" { (explanation) }
+            @if let Some(reason) = &erika.status_reason {
+                p class="text-gray-200 text-sm mt-2" { "Powód: " (reason) }
+            }
+            @if let Some(changed_at) = erika.status_changed_at {
+                p class="text-gray-500 text-xs mt-1" { (layout::format_datetime(changed_at)) }
+            }
+            @if erika.status != AccountStatus::Banned {
+                a href="/panel/verification" class="inline-block mt-2 text-blue-400 hover:text-blue-300 text-sm" {
+                    "Weryfikacja tożsamości →"
+                }
+            }
+        }
+    }
+}
+
+/// Strona z formularzem, który nie przeszedł walidacji (422).
+pub fn form_errors_response(page: maud::Markup) -> Response {
+    (StatusCode::UNPROCESSABLE_ENTITY, Html(page.into_string())).into_response()
 }
 
 // Handler do wyświetlania strony głównej
@@ -313,41 +597,89 @@ pub async fn homepage(State(state): State<AppState>) -> Result<Html<String>, App
     let erikas = Erika::find_active(&state.db)
         .await
         .map_err(|_| AppError::InternalServerError)?;
+    let headline = state.settings().homepage_headline;
 
     let content = maud::html! {
+        // Rozszerzenie SSE dla HTMX - lista odświeża się sama (`homepage_events`)
+        script src="https://cdn.jsdelivr.net/npm/htmx-ext-sse@2.2.3/dist/sse.js" defer {}
         div class="max-w-7xl mx-auto" {
-            h1 class="text-4xl font-bold text-white mb-8 text-center" { "Poznaj nasze modelki" }
+            h1 class="text-4xl font-bold text-white mb-8 text-center" { (headline) }
 
-            @if erikas.is_empty() {
-                p class="text-gray-400 text-center" { "Obecnie żadna modelka nie jest dostępna." }
-            } @else {
-                div class="grid grid-cols-1 sm:grid-cols-2 md:grid-cols-3 lg:grid-cols-4 gap-6" {
-                    @for erika in erikas {
-                        // Link do przyszłej strony profilowej
-                        a href=(format!("/erika/{}", erika.username)) class="bg-gray-800 rounded-lg overflow-hidden shadow-lg transform hover:-translate-y-1 transition-transform duration-300 block" {
-                            div class="relative" {
-                                img src=(erika.profile_image_url.as_deref().unwrap_or("/placeholder.jpg")) alt=(erika.username) class="w-full h-80 object-cover";
-                                // Wskaźnik statusu online
-                                @if erika.is_online {
-                                    span class="absolute top-3 right-3 block w-4 h-4 bg-green-500 rounded-full border-2 border-gray-800" title="Online" {}
-                                }
-                            }
-                            div class="p-4" {
-                                h3 class="text-xl font-bold text-white" { (erika.username) }
-                                p class="text-gray-400 mt-1 h-12 overflow-hidden" { (erika.bio.as_deref().unwrap_or("Brak opisu.")) }
+            div hx-ext="sse" sse-connect="/events/creators" {
+                div id="creator-grid" sse-swap=(CREATORS_EVENT) {
+                    (creator_grid(&erikas))
+                }
+            }
+        }
+    };
+    Ok(Html(layout::page("Strona Główna", content).into_string()))
+}
+
+/// Nazwa zdarzenia SSE z nową listą modelek.
+const CREATORS_EVENT: &str = "creators";
+
+// Karty modelek na stronie głównej - online na początku
+fn creator_grid(erikas: &[Erika]) -> maud::Markup {
+    maud::html! {
+        @if erikas.is_empty() {
+            p class="text-gray-400 text-center" { "Obecnie żadna modelka nie jest dostępna." }
+        } @else {
+            div class="grid grid-cols-1 sm:grid-cols-2 md:grid-cols-3 lg:grid-cols-4 gap-6" {
+                @for erika in erikas {
+                    // Link do przyszłej strony profilowej
+                    a href=(format!("/erika/{}", erika.username)) class="bg-gray-800 rounded-lg overflow-hidden shadow-lg transform hover:-translate-y-1 transition-transform duration-300 block" {
+                        div class="relative" {
+                            img src=(erika.profile_image_url.as_deref().unwrap_or("/placeholder.jpg")) alt=(erika.username) class="w-full h-80 object-cover";
+                            // Wskaźnik statusu online
+                            @if erika.is_online {
+                                span class="absolute top-3 right-3 block w-4 h-4 bg-green-500 rounded-full border-2 border-gray-800" title="Online" {}
                             }
                         }
+                        div class="p-4" {
+                            h3 class="text-xl font-bold text-white" { (erika.username) }
+                            p class="text-gray-400 mt-1 h-12 overflow-hidden" { (erika.bio.as_deref().unwrap_or("Brak opisu.")) }
+                        }
                     }
                 }
             }
         }
-    };
-    Ok(Html(layout::page("Strona Główna", content).into_string()))
+    }
+}
+
+// Strumień SSE strony głównej: po każdej zmianie obecności lub listy modelek
+// wysyła świeżo wyrenderowaną siatkę kart, więc kolejność też się zgadza.
+pub async fn homepage_events(
+    State(state): State<AppState>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let events = BroadcastStream::new(state.creator_events.subscribe());
+    let stream = events
+        // Poza tym treść zdarzenia nie ma znaczenia - `Lagged` też odświeża
+        // listę, bo pominięte zmiany i tak są już w bazie
+        .filter(|event| event.as_ref().map_or(true, CreatorEvent::changes_listing))
+        .then(move |_event| {
+            let state = state.clone();
+            async move {
+                match Erika::find_active(&state.db).await {
+                    Ok(erikas) => Some(
+                        Event::default()
+                            .event(CREATORS_EVENT)
+                            .data(creator_grid(&erikas).into_string()),
+                    ),
+                    Err(e) => {
+                        warn!("Nie udało się odświeżyć listy modelek: {}", e);
+                        None
+                    }
+                }
+            }
+        })
+        .filter_map(|event| event.map(Ok));
+    Sse::new(stream).keep_alive(KeepAlive::default())
 }
 
 // NOWY HANDLER: Wyświetla publiczną stronę profilową Eriki
 pub async fn show_erika_profile(
     AxumPath(username): AxumPath<String>, // Pobieramy nazwę z URL
+    OptionalViewer(viewer): OptionalViewer,
     State(state): State<AppState>,
 ) -> Result<Html<String>, AppError> {
     // 1. Znajdź Erikę w bazie po jej publicznej nazwie
@@ -356,12 +688,42 @@ pub async fn show_erika_profile(
         .map_err(|_| AppError::InternalServerError)?
         .ok_or(AppError::NotFound)?; // Zwróci błąd 404, jeśli nie ma takiej Eriki
 
-    // 2. Znajdź wszystkie jej galerie
-    let galleries = Gallery::find_by_erika_id(erika.id, &state.db)
+    // 2. Znajdź jej opublikowane galerie
+    let galleries = Gallery::find_published_by_erika_id(erika.id, &state.db)
         .await
         .map_err(|_| AppError::InternalServerError)?;
 
-    // 3. Renderuj stronę
+    // 3. Liczba zdjęć w galeriach - tylko zaakceptowane przez moderację
+    let photo_counts: HashMap<Uuid, i64> = Photo::count_approved_by_erika_id(erika.id, &state.db)
+        .await?
+        .into_iter()
+        .collect();
+
+    // 4. Trwająca transmisja z napiwkami, pokaz prywatny i zapowiedzi kolejnych
+    let live = StreamSession::find_live(erika.id, &state.db).await?;
+    let in_private_show = live.as_ref().is_some_and(|session| session.is_private);
+    let live_box = match &live {
+        Some(session) if !session.is_private => {
+            let menu = TipMenuItem::find_by_erika_id(erika.id, &state.db).await?;
+            let balance = match &viewer {
+                Some(viewer) => Some(Wallet::balance(viewer.id, &state.db).await?),
+                None => None,
+            };
+            let feed = tip_handlers::render_live_feed(erika.id, &state.db).await?;
+            Some((menu, balance, feed))
+        }
+        _ => None,
+    };
+    let private_box = private_show_handlers::render_profile_box(
+        &erika,
+        viewer.as_ref().map(|viewer| viewer.id),
+        in_private_show,
+        &state.db,
+    )
+    .await?;
+    let upcoming = StreamSession::find_upcoming_by_erika_id(erika.id, &state.db).await?;
+
+    // 5. Renderuj stronę
     let content = maud::html! {
         div class="max-w-4xl mx-auto" {
             // Sekcja profilu
@@ -374,11 +736,38 @@ pub async fn show_erika_profile(
                         span class="text-green-400 font-semibold" { "● Online" }
                     } @else {
                         span class="text-gray-400 font-semibold" { "● Offline" }
+                        // Modelka, która pokazuje się jako offline, nie zdradza też ostatniej aktywności
+                        @if let Some(last_seen_at) = erika.last_seen_at.filter(|_| !erika.appear_offline) {
+                            span class="text-gray-500 text-sm ml-2" { "ostatnio aktywna " (layout::format_time_ago(last_seen_at)) }
+                        }
                     }
                     p class="text-gray-300 mt-4" { (erika.bio.as_deref().unwrap_or("Brak opisu.")) }
+                    a href=(format!("/report/profile/{}", erika.id)) class="inline-block mt-4 text-xs text-gray-500 hover:text-red-400" {
+                        "Zgłoś profil"
+                    }
                 }
             }
 
+            @if let (Some(session), Some((menu, balance, feed))) = (&live, live_box) {
+                // Rozszerzenie SSE dla HTMX - napiwki pojawiają się na bieżąco
+                script src="https://cdn.jsdelivr.net/npm/htmx-ext-sse@2.2.3/dist/sse.js" defer {}
+                div class="bg-gray-800 p-6 rounded-lg shadow-lg mb-8" {
+                    h2 class="text-2xl font-bold text-white mb-4" { span class="text-red-400" { "● Na żywo: " } (session.title) }
+                    div class="h-64 overflow-y-auto bg-gray-700 rounded p-3 mb-4" {
+                        (tip_handlers::live_feed_container(erika.id, feed))
+                    }
+                    (tip_handlers::render_tip_box(&erika.username, &menu, balance.as_ref()))
+                }
+            } @else if in_private_show {
+                div class="bg-gray-800 p-6 rounded-lg shadow-lg mb-8" {
+                    p class="text-purple-400 font-semibold" { "● Modelka jest teraz w pokazie prywatnym." }
+                }
+            }
+
+            (private_box)
+
+            (stream_handlers::render_upcoming_shows(&erika.username, &upcoming))
+
             // Sekcja galerii
             h2 class="text-3xl font-bold text-white mb-6" { "Płatne galerie" }
             @if galleries.is_empty() {
@@ -394,6 +783,9 @@ pub async fn show_erika_profile(
                             div class="p-4 flex justify-between items-center" {
                                 div {
                                     h3 class="text-lg font-bold text-white" { (gallery.name) }
+                                    p class="text-sm text-gray-400" {
+                                        (photo_counts.get(&gallery.id).copied().unwrap_or(0)) " zdjęć"
+                                    }
                                     // WYŚWIETLANIE CENY
                                     @if let Some(price) = &gallery.price_pln {
                                         p class="text-green-400 font-bold" { (price.with_scale(2).to_string()) " PLN" }
@@ -402,8 +794,11 @@ pub async fn show_erika_profile(
                                     }
                                 }
                                 // Zmieniamy link na przycisk płatności
-                                a href=(format!("/pay/gallery/{}", gallery.id)) class="bg-blue-600 hover:bg-blue-700 text-white font-bold py-2 px-4 rounded-md text-sm transition duration-300" {
-                                    "Odblokuj"
+                                div class="flex flex-col items-end gap-2" {
+                                    a href=(format!("/pay/gallery/{}", gallery.id)) class="bg-blue-600 hover:bg-blue-700 text-white font-bold py-2 px-4 rounded-md text-sm transition duration-300" {
+                                        "Odblokuj"
+                                    }
+                                    a href=(format!("/report/gallery/{}", gallery.id)) class="text-xs text-gray-500 hover:text-red-400" { "Zgłoś" }
                                 }
                             }
                         }
@@ -419,7 +814,7 @@ pub async fn initiate_gallery_payment(
     AxumPath(gallery_id): AxumPath<Uuid>,
     State(state): State<AppState>,
 ) -> Result<Html<String>, AppError> {
-    let gallery = Gallery::find_by_id(gallery_id, &state.db)
+    let gallery = Gallery::find_public_by_id(gallery_id, &state.db)
         .await
         .map_err(|_| AppError::InternalServerError)?
         .ok_or(AppError::NotFound)?;
@@ -435,104 +830,90 @@ pub async fn initiate_gallery_payment(
         gallery.name, price_str
     );
 
-    let page = layout::info_page(
-        "Potwierdzenie płatności",
-        &message,
-        Some(("#", "Zapłać z PayU")),
-    );
-    Ok(Html(page.into_string()))
+    let content = maud::html! {
+        div class="max-w-xl mx-auto bg-gray-800 p-8 rounded-lg shadow-lg text-center" {
+            h1 class="text-3xl font-bold text-white mb-6" { (message) }
+            a href="#" class="inline-block bg-blue-600 hover:bg-blue-700 text-white font-bold py-2 px-4 rounded-md transition duration-300" {
+                "Zapłać z PayU"
+            }
+            p class="mt-6" {
+                a href=(format!("/report/gallery/{}", gallery.id)) class="text-xs text-gray-500 hover:text-red-400" { "Zgłoś tę galerię" }
+            }
+        }
+    };
+    Ok(Html(
+        layout::page("Potwierdzenie płatności", content).into_string(),
+    ))
 }
 
 // NOWY HANDLER: Obsługuje wylogowanie
-pub async fn logout(session: Session) -> Result<Redirect, AppError> {
-    // Czyścimy sesję, usuwając wszystkie zapisane w niej dane
-    session.clear().await;
+pub async fn logout(session: Session, State(state): State<AppState>) -> Result<Redirect, AppError> {
+    // Usuwamy sesję z magazynu razem z jej wpisem na liście aktywnych sesji
+    auth::end_session(&session, &state.db).await?;
     info!("Użytkownik pomyślnie wylogowany.");
     // Przekierowujemy na stronę główną
     Ok(Redirect::to("/"))
 }
 
-// NOWY HANDLER: Przełącza status online/offline
-pub async fn toggle_online_status(
-    session: Session,
+// Przełącznik "pokazuj mnie jako offline". Sam status online wynika
+// z sygnałów panelu kamerki (`presence_heartbeat`).
+pub async fn toggle_appear_offline(
+    current: CurrentErika,
     State(state): State<AppState>,
 ) -> Result<Html<String>, AppError> {
-    let erika_id = session
-        .get::<Uuid>("erika_id")
-        .await
-        .unwrap_or(None)
-        .ok_or(AppError::Unauthorized)?;
-
-    let new_status = Erika::toggle_online_status(erika_id, &state.db)
-        .await
-        .map_err(|_| AppError::InternalServerError)?;
-
-    info!("Zmieniono status online dla {}: {}", erika_id, new_status);
+    // Niezaakceptowane konto nie może pojawić się jako online
+    current.ensure_approved()?;
+    let erika_id = current.id();
+
+    let appear_offline = Erika::toggle_appear_offline(erika_id, &state.db).await?;
+    if appear_offline {
+        state.publish(CreatorEvent::Presence {
+            erika_id,
+            is_online: false,
+        });
+    }
+    info!(
+        "Zmieniono widoczność statusu dla {}: offline={}",
+        erika_id, appear_offline
+    );
 
     // Zwracamy zaktualizowany fragment HTML przycisku
-    Ok(Html(render_status_button(new_status)))
+    Ok(Html(render_status_button(appear_offline)))
+}
+
+// Sygnał obecności wysyłany co chwilę przez otwarty panel kamerki.
+// Admin w podglądzie konta nie może sprawić, że modelka będzie online.
+pub async fn presence_heartbeat(
+    current: CurrentErika,
+    impersonation: Option<Extension<Impersonation>>,
+    State(state): State<AppState>,
+) -> Result<StatusCode, AppError> {
+    current.ensure_approved()?;
+    if impersonation.is_none() && Erika::record_heartbeat(current.id(), &state.db).await? {
+        state.publish(CreatorEvent::Presence {
+            erika_id: current.id(),
+            is_online: true,
+        });
+    }
+    Ok(StatusCode::NO_CONTENT)
 }
 
-// NOWA FUNKCJA POMOCNICZA: Renderuje przycisk statusu
-fn render_status_button(is_online: bool) -> String {
+// Renderuje przełącznik widoczności statusu
+fn render_status_button(appear_offline: bool) -> String {
     maud::html! {
         // Ten div zostanie podmieniony przez HTMX
         div id="status-button-container" {
-            @if is_online {
+            @if appear_offline {
                 button hx-post="/panel/status-toggle" hx-target="#status-button-container" hx-swap="outerHTML"
-                       class="w-full bg-green-600 hover:bg-green-700 text-white font-bold py-2 px-4 rounded-md transition duration-300" {
-                    "● Jesteś Online (Kliknij, aby przejść Offline)"
+                       class="w-full bg-gray-600 hover:bg-gray-700 text-white font-bold py-2 px-4 rounded-md transition duration-300" {
+                    "○ Pokazujesz się jako offline (kliknij, aby wrócić do statusu automatycznego)"
                 }
             } @else {
                 button hx-post="/panel/status-toggle" hx-target="#status-button-container" hx-swap="outerHTML"
-                       class="w-full bg-gray-600 hover:bg-gray-700 text-white font-bold py-2 px-4 rounded-md transition duration-300" {
-                    "○ Jesteś Offline (Kliknij, aby przejść Online)"
+                       class="w-full bg-green-600 hover:bg-green-700 text-white font-bold py-2 px-4 rounded-md transition duration-300" {
+                    "● Jesteś online, gdy panel kamerki jest otwarty (kliknij, aby pokazywać się jako offline)"
                 }
             }
         }
     }.into_string()
 }
-
-// NOWY HANDLER: Wyświetla panel do streamowania
-pub async fn show_stream_panel(session: Session) -> Result<Html<String>, AppError> {
-    // Sprawdzamy, czy użytkownik jest zalogowany
-    session
-        .get::<Uuid>("erika_id")
-        .await
-        .unwrap_or(None)
-        .ok_or(AppError::Unauthorized)?;
-
-    let content = maud::html! {
-        div class="max-w-7xl mx-auto" {
-            a href="/panel" class="inline-block mb-6 text-blue-400 hover:text-blue-300 transition-colors" {
-                "← Wróć do głównego panelu"
-            }
-            h1 class="text-3xl font-bold text-white mb-6" { "Panel Twojej Kamerki" }
-
-            // --- POPRAWKA TUTAJ: Zmieniamy Grida na Flexbox ---
-            // Domyślnie flex-col (pionowo), na dużych ekranach (lg) flex-row (poziomo)
-            div class="flex flex-col lg:flex-row gap-6" {
-                // Kolumna z wideo
-                div class="lg:w-2/3 bg-gray-800 p-4 rounded-lg shadow-lg" {
-                    // Główny widok
-                    div class="bg-black aspect-video w-full mb-4 rounded flex items-center justify-center" {
-                        p class="text-gray-500" { "Oczekiwanie na połączenie..." }
-                    }
-                    // Miniaturka
-                    div class="w-1/2 md:w-1/3 lg:w-1/4 bg-black aspect-video rounded ml-auto flex items-center justify-center" {
-                        p class="text-gray-500 text-sm" { "Twój podgląd" }
-                    }
-                }
-                // Kolumna z czatem i kontrolkami
-                div class="lg:w-1/3 bg-gray-800 p-4 rounded-lg shadow-lg" {
-                    h2 class="text-xl font-semibold text-white mb-4" { "Czat" }
-                    div class="h-96 bg-gray-700 rounded p-2 mb-4" {
-                        // Tutaj będą wiadomości
-                    }
-                    input type="text" placeholder="Napisz wiadomość..." class="w-full px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white";
-                }
-            }
-        }
-    };
-    Ok(Html(layout::page("Panel Kamerki", content).into_string()))
-}
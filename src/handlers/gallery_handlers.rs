@@ -1,14 +1,18 @@
 // src/handlers/gallery_handlers.rs
 
 use super::layout;
-use crate::models::gallery::GalleryCategory;
-use crate::models::photo::Photo;
+use crate::auth::CurrentErika;
+use crate::models::category::Category;
+use crate::models::co_performer::CoPerformer;
+use crate::models::photo::{Photo, PhotoStatus};
+use crate::models::verification::VerificationStatus;
 use crate::{app_state::AppState, errors::AppError, models::gallery::Gallery};
 use axum::extract::{Multipart, Path as AxumPath};
-use axum::response::IntoResponse;
+use axum::response::{IntoResponse, Response};
 use axum::{
     Form,
     extract::State,
+    http::StatusCode,
     response::{Html, Redirect},
 };
 use bigdecimal::BigDecimal;
@@ -16,32 +20,46 @@ use serde::Deserialize;
 use serde::de::Error as _;
 use std::path::Path;
 use std::str::FromStr;
-use strum::IntoEnumIterator;
 use tokio::fs;
-use tower_sessions::Session;
 use tracing::{info, warn};
 use uuid::Uuid;
-use axum::http::header::HeaderName;
 
 #[derive(Deserialize)]
 pub struct CreateGalleryPayload {
-    name: GalleryCategory,
+    category_id: Uuid,
+}
+
+/// Czy kategorię można przypisać galerii: musi być aktywna, chyba że galeria
+/// już ją ma (wyłączenie kategorii nie zmusza do zmiany istniejących galerii).
+pub async fn is_selectable_category(
+    category_id: Uuid,
+    current: Option<Uuid>,
+    state: &AppState,
+) -> Result<bool, AppError> {
+    let category = Category::find_by_id(category_id, &state.db).await?;
+    Ok(category.is_some_and(|c| c.is_active || current == Some(c.id)))
+}
+
+pub fn category_unavailable_response(back: &str) -> Response {
+    let page = layout::info_page(
+        "Kategoria niedostępna",
+        "Wybrana kategoria nie jest już dostępna. Wybierz inną.",
+        Some((back, "Wróć")),
+    );
+    (StatusCode::UNPROCESSABLE_ENTITY, Html(page.into_string())).into_response()
 }
 
 // Handler do wyświetlania strony zarządzania galeriami
 pub async fn show_galleries_page(
-    session: Session,
+    current: CurrentErika,
     State(state): State<AppState>,
 ) -> Result<Html<String>, AppError> {
-    let erika_id = session
-        .get::<Uuid>("erika_id")
-        .await
-        .unwrap_or(None)
-        .ok_or(AppError::Unauthorized)?;
+    let erika_id = current.id();
 
     let galleries = Gallery::find_by_erika_id(erika_id, &state.db)
         .await
         .map_err(|_| AppError::InternalServerError)?;
+    let categories = Category::find_active(&state.db).await?;
 
     let content = maud::html! {
         div class="max-w-4xl mx-auto" {
@@ -55,11 +73,11 @@ pub async fn show_galleries_page(
                 h2 class="text-xl font-semibold text-white mb-4" { "Stwórz nową galerię" }
                     form action="/panel/galleries" method="post" class="flex items-center gap-4" {
                         // Zamieniamy pole tekstowe na listę rozwijaną
-                        select name="name" required
+                        select name="category_id" required
                                class="flex-grow px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white focus:outline-none focus:ring-2 focus:ring-blue-500" {
                             option value="" disabled selected { "Wybierz kategorię..." }
-                            @for category in GalleryCategory::iter() {
-                                option value=(category.to_string()) { (category.to_string()) }
+                            @for category in &categories {
+                                option value=(category.id) { (category.name) }
                             }
                         }
                         button type="submit" class="bg-blue-600 hover:bg-blue-700 text-white font-bold py-2 px-4 rounded-md transition duration-300" { "Stwórz" }
@@ -74,7 +92,16 @@ pub async fn show_galleries_page(
                 div class="space-y-4" {
                     @for gallery in galleries {
                         div class="bg-gray-800 p-4 rounded-lg flex justify-between items-center" {
-                            p class="text-white" { (gallery.name) }
+                            div {
+                                p class="text-white" { (gallery.name) }
+                                @if gallery.blocked_at.is_some() {
+                                    span class="text-red-400 text-sm" { "Ukryta przez moderację" }
+                                } @else if gallery.published_at.is_some() {
+                                    span class="text-green-400 text-sm" { "Opublikowana" }
+                                } @else {
+                                    span class="text-gray-400 text-sm" { "Szkic" }
+                                }
+                            }
                             a href=(format!("/panel/galleries/{}", gallery.id)) class="text-blue-400 hover:underline" { "Zarządzaj zdjęciami" }
                         }
                     }
@@ -89,45 +116,43 @@ pub async fn show_galleries_page(
 
 // Handler do przetwarzania formularza tworzenia galerii
 pub async fn create_gallery(
-    session: Session,
+    current: CurrentErika,
     State(state): State<AppState>,
     Form(payload): Form<CreateGalleryPayload>,
-) -> Result<Redirect, AppError> {
-    let erika_id = session
-        .get::<Uuid>("erika_id")
-        .await
-        .unwrap_or(None)
-        .ok_or(AppError::Unauthorized)?;
+) -> Result<Response, AppError> {
+    let erika_id = current.id();
+
+    if !is_selectable_category(payload.category_id, None, &state).await? {
+        return Ok(category_unavailable_response("/panel/galleries"));
+    }
 
-    Gallery::create(erika_id, payload.name, &state.db)
+    Gallery::create(erika_id, payload.category_id, &state.db)
         .await
         .map_err(|_| AppError::InternalServerError)?;
 
-    Ok(Redirect::to("/panel/galleries"))
+    Ok(Redirect::to("/panel/galleries").into_response())
 }
 
 // Handler do wyświetlania strony zarządzania JEDNĄ galerią
 pub async fn show_single_gallery_page(
     AxumPath(gallery_id): AxumPath<Uuid>, // Pobieramy ID galerii z URL
-    session: Session,
+    current: CurrentErika,
     State(state): State<AppState>,
 ) -> Result<Html<String>, AppError> {
-    let erika_id = session
-        .get::<Uuid>("erika_id")
-        .await
-        .unwrap_or(None)
-        .ok_or(AppError::Unauthorized)?;
-
-    // Pobieramy dane galerii, żeby wypełnić formularz
-    let gallery = Gallery::find_by_id_and_erika_id(gallery_id, erika_id, &state.db) // <-- ZMIANA
-        .await
-        .map_err(|_| AppError::InternalServerError)?
-        .ok_or(AppError::Unauthorized)?; // Zwróć błąd, jeśli galeria nie należy do tej Eriki
+    // Pobieramy dane galerii, żeby wypełnić formularz (404, jeśli nie należy do tej Eriki)
+    let gallery = current.gallery(gallery_id, &state.db).await?;
 
     let photos = Photo::find_by_gallery_id(gallery_id, &state.db)
         .await
         .map_err(|_| AppError::InternalServerError)?;
 
+    let categories = Category::find_active(&state.db).await?;
+    let co_performers = CoPerformer::find_by_gallery_id(gallery_id, &state.db).await?;
+    let unverified = co_performers
+        .iter()
+        .filter(|person| person.status != VerificationStatus::Verified)
+        .count();
+
     let content = maud::html! {
         div class="max-w-4xl mx-auto" {
             a href="/panel/galleries" class="inline-block mb-6 text-blue-400 hover:text-blue-300 transition-colors" {
@@ -135,6 +160,38 @@ pub async fn show_single_gallery_page(
             }
             h1 class="text-3xl font-bold text-white mb-6" { "Zarządzaj zdjęciami w galerii" }
 
+            // Publikacja i osoby współwystępujące
+            div class="bg-gray-800 p-6 rounded-lg shadow-lg mb-8 flex flex-col md:flex-row md:justify-between md:items-center gap-4" {
+                div {
+                    @if gallery.blocked_at.is_some() {
+                        p class="text-red-400 font-semibold" { "Galeria ukryta decyzją moderacji" }
+                        a href="/panel/notices" class="text-blue-400 hover:underline text-sm" { "Zobacz uzasadnienie" }
+                    } @else if let Some(published_at) = gallery.published_at {
+                        p class="text-green-400 font-semibold" { "Opublikowana " (layout::format_datetime(published_at)) }
+                    } @else {
+                        p class="text-gray-300 font-semibold" { "Szkic - galeria nie jest widoczna publicznie" }
+                    }
+                    p class="text-gray-400 text-sm" {
+                        "Osoby współwystępujące: " (co_performers.len())
+                        @if unverified > 0 {
+                            " (" (unverified) " bez weryfikacji)"
+                        }
+                        " · "
+                        a href=(format!("/panel/galleries/{}/co-performers", gallery_id)) class="text-blue-400 hover:underline" { "Zarządzaj" }
+                    }
+                }
+                @if gallery.published_at.is_some() {
+                    form action=(format!("/panel/galleries/{}/unpublish", gallery_id)) method="post" {
+                        button type="submit" class="bg-gray-600 hover:bg-gray-700 text-white font-bold py-2 px-4 rounded-md" { "Cofnij publikację" }
+                    }
+                } @else {
+                    form action=(format!("/panel/galleries/{}/publish", gallery_id)) method="post" {
+                        button type="submit" disabled[unverified > 0 || gallery.blocked_at.is_some()]
+                               class="bg-green-600 hover:bg-green-700 disabled:opacity-50 text-white font-bold py-2 px-4 rounded-md" { "Opublikuj" }
+                    }
+                }
+            }
+
             // Formularz do wgrywania zdjęć
             div class="bg-gray-800 p-6 rounded-lg shadow-lg mb-8" {
                 h2 class="text-xl font-semibold text-white mb-4" { "Dodaj nowe zdjęcie" }
@@ -149,26 +206,7 @@ pub async fn show_single_gallery_page(
             // NOWY FORMULARZ: Edycja danych galerii
             div class="bg-gray-800 p-6 rounded-lg shadow-lg mb-8" {
                 h2 class="text-xl font-semibold text-white mb-4" { "Edytuj szczegóły galerii" }
-                form action={"/panel/galleries/" (gallery.id)} method="post" {
-                    div class="mb-4" {
-                        label for="name" class="block text-gray-300 text-sm font-bold mb-2" { "Nazwa galerii:" }
-                        input type="text" name="name" value=(gallery.name) required
-                              class="w-full px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white ...";
-                    }
-                    div class="mb-4" {
-                        label for="description" class="block text-gray-300 text-sm font-bold mb-2" { "Opis:" }
-                        textarea name="description" rows="3"
-                                  class="w-full px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white ..." {
-                            (gallery.description.as_deref().unwrap_or(""))
-                        }
-                    }
-                    div class="mb-6" {
-                        label for="price_pln" class="block text-gray-300 text-sm font-bold mb-2" { "Cena (PLN):" }
-                        input type="number" name="price_pln" step="0.01" placeholder="np. 19.99" value=[gallery.price_pln.as_ref().map(|p| p.with_scale(2).to_string())]
-                              class="w-full px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white ...";
-                    }
-                    button type="submit" class="w-full bg-purple-600 hover:bg-purple-700 text-white font-bold py-2 px-4 rounded-md ..."{ "Zapisz szczegóły" }
-                }
+                (gallery_details_form(&format!("/panel/galleries/{}", gallery.id), &gallery, &categories))
             }
 
 
@@ -178,8 +216,8 @@ pub async fn show_single_gallery_page(
                 p class="text-gray-400" { "Brak zdjęć w tej galerii." }
             } @else {
                 div id="photo-grid" class="grid grid-cols-2 md:grid-cols-4 gap-4" {
-                    @for photo in photos {
-                        (maud::PreEscaped(render_photo_partial(gallery_id, &photo)))
+                    @for photo in &photos {
+                        (maud::PreEscaped(render_photo_partial(&creator_confirm_url(gallery_id, photo), photo)))
                     }
                 }
             }
@@ -193,20 +231,42 @@ pub async fn show_single_gallery_page(
 // Handler do przetwarzania uploadu zdjęcia
 pub async fn upload_photo(
     AxumPath(gallery_id): AxumPath<Uuid>,
-    session: Session,
+    current: CurrentErika,
     State(state): State<AppState>,
     mut multipart: Multipart,
-) -> Result<Redirect, AppError> {
-    let erika_id = session
-        .get::<Uuid>("erika_id")
-        .await
-        .unwrap_or(None)
-        .ok_or(AppError::Unauthorized)?;
-
-    while let Some(field) = multipart.next_field().await.unwrap() {
+) -> Result<Response, AppError> {
+    let erika_id = current.id();
+    // Nie pozwalamy wgrywać zdjęć do cudzych galerii
+    current.gallery(gallery_id, &state.db).await?;
+    let settings = state.settings();
+
+    loop {
+        // Błąd odczytu formularza (np. przekroczony limit body) ma własny status HTTP
+        let field = match multipart.next_field().await {
+            Ok(Some(field)) => field,
+            Ok(None) => break,
+            Err(e) => return Ok(e.into_response()),
+        };
         if field.name() == Some("photo") {
             let file_name = field.file_name().unwrap_or("photo.jpg").to_string();
-            let data = field.bytes().await.unwrap();
+            let data = match field.bytes().await {
+                Ok(data) => data,
+                Err(e) => return Ok(e.into_response()),
+            };
+
+            if data.len() > settings.max_upload_bytes() {
+                let page = layout::info_page(
+                    "Zdjęcie za duże",
+                    &format!("Zdjęcie może mieć najwyżej {} MB.", settings.max_upload_mb),
+                    Some((
+                        &format!("/panel/galleries/{}", gallery_id),
+                        "Wróć do galerii",
+                    )),
+                );
+                return Ok(
+                    (StatusCode::PAYLOAD_TOO_LARGE, Html(page.into_string())).into_response()
+                );
+            }
 
             if !data.is_empty() {
                 let extension = Path::new(&file_name)
@@ -238,12 +298,46 @@ pub async fn upload_photo(
         }
     }
 
-    Ok(Redirect::to(&format!("/panel/galleries/{}", gallery_id)))
+    Ok(Redirect::to(&format!("/panel/galleries/{}", gallery_id)).into_response())
+}
+
+// Publikuje galerię - tylko gdy wszystkie osoby współwystępujące są zweryfikowane
+pub async fn publish_gallery(
+    AxumPath(gallery_id): AxumPath<Uuid>,
+    current: CurrentErika,
+    State(state): State<AppState>,
+) -> Result<Response, AppError> {
+    let gallery = current.gallery(gallery_id, &state.db).await?;
+
+    if gallery.published_at.is_none() && !Gallery::publish(gallery.id, &state.db).await? {
+        let page = layout::info_page(
+            "Publikacja galerii",
+            "Galerię można opublikować dopiero, gdy wszystkie osoby współwystępujące zostaną zweryfikowane, a moderacja jej nie ukryła.",
+            Some((
+                &format!("/panel/galleries/{}/co-performers", gallery.id),
+                "Osoby współwystępujące",
+            )),
+        );
+        return Ok((StatusCode::UNPROCESSABLE_ENTITY, Html(page.into_string())).into_response());
+    }
+
+    info!("Erika {} opublikowała galerię {}", current.id(), gallery.id);
+    Ok(Redirect::to(&format!("/panel/galleries/{}", gallery.id)).into_response())
+}
+
+pub async fn unpublish_gallery(
+    AxumPath(gallery_id): AxumPath<Uuid>,
+    current: CurrentErika,
+    State(state): State<AppState>,
+) -> Result<Redirect, AppError> {
+    let gallery = current.gallery(gallery_id, &state.db).await?;
+    Gallery::unpublish(gallery.id, &state.db).await?;
+    Ok(Redirect::to(&format!("/panel/galleries/{}", gallery.id)))
 }
 
 #[derive(Deserialize)]
 pub struct UpdateGalleryPayload {
-    pub name: String,
+    pub category_id: Uuid,
     pub description: String,
     #[serde(deserialize_with = "empty_string_as_none")]
     pub price_pln: Option<BigDecimal>,
@@ -253,30 +347,20 @@ pub struct UpdateGalleryPayload {
 
 pub async fn update_gallery(
     AxumPath(gallery_id): AxumPath<Uuid>,
-    session: Session,
+    current: CurrentErika,
     State(state): State<AppState>,
     Form(payload): Form<UpdateGalleryPayload>,
-) -> Result<Redirect, AppError> {
-    let erika_id = session
-        .get::<Uuid>("erika_id")
-        .await
-        .unwrap_or(None)
-        .ok_or(AppError::Unauthorized)?;
-
-    Gallery::find_by_id_and_erika_id(gallery_id, erika_id, &state.db)
-        .await
-        .map_err(|_| AppError::InternalServerError)?
-        .ok_or(AppError::Unauthorized)?;
+) -> Result<Response, AppError> {
+    let gallery = current.gallery(gallery_id, &state.db).await?;
+    let back = format!("/panel/galleries/{}", gallery_id);
 
-    // --- POPRAWKA TUTAJ ---
-    // 1. Parsujemy String z formularza na nasz enum GalleryCategory
-    let category =
-        GalleryCategory::from_str(&payload.name).map_err(|_| AppError::InternalServerError)?; // Obsłuż błąd, jeśli nazwa jest nieprawidłowa
+    if !is_selectable_category(payload.category_id, Some(gallery.category_id), &state).await? {
+        return Ok(category_unavailable_response(&back));
+    }
 
-    // 2. Przekazujemy sparsowaną kategorię do modelu
     Gallery::update_details(
         gallery_id,
-        category, // <-- Przekazujemy poprawny typ
+        payload.category_id,
         &payload.description,
         payload.price_pln,
         &state.db,
@@ -284,60 +368,33 @@ pub async fn update_gallery(
     .await
     .map_err(|_| AppError::InternalServerError)?;
 
-    Ok(Redirect::to(&format!("/panel/galleries/{}", gallery_id)))
+    Ok(Redirect::to(&back).into_response())
 }
 
 pub async fn delete_photo(
     AxumPath((gallery_id, photo_id)): AxumPath<(Uuid, Uuid)>,
-    session: Session,
+    current: CurrentErika,
     State(state): State<AppState>,
 ) -> Result<impl IntoResponse, AppError> {
-    let erika_id = session
-        .get::<Uuid>("erika_id")
-        .await
-        .unwrap_or(None)
-        .ok_or(AppError::Unauthorized)?;
+    // 1-2. Galeria musi należeć do zalogowanej Eriki, a zdjęcie leżeć w tej galerii
+    let (_, photo) = current.photo(gallery_id, photo_id, &state.db).await?;
 
-    // 1. Sprawdź, czy galeria należy do zalogowanej Eriki
-    let gallery = Gallery::find_by_id_and_erika_id(gallery_id, erika_id, &state.db)
-        .await
-        .map_err(|_| AppError::InternalServerError)?
-        .ok_or(AppError::Unauthorized)?;
-
-    // 2. Znajdź zdjęcie, aby uzyskać jego URL
-    let photo = Photo::find_by_id(photo_id, &state.db)
-        .await
-        .map_err(|_| AppError::InternalServerError)?
-        .ok_or(AppError::NotFound)?;
-
-    // Upewnij się, że zdjęcie jest w odpowiedniej galerii
-    if photo.gallery_id != gallery.id {
-        return Err(AppError::Unauthorized);
-    }
-
-    // 3. Usuń plik z dysku
-    // Usuwamy wiodący '/' z URL, aby otrzymać ścieżkę do pliku
-    let file_path = photo.file_url.strip_prefix('/').unwrap_or(&photo.file_url);
-    fs::remove_file(file_path).await.map_err(|e| {
-        warn!("Nie udało się usunąć pliku {}: {}", file_path, e);
-        AppError::InternalServerError
-    })?;
-
-    // 4. Usuń wpis z bazy danych
-    Photo::delete(photo_id, &state.db)
-        .await
-        .map_err(|_| AppError::InternalServerError)?;
+    // 3-4. Usuń plik z dysku i wpis z bazy danych
+    delete_photo_with_file(&photo, &state.db).await?;
 
     info!("Usunięto zdjęcie o ID: {}", photo_id);
-    
+
     // Po usunięciu, pobierz odświeżoną listę zdjęć
     let photos = Photo::find_by_gallery_id(gallery_id, &state.db).await?;
-    let updated_grid = render_photos_grid(gallery_id, &photos);
+    let updated_grid = render_photos_grid(&photos, |photo| creator_confirm_url(gallery_id, photo));
 
     // --- POPRAWKA TUTAJ ---
     // Zwracamy odpowiedź z nagłówkiem, który wywoła nasze zdarzenie `closeModal`
     Ok((
-        [(axum::http::header::HeaderName::from_static("hx-trigger"), "closeModal")],
+        [(
+            axum::http::header::HeaderName::from_static("hx-trigger"),
+            "closeModal",
+        )],
         Html(updated_grid),
     ))
 }
@@ -345,14 +402,24 @@ pub async fn delete_photo(
 // NOWY HANDLER: Zwraca fragment HTML z potwierdzeniem usunięcia
 pub async fn confirm_delete_photo(
     AxumPath((gallery_id, photo_id)): AxumPath<(Uuid, Uuid)>,
+    current: CurrentErika,
+    State(state): State<AppState>,
 ) -> Result<Html<String>, AppError> {
-    let content = maud::html! {
+    current.photo(gallery_id, photo_id, &state.db).await?;
+
+    let delete_url = format!("/panel/galleries/{}/photo/{}/delete", gallery_id, photo_id);
+    Ok(Html(delete_photo_dialog(&delete_url).into_string()))
+}
+
+/// Treść modala z potwierdzeniem usunięcia - po "Tak" podmienia siatkę zdjęć.
+pub fn delete_photo_dialog(delete_url: &str) -> maud::Markup {
+    maud::html! {
         div class="text-center" {
             p class="text-white mb-4 text-lg" { "Czy na pewno chcesz usunąć to zdjęcie?" }
             div class="flex justify-center gap-4" {
-                button hx-post=(format!("/panel/galleries/{}/photo/{}/delete", gallery_id, photo_id))
+                button hx-post=(delete_url)
                        hx-target="#photo-grid"
-                       hx-swap="innerHTML"
+                       hx-swap="outerHTML"
                        class="bg-red-600 hover:bg-red-700 text-white font-bold py-2 px-4 rounded-md" {
                     "Tak, usuń"
                 }
@@ -364,41 +431,117 @@ pub async fn confirm_delete_photo(
                 }
             }
         }
-    };
-    Ok(Html(content.into_string()))
+    }
 }
 
 // Potrzebujemy też handlera, który zwróci HTML dla pojedynczego zdjęcia (do anulowania)
 pub async fn get_photo_partial(
     AxumPath((gallery_id, photo_id)): AxumPath<(Uuid, Uuid)>,
+    current: CurrentErika,
     State(state): State<AppState>,
 ) -> Result<Html<String>, AppError> {
-    // Tutaj normalnie pobralibyśmy dane zdjęcia z bazy, ale na razie uprośćmy
-    // i załóżmy, że potrzebujemy tylko URL-a, który już mamy w innej funkcji.
-    // W przyszłości można to zoptymalizować.
-    let photo = crate::models::photo::Photo::find_by_id(photo_id, &state.db)
-        .await // <-- POTRZEBUJEMY TEJ FUNKCJI
-        .map_err(|_| AppError::InternalServerError)?
-        .ok_or(AppError::NotFound)?;
-
-    Ok(Html(render_photo_partial(gallery_id, &photo)))
+    let (_, photo) = current.photo(gallery_id, photo_id, &state.db).await?;
+
+    Ok(Html(render_photo_partial(
+        &creator_confirm_url(gallery_id, &photo),
+        &photo,
+    )))
+}
+
+// Adres modala z potwierdzeniem usunięcia w panelu modelki
+fn creator_confirm_url(gallery_id: Uuid, photo: &Photo) -> String {
+    format!("/panel/photo/delete-confirm/{}/{}", gallery_id, photo.id)
+}
+
+/// Usuwa plik zdjęcia z dysku, a potem wpis w bazie. Brak pliku nie blokuje
+/// usunięcia wpisu - i tak nie ma już czego pokazywać.
+pub async fn delete_photo_with_file(photo: &Photo, db: &sqlx::PgPool) -> Result<(), AppError> {
+    // Odrzucone zdjęcia leżą w kwarantannie, pozostałe w `uploads`
+    let file_path = photo.disk_path();
+    match fs::remove_file(&file_path).await {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            warn!("Plik zdjęcia {} już nie istnieje", file_path.display());
+        }
+        Err(e) => {
+            warn!("Nie udało się usunąć pliku {}: {}", file_path.display(), e);
+            return Err(AppError::InternalServerError);
+        }
+    }
+
+    Photo::delete(photo.id, db)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    Ok(())
 }
 
-/// Renderuje fragment HTML dla JEDNEGO zdjęcia.
-fn render_photo_partial(gallery_id: Uuid, photo: &Photo) -> String {
+/// Formularz szczegółów galerii (kategoria, opis, cena) - panel modelki i panel admina.
+pub fn gallery_details_form(
+    action: &str,
+    gallery: &Gallery,
+    categories: &[Category],
+) -> maud::Markup {
+    maud::html! {
+        form action=(action) method="post" {
+            div class="mb-4" {
+                label for="category_id" class="block text-gray-300 text-sm font-bold mb-2" { "Kategoria:" }
+                select id="category_id" name="category_id" required
+                       class="w-full px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white focus:outline-none focus:ring-2 focus:ring-blue-500" {
+                    // Wyłączona kategoria zostaje na liście, jeśli galeria już ją ma
+                    @if !categories.iter().any(|c| c.id == gallery.category_id) {
+                        option value=(gallery.category_id) selected { (gallery.name) }
+                    }
+                    @for category in categories {
+                        option value=(category.id) selected[category.id == gallery.category_id] { (category.name) }
+                    }
+                }
+            }
+            div class="mb-4" {
+                label for="description" class="block text-gray-300 text-sm font-bold mb-2" { "Opis:" }
+                textarea name="description" rows="3"
+                          class="w-full px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white ..." {
+                    (gallery.description.as_deref().unwrap_or(""))
+                }
+            }
+            div class="mb-6" {
+                label for="price_pln" class="block text-gray-300 text-sm font-bold mb-2" { "Cena (PLN):" }
+                input type="number" name="price_pln" step="0.01" placeholder="np. 19.99" value=[gallery.price_pln.as_ref().map(|p| p.with_scale(2).to_string())]
+                      class="w-full px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white ...";
+            }
+            button type="submit" class="w-full bg-purple-600 hover:bg-purple-700 text-white font-bold py-2 px-4 rounded-md ..."{ "Zapisz szczegóły" }
+        }
+    }
+}
+
+/// Renderuje fragment HTML dla JEDNEGO zdjęcia. `confirm_url` zwraca treść
+/// modala z potwierdzeniem usunięcia.
+pub fn render_photo_partial(confirm_url: &str, photo: &Photo) -> String {
     maud::html! {
         // Kontener dla zdjęcia jest teraz celem dla HTMX
         div class="photo-container bg-gray-800 rounded-lg overflow-hidden shadow-lg relative group" {
             img src=(photo.file_url) alt="Zdjęcie z galerii" class="w-full h-48 object-cover";
 
+            // Status moderacji - widoczny tylko dla autorki i admina
+            span class={ "absolute top-2 left-2 z-20 pointer-events-none text-xs font-semibold px-2 py-1 rounded-full "
+                         (match photo.status {
+                             PhotoStatus::Pending => "bg-yellow-500 text-black",
+                             PhotoStatus::Approved => "bg-green-500 text-white",
+                             PhotoStatus::Rejected => "bg-red-600 text-white",
+                         }) } {
+                (photo.status.label())
+            }
+            @if let Some(reason) = photo.rejection_reason.as_deref().filter(|_| photo.status == PhotoStatus::Rejected) {
+                p class="p-2 text-xs text-red-300" { "Powód: " (reason) }
+            }
+
             // Nakładka jest teraz JEDNYM wielkim, klikalnym przyciskiem dla HTMX.
             // Po załadowaniu treści do modala, aktywuje go (`x-on:htmx:after-swap`).
-            div hx-get=(format!("/panel/photo/delete-confirm/{}/{}", gallery_id, photo.id))
+            div hx-get=(confirm_url)
                 hx-target="#modal-content"
                 hx-swap="innerHTML"
                 x-on:htmx:after-swap="modalOpen = true"
                 class="absolute inset-0 z-10 bg-black/60 flex items-center justify-center opacity-0 group-hover:opacity-100 transition-opacity cursor-pointer" {
-                
+
                 // To jest tylko wizualna etykieta, a nie faktyczny przycisk.
                 span class="bg-red-600 text-white font-bold py-2 px-4 rounded-md pointer-events-none" {
                     "Usuń"
@@ -409,41 +552,19 @@ fn render_photo_partial(gallery_id: Uuid, photo: &Photo) -> String {
 }
 
 /// Renderuje całą siatkę zdjęć.
-fn render_photos_grid(gallery_id: Uuid, photos: &[Photo]) -> String {
+pub fn render_photos_grid(photos: &[Photo], confirm_url: impl Fn(&Photo) -> String) -> String {
     maud::html! {
         div id="photo-grid" class="grid grid-cols-2 md:grid-cols-4 gap-4" {
             @if photos.is_empty() {
                 p class="text-gray-400 col-span-full" { "Brak zdjęć w tej galerii." }
             } @else {
                 @for photo in photos {
-                    (maud::PreEscaped(render_photo_partial(gallery_id, photo)))
+                    (maud::PreEscaped(render_photo_partial(&confirm_url(photo), photo)))
                 }
             }
         }
-    }.into_string()
-}
-
-// NOWY HANDLER: Zwraca tylko fragment HTML z siatką zdjęć
-async fn get_photos_grid_partial(
-    gallery_id: Uuid,
-    db: &sqlx::PgPool,
-) -> Result<Html<String>, AppError> {
-    let photos = Photo::find_by_gallery_id(gallery_id, db)
-        .await
-        .map_err(|_| AppError::InternalServerError)?;
-
-    let content = maud::html! {
-        @if photos.is_empty() {
-            p class="text-gray-400" { "Brak zdjęć w tej galerii." }
-        } @else {
-            div id="photo-grid" class="grid grid-cols-2 md:grid-cols-4 gap-4" {
-                @for photo in photos {
-                    (maud::PreEscaped(render_photo_partial(gallery_id, &photo)))
-                }
-            }
-        }
-    };
-    Ok(Html(content.into_string()))
+    }
+    .into_string()
 }
 
 // --- NOWA FUNKCJA POMOCNICZA ---
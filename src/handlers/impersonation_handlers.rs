@@ -0,0 +1,83 @@
+// src/handlers/impersonation_handlers.rs
+
+// Podgląd konta ("zobacz jako") dla pomocy technicznej. Admin widzi serwis
+// tak jak modelka; samą podmianę konta i blokady robi middleware `impersonation`.
+
+use crate::auth::{self, ClientInfo, CurrentStaff, Impersonation};
+use crate::models::audit::AuditAction;
+use crate::models::erika::Erika;
+use crate::{app_state::AppState, errors::AppError};
+use axum::{
+    Extension,
+    extract::{Path, State},
+    response::Redirect,
+};
+use serde_json::json;
+use tower_sessions::Session;
+use tracing::info;
+use uuid::Uuid;
+
+pub async fn start_impersonation(
+    Path(erika_id): Path<Uuid>,
+    admin: CurrentStaff,
+    client: ClientInfo,
+    session: Session,
+    State(state): State<AppState>,
+) -> Result<Redirect, AppError> {
+    let target = Erika::find_by_id_for_auth(erika_id, &state.db)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    if !auth::can_be_impersonated(&target.role) {
+        return Err(AppError::Forbidden);
+    }
+
+    auth::start_impersonation(&session, target.id).await?;
+    admin
+        .audit(
+            AuditAction::ImpersonationStarted,
+            "erika",
+            Some(target.id),
+            json!({ "username": target.username }),
+            &client,
+            &state.db,
+        )
+        .await?;
+
+    info!(
+        "Admin {} rozpoczął podgląd konta {} ({})",
+        admin.0.username, target.username, target.id
+    );
+    Ok(Redirect::to(auth::home_path(&target)))
+}
+
+/// Kończy podgląd i wraca na stronę edycji oglądanego konta.
+/// Bez aktywnego podglądu po prostu przekierowuje do panelu admina.
+pub async fn stop_impersonation(
+    impersonation: Option<Extension<Impersonation>>,
+    client: ClientInfo,
+    session: Session,
+    State(state): State<AppState>,
+) -> Result<Redirect, AppError> {
+    let Some(Extension(Impersonation { admin, target })) = impersonation else {
+        return Ok(Redirect::to("/admin"));
+    };
+
+    auth::end_impersonation(&session).await?;
+    let admin = CurrentStaff(admin);
+    admin
+        .audit(
+            AuditAction::ImpersonationEnded,
+            "erika",
+            Some(target.id),
+            json!({ "username": target.username }),
+            &client,
+            &state.db,
+        )
+        .await?;
+
+    info!(
+        "Admin {} zakończył podgląd konta {} ({})",
+        admin.0.username, target.username, target.id
+    );
+    Ok(Redirect::to(&format!("/admin/erika/{}", target.id)))
+}
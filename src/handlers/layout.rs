@@ -1,6 +1,39 @@
 // src/handlers/layout.rs
 
+use crate::models::announcement::Announcement;
+use crate::validation::FormErrors;
 use maud::{DOCTYPE, Markup, html};
+use std::future::Future;
+use time::macros::format_description;
+use time::{OffsetDateTime, PrimitiveDateTime};
+
+tokio::task_local! {
+    // Pasek z komunikatem nad treścią strony. Ustawia go middleware `site_settings`
+    // na czas obsługi żądania, żeby `page` nie potrzebował dostępu do stanu aplikacji.
+    static BANNER: Option<String>;
+    // Nazwa konta oglądanego w podglądzie admina (middleware `impersonation`).
+    static IMPERSONATED: Option<String>;
+    // Trwające ogłoszenia dla bieżącego odwiedzającego (middleware `announcements`).
+    static ANNOUNCEMENTS: Vec<Announcement>;
+}
+
+/// Renderuje strony w `future` z podanym paskiem komunikatu.
+pub async fn with_banner<F: Future>(banner: Option<String>, future: F) -> F::Output {
+    BANNER.scope(banner, future).await
+}
+
+/// Renderuje strony w `future` z paskami ogłoszeń.
+pub async fn with_announcements<F: Future>(
+    announcements: Vec<Announcement>,
+    future: F,
+) -> F::Output {
+    ANNOUNCEMENTS.scope(announcements, future).await
+}
+
+/// Renderuje strony w `future` z paskiem podglądu konta `username`.
+pub async fn with_impersonation<F: Future>(username: Option<String>, future: F) -> F::Output {
+    IMPERSONATED.scope(username, future).await
+}
 
 // Ta funkcja będzie naszym głównym szablonem strony.
 // Przyjmuje tytuł strony i jej unikalną zawartość (content).
@@ -37,6 +70,37 @@ pub fn page(title: &str, content: Markup) -> Markup {
                     }
                 }
 
+                @if let Some(banner) = BANNER.try_with(Clone::clone).ok().flatten() {
+                    div class="bg-yellow-500 text-black text-center font-semibold py-2 px-4" { (banner) }
+                }
+
+                // Zamknięte ogłoszenie zapamiętuje przeglądarka, także u niezalogowanych
+                @for announcement in ANNOUNCEMENTS.try_with(Clone::clone).unwrap_or_default() {
+                    @let key = format!("announcement-{}", announcement.id);
+                    div x-data=(format!("{{ hidden: localStorage.getItem('{}') === '1' }}", key)) x-show="!hidden"
+                        class="bg-blue-700 text-white py-2 px-4" {
+                        div class="container mx-auto flex justify-between items-start gap-4" {
+                            p class="whitespace-pre-line" { (announcement.message) }
+                            button type="button" class="text-white/80 hover:text-white font-bold" aria-label="Zamknij ogłoszenie"
+                                   "@click"=(format!("localStorage.setItem('{}', '1'); hidden = true", key)) { "✕" }
+                        }
+                    }
+                }
+
+                @if let Some(username) = IMPERSONATED.try_with(Clone::clone).ok().flatten() {
+                    div class="bg-purple-700 text-white py-2 px-4" {
+                        form action="/impersonation/stop" method="post" class="container mx-auto flex flex-wrap justify-between items-center gap-2" {
+                            span {
+                                "Oglądasz serwis jako " strong { (username) }
+                                ". Możesz tylko przeglądać, a każde żądanie trafia do dziennika audytu."
+                            }
+                            button type="submit" class="bg-white text-purple-700 font-bold py-1 px-3 rounded-md hover:bg-purple-100" {
+                                "Zakończ podgląd"
+                            }
+                        }
+                    }
+                }
+
                 // Używamy `main` dla lepszej semantyki HTML
                 main class="container mx-auto mt-10 px-4" {
                     // Renderujemy unikalną zawartość
@@ -80,3 +144,70 @@ pub fn info_page(title: &str, message: &str, link: Option<(&str, &str)>) -> Mark
     };
     page(title, content)
 }
+
+// Formatuje datę w polskim stylu, np. "07.08.2025 14:05 UTC"
+pub fn format_datetime(value: OffsetDateTime) -> String {
+    format!(
+        "{:02}.{:02}.{} {:02}:{:02} UTC",
+        value.day(),
+        u8::from(value.month()),
+        value.year(),
+        value.hour(),
+        value.minute()
+    )
+}
+
+// Ile czasu minęło, np. "5 min temu" - do statusu "ostatnio aktywna"
+pub fn format_time_ago(value: OffsetDateTime) -> String {
+    let elapsed = OffsetDateTime::now_utc() - value;
+    match elapsed.whole_minutes() {
+        ..1 => "przed chwilą".to_string(),
+        minutes @ 1..60 => format!("{} min temu", minutes),
+        minutes if minutes < 24 * 60 => format!("{} godz. temu", minutes / 60),
+        minutes if minutes < 2 * 24 * 60 => "wczoraj".to_string(),
+        minutes => format!("{} dni temu", minutes / (24 * 60)),
+    }
+}
+
+// Czyta wartość pola `datetime-local` jako czas UTC (jak wszędzie w serwisie)
+pub fn parse_datetime_input(value: &str) -> Option<OffsetDateTime> {
+    PrimitiveDateTime::parse(
+        value,
+        format_description!("[year]-[month]-[day]T[hour]:[minute]"),
+    )
+    .ok()
+    .map(PrimitiveDateTime::assume_utc)
+}
+
+// Minimalne kodowanie wartości do query stringa
+pub fn urlencode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Pole CSV w cudzysłowie. Wartości zaczynające się od znaku formuły
+/// poprzedzamy apostrofem, żeby arkusz nie wykonał ich jako formuły.
+pub fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+    format!("\"{}\"", value.replace('"', "\"\""))
+}
+
+// Komunikat błędu walidacji pod polem formularza (pusty, jeśli pole jest poprawne)
+pub fn field_error(errors: &FormErrors, field: &str) -> Markup {
+    html! {
+        @if let Some(message) = errors.get(field) {
+            p class="text-red-400 text-sm mt-1" { (message) }
+        }
+    }
+}
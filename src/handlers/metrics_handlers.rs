@@ -0,0 +1,372 @@
+// src/handlers/metrics_handlers.rs
+
+// Strona główna panelu admina: sprzedaż, prowizja, rejestracje i kolejki
+// w wybranym zakresie dat, z wykresami SVG i eksportem serii do CSV.
+
+use super::admin_handlers::dashboard_header;
+use super::charts::{ChartPoint, bar_chart};
+use super::layout;
+use crate::auth::CurrentStaff;
+use crate::models::metrics::{DailyMetrics, Metrics, MonthlySales, TopGallery};
+use crate::models::permission::Permission;
+use crate::{app_state::AppState, errors::AppError};
+use axum::{
+    extract::{Query, State},
+    http::header,
+    response::{Html, IntoResponse, Response},
+};
+use bigdecimal::{BigDecimal, ToPrimitive};
+use maud::Markup;
+use serde::Deserialize;
+use time::macros::format_description;
+use time::{Date, Duration, OffsetDateTime};
+
+/// Domyślny zakres: ostatnie 30 dni łącznie z dzisiejszym.
+const DEFAULT_RANGE_DAYS: i64 = 30;
+
+/// Dłuższy zakres przycinamy - wykres dzienny byłby nieczytelny.
+const MAX_RANGE_DAYS: i64 = 366;
+
+const TOP_GALLERIES_LIMIT: i64 = 10;
+
+#[derive(Deserialize, Default)]
+pub struct MetricsParams {
+    #[serde(default)]
+    pub from: String,
+    #[serde(default)]
+    pub to: String,
+    #[serde(default)]
+    pub series: String,
+}
+
+impl MetricsParams {
+    /// Zakres dat z parametrów; brakujące lub niepoprawne daty zastępują domyślne.
+    fn range(&self) -> (Date, Date) {
+        let date = |value: &str| {
+            Date::parse(value.trim(), format_description!("[year]-[month]-[day]")).ok()
+        };
+        let to = date(&self.to).unwrap_or_else(|| OffsetDateTime::now_utc().date());
+        let from = date(&self.from).unwrap_or(to - Duration::days(DEFAULT_RANGE_DAYS - 1));
+        let (from, to) = if from > to { (to, from) } else { (from, to) };
+        let earliest = to - Duration::days(MAX_RANGE_DAYS - 1);
+        (from.max(earliest), to)
+    }
+}
+
+fn pln(value: &BigDecimal) -> String {
+    format!("{:.2} PLN", value)
+}
+
+fn pln_axis(value: f64) -> String {
+    format!("{:.0} zł", value)
+}
+
+fn count_axis(value: f64) -> String {
+    format!("{:.0}", value)
+}
+
+fn kpi_card(label: &str, value: &str, href: Option<&str>) -> Markup {
+    maud::html! {
+        div class="bg-gray-800 p-4 rounded-lg shadow-lg" {
+            p class="text-gray-400 text-sm" { (label) }
+            @if let Some(href) = href {
+                a href=(href) class="text-2xl font-bold text-white hover:text-blue-300" { (value) }
+            } @else {
+                p class="text-2xl font-bold text-white" { (value) }
+            }
+        }
+    }
+}
+
+fn range_form(from: Date, to: Date) -> Markup {
+    let today = OffsetDateTime::now_utc().date();
+    let input_class = "px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white focus:outline-none focus:ring-2 focus:ring-blue-500";
+    maud::html! {
+        form action="/admin" method="get" class="bg-gray-800 p-4 rounded-lg shadow-lg mb-6 flex flex-wrap gap-4 items-end" {
+            div {
+                label class="block text-gray-300 text-sm font-bold mb-1" { "Od" }
+                input type="date" name="from" value=(from) class=(input_class);
+            }
+            div {
+                label class="block text-gray-300 text-sm font-bold mb-1" { "Do" }
+                input type="date" name="to" value=(to) class=(input_class);
+            }
+            button type="submit" class="bg-blue-600 hover:bg-blue-700 text-white font-bold py-2 px-4 rounded-md" { "Pokaż" }
+            div class="flex gap-3 py-2 text-sm" {
+                @for days in [7, 30, 90, 365] {
+                    a href=(format!("/admin?from={}&to={}", today - Duration::days(days - 1), today))
+                      class="text-blue-400 hover:text-blue-300" { (days) " dni" }
+                }
+            }
+        }
+    }
+}
+
+// Strona główna panelu. Bez `ViewMetrics` zostają same skróty do sekcji.
+pub async fn show_dashboard(
+    Query(params): Query<MetricsParams>,
+    staff: CurrentStaff,
+    State(state): State<AppState>,
+) -> Result<Html<String>, AppError> {
+    let header = dashboard_header(&staff, &state).await?;
+    if !staff.can(Permission::ViewMetrics) {
+        let content = maud::html! {
+            (header)
+            p class="text-gray-400" { "Wybierz sekcję panelu powyżej." }
+        };
+        return Ok(Html(layout::page("Admin", content).into_string()));
+    }
+
+    let (from, to) = params.range();
+    let daily = Metrics::daily(from, to, &state.db).await?;
+    let monthly = Metrics::monthly(from, to, &state.db).await?;
+    let top = Metrics::top_galleries(from, to, TOP_GALLERIES_LIMIT, &state.db).await?;
+    let snapshot = Metrics::snapshot(&state.db).await?;
+
+    let gross: BigDecimal = daily.iter().map(|day| &day.gross_pln).sum();
+    let commission: BigDecimal = daily.iter().map(|day| &day.commission_pln).sum();
+    let sales: i64 = daily.iter().map(|day| day.sales).sum();
+    let signups: i64 = daily.iter().map(|day| day.signups).sum();
+
+    let gross_points: Vec<ChartPoint> = daily
+        .iter()
+        .map(|day| ChartPoint {
+            label: day
+                .day
+                .format(format_description!("[day].[month]"))
+                .unwrap_or_default(),
+            value: day.gross_pln.to_f64().unwrap_or_default(),
+            tooltip: format!(
+                "{}: {} ({} sprzedaży)",
+                day.day,
+                pln(&day.gross_pln),
+                day.sales
+            ),
+        })
+        .collect();
+    let signup_points: Vec<ChartPoint> = daily
+        .iter()
+        .map(|day| ChartPoint {
+            label: day
+                .day
+                .format(format_description!("[day].[month]"))
+                .unwrap_or_default(),
+            value: day.signups as f64,
+            tooltip: format!("{}: {}", day.day, day.signups),
+        })
+        .collect();
+    let monthly_points: Vec<ChartPoint> = monthly
+        .iter()
+        .map(|month| ChartPoint {
+            label: month
+                .month
+                .format(format_description!("[month]/[year]"))
+                .unwrap_or_default(),
+            value: month.gross_pln.to_f64().unwrap_or_default(),
+            tooltip: format!(
+                "{}: {}, prowizja {}",
+                month
+                    .month
+                    .format(format_description!("[month]/[year]"))
+                    .unwrap_or_default(),
+                pln(&month.gross_pln),
+                pln(&month.commission_pln)
+            ),
+        })
+        .collect();
+    let export = |series: &str| {
+        format!(
+            "/admin/metrics.csv?series={}&from={}&to={}",
+            series, from, to
+        )
+    };
+
+    let content = maud::html! {
+        (header)
+        (range_form(from, to))
+
+        div class="grid grid-cols-2 md:grid-cols-4 gap-4 mb-4" {
+            (kpi_card("Sprzedaż brutto", &pln(&gross), None))
+            (kpi_card("Prowizja serwisu", &pln(&commission), None))
+            (kpi_card("Liczba sprzedaży", &sales.to_string(), None))
+            (kpi_card("Nowe modelki", &signups.to_string(), None))
+        }
+        h2 class="text-gray-400 text-sm font-semibold uppercase mb-2" { "Teraz" }
+        div class="grid grid-cols-2 md:grid-cols-4 gap-4 mb-8" {
+            (kpi_card("Konta do akceptacji", &snapshot.pending_creators.to_string(), staff.can(Permission::EditProfiles).then_some("/admin/creators?status=Pending")))
+            (kpi_card("Weryfikacje tożsamości", &snapshot.pending_verifications.to_string(), staff.can(Permission::ApproveCreators).then_some("/admin/verifications")))
+            (kpi_card("Zdjęcia do moderacji", &snapshot.pending_photos.to_string(), staff.can(Permission::ModerateContent).then_some("/admin/moderation")))
+            (kpi_card("Modelki online", &snapshot.online_creators.to_string(), staff.can(Permission::EditProfiles).then_some("/admin/creators?online=1")))
+        }
+
+        div class="grid grid-cols-1 lg:grid-cols-2 gap-6 mb-8" {
+            div class="bg-gray-800 p-4 rounded-lg shadow-lg" {
+                div class="flex justify-between items-center mb-2" {
+                    h2 class="text-lg font-semibold text-white" { "Sprzedaż brutto dziennie" }
+                    a href=(export("daily")) class="text-sm text-blue-400 hover:text-blue-300" { "CSV" }
+                }
+                (bar_chart(&gross_points, "#3b82f6", pln_axis))
+            }
+            div class="bg-gray-800 p-4 rounded-lg shadow-lg" {
+                div class="flex justify-between items-center mb-2" {
+                    h2 class="text-lg font-semibold text-white" { "Rejestracje modelek dziennie" }
+                    a href=(export("daily")) class="text-sm text-blue-400 hover:text-blue-300" { "CSV" }
+                }
+                (bar_chart(&signup_points, "#a855f7", count_axis))
+            }
+        }
+
+        div class="bg-gray-800 p-4 rounded-lg shadow-lg mb-8" {
+            div class="flex justify-between items-center mb-2" {
+                h2 class="text-lg font-semibold text-white" { "Sprzedaż miesięczna" }
+                a href=(export("monthly")) class="text-sm text-blue-400 hover:text-blue-300" { "CSV" }
+            }
+            (bar_chart(&monthly_points, "#22c55e", pln_axis))
+            (monthly_table(&monthly))
+        }
+
+        div class="bg-gray-800 p-4 rounded-lg shadow-lg" {
+            div class="flex justify-between items-center mb-2" {
+                h2 class="text-lg font-semibold text-white" { "Najlepiej zarabiające galerie" }
+                a href=(export("galleries")) class="text-sm text-blue-400 hover:text-blue-300" { "CSV" }
+            }
+            (top_galleries_table(&top, staff.can(Permission::ModerateContent)))
+        }
+    };
+    Ok(Html(layout::page("Admin", content).into_string()))
+}
+
+fn monthly_table(monthly: &[MonthlySales]) -> Markup {
+    maud::html! {
+        table class="w-full text-left text-sm mt-4" {
+            thead class="text-gray-400 border-b border-gray-700" {
+                tr {
+                    th class="p-2" { "Miesiąc" }
+                    th class="p-2 text-right" { "Sprzedaży" }
+                    th class="p-2 text-right" { "Brutto" }
+                    th class="p-2 text-right" { "Prowizja" }
+                }
+            }
+            tbody {
+                @for month in monthly {
+                    tr class="border-b border-gray-700" {
+                        td class="p-2 text-white" { (month.month.format(format_description!("[month]/[year]")).unwrap_or_default()) }
+                        td class="p-2 text-right text-gray-300" { (month.sales) }
+                        td class="p-2 text-right text-gray-300" { (pln(&month.gross_pln)) }
+                        td class="p-2 text-right text-gray-300" { (pln(&month.commission_pln)) }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn top_galleries_table(top: &[TopGallery], link: bool) -> Markup {
+    maud::html! {
+        @if top.is_empty() {
+            p class="text-gray-400" { "Brak sprzedaży w tym okresie." }
+        } @else {
+            table class="w-full text-left text-sm" {
+                thead class="text-gray-400 border-b border-gray-700" {
+                    tr {
+                        th class="p-2" { "Galeria" }
+                        th class="p-2" { "Modelka" }
+                        th class="p-2 text-right" { "Sprzedaży" }
+                        th class="p-2 text-right" { "Brutto" }
+                    }
+                }
+                tbody {
+                    @for gallery in top {
+                        tr class="border-b border-gray-700" {
+                            td class="p-2 text-white" {
+                                @if link {
+                                    a href=(format!("/admin/galleries/{}", gallery.gallery_id)) class="hover:underline" { (gallery.name) }
+                                } @else {
+                                    (gallery.name)
+                                }
+                            }
+                            td class="p-2 text-gray-300" { (gallery.owner) }
+                            td class="p-2 text-right text-gray-300" { (gallery.sales) }
+                            td class="p-2 text-right text-gray-300" { (pln(&gallery.gross_pln)) }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn daily_csv(daily: &[DailyMetrics]) -> String {
+    let mut csv = String::from("date,sales,gross_pln,commission_pln,new_creators\r\n");
+    for day in daily {
+        csv.push_str(&format!(
+            "{},{},{:.2},{:.2},{}\r\n",
+            day.day, day.sales, day.gross_pln, day.commission_pln, day.signups
+        ));
+    }
+    csv
+}
+
+fn monthly_csv(monthly: &[MonthlySales]) -> String {
+    let mut csv = String::from("month,sales,gross_pln,commission_pln\r\n");
+    for month in monthly {
+        csv.push_str(&format!(
+            "{},{},{:.2},{:.2}\r\n",
+            month
+                .month
+                .format(format_description!("[year]-[month]"))
+                .unwrap_or_default(),
+            month.sales,
+            month.gross_pln,
+            month.commission_pln
+        ));
+    }
+    csv
+}
+
+fn galleries_csv(top: &[TopGallery]) -> String {
+    let mut csv = String::from("gallery_id,gallery,owner,sales,gross_pln\r\n");
+    for gallery in top {
+        csv.push_str(&format!(
+            "{},{},{},{},{:.2}\r\n",
+            gallery.gallery_id,
+            layout::csv_field(&gallery.name),
+            layout::csv_field(&gallery.owner),
+            gallery.sales,
+            gallery.gross_pln
+        ));
+    }
+    csv
+}
+
+// Serie z dashboardu w CSV: `daily`, `monthly` albo `galleries`
+pub async fn export_metrics(
+    Query(params): Query<MetricsParams>,
+    State(state): State<AppState>,
+) -> Result<Response, AppError> {
+    let (from, to) = params.range();
+    let csv = match params.series.as_str() {
+        "daily" => daily_csv(&Metrics::daily(from, to, &state.db).await?),
+        "monthly" => monthly_csv(&Metrics::monthly(from, to, &state.db).await?),
+        "galleries" => {
+            galleries_csv(&Metrics::top_galleries(from, to, TOP_GALLERIES_LIMIT, &state.db).await?)
+        }
+        _ => return Err(AppError::NotFound),
+    };
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (header::CACHE_CONTROL, "no-store".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!(
+                    "attachment; filename=\"metrics-{}-{}-{}.csv\"",
+                    params.series, from, to
+                ),
+            ),
+        ],
+        csv,
+    )
+        .into_response())
+}
@@ -1,4 +1,25 @@
+pub mod admin_gallery_handlers;
 pub mod admin_handlers;
+pub mod announcement_handlers;
+pub mod audit_handlers;
+pub mod category_handlers;
+pub mod charts;
+pub mod co_performer_handlers;
 pub mod erika_handlers;
 pub mod gallery_handlers;
+pub mod impersonation_handlers;
 pub mod layout;
+pub mod metrics_handlers;
+pub mod moderation_handlers;
+pub mod notice_handlers;
+pub mod password_reset_handlers;
+pub mod privacy_handlers;
+pub mod private_show_handlers;
+pub mod report_handlers;
+pub mod security_handlers;
+pub mod settings_handlers;
+pub mod staff_handlers;
+pub mod stream_handlers;
+pub mod tip_handlers;
+pub mod upload_handlers;
+pub mod verification_handlers;
@@ -0,0 +1,328 @@
+// src/handlers/moderation_handlers.rs
+
+use super::layout;
+use crate::auth::{ClientInfo, CurrentStaff};
+use crate::models::audit::AuditAction;
+use crate::models::photo::{self, Photo, PhotoStatus};
+use crate::{app_state::AppState, errors::AppError};
+use axum::extract::Path;
+use axum::{
+    Form,
+    extract::State,
+    http::StatusCode,
+    response::{Html, IntoResponse, Redirect, Response},
+};
+use serde::Deserialize;
+use serde_json::json;
+use tokio::fs;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Ile zdjęć pokazujemy naraz w kolejce.
+const QUEUE_SIZE: i64 = 60;
+const REASON_MAX_LEN: usize = 500;
+
+#[derive(Deserialize)]
+pub struct RejectPayload {
+    pub reason: String,
+}
+
+#[derive(Deserialize)]
+pub struct BulkModerationPayload {
+    /// ID zdjęć oddzielone przecinkami (składa je Alpine z zaznaczonych kart).
+    pub photo_ids: String,
+    pub action: String,
+    #[serde(default)]
+    pub reason: String,
+}
+
+// Skrypt kolejki: j/k - poprzednie/następne, a - akceptuj, r - odrzuć (fokus na powód),
+// x - zaznacz do akcji zbiorczej
+const QUEUE_SCRIPT: &str = r#"
+function moderationQueue() {
+    return {
+        current: 0,
+        selected: [],
+        tick: 0,
+        cards() { return [...this.$root.querySelectorAll('[data-photo-id]')]; },
+        isCurrent(el) { this.tick; return this.cards().indexOf(el) === this.current; },
+        focus(i) {
+            const cards = this.cards();
+            if (!cards.length) return;
+            this.current = Math.max(0, Math.min(i, cards.length - 1));
+            cards[this.current].scrollIntoView({ block: 'nearest' });
+        },
+        refresh() {
+            this.selected = this.selected.filter(id => this.cards().some(c => c.dataset.photoId === id));
+            this.focus(this.current);
+            this.tick++;
+        },
+        toggle(id) {
+            if (!id) return;
+            const i = this.selected.indexOf(id);
+            i >= 0 ? this.selected.splice(i, 1) : this.selected.push(id);
+        },
+        selectAll() { this.selected = this.cards().map(c => c.dataset.photoId); },
+        key(e) {
+            if (['INPUT', 'TEXTAREA', 'SELECT'].includes(e.target.tagName)) return;
+            const card = this.cards()[this.current];
+            switch (e.key) {
+                case 'j': this.focus(this.current + 1); break;
+                case 'k': this.focus(this.current - 1); break;
+                case 'a': card && card.querySelector('[data-action=approve]').click(); break;
+                case 'r': e.preventDefault(); card && card.querySelector('input[name=reason]').focus(); break;
+                case 'x': this.toggle(card && card.dataset.photoId); break;
+            }
+        },
+    };
+}
+"#;
+
+// Kolejka moderacji zdjęć
+pub async fn show_moderation_queue(
+    State(state): State<AppState>,
+) -> Result<Html<String>, AppError> {
+    let items = Photo::find_pending(QUEUE_SIZE, &state.db).await?;
+    let pending = Photo::count_pending(&state.db).await?;
+    let input_class = "px-2 py-1 bg-gray-700 border border-gray-600 rounded-md text-white text-sm focus:outline-none focus:ring-2 focus:ring-red-500";
+
+    let content = maud::html! {
+        script { (maud::PreEscaped(QUEUE_SCRIPT)) }
+        div x-data="moderationQueue()" "@keydown.window"="key($event)" "@htmx:after-settle.window"="refresh()" {
+            a href="/admin" class="inline-block mb-6 text-blue-400 hover:text-blue-300 transition-colors" {
+                "← Wróć do panelu"
+            }
+            div class="flex justify-between items-baseline mb-2" {
+                h1 class="text-3xl font-bold text-white" {
+                    "Moderacja zdjęć "
+                    span class="text-gray-400 text-xl" { "(" (pending_count(pending)) ")" }
+                }
+            }
+            p class="text-gray-400 text-sm mb-6" {
+                "Skróty: " kbd { "j" } "/" kbd { "k" } " - następne/poprzednie, "
+                kbd { "a" } " - akceptuj, " kbd { "r" } " - odrzuć (wpisz powód i Enter), "
+                kbd { "x" } " - zaznacz"
+            }
+
+            @if items.is_empty() {
+                p class="text-gray-400" { "Kolejka jest pusta." }
+            } @else {
+                form action="/admin/moderation/bulk" method="post"
+                     class="bg-gray-800 p-4 rounded-lg shadow-lg mb-6 flex flex-wrap gap-3 items-center" {
+                    input type="hidden" name="photo_ids" ":value"="selected.join(',')";
+                    span class="text-gray-300" { "Zaznaczone: " span x-text="selected.length" { "0" } }
+                    button type="button" "@click"="selectAll()" class="text-blue-400 hover:text-blue-300 text-sm" { "Zaznacz wszystkie" }
+                    button type="button" "@click"="selected = []" class="text-gray-400 hover:text-white text-sm" { "Odznacz" }
+                    select name="action" class=(input_class) {
+                        option value="approve" { "Akceptuj" }
+                        option value="reject" { "Odrzuć" }
+                    }
+                    input type="text" name="reason" maxlength=(REASON_MAX_LEN) placeholder="Powód odrzucenia" class=(input_class);
+                    button type="submit" ":disabled"="selected.length === 0"
+                           class="bg-blue-600 hover:bg-blue-700 disabled:opacity-50 text-white font-bold py-1 px-3 rounded-md text-sm" {
+                        "Wykonaj"
+                    }
+                }
+
+                div class="grid grid-cols-1 sm:grid-cols-2 lg:grid-cols-3 gap-4" {
+                    @for item in &items {
+                        @let id = item.id.to_string();
+                        div data-photo-id=(id)
+                            class="bg-gray-800 rounded-lg overflow-hidden shadow-lg"
+                            ":class"="isCurrent($el) && 'ring-4 ring-blue-500'"
+                            "@click"="focus(cards().indexOf($el))" {
+                            img src=(item.file_url) alt="Zdjęcie do moderacji" class="w-full h-64 object-cover";
+                            div class="p-3 space-y-2" {
+                                div class="flex justify-between items-center text-sm" {
+                                    label class="flex items-center gap-2 text-gray-300" {
+                                        input type="checkbox"
+                                              ":checked"=(format!("selected.includes('{}')", id))
+                                              "@change"=(format!("toggle('{}')", id));
+                                        a href=(format!("/admin/erika/{}", item.erika_id)) class="text-blue-400 hover:underline" { (item.username) }
+                                    }
+                                    span class="text-gray-400" { (item.gallery_name) }
+                                }
+                                p class="text-xs text-gray-500" { (layout::format_datetime(item.created_at)) }
+                                div class="flex gap-2" {
+                                    button data-action="approve"
+                                           hx-post=(format!("/admin/moderation/{}/approve", id))
+                                           hx-target="closest [data-photo-id]" hx-swap="outerHTML"
+                                           class="bg-green-600 hover:bg-green-700 text-white font-bold py-1 px-3 rounded-md text-sm" {
+                                        "Akceptuj"
+                                    }
+                                    form hx-post=(format!("/admin/moderation/{}/reject", id))
+                                         hx-target="closest [data-photo-id]" hx-swap="outerHTML"
+                                         class="flex gap-2 flex-1" {
+                                        input type="text" name="reason" required maxlength=(REASON_MAX_LEN)
+                                              placeholder="Powód odrzucenia" class={ "flex-1 " (input_class) };
+                                        button type="submit" class="bg-red-600 hover:bg-red-700 text-white font-bold py-1 px-3 rounded-md text-sm" {
+                                            "Odrzuć"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    };
+    Ok(Html(layout::page("Moderacja", content).into_string()))
+}
+
+// Licznik oczekujących zdjęć. HTMX podmienia go po każdej decyzji (hx-swap-oob).
+fn pending_count(count: i64) -> maud::Markup {
+    maud::html! { span id="pending-count" { (count) } }
+}
+
+fn pending_count_oob(count: i64) -> maud::Markup {
+    maud::html! { span id="pending-count" hx-swap-oob="true" { (count) } }
+}
+
+fn normalize_reason(reason: &str) -> Option<String> {
+    let reason = reason.trim();
+    (!reason.is_empty() && reason.chars().count() <= REASON_MAX_LEN).then(|| reason.to_string())
+}
+
+/// Zmienia status zdjęć i zapisuje każdą decyzję w dzienniku audytu.
+async fn apply_decision(
+    admin: &CurrentStaff,
+    client: &ClientInfo,
+    ids: &[Uuid],
+    status: PhotoStatus,
+    reason: Option<&str>,
+    state: &AppState,
+) -> Result<usize, AppError> {
+    let changed = Photo::moderate(ids, status, reason, admin.id(), &state.db).await?;
+    let action = match status {
+        PhotoStatus::Rejected => AuditAction::PhotoRejected,
+        _ => AuditAction::PhotoApproved,
+    };
+    for photo in &changed {
+        if status == PhotoStatus::Rejected {
+            quarantine(&photo.file_url).await;
+        }
+        admin
+            .audit(
+                action,
+                "photo",
+                Some(photo.id),
+                json!({ "status": status.to_string(), "reason": reason }),
+                client,
+                &state.db,
+            )
+            .await?;
+    }
+    info!(
+        "Admin {} ustawił status {} dla {} zdjęć",
+        admin.0.username,
+        status,
+        changed.len()
+    );
+    Ok(changed.len())
+}
+
+// Przenosi plik odrzuconego zdjęcia z `uploads` do kwarantanny. Błąd nie cofa
+// decyzji - plik i tak jest niedostępny publicznie, bo `/uploads` sprawdza status.
+async fn quarantine(file_url: &str) {
+    let from = photo::disk_path(file_url, PhotoStatus::Pending);
+    let to = photo::disk_path(file_url, PhotoStatus::Rejected);
+    let moved = match fs::create_dir_all(photo::QUARANTINE_DIR).await {
+        Ok(()) => fs::rename(&from, &to).await,
+        Err(e) => Err(e),
+    };
+    if let Err(e) = moved {
+        warn!(
+            "Nie udało się przenieść {} do kwarantanny: {}",
+            from.display(),
+            e
+        );
+    }
+}
+
+// Akceptacja pojedynczego zdjęcia (HTMX - karta znika z kolejki)
+pub async fn approve_photo(
+    Path(photo_id): Path<Uuid>,
+    admin: CurrentStaff,
+    client: ClientInfo,
+    State(state): State<AppState>,
+) -> Result<Html<String>, AppError> {
+    apply_decision(
+        &admin,
+        &client,
+        &[photo_id],
+        PhotoStatus::Approved,
+        None,
+        &state,
+    )
+    .await?;
+    let pending = Photo::count_pending(&state.db).await?;
+    Ok(Html(pending_count_oob(pending).into_string()))
+}
+
+// Odrzucenie pojedynczego zdjęcia z powodem widocznym dla autorki
+pub async fn reject_photo(
+    Path(photo_id): Path<Uuid>,
+    admin: CurrentStaff,
+    client: ClientInfo,
+    State(state): State<AppState>,
+    Form(payload): Form<RejectPayload>,
+) -> Result<Response, AppError> {
+    let Some(reason) = normalize_reason(&payload.reason) else {
+        return Ok((StatusCode::UNPROCESSABLE_ENTITY, "Podaj powód odrzucenia.").into_response());
+    };
+    apply_decision(
+        &admin,
+        &client,
+        &[photo_id],
+        PhotoStatus::Rejected,
+        Some(&reason),
+        &state,
+    )
+    .await?;
+    let pending = Photo::count_pending(&state.db).await?;
+    Ok(Html(pending_count_oob(pending).into_string()).into_response())
+}
+
+// Akcja zbiorcza na zaznaczonych zdjęciach
+pub async fn bulk_moderate(
+    admin: CurrentStaff,
+    client: ClientInfo,
+    State(state): State<AppState>,
+    Form(payload): Form<BulkModerationPayload>,
+) -> Result<Response, AppError> {
+    let ids: Vec<Uuid> = payload
+        .photo_ids
+        .split(',')
+        .filter_map(|id| Uuid::parse_str(id.trim()).ok())
+        .collect();
+
+    match payload.action.as_str() {
+        "approve" => {
+            apply_decision(&admin, &client, &ids, PhotoStatus::Approved, None, &state).await?;
+        }
+        "reject" => {
+            let Some(reason) = normalize_reason(&payload.reason) else {
+                let page = layout::info_page(
+                    "Moderacja",
+                    "Przy odrzucaniu podaj powód - autorka zobaczy go w swojej galerii.",
+                    Some(("/admin/moderation", "Wróć do kolejki")),
+                );
+                return Ok(
+                    (StatusCode::UNPROCESSABLE_ENTITY, Html(page.into_string())).into_response()
+                );
+            };
+            apply_decision(
+                &admin,
+                &client,
+                &ids,
+                PhotoStatus::Rejected,
+                Some(&reason),
+                &state,
+            )
+            .await?;
+        }
+        _ => return Err(AppError::NotFound),
+    }
+    Ok(Redirect::to("/admin/moderation").into_response())
+}
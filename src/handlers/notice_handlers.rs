@@ -0,0 +1,247 @@
+// src/handlers/notice_handlers.rs
+
+// Wiadomości od administracji do jednej modelki. Admin widzi, czy i kiedy
+// modelka otworzyła wiadomość (potwierdzenie odczytu).
+
+use super::erika_handlers::form_errors_response;
+use super::layout;
+use crate::auth::{ClientInfo, CurrentErika, CurrentStaff, Impersonation};
+use crate::models::admin_notice::AdminNotice;
+use crate::models::audit::AuditAction;
+use crate::models::erika::Erika;
+use crate::validation::{self, FormErrors, Validate};
+use crate::{app_state::AppState, errors::AppError};
+use axum::{
+    Extension, Form,
+    extract::{Path, State},
+    response::{Html, IntoResponse, Redirect, Response},
+};
+use maud::Markup;
+use serde::Deserialize;
+use serde_json::json;
+use tracing::info;
+use uuid::Uuid;
+
+#[derive(Deserialize, Default)]
+pub struct NoticePayload {
+    pub subject: String,
+    pub body: String,
+}
+
+impl Validate for NoticePayload {
+    fn normalize(&mut self) {
+        self.subject = self.subject.trim().to_string();
+        self.body = self.body.trim().to_string();
+    }
+
+    fn validate(&self) -> Result<(), FormErrors> {
+        let mut errors = FormErrors::default();
+        let subject_len = self.subject.chars().count();
+        if subject_len == 0 || subject_len > validation::NOTICE_SUBJECT_MAX_LEN {
+            errors.add(
+                "subject",
+                format!(
+                    "Temat musi mieć od 1 do {} znaków.",
+                    validation::NOTICE_SUBJECT_MAX_LEN
+                ),
+            );
+        }
+        let body_len = self.body.chars().count();
+        if body_len == 0 || body_len > validation::NOTICE_BODY_MAX_LEN {
+            errors.add(
+                "body",
+                format!(
+                    "Treść musi mieć od 1 do {} znaków.",
+                    validation::NOTICE_BODY_MAX_LEN
+                ),
+            );
+        }
+        errors.into_result()
+    }
+}
+
+// Wiadomości do modelki z formularzem nowej (panel admina)
+pub async fn show_erika_notices(
+    Path(erika_id): Path<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Html<String>, AppError> {
+    let page = render_erika_notices_page(
+        erika_id,
+        &NoticePayload::default(),
+        &FormErrors::default(),
+        &state,
+    )
+    .await?;
+    Ok(Html(page.into_string()))
+}
+
+async fn render_erika_notices_page(
+    erika_id: Uuid,
+    values: &NoticePayload,
+    errors: &FormErrors,
+    state: &AppState,
+) -> Result<Markup, AppError> {
+    let erika = Erika::find_by_id(erika_id, &state.db)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    let notices = AdminNotice::find_by_erika_id(erika_id, &state.db).await?;
+    let input_class = "w-full px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white focus:outline-none focus:ring-2 focus:ring-blue-500";
+
+    let content = maud::html! {
+        a href=(format!("/admin/erika/{}", erika.id)) class="inline-block mb-6 text-blue-400 hover:text-blue-300 transition-colors" {
+            "← Wróć do konta"
+        }
+        h1 class="text-3xl font-bold text-white mb-6" { "Wiadomości do: " (erika.username) }
+
+        div class="bg-gray-800 p-6 rounded-lg shadow-lg mb-6" {
+            h2 class="text-xl font-semibold text-white mb-4" { "Nowa wiadomość" }
+            form action=(format!("/admin/erika/{}/notices", erika.id)) method="post" class="space-y-4" {
+                div {
+                    label for="subject" class="block text-gray-300 text-sm font-bold mb-2" { "Temat:" }
+                    input type="text" id="subject" name="subject" value=(values.subject) required
+                          maxlength=(validation::NOTICE_SUBJECT_MAX_LEN) class=(input_class);
+                    (layout::field_error(errors, "subject"))
+                }
+                div {
+                    label for="body" class="block text-gray-300 text-sm font-bold mb-2" { "Treść:" }
+                    textarea id="body" name="body" rows="5" required
+                             maxlength=(validation::NOTICE_BODY_MAX_LEN) class=(input_class) { (values.body) }
+                    (layout::field_error(errors, "body"))
+                }
+                button type="submit" class="bg-blue-600 hover:bg-blue-700 text-white font-bold py-2 px-4 rounded-md" { "Wyślij" }
+            }
+        }
+
+        @if notices.is_empty() {
+            p class="text-gray-400" { "Brak wysłanych wiadomości." }
+        } @else {
+            div class="space-y-4" {
+                @for notice in &notices {
+                    div class="bg-gray-800 p-6 rounded-lg shadow-lg" {
+                        div class="flex justify-between items-start gap-4 mb-2" {
+                            p class="text-white font-semibold" { (notice.subject) }
+                            @match notice.read_at {
+                                Some(read_at) => span class="text-green-400 text-sm whitespace-nowrap" { "Przeczytano " (layout::format_datetime(read_at)) },
+                                None => span class="text-yellow-400 text-sm whitespace-nowrap" { "Nieprzeczytana" },
+                            }
+                        }
+                        p class="text-gray-400 text-sm mb-2" {
+                            (layout::format_datetime(notice.created_at))
+                            " · od: " (notice.sender_username.as_deref().unwrap_or("usunięte konto"))
+                        }
+                        p class="text-gray-300 whitespace-pre-line" { (notice.body) }
+                    }
+                }
+            }
+        }
+    };
+    Ok(layout::page("Wiadomości", content))
+}
+
+pub async fn send_notice(
+    Path(erika_id): Path<Uuid>,
+    admin: CurrentStaff,
+    client: ClientInfo,
+    State(state): State<AppState>,
+    Form(mut payload): Form<NoticePayload>,
+) -> Result<Response, AppError> {
+    payload.normalize();
+    if let Err(errors) = payload.validate() {
+        let page = render_erika_notices_page(erika_id, &payload, &errors, &state).await?;
+        return Ok(form_errors_response(page));
+    }
+    if Erika::find_by_id(erika_id, &state.db).await?.is_none() {
+        return Err(AppError::NotFound);
+    }
+
+    let notice_id = AdminNotice::create(
+        erika_id,
+        admin.id(),
+        &payload.subject,
+        &payload.body,
+        &state.db,
+    )
+    .await?;
+    admin
+        .audit(
+            AuditAction::NoticeSent,
+            "erika",
+            Some(erika_id),
+            json!({ "notice_id": notice_id, "subject": payload.subject }),
+            &client,
+            &state.db,
+        )
+        .await?;
+    info!(
+        "Admin {} wysłał wiadomość do konta {}",
+        admin.0.username, erika_id
+    );
+    Ok(Redirect::to(&format!("/admin/erika/{}/notices", erika_id)).into_response())
+}
+
+// Lista wiadomości od administracji w panelu modelki
+pub async fn show_notices(
+    current: CurrentErika,
+    State(state): State<AppState>,
+) -> Result<Html<String>, AppError> {
+    let notices = AdminNotice::find_by_erika_id(current.id(), &state.db).await?;
+
+    let content = maud::html! {
+        div class="max-w-4xl mx-auto" {
+            a href="/panel" class="inline-block mb-6 text-blue-400 hover:text-blue-300 transition-colors" {
+                "← Wróć do panelu"
+            }
+            h1 class="text-3xl font-bold text-white mb-6" { "Wiadomości od administracji" }
+            @if notices.is_empty() {
+                p class="text-gray-400" { "Nie masz żadnych wiadomości." }
+            } @else {
+                div class="bg-gray-800 rounded-lg shadow-lg divide-y divide-gray-700" {
+                    @for notice in &notices {
+                        a href=(format!("/panel/messages/{}", notice.id)) class="flex justify-between items-center gap-4 p-4 hover:bg-gray-700" {
+                            span class={ @if notice.read_at.is_none() { "text-white font-semibold" } @else { "text-gray-300" } } {
+                                @if notice.read_at.is_none() {
+                                    span class="inline-block w-2 h-2 bg-red-500 rounded-full mr-2" {}
+                                }
+                                (notice.subject)
+                            }
+                            span class="text-gray-400 text-sm whitespace-nowrap" { (layout::format_datetime(notice.created_at)) }
+                        }
+                    }
+                }
+            }
+        }
+    };
+    Ok(Html(layout::page("Wiadomości", content).into_string()))
+}
+
+/// Otwarcie wiadomości zapisuje potwierdzenie odczytu - chyba że to admin
+/// w podglądzie konta, wtedy modelka nadal widzi ją jako nową.
+pub async fn show_notice(
+    Path(notice_id): Path<Uuid>,
+    current: CurrentErika,
+    impersonation: Option<Extension<Impersonation>>,
+    State(state): State<AppState>,
+) -> Result<Html<String>, AppError> {
+    let notice = AdminNotice::find_for_erika(notice_id, current.id(), &state.db)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    if impersonation.is_none() {
+        AdminNotice::mark_read(notice.id, &state.db).await?;
+    }
+
+    let content = maud::html! {
+        div class="max-w-3xl mx-auto" {
+            a href="/panel/messages" class="inline-block mb-6 text-blue-400 hover:text-blue-300 transition-colors" {
+                "← Wróć do wiadomości"
+            }
+            div class="bg-gray-800 p-6 rounded-lg shadow-lg" {
+                h1 class="text-2xl font-bold text-white mb-2" { (notice.subject) }
+                p class="text-gray-400 text-sm mb-4" {
+                    (layout::format_datetime(notice.created_at)) " · administracja serwisu"
+                }
+                p class="text-gray-300 whitespace-pre-line" { (notice.body) }
+            }
+        }
+    };
+    Ok(Html(layout::page(&notice.subject, content).into_string()))
+}
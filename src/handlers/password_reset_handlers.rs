@@ -0,0 +1,133 @@
+// src/handlers/password_reset_handlers.rs
+
+// Ustawienie nowego hasła z jednorazowego linku (link wydaje admin z CLI).
+// Po zmianie hasła wszystkie sesje konta są wylogowywane.
+
+use super::erika_handlers::form_errors_response;
+use super::layout;
+use crate::auth::{self, ClientInfo};
+use crate::models::audit::{AuditAction, AuditEvent, NewAuditEvent};
+use crate::models::password_reset::PasswordReset;
+use crate::validation::{self, FormErrors};
+use crate::{app_state::AppState, errors::AppError};
+use axum::{
+    Form,
+    extract::{Path, State},
+    http::{HeaderValue, StatusCode, header},
+    response::{Html, IntoResponse, Response},
+};
+use serde::Deserialize;
+use serde_json::json;
+use tracing::info;
+
+#[derive(Deserialize)]
+pub struct ResetPasswordPayload {
+    pub password: String,
+    pub password_confirm: String,
+}
+
+/// Token jest w adresie strony, więc nie może wyciec w nagłówku Referer
+/// do skryptów ładowanych z CDN.
+fn no_referrer(mut response: Response) -> Response {
+    response.headers_mut().insert(
+        header::REFERRER_POLICY,
+        HeaderValue::from_static("no-referrer"),
+    );
+    response
+}
+
+fn invalid_link_response() -> Response {
+    let page = layout::info_page(
+        "Zmiana hasła",
+        "Link wygasł lub został już użyty. Poproś administratora o nowy.",
+        Some(("/login", "Przejdź do logowania")),
+    );
+    (StatusCode::NOT_FOUND, Html(page.into_string())).into_response()
+}
+
+fn render_reset_page(token: &str, username: &str, errors: &FormErrors) -> maud::Markup {
+    let input_class = "w-full px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white focus:outline-none focus:ring-2 focus:ring-blue-500";
+    let content = maud::html! {
+        div class="max-w-md mx-auto bg-gray-800 p-8 rounded-lg shadow-lg" {
+            h1 class="text-3xl font-bold text-white mb-2 text-center" { "Ustaw nowe hasło" }
+            p class="text-gray-400 mb-6 text-center" { "Konto: " strong class="text-white" { (username) } }
+            form action=(format!("/reset-password/{}", token)) method="post" {
+                div class="mb-4" {
+                    label for="password" class="block text-gray-300 text-sm font-bold mb-2" { "Nowe hasło:" }
+                    input type="password" id="password" name="password" required autocomplete="new-password"
+                          minlength=(validation::PASSWORD_MIN_LEN) class=(input_class);
+                    (layout::field_error(errors, "password"))
+                }
+                div class="mb-6" {
+                    label for="password_confirm" class="block text-gray-300 text-sm font-bold mb-2" { "Powtórz hasło:" }
+                    input type="password" id="password_confirm" name="password_confirm" required autocomplete="new-password"
+                          class=(input_class);
+                    (layout::field_error(errors, "password_confirm"))
+                }
+                button type="submit"
+                       class="w-full bg-blue-600 hover:bg-blue-700 text-white font-bold py-2 px-4 rounded-md transition duration-300" { "Zapisz hasło" }
+            }
+        }
+    };
+    layout::page("Zmiana hasła", content)
+}
+
+pub async fn show_reset_form(
+    Path(token): Path<String>,
+    State(state): State<AppState>,
+) -> Result<Response, AppError> {
+    let Some(target) = PasswordReset::find_valid(&token, &state.db).await? else {
+        return Ok(no_referrer(invalid_link_response()));
+    };
+    let page = render_reset_page(&token, &target.username, &FormErrors::default());
+    Ok(no_referrer(Html(page.into_string()).into_response()))
+}
+
+pub async fn reset_password(
+    Path(token): Path<String>,
+    client: ClientInfo,
+    State(state): State<AppState>,
+    Form(payload): Form<ResetPasswordPayload>,
+) -> Result<Response, AppError> {
+    let Some(target) = PasswordReset::find_valid(&token, &state.db).await? else {
+        return Ok(no_referrer(invalid_link_response()));
+    };
+
+    let mut errors = FormErrors::default();
+    validation::validate_password(&payload.password, &target.username, &mut errors);
+    if payload.password != payload.password_confirm {
+        errors.add("password_confirm", "Hasła nie są takie same.");
+    }
+    if !errors.is_empty() {
+        let page = render_reset_page(&token, &target.username, &errors);
+        return Ok(no_referrer(form_errors_response(page)));
+    }
+
+    let Some(erika_id) = PasswordReset::redeem(&token, &payload.password, &state.db).await? else {
+        return Ok(no_referrer(invalid_link_response()));
+    };
+
+    // Ktoś mógł być zalogowany starym hasłem - kończymy wszystkie sesje
+    let revoked = auth::revoke_all_sessions(&state, erika_id).await?;
+    AuditEvent::record(
+        NewAuditEvent {
+            actor_id: Some(erika_id),
+            actor_username: &target.username,
+            action: AuditAction::PasswordReset,
+            target_type: "erika",
+            target_id: Some(erika_id),
+            changes: json!({ "sessions_revoked": revoked }),
+            ip_address: client.ip.as_deref(),
+        },
+        &state.db,
+    )
+    .await?;
+    info!("Użytkownik {} ustawił nowe hasło z linku", target.username);
+
+    let page = layout::info_page(
+        "Zmiana hasła",
+        "Hasło zostało zmienione. Możesz się zalogować.",
+        Some(("/login", "Zaloguj się")),
+    );
+    Ok(no_referrer(Html(page.into_string()).into_response()))
+}
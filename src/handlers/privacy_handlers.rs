@@ -0,0 +1,260 @@
+// src/handlers/privacy_handlers.rs
+
+use super::erika_handlers::form_errors_response;
+use super::layout;
+use crate::auth::{ClientInfo, CurrentAccount};
+use crate::jobs;
+use crate::models::account_job::{AccountJob, AccountJobKind, AccountJobStatus};
+use crate::models::audit::{AuditAction, AuditEvent, NewAuditEvent};
+use crate::models::erika::Erika;
+use crate::models::wallet::Wallet;
+use crate::validation::FormErrors;
+use crate::{app_state::AppState, errors::AppError};
+use axum::extract::Path as AxumPath;
+use axum::{
+    Form,
+    extract::State,
+    http::header,
+    response::{Html, IntoResponse, Redirect, Response},
+};
+use bigdecimal::BigDecimal;
+use maud::Markup;
+use serde::Deserialize;
+use serde_json::json;
+use tracing::info;
+use uuid::Uuid;
+
+/// Tekst, który trzeba przepisać, żeby potwierdzić usunięcie konta.
+const DELETE_CONFIRMATION: &str = "USUŃ";
+
+#[derive(Deserialize)]
+pub struct DeleteAccountPayload {
+    pub password: String,
+    pub confirmation: String,
+}
+
+// Strona "Prywatność" - eksport danych i usunięcie konta
+pub async fn show_privacy_page(
+    current: CurrentAccount,
+    State(state): State<AppState>,
+) -> Result<Html<String>, AppError> {
+    let page = render_privacy_page(current.0.id, &FormErrors::default(), &state).await?;
+    Ok(Html(page.into_string()))
+}
+
+async fn render_privacy_page(
+    erika_id: Uuid,
+    errors: &FormErrors,
+    state: &AppState,
+) -> Result<Markup, AppError> {
+    let jobs = AccountJob::find_by_erika_id(erika_id, &state.db).await?;
+    let deletion_pending = jobs.iter().any(|job| {
+        job.kind == AccountJobKind::Deletion
+            && matches!(
+                job.status,
+                AccountJobStatus::Pending | AccountJobStatus::Running
+            )
+    });
+    // Pieniędzy widza nie usuwamy razem z kontem - najpierw zwrot salda
+    let balance = Wallet::balance(erika_id, &state.db).await?;
+    let input_class = "w-full px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white focus:outline-none focus:ring-2 focus:ring-red-500";
+
+    let content = maud::html! {
+        div class="max-w-4xl mx-auto" {
+            a href="/panel" class="inline-block mb-6 text-blue-400 hover:text-blue-300 transition-colors" {
+                "← Wróć do panelu"
+            }
+            h1 class="text-3xl font-bold text-white mb-6" { "Prywatność i dane" }
+
+            div class="bg-gray-800 p-6 rounded-lg shadow-lg mb-6" {
+                h2 class="text-xl font-semibold text-white mb-2" { "Pobierz moje dane" }
+                p class="text-gray-400 mb-4" {
+                    "Przygotujemy archiwum ZIP z danymi konta, galeriami i wgranymi zdjęciami. "
+                    "Może to potrwać kilka minut - status zobaczysz poniżej."
+                }
+                form action="/panel/privacy/export" method="post" {
+                    button type="submit" class="bg-blue-600 hover:bg-blue-700 text-white font-bold py-2 px-4 rounded-md" {
+                        "Pobierz moje dane"
+                    }
+                }
+            }
+
+            div class="bg-gray-800 p-6 rounded-lg shadow-lg mb-6" {
+                h2 class="text-xl font-semibold text-white mb-4" { "Zlecenia" }
+                @if jobs.is_empty() {
+                    p class="text-gray-400" { "Brak zleceń." }
+                } @else {
+                    table class="w-full text-left text-sm" {
+                        thead class="text-gray-400 border-b border-gray-700" {
+                            tr {
+                                th class="p-2" { "Rodzaj" }
+                                th class="p-2" { "Zlecono" }
+                                th class="p-2" { "Status" }
+                                th class="p-2" {}
+                            }
+                        }
+                        tbody {
+                            @for job in &jobs {
+                                tr class="border-b border-gray-700" {
+                                    td class="p-2 text-white" {
+                                        @match job.kind {
+                                            AccountJobKind::Export => "Eksport danych",
+                                            AccountJobKind::Deletion => "Usunięcie konta",
+                                        }
+                                    }
+                                    td class="p-2 text-gray-300" { (layout::format_datetime(job.created_at)) }
+                                    td class="p-2 text-gray-300" {
+                                        (job.status.label())
+                                        @if let Some(finished_at) = job.finished_at {
+                                            span class="block text-gray-500 text-xs" { (layout::format_datetime(finished_at)) }
+                                        }
+                                        @if let Some(error) = &job.error {
+                                            span class="block text-red-400 text-xs" { (error) }
+                                        }
+                                    }
+                                    td class="p-2 text-right" {
+                                        @if job.kind == AccountJobKind::Export && job.status == AccountJobStatus::Done {
+                                            a href=(format!("/panel/privacy/export/{}", job.id)) class="text-blue-400 hover:underline" { "Pobierz ZIP" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            div class="bg-gray-800 p-6 rounded-lg shadow-lg border border-red-700" {
+                h2 class="text-xl font-semibold text-red-400 mb-2" { "Usuń konto" }
+                @if deletion_pending {
+                    p class="text-gray-300" { "Wniosek o usunięcie konta jest w trakcie realizacji." }
+                } @else if balance > BigDecimal::from(0) {
+                    p class="text-gray-300" {
+                        "Na saldzie masz " (balance.with_scale(2)) " PLN. Konta z pieniędzmi na saldzie nie usuwamy - "
+                        "napisz do pomocy o zwrot środków, a potem złóż wniosek."
+                    }
+                } @else {
+                    p class="text-gray-400 mb-4" {
+                        "Usuniemy profil, galerie, zdjęcia i wszystkie pliki. Tej operacji nie można cofnąć."
+                    }
+                    form action="/panel/privacy/delete" method="post" class="space-y-4 max-w-md" {
+                        div {
+                            label for="password" class="block text-gray-300 text-sm font-bold mb-2" { "Hasło:" }
+                            input type="password" id="password" name="password" required class=(input_class);
+                            (layout::field_error(errors, "password"))
+                        }
+                        div {
+                            label for="confirmation" class="block text-gray-300 text-sm font-bold mb-2" {
+                                "Wpisz " code { (DELETE_CONFIRMATION) } ", aby potwierdzić:"
+                            }
+                            input type="text" id="confirmation" name="confirmation" required autocomplete="off" class=(input_class);
+                            (layout::field_error(errors, "confirmation"))
+                        }
+                        button type="submit" class="bg-red-600 hover:bg-red-700 text-white font-bold py-2 px-4 rounded-md" {
+                            "Usuń konto na zawsze"
+                        }
+                    }
+                }
+            }
+        }
+    };
+    Ok(layout::page("Prywatność", content))
+}
+
+// Zleca przygotowanie eksportu. Kolejne kliknięcie nie tworzy duplikatu.
+pub async fn request_export(
+    current: CurrentAccount,
+    State(state): State<AppState>,
+) -> Result<Redirect, AppError> {
+    if !AccountJob::has_open(current.0.id, AccountJobKind::Export, &state.db).await? {
+        let job = AccountJob::create(current.0.id, AccountJobKind::Export, &state.db).await?;
+        info!(
+            "Zlecono eksport danych {} dla {}",
+            job.id, current.0.username
+        );
+    }
+    Ok(Redirect::to("/panel/privacy"))
+}
+
+// Pobranie gotowego archiwum - tylko przez właściciela
+pub async fn download_export(
+    current: CurrentAccount,
+    AxumPath(job_id): AxumPath<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Response, AppError> {
+    let job = AccountJob::find_by_id_and_erika_id(job_id, current.0.id, &state.db)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    let file_path = job
+        .file_path
+        .filter(|path| job.status == AccountJobStatus::Done && jobs::is_export_path(path))
+        .ok_or(AppError::NotFound)?;
+    let bytes = tokio::fs::read(&file_path)
+        .await
+        .map_err(|_| AppError::NotFound)?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/zip".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"dane-{}.zip\"", current.0.username),
+            ),
+        ],
+        bytes,
+    )
+        .into_response())
+}
+
+// Wniosek o usunięcie konta - wymaga hasła i przepisania potwierdzenia
+pub async fn request_deletion(
+    current: CurrentAccount,
+    client: ClientInfo,
+    State(state): State<AppState>,
+    Form(payload): Form<DeleteAccountPayload>,
+) -> Result<Response, AppError> {
+    let erika = Erika::find_by_id(current.0.id, &state.db)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    let mut errors = FormErrors::default();
+    if !erika.verify_password(&payload.password).await {
+        errors.add("password", "Nieprawidłowe hasło.");
+    }
+    if Wallet::balance(erika.id, &state.db).await? > BigDecimal::from(0) {
+        errors.add(
+            "confirmation",
+            "Na saldzie są jeszcze pieniądze - najpierw poproś o ich zwrot.",
+        );
+    }
+    if payload.confirmation.trim() != DELETE_CONFIRMATION {
+        errors.add(
+            "confirmation",
+            format!("Wpisz dokładnie {}.", DELETE_CONFIRMATION),
+        );
+    }
+    if !errors.is_empty() {
+        let page = render_privacy_page(erika.id, &errors, &state).await?;
+        return Ok(form_errors_response(page));
+    }
+
+    if !AccountJob::has_open(erika.id, AccountJobKind::Deletion, &state.db).await? {
+        let job = AccountJob::create(erika.id, AccountJobKind::Deletion, &state.db).await?;
+        AuditEvent::record(
+            NewAuditEvent {
+                actor_id: Some(erika.id),
+                actor_username: &erika.username,
+                action: AuditAction::DeletionRequested,
+                target_type: "erika",
+                target_id: Some(erika.id),
+                changes: json!({ "job_id": job.id }),
+                ip_address: client.ip.as_deref(),
+            },
+            &state.db,
+        )
+        .await?;
+        info!("Zlecono usunięcie konta {}", erika.username);
+    }
+    Ok(Redirect::to("/panel/privacy").into_response())
+}
@@ -0,0 +1,570 @@
+// src/handlers/private_show_handlers.rs
+
+// Pokazy prywatne jeden na jeden. Widz prosi o pokaz na profilu modelki,
+// modelka przyjmuje albo odrzuca prośbę w panelu kamerki, a minuty nalicza
+// `metering`. Obie strony widzą zmiany na bieżąco przez SSE.
+
+use super::erika_handlers::form_errors_response;
+use super::layout;
+use super::stream_handlers::{self, StreamPanelForms};
+use crate::auth::{CurrentAccount, CurrentErika, Impersonation};
+use crate::events::CreatorEvent;
+use crate::models::erika::Erika;
+use crate::models::private_show::{
+    AcceptOutcome, PrivateShow, PrivateShowEndReason, PrivateShowRate, PrivateShowStatus,
+};
+use crate::models::wallet::Wallet;
+use crate::presence;
+use crate::validation::{self, FormErrors, Validate};
+use crate::{app_state::AppState, errors::AppError};
+use axum::{
+    Extension, Form,
+    extract::{Path, State},
+    http::StatusCode,
+    response::{
+        Html, IntoResponse, Redirect, Response,
+        sse::{Event, KeepAlive, Sse},
+    },
+};
+use bigdecimal::BigDecimal;
+use maud::Markup;
+use serde::Deserialize;
+use sqlx::PgPool;
+use std::convert::Infallible;
+use tokio_stream::{Stream, StreamExt, wrappers::BroadcastStream};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Nazwa zdarzenia SSE z odświeżonym stanem pokazu (u widza) albo listą próśb (u modelki).
+const PRIVATE_SHOW_EVENT: &str = "private-show";
+
+#[derive(Deserialize, Default)]
+pub struct PrivateRatePayload {
+    pub private_rate: String,
+}
+
+impl Validate for PrivateRatePayload {
+    fn normalize(&mut self) {
+        self.private_rate = self.private_rate.trim().to_string();
+    }
+
+    fn validate(&self) -> Result<(), FormErrors> {
+        let mut errors = FormErrors::default();
+        if !validation::pln_in_range(
+            &self.private_rate,
+            validation::TIP_MIN_PLN,
+            validation::PRIVATE_RATE_MAX_PLN,
+        ) {
+            errors.add(
+                "private_rate",
+                format!(
+                    "Podaj stawkę od {} do {} PLN za minutę.",
+                    validation::TIP_MIN_PLN,
+                    validation::PRIVATE_RATE_MAX_PLN
+                ),
+            );
+        }
+        errors.into_result()
+    }
+}
+
+/// Ustawienia pokazów prywatnych i prośby widzów w panelu kamerki.
+pub fn render_private_settings(
+    rate: Option<&BigDecimal>,
+    requests: Markup,
+    forms: &StreamPanelForms,
+    errors: &FormErrors,
+) -> Markup {
+    let input_class = "w-full px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white focus:outline-none focus:ring-2 focus:ring-blue-500";
+    let rate_value = match rate {
+        Some(rate) if forms.private_rate.private_rate.is_empty() => rate.with_scale(2).to_string(),
+        _ => forms.private_rate.private_rate.clone(),
+    };
+    maud::html! {
+        div class="bg-gray-800 p-6 rounded-lg shadow-lg" {
+            h2 class="text-xl font-semibold text-white mb-4" { "Pokazy prywatne" }
+            p class="text-gray-400 text-sm mb-4" {
+                @if rate.is_some() {
+                    "Widzowie mogą prosić o pokaz, gdy jesteś online. Minuta jest pobierana z góry z ich salda."
+                } @else {
+                    "Ustaw stawkę, żeby widzowie mogli prosić o pokaz jeden na jeden."
+                }
+            }
+            form action="/panel/stream/private-rate" method="post" class="flex flex-col sm:flex-row gap-4 sm:items-start" {
+                div class="flex-grow" {
+                    input type="number" name="private_rate" value=(rate_value) placeholder="PLN za minutę" required
+                          min=(validation::TIP_MIN_PLN) max=(validation::PRIVATE_RATE_MAX_PLN) step="0.01" class=(input_class);
+                    (layout::field_error(errors, "private_rate"))
+                }
+                button type="submit" class="bg-blue-600 hover:bg-blue-700 text-white font-bold py-2 px-4 rounded-md whitespace-nowrap" {
+                    @if rate.is_some() { "Zmień stawkę" } @else { "Przyjmuj prośby" }
+                }
+            }
+            @if rate.is_some() {
+                form action="/panel/stream/private-rate/delete" method="post" class="mt-2 text-right" {
+                    button type="submit" class="text-red-400 hover:text-red-300 text-sm" { "Nie przyjmuj nowych próśb" }
+                }
+            }
+            div class="mt-4" hx-ext="sse" sse-connect="/events/private-requests" {
+                div sse-swap=(PRIVATE_SHOW_EVENT) { (requests) }
+            }
+        }
+    }
+}
+
+/// Prośby o pokaz i trwający pokaz modelki.
+pub async fn render_requests(erika_id: Uuid, db: &PgPool) -> Result<Markup, sqlx::Error> {
+    let shows = PrivateShow::find_open_for_erika(erika_id, db).await?;
+    Ok(maud::html! {
+        @if shows.is_empty() {
+            p class="text-gray-500 text-sm" { "Brak próśb o pokaz prywatny." }
+        } @else {
+            div class="divide-y divide-gray-700" {
+                @for show in &shows {
+                    @let viewer = show.viewer_username.as_deref().unwrap_or("usunięte konto");
+                    div class="flex flex-wrap justify-between items-center gap-2 py-3" {
+                        @if show.status == PrivateShowStatus::Active {
+                            div {
+                                p class="text-red-400 font-semibold" { "● Pokaz z " (viewer) }
+                                p class="text-gray-400 text-sm" {
+                                    (show.minutes_billed) " min · " (show.billed_pln.with_scale(2)) " PLN (przed prowizją)"
+                                }
+                            }
+                            form action="/panel/stream/end" method="post" {
+                                button type="submit" class="bg-red-600 hover:bg-red-700 text-white text-sm font-bold py-1 px-3 rounded-md" { "Zakończ pokaz" }
+                            }
+                        } @else {
+                            div {
+                                p class="text-white" { (viewer) " prosi o pokaz" }
+                                p class="text-gray-400 text-sm" {
+                                    (show.rate_pln.with_scale(2)) " PLN/min · " (layout::format_time_ago(show.requested_at))
+                                }
+                            }
+                            div class="flex items-center gap-3" {
+                                form action=(format!("/panel/private/{}/accept", show.id)) method="post" {
+                                    button type="submit" class="text-green-400 hover:text-green-300" { "Przyjmij" }
+                                }
+                                form action=(format!("/panel/private/{}/decline", show.id)) method="post" {
+                                    button type="submit" class="text-red-400 hover:text-red-300" { "Odrzuć" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    })
+}
+
+pub async fn set_private_rate(
+    current: CurrentErika,
+    State(state): State<AppState>,
+    Form(mut payload): Form<PrivateRatePayload>,
+) -> Result<Response, AppError> {
+    current.ensure_approved()?;
+    payload.normalize();
+    if let Err(errors) = payload.validate() {
+        let forms = StreamPanelForms {
+            private_rate: payload,
+            ..StreamPanelForms::default()
+        };
+        let page = stream_handlers::render_stream_panel(&current, &forms, &errors, &state).await?;
+        return Ok(form_errors_response(page));
+    }
+
+    let rate = validation::parse_pln(&payload.private_rate).unwrap_or_default();
+    PrivateShowRate::set(current.id(), &rate, &state.db).await?;
+    info!(
+        "Modelka {} ustawiła stawkę pokazów prywatnych",
+        current.id()
+    );
+    Ok(Redirect::to("/panel/stream").into_response())
+}
+
+pub async fn clear_private_rate(
+    current: CurrentErika,
+    State(state): State<AppState>,
+) -> Result<Redirect, AppError> {
+    PrivateShowRate::clear(current.id(), &state.db).await?;
+    Ok(Redirect::to("/panel/stream"))
+}
+
+pub async fn accept_show(
+    Path(show_id): Path<Uuid>,
+    current: CurrentErika,
+    State(state): State<AppState>,
+) -> Result<Response, AppError> {
+    current.ensure_approved()?;
+    let mut errors = FormErrors::default();
+    match PrivateShow::accept(show_id, current.id(), &state.db).await? {
+        AcceptOutcome::NotFound => return Err(AppError::NotFound),
+        AcceptOutcome::Busy => {
+            errors.add("private_rate", "Najpierw zakończ trwający pokaz prywatny.")
+        }
+        AcceptOutcome::InsufficientFunds => {
+            state.publish(CreatorEvent::PrivateShow {
+                erika_id: current.id(),
+                show_id,
+            });
+            errors.add(
+                "private_rate",
+                "Widza nie stać już na pierwszą minutę - prośba została anulowana.",
+            );
+        }
+        AcceptOutcome::Started { ended_public } => {
+            if let Some(session_id) = ended_public {
+                info!(
+                    "Transmisja {} zakończona przez start pokazu prywatnego",
+                    session_id
+                );
+            }
+            state.publish(CreatorEvent::PrivateShow {
+                erika_id: current.id(),
+                show_id,
+            });
+            info!(
+                "Modelka {} rozpoczęła pokaz prywatny {}",
+                current.id(),
+                show_id
+            );
+            return Ok(Redirect::to("/panel/stream").into_response());
+        }
+    }
+    let page = stream_handlers::render_stream_panel(
+        &current,
+        &StreamPanelForms::default(),
+        &errors,
+        &state,
+    )
+    .await?;
+    Ok(form_errors_response(page))
+}
+
+pub async fn decline_show(
+    Path(show_id): Path<Uuid>,
+    current: CurrentErika,
+    State(state): State<AppState>,
+) -> Result<Redirect, AppError> {
+    if !PrivateShow::decline(show_id, current.id(), &state.db).await? {
+        return Err(AppError::NotFound);
+    }
+    state.publish(CreatorEvent::PrivateShow {
+        erika_id: current.id(),
+        show_id,
+    });
+    Ok(Redirect::to("/panel/stream"))
+}
+
+/// Okienko pokazu prywatnego na profilu modelki.
+pub async fn render_profile_box(
+    erika: &Erika,
+    viewer_id: Option<Uuid>,
+    in_private_show: bool,
+    db: &PgPool,
+) -> Result<Markup, sqlx::Error> {
+    let Some(rate) = PrivateShowRate::find(erika.id, db).await? else {
+        return Ok(maud::html! {});
+    };
+    let open_show = match viewer_id {
+        Some(viewer_id) => PrivateShow::find_open_for_viewer(viewer_id, db).await?,
+        None => None,
+    };
+    Ok(maud::html! {
+        div class="bg-gray-800 p-6 rounded-lg shadow-lg mb-8 flex flex-wrap justify-between items-center gap-4" {
+            div {
+                h2 class="text-2xl font-bold text-white" { "Pokaz prywatny" }
+                p class="text-gray-400" { "Jeden na jeden, " (rate.with_scale(2)) " PLN za minutę z Twojego salda." }
+            }
+            @if let Some(show) = &open_show {
+                a href=(format!("/private-shows/{}", show.id)) class="bg-purple-600 hover:bg-purple-700 text-white font-bold py-2 px-4 rounded-md" {
+                    @if show.erika_id == Some(erika.id) { "Przejdź do swojego pokazu" } @else { "Masz już otwartą prośbę o pokaz" }
+                }
+            } @else if viewer_id.is_none() {
+                a href="/login" class="text-blue-400 hover:underline" { "Zaloguj się, aby poprosić o pokaz" }
+            } @else if in_private_show {
+                span class="text-gray-400" { "Poproś o pokaz, gdy skończy się obecny." }
+            } @else if !erika.is_online {
+                span class="text-gray-400" { "Poproś o pokaz, gdy modelka będzie online." }
+            } @else {
+                form action=(format!("/pay/private/{}", erika.username)) method="post" {
+                    button type="submit" class="bg-purple-600 hover:bg-purple-700 text-white font-bold py-2 px-4 rounded-md" { "Poproś o pokaz" }
+                }
+            }
+        }
+    })
+}
+
+/// Prośba widza o pokaz. Saldo musi wystarczyć co najmniej na pierwszą minutę.
+pub async fn request_show(
+    Path(username): Path<String>,
+    viewer: CurrentAccount,
+    State(state): State<AppState>,
+) -> Result<Response, AppError> {
+    let erika = Erika::find_by_public_username(&username, &state.db)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    let profile = format!("/erika/{}", erika.username);
+    let message = |text: &str| {
+        Html(
+            layout::info_page("Pokaz prywatny", text, Some((&profile, "Wróć do profilu")))
+                .into_string(),
+        )
+        .into_response()
+    };
+
+    if erika.id == viewer.0.id {
+        return Ok(message("Nie możesz poprosić o pokaz samą siebie."));
+    }
+    let Some(rate) = PrivateShowRate::find(erika.id, &state.db).await? else {
+        return Ok(message(
+            "Ta modelka nie przyjmuje teraz próśb o pokaz prywatny.",
+        ));
+    };
+    if !erika.is_online {
+        return Ok(message("Modelka nie jest teraz online."));
+    }
+    if Wallet::balance(viewer.0.id, &state.db).await? < rate {
+        return Ok(message(
+            "Masz za mało środków na saldzie na pierwszą minutę pokazu.",
+        ));
+    }
+    let Some(show_id) = PrivateShow::request(erika.id, viewer.0.id, &rate, &state.db).await? else {
+        return Ok(message(
+            "Masz już otwartą prośbę albo trwający pokaz prywatny.",
+        ));
+    };
+
+    state.publish(CreatorEvent::PrivateShow {
+        erika_id: erika.id,
+        show_id,
+    });
+    info!(
+        "Konto {} prosi o pokaz prywatny {} u {}",
+        viewer.0.id, show_id, erika.id
+    );
+    Ok(Redirect::to(&format!("/private-shows/{}", show_id)).into_response())
+}
+
+/// Pokaz prywatny widza, tylko dla niego.
+async fn viewer_show(
+    show_id: Uuid,
+    viewer: &CurrentAccount,
+    db: &PgPool,
+) -> Result<PrivateShow, AppError> {
+    PrivateShow::find_by_id(show_id, db)
+        .await?
+        .filter(|show| show.viewer_id == Some(viewer.0.id))
+        .ok_or(AppError::NotFound)
+}
+
+pub async fn show_private_show(
+    Path(show_id): Path<Uuid>,
+    viewer: CurrentAccount,
+    State(state): State<AppState>,
+) -> Result<Html<String>, AppError> {
+    let show = viewer_show(show_id, &viewer, &state.db).await?;
+    let status = render_show_status(&show, &state.db).await?;
+    let content = maud::html! {
+        // Rozszerzenie SSE dla HTMX - stan pokazu odświeża się sam
+        script src="https://cdn.jsdelivr.net/npm/htmx-ext-sse@2.2.3/dist/sse.js" defer {}
+        div class="max-w-4xl mx-auto" {
+            @if let Some(username) = &show.erika_username {
+                a href=(format!("/erika/{}", username)) class="inline-block mb-6 text-blue-400 hover:text-blue-300 transition-colors" {
+                    "← Wróć do profilu"
+                }
+            }
+            h1 class="text-3xl font-bold text-white mb-6" {
+                "Pokaz prywatny: " (show.erika_username.as_deref().unwrap_or("usunięte konto"))
+            }
+            div hx-ext="sse" sse-connect=(format!("/events/private-shows/{}", show.id)) {
+                div sse-swap=(PRIVATE_SHOW_EVENT) { (status) }
+            }
+        }
+    };
+    Ok(Html(layout::page("Pokaz prywatny", content).into_string()))
+}
+
+async fn render_show_status(show: &PrivateShow, db: &PgPool) -> Result<Markup, sqlx::Error> {
+    let balance = match show.viewer_id {
+        Some(viewer_id) => Wallet::balance(viewer_id, db).await?,
+        None => BigDecimal::from(0),
+    };
+    let show_url = format!("/private-shows/{}", show.id);
+    Ok(maud::html! {
+        div class="bg-gray-800 p-6 rounded-lg shadow-lg" {
+            @match show.status {
+                PrivateShowStatus::Requested => {
+                    p class="text-white mb-2" { "Czekamy, aż modelka przyjmie prośbę." }
+                    p class="text-gray-400 text-sm mb-4" {
+                        "Stawka: " (show.rate_pln.with_scale(2)) " PLN/min · saldo: " (balance.with_scale(2)) " PLN"
+                    }
+                    form action=(format!("{}/cancel", show_url)) method="post" {
+                        button type="submit" class="bg-gray-600 hover:bg-gray-700 text-white font-bold py-2 px-4 rounded-md" { "Anuluj prośbę" }
+                    }
+                }
+                PrivateShowStatus::Active => {
+                    // Dopóki ta strona jest otwarta, pokaz trwa i jest naliczany
+                    div hx-post=(format!("{}/presence", show_url)) hx-trigger=(format!("load, every {}s", presence::HEARTBEAT_INTERVAL_SECS)) hx-swap="none" {}
+                    div class="bg-black aspect-video w-full mb-4 rounded flex items-center justify-center" {
+                        p class="text-gray-500" { "Oczekiwanie na połączenie..." }
+                    }
+                    div class="flex flex-wrap justify-between items-center gap-4" {
+                        p class="text-gray-300" {
+                            span class="text-red-400 font-semibold" { "● Trwa · " }
+                            (show.minutes_billed) " min · zapłacono " (show.billed_pln.with_scale(2)) " PLN · saldo: " (balance.with_scale(2)) " PLN"
+                        }
+                        form action=(format!("{}/end", show_url)) method="post" {
+                            button type="submit" class="bg-red-600 hover:bg-red-700 text-white font-bold py-2 px-4 rounded-md" { "Zakończ pokaz" }
+                        }
+                    }
+                    p class="text-gray-500 text-sm mt-2" {
+                        "Każda rozpoczęta minuta kosztuje " (show.rate_pln.with_scale(2)) " PLN. Pokaz skończy się sam, gdy zabraknie środków."
+                    }
+                }
+                PrivateShowStatus::Ended => {
+                    p class="text-white mb-2" {
+                        "Pokaz zakończony"
+                        @if let Some(reason) = show.end_reason { ": " (reason.label()) }
+                        "."
+                    }
+                    p class="text-gray-400 text-sm" {
+                        (show.minutes_billed) " min · zapłacono " (show.billed_pln.with_scale(2)) " PLN · saldo: " (balance.with_scale(2)) " PLN"
+                    }
+                }
+                PrivateShowStatus::Declined | PrivateShowStatus::Cancelled => {
+                    p class="text-white" {
+                        "Prośba: " (show.status.label().to_lowercase())
+                        @if let Some(reason) = show.end_reason { " (" (reason.label()) ")" }
+                        "."
+                    }
+                }
+            }
+        }
+    })
+}
+
+pub async fn cancel_show(
+    Path(show_id): Path<Uuid>,
+    viewer: CurrentAccount,
+    State(state): State<AppState>,
+) -> Result<Redirect, AppError> {
+    let show = viewer_show(show_id, &viewer, &state.db).await?;
+    if PrivateShow::cancel(show.id, viewer.0.id, &state.db).await?
+        && let Some(erika_id) = show.erika_id
+    {
+        state.publish(CreatorEvent::PrivateShow { erika_id, show_id });
+    }
+    Ok(Redirect::to(&format!("/private-shows/{}", show_id)))
+}
+
+pub async fn end_show_by_viewer(
+    Path(show_id): Path<Uuid>,
+    viewer: CurrentAccount,
+    State(state): State<AppState>,
+) -> Result<Redirect, AppError> {
+    let show = viewer_show(show_id, &viewer, &state.db).await?;
+    let commission_percent = state.settings().commission_percent;
+    if let Some(erika_id) = PrivateShow::end(
+        show.id,
+        PrivateShowEndReason::Viewer,
+        commission_percent,
+        &state.db,
+    )
+    .await?
+    {
+        state.publish(CreatorEvent::PrivateShow { erika_id, show_id });
+        info!("Widz {} zakończył pokaz prywatny {}", viewer.0.id, show_id);
+    }
+    Ok(Redirect::to(&format!("/private-shows/{}", show_id)))
+}
+
+// Sygnał obecności ze strony pokazu. Admin w podglądzie konta nie przedłuża
+// płatnego pokazu (ścieżka jest też zablokowana przez middleware).
+pub async fn viewer_heartbeat(
+    Path(show_id): Path<Uuid>,
+    viewer: CurrentAccount,
+    impersonation: Option<Extension<Impersonation>>,
+    State(state): State<AppState>,
+) -> Result<StatusCode, AppError> {
+    if impersonation.is_none() {
+        PrivateShow::record_viewer_heartbeat(show_id, viewer.0.id, &state.db).await?;
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// Strumień SSE strony pokazu u widza
+pub async fn show_events(
+    Path(show_id): Path<Uuid>,
+    viewer: CurrentAccount,
+    State(state): State<AppState>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, AppError> {
+    viewer_show(show_id, &viewer, &state.db).await?;
+    let events = BroadcastStream::new(state.creator_events.subscribe());
+    let stream = events
+        // `Lagged` też odświeża stan - jest w bazie
+        .filter(move |event| match event {
+            Ok(CreatorEvent::PrivateShow {
+                show_id: changed, ..
+            }) => *changed == show_id,
+            Ok(_) => false,
+            Err(_) => true,
+        })
+        .then(move |_event| {
+            let state = state.clone();
+            async move {
+                let status = match PrivateShow::find_by_id(show_id, &state.db).await {
+                    Ok(Some(show)) => render_show_status(&show, &state.db).await,
+                    Ok(None) => return None,
+                    Err(e) => Err(e),
+                };
+                match status {
+                    Ok(status) => Some(
+                        Event::default()
+                            .event(PRIVATE_SHOW_EVENT)
+                            .data(status.into_string()),
+                    ),
+                    Err(e) => {
+                        warn!("Nie udało się odświeżyć pokazu prywatnego: {}", e);
+                        None
+                    }
+                }
+            }
+        })
+        .filter_map(|event| event.map(Ok));
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+// Strumień SSE próśb o pokaz w panelu kamerki
+pub async fn request_events(
+    current: CurrentErika,
+    State(state): State<AppState>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let erika_id = current.id();
+    let events = BroadcastStream::new(state.creator_events.subscribe());
+    let stream = events
+        .filter(move |event| match event {
+            Ok(CreatorEvent::PrivateShow {
+                erika_id: changed, ..
+            }) => *changed == erika_id,
+            Ok(_) => false,
+            Err(_) => true,
+        })
+        .then(move |_event| {
+            let state = state.clone();
+            async move {
+                match render_requests(erika_id, &state.db).await {
+                    Ok(requests) => Some(
+                        Event::default()
+                            .event(PRIVATE_SHOW_EVENT)
+                            .data(requests.into_string()),
+                    ),
+                    Err(e) => {
+                        warn!("Nie udało się odświeżyć próśb o pokaz: {}", e);
+                        None
+                    }
+                }
+            }
+        })
+        .filter_map(|event| event.map(Ok));
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
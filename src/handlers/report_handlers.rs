@@ -0,0 +1,668 @@
+// src/handlers/report_handlers.rs
+
+// Zgłoszenia treści (notice and action). Odwiedzający zgłasza profil,
+// galerię lub zdjęcie, admin rozpatruje zgłoszenia w kolejce, a każda decyzja ograniczająca
+// trafia do panelu modelki razem z uzasadnieniem (statement of reasons).
+
+use super::erika_handlers::form_errors_response;
+use super::layout;
+use crate::auth::{ClientInfo, CurrentErika, CurrentStaff, OptionalViewer};
+use crate::events::CreatorEvent;
+use crate::models::audit::{self, AuditAction};
+use crate::models::erika::{AccountStatus, Erika};
+use crate::models::gallery::Gallery;
+use crate::models::photo::Photo;
+use crate::models::report::{
+    ModerationAction, ModerationDecision, NewDecision, NewReport, Report, ReportReason,
+    ReportStatus, ReportTarget,
+};
+use crate::validation::{self, FormErrors};
+use crate::{app_state::AppState, errors::AppError};
+use axum::extract::{Path, Query};
+use axum::{
+    Form,
+    extract::State,
+    response::{Html, IntoResponse, Redirect, Response},
+};
+use maud::Markup;
+use serde::Deserialize;
+use serde_json::json;
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+const DETAILS_MAX_LEN: usize = 2000;
+const STATEMENT_MAX_LEN: usize = 2000;
+const QUEUE_SIZE: i64 = 100;
+
+/// Treść, której dotyczy zgłoszenie: konto właścicielki i opis do wyświetlenia.
+struct ReportedContent {
+    erika_id: Uuid,
+    username: String,
+    title: String,
+}
+
+/// Szuka zgłaszanej treści. Zgłosić można tylko to, co jest publicznie widoczne.
+async fn find_public_target(
+    kind: ReportTarget,
+    target_id: Uuid,
+    state: &AppState,
+) -> Result<ReportedContent, AppError> {
+    let erika_id = match kind {
+        ReportTarget::Profile => target_id,
+        ReportTarget::Gallery => {
+            Gallery::find_public_by_id(target_id, &state.db)
+                .await?
+                .ok_or(AppError::NotFound)?
+                .erika_id
+        }
+        ReportTarget::Photo => Photo::find_public_erika_id(target_id, &state.db)
+            .await?
+            .ok_or(AppError::NotFound)?,
+    };
+    let erika = Erika::find_by_id(erika_id, &state.db)
+        .await?
+        .filter(|erika| erika.status == AccountStatus::Approved)
+        .ok_or(AppError::NotFound)?;
+    describe_target(kind, target_id, erika, state).await
+}
+
+async fn describe_target(
+    kind: ReportTarget,
+    target_id: Uuid,
+    erika: Erika,
+    state: &AppState,
+) -> Result<ReportedContent, AppError> {
+    let title = match kind {
+        ReportTarget::Profile => format!("Profil {}", erika.username),
+        ReportTarget::Gallery => {
+            match Gallery::find_by_id_and_erika_id(target_id, erika.id, &state.db).await? {
+                Some(gallery) => format!("Galeria {} ({})", gallery.name, erika.username),
+                None => format!("Usunięta galeria ({})", erika.username),
+            }
+        }
+        ReportTarget::Photo => {
+            let gallery = match Photo::find_by_id(target_id, &state.db).await? {
+                Some(photo) => {
+                    Gallery::find_by_id_and_erika_id(photo.gallery_id, erika.id, &state.db).await?
+                }
+                None => None,
+            };
+            match gallery {
+                Some(gallery) => format!("Zdjęcie z galerii {} ({})", gallery.name, erika.username),
+                None => format!("Usunięte zdjęcie ({})", erika.username),
+            }
+        }
+    };
+    Ok(ReportedContent {
+        erika_id: erika.id,
+        username: erika.username,
+        title,
+    })
+}
+
+#[derive(Deserialize)]
+pub struct ReportPayload {
+    #[serde(default)]
+    pub reason: String,
+    #[serde(default)]
+    pub details: String,
+    #[serde(default)]
+    pub email: String,
+    pub good_faith: Option<String>,
+}
+
+// Formularz zgłoszenia profilu, galerii lub zdjęcia
+pub async fn show_report_form(
+    Path((kind, target_id)): Path<(String, Uuid)>,
+    viewer: OptionalViewer,
+    State(state): State<AppState>,
+) -> Result<Html<String>, AppError> {
+    let kind = ReportTarget::from_slug(&kind).ok_or(AppError::NotFound)?;
+    let target = find_public_target(kind, target_id, &state).await?;
+    let page = render_report_form(
+        kind,
+        target_id,
+        &target,
+        viewer.0.is_some(),
+        None,
+        &FormErrors::default(),
+    );
+    Ok(Html(page.into_string()))
+}
+
+fn render_report_form(
+    kind: ReportTarget,
+    target_id: Uuid,
+    target: &ReportedContent,
+    logged_in: bool,
+    values: Option<&ReportPayload>,
+    errors: &FormErrors,
+) -> Markup {
+    let input_class = "w-full px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white focus:outline-none focus:ring-2 focus:ring-red-500";
+    let selected = values.and_then(|v| ReportReason::from_str(&v.reason).ok());
+
+    let content = maud::html! {
+        div class="max-w-2xl mx-auto" {
+            a href=(format!("/erika/{}", target.username)) class="inline-block mb-6 text-blue-400 hover:text-blue-300 transition-colors" {
+                "← Wróć do profilu"
+            }
+            h1 class="text-3xl font-bold text-white mb-2" { "Zgłoś naruszenie" }
+            p class="text-gray-400 mb-6" { "Zgłaszasz: " strong class="text-white" { (target.title) } }
+
+            form action=(format!("/report/{}/{}", kind.slug(), target_id)) method="post"
+                 class="bg-gray-800 p-6 rounded-lg shadow-lg space-y-5" {
+                fieldset {
+                    legend class="block text-gray-300 text-sm font-bold mb-2" { "Powód zgłoszenia:" }
+                    @for reason in ReportReason::iter() {
+                        label class="flex items-center gap-2 text-gray-300 mb-1" {
+                            input type="radio" name="reason" value=(reason.to_string()) required checked[selected == Some(reason)];
+                            (reason.label())
+                        }
+                    }
+                    (layout::field_error(errors, "reason"))
+                }
+                div {
+                    label for="details" class="block text-gray-300 text-sm font-bold mb-2" {
+                        "Opisz, co narusza prawo lub regulamin i dlaczego:"
+                    }
+                    textarea id="details" name="details" rows="5" required maxlength=(DETAILS_MAX_LEN) class=(input_class) {
+                        (values.map(|v| v.details.as_str()).unwrap_or(""))
+                    }
+                    (layout::field_error(errors, "details"))
+                }
+                @if !logged_in {
+                    div {
+                        label for="email" class="block text-gray-300 text-sm font-bold mb-2" {
+                            "Email do kontaktu (opcjonalnie):"
+                        }
+                        input type="email" id="email" name="email" value=[values.map(|v| v.email.as_str())] class=(input_class);
+                        (layout::field_error(errors, "email"))
+                    }
+                }
+                div {
+                    label class="flex items-start gap-2 text-gray-300 text-sm" {
+                        input type="checkbox" name="good_faith" value="1" required class="mt-1";
+                        "Oświadczam w dobrej wierze, że informacje w zgłoszeniu są prawdziwe i kompletne."
+                    }
+                    (layout::field_error(errors, "good_faith"))
+                }
+                button type="submit" class="w-full bg-red-600 hover:bg-red-700 text-white font-bold py-2 px-4 rounded-md" {
+                    "Wyślij zgłoszenie"
+                }
+            }
+        }
+    };
+    layout::page("Zgłoś naruszenie", content)
+}
+
+pub async fn submit_report(
+    Path((kind, target_id)): Path<(String, Uuid)>,
+    viewer: OptionalViewer,
+    client: ClientInfo,
+    State(state): State<AppState>,
+    Form(payload): Form<ReportPayload>,
+) -> Result<Response, AppError> {
+    let kind = ReportTarget::from_slug(&kind).ok_or(AppError::NotFound)?;
+    let target = find_public_target(kind, target_id, &state).await?;
+
+    let mut errors = FormErrors::default();
+    let reason = ReportReason::from_str(&payload.reason).ok();
+    if reason.is_none() {
+        errors.add("reason", "Wybierz powód zgłoszenia.");
+    }
+    let details = payload.details.trim();
+    if details.is_empty() || details.chars().count() > DETAILS_MAX_LEN {
+        errors.add(
+            "details",
+            format!("Opisz naruszenie (najwyżej {} znaków).", DETAILS_MAX_LEN),
+        );
+    }
+    let email = payload.email.trim().to_lowercase();
+    if viewer.0.is_none() && !email.is_empty() {
+        validation::validate_email(&email, &mut errors);
+    }
+    if payload.good_faith.is_none() {
+        errors.add("good_faith", "Potwierdź oświadczenie.");
+    }
+    let Some(reason) = reason.filter(|_| errors.is_empty()) else {
+        let page = render_report_form(
+            kind,
+            target_id,
+            &target,
+            viewer.0.is_some(),
+            Some(&payload),
+            &errors,
+        );
+        return Ok(form_errors_response(page));
+    };
+
+    // Ponowne wysłanie tego samego zgłoszenia nie tworzy duplikatu w kolejce
+    let duplicate = match client.ip.as_deref() {
+        Some(ip) => Report::is_duplicate(kind, target_id, ip, &state.db).await?,
+        None => false,
+    };
+    if !duplicate {
+        let reporter_email = match &viewer.0 {
+            Some(_) => None,
+            None => Some(email.as_str()).filter(|e| !e.is_empty()),
+        };
+        let report_id = Report::create(
+            NewReport {
+                target_kind: kind,
+                target_id,
+                erika_id: target.erika_id,
+                reason,
+                details,
+                reporter_id: viewer.0.as_ref().map(|v| v.id),
+                reporter_email,
+                ip_address: client.ip.as_deref(),
+            },
+            &state.db,
+        )
+        .await?;
+        info!("Nowe zgłoszenie {} ({}: {})", report_id, kind, target_id);
+    }
+
+    let page = layout::info_page(
+        "Zgłoszenie przyjęte",
+        "Dziękujemy. Zgłoszenie trafiło do moderacji i zostanie rozpatrzone bez zbędnej zwłoki.",
+        Some((&format!("/erika/{}", target.username), "Wróć do profilu")),
+    );
+    Ok(Html(page.into_string()).into_response())
+}
+
+#[derive(Deserialize, Default)]
+pub struct ReportsFilter {
+    #[serde(default)]
+    pub status: String,
+}
+
+// Kolejka zgłoszeń dla admina
+pub async fn show_reports(
+    Query(filter): Query<ReportsFilter>,
+    State(state): State<AppState>,
+) -> Result<Html<String>, AppError> {
+    let status = ReportStatus::from_str(&filter.status).unwrap_or(ReportStatus::Open);
+    let reports = Report::find_by_status(status, QUEUE_SIZE, &state.db).await?;
+
+    let content = maud::html! {
+        a href="/admin" class="inline-block mb-6 text-blue-400 hover:text-blue-300 transition-colors" {
+            "← Wróć do panelu"
+        }
+        h1 class="text-3xl font-bold text-white mb-4" { "Zgłoszenia" }
+        nav class="flex gap-4 mb-6" {
+            @for tab in ReportStatus::iter() {
+                @if tab == status {
+                    span class="text-white font-semibold border-b-2 border-blue-500" { (tab.label()) }
+                } @else {
+                    a href=(format!("/admin/reports?status={}", tab)) class="text-blue-400 hover:text-blue-300" { (tab.label()) }
+                }
+            }
+        }
+        @if reports.is_empty() {
+            p class="text-gray-400" { "Brak zgłoszeń." }
+        } @else {
+            div class="bg-gray-800 rounded-lg shadow-lg" {
+                table class="w-full text-left text-sm" {
+                    thead class="text-gray-400 border-b border-gray-700" {
+                        tr {
+                            th class="p-3" { "Zgłoszono" }
+                            th class="p-3" { "Dotyczy" }
+                            th class="p-3" { "Konto" }
+                            th class="p-3" { "Powód" }
+                            th class="p-3" {}
+                        }
+                    }
+                    tbody {
+                        @for report in &reports {
+                            tr class="border-b border-gray-700" {
+                                td class="p-3 text-gray-300" { (layout::format_datetime(report.created_at)) }
+                                td class="p-3 text-gray-300" { (report.target_kind.label()) }
+                                td class="p-3 text-white" { (report.username) }
+                                td class="p-3 text-gray-300" { (report.reason.label()) }
+                                td class="p-3 text-right" {
+                                    a href=(format!("/admin/reports/{}", report.id)) class="text-blue-400 hover:underline" {
+                                        @if report.status == ReportStatus::Open { "Rozpatrz" } @else { "Szczegóły" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    };
+    Ok(Html(layout::page("Zgłoszenia", content).into_string()))
+}
+
+// Szczegóły zgłoszenia, inne zgłoszenia tej treści i formularz decyzji
+pub async fn show_report(
+    Path(report_id): Path<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Html<String>, AppError> {
+    let page = render_report_page(report_id, &FormErrors::default(), &state).await?;
+    Ok(Html(page.into_string()))
+}
+
+async fn render_report_page(
+    report_id: Uuid,
+    errors: &FormErrors,
+    state: &AppState,
+) -> Result<Markup, AppError> {
+    let report = Report::find_by_id(report_id, &state.db)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    let erika = Erika::find_by_id(report.erika_id, &state.db)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    let erika_status = erika.status;
+    let target = describe_target(report.target_kind, report.target_id, erika, state).await?;
+    let related = Report::find_by_target(report.target_kind, report.target_id, &state.db).await?;
+    let decision = match report.decision_id {
+        Some(id) => ModerationDecision::find_by_id(id, &state.db).await?,
+        None => None,
+    };
+    let input_class = "w-full px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white focus:outline-none focus:ring-2 focus:ring-red-500";
+
+    let content = maud::html! {
+        a href="/admin/reports" class="inline-block mb-6 text-blue-400 hover:text-blue-300 transition-colors" {
+            "← Wróć do zgłoszeń"
+        }
+        h1 class="text-3xl font-bold text-white mb-2" { (target.title) }
+        p class="text-gray-300 mb-6" {
+            a href=(format!("/admin/erika/{}", target.erika_id)) class="text-blue-400 hover:underline" { "Konto " (target.username) }
+            " · status konta: " (erika_status.label())
+            " · "
+            a href=(format!("/erika/{}", target.username)) target="_blank" class="text-blue-400 hover:underline" { "profil publiczny" }
+        }
+
+        div class="bg-gray-800 p-6 rounded-lg shadow-lg mb-6" {
+            h2 class="text-xl font-semibold text-white mb-4" { "Zgłoszenia tej treści (" (related.len()) ")" }
+            ul class="space-y-4" {
+                @for item in &related {
+                    li class={ "border-l-4 pl-4 " @if item.id == report.id { "border-blue-500" } @else { "border-gray-600" } } {
+                        p class="text-white font-semibold" {
+                            (item.reason.label())
+                            span class="ml-2 text-gray-400 text-sm font-normal" {
+                                (layout::format_datetime(item.created_at)) " · " (item.status.label())
+                            }
+                        }
+                        p class="text-gray-300 whitespace-pre-line" { (item.details) }
+                        p class="text-gray-500 text-xs" {
+                            "Kontakt: " (item.reporter_email.as_deref().unwrap_or("brak"))
+                            " · IP: " (item.ip_address.as_deref().unwrap_or("-"))
+                        }
+                    }
+                }
+            }
+        }
+
+        @if let Some(decision) = &decision {
+            div class="bg-gray-800 p-6 rounded-lg shadow-lg" {
+                h2 class="text-xl font-semibold text-white mb-2" { "Decyzja" }
+                p class="text-gray-300" {
+                    (decision.action.label()) " · podstawa: " (decision.ground.label())
+                    " · " (layout::format_datetime(decision.created_at))
+                }
+                @if !decision.statement.is_empty() {
+                    p class="text-gray-300 mt-2 whitespace-pre-line" { (decision.statement) }
+                }
+            }
+        } @else if report.status == ReportStatus::Open {
+            form action=(format!("/admin/reports/{}/decide", report.id)) method="post"
+                 class="bg-gray-800 p-6 rounded-lg shadow-lg space-y-4" {
+                h2 class="text-xl font-semibold text-white" { "Decyzja" }
+                p class="text-gray-400 text-sm" {
+                    "Decyzja zamknie wszystkie otwarte zgłoszenia tej treści. Uzasadnienie zobaczy modelka w swoim panelu."
+                }
+                fieldset {
+                    @for action in ModerationAction::iter() {
+                        @let disabled = action == ModerationAction::HideContent && !report.target_kind.can_be_hidden()
+                            || action == ModerationAction::SuspendCreator && !erika_status.can_transition_to(AccountStatus::Suspended);
+                        label class={ "flex items-center gap-2 mb-1 " @if disabled { "text-gray-500" } @else { "text-gray-300" } } {
+                            input type="radio" name="action" value=(action.to_string()) required disabled[disabled];
+                            (action.label())
+                        }
+                    }
+                    (layout::field_error(errors, "action"))
+                }
+                div {
+                    label for="ground" class="block text-gray-300 text-sm font-bold mb-2" { "Podstawa decyzji:" }
+                    select id="ground" name="ground" class=(input_class) {
+                        @for reason in ReportReason::iter() {
+                            option value=(reason.to_string()) selected[reason == report.reason] {
+                                (reason.label())
+                                @if reason.is_legal_ground() { " (prawo)" } @else { " (regulamin)" }
+                            }
+                        }
+                    }
+                    (layout::field_error(errors, "ground"))
+                }
+                div {
+                    label for="statement" class="block text-gray-300 text-sm font-bold mb-2" {
+                        "Uzasadnienie dla modelki (wymagane przy ograniczeniu):"
+                    }
+                    textarea id="statement" name="statement" rows="5" maxlength=(STATEMENT_MAX_LEN) class=(input_class) {}
+                    (layout::field_error(errors, "statement"))
+                }
+                button type="submit" class="bg-blue-600 hover:bg-blue-700 text-white font-bold py-2 px-4 rounded-md" {
+                    "Zapisz decyzję"
+                }
+            }
+        }
+    };
+    Ok(layout::page("Zgłoszenie", content))
+}
+
+#[derive(Deserialize)]
+pub struct DecisionPayload {
+    #[serde(default)]
+    pub action: String,
+    pub ground: String,
+    pub statement: String,
+}
+
+pub async fn decide_report(
+    Path(report_id): Path<Uuid>,
+    admin: CurrentStaff,
+    client: ClientInfo,
+    State(state): State<AppState>,
+    Form(payload): Form<DecisionPayload>,
+) -> Result<Response, AppError> {
+    let report = Report::find_by_id(report_id, &state.db)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    let erika = Erika::find_by_id(report.erika_id, &state.db)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    let mut errors = FormErrors::default();
+    let action = ModerationAction::from_str(&payload.action).ok();
+    match action {
+        None => errors.add("action", "Wybierz działanie."),
+        Some(ModerationAction::HideContent) if !report.target_kind.can_be_hidden() => errors.add(
+            "action",
+            "Ukryć można galerię lub zdjęcie - profil ogranicza się przez zawieszenie konta.",
+        ),
+        Some(ModerationAction::SuspendCreator)
+            if !erika.status.can_transition_to(AccountStatus::Suspended) =>
+        {
+            errors.add(
+                "action",
+                format!(
+                    "Konta w statusie \"{}\" nie można zawiesić.",
+                    erika.status.label()
+                ),
+            )
+        }
+        Some(_) => {}
+    }
+    let ground = ReportReason::from_str(&payload.ground).ok();
+    if ground.is_none() {
+        errors.add("ground", "Wybierz podstawę decyzji.");
+    }
+    let statement = payload.statement.trim();
+    if statement.chars().count() > STATEMENT_MAX_LEN {
+        errors.add(
+            "statement",
+            format!(
+                "Uzasadnienie może mieć najwyżej {} znaków.",
+                STATEMENT_MAX_LEN
+            ),
+        );
+    } else if statement.is_empty() && action.is_some_and(|a| a != ModerationAction::NoAction) {
+        errors.add("statement", "Ograniczenie wymaga uzasadnienia dla modelki.");
+    }
+    let (Some(action), Some(ground)) = (action, ground.filter(|_| errors.is_empty())) else {
+        let page = render_report_page(report_id, &errors, &state).await?;
+        return Ok(form_errors_response(page));
+    };
+
+    let decision_id = ModerationDecision::record(
+        NewDecision {
+            erika_id: erika.id,
+            target_kind: report.target_kind,
+            target_id: report.target_id,
+            action,
+            ground,
+            statement,
+            decided_by: admin.id(),
+        },
+        &state.db,
+    )
+    .await?;
+    let Some(decision_id) = decision_id else {
+        let mut errors = FormErrors::default();
+        errors.add("action", "Zgłoszenia tej treści zostały już rozpatrzone.");
+        let page = render_report_page(report_id, &errors, &state).await?;
+        return Ok(form_errors_response(page));
+    };
+
+    admin
+        .audit(
+            AuditAction::ReportResolved,
+            "erika",
+            Some(erika.id),
+            json!({
+                "report_id": report_id,
+                "decision_id": decision_id,
+                "target": report.target_kind.to_string(),
+                "target_id": report.target_id,
+                "action": action.to_string(),
+                "ground": ground.to_string(),
+            }),
+            &client,
+            &state.db,
+        )
+        .await?;
+
+    if action == ModerationAction::SuspendCreator {
+        // Zawieszenie idzie tą samą ścieżką co ręczna zmiana statusu, żeby trafiło do historii konta
+        if Erika::change_status(
+            erika.id,
+            erika.status,
+            AccountStatus::Suspended,
+            statement,
+            &state.db,
+        )
+        .await?
+        {
+            state.publish(CreatorEvent::Listing {
+                erika_id: erika.id,
+                listed: false,
+            });
+            let mut changes = audit::diff(&[(
+                "status",
+                json!(erika.status.to_string()),
+                json!(AccountStatus::Suspended.to_string()),
+            )]);
+            changes["reason"] = json!(statement);
+            changes["decision_id"] = json!(decision_id);
+            admin
+                .audit(
+                    AuditAction::StatusChanged,
+                    "erika",
+                    Some(erika.id),
+                    changes,
+                    &client,
+                    &state.db,
+                )
+                .await?;
+        } else {
+            warn!(
+                "Decyzja {}: status konta {} zmienił się w międzyczasie, nie zawieszono",
+                decision_id, erika.id
+            );
+        }
+    }
+
+    info!(
+        "Admin {} rozpatrzył zgłoszenie {}: {}",
+        admin.0.username, report_id, action
+    );
+    Ok(Redirect::to("/admin/reports").into_response())
+}
+
+// Decyzje moderacji dotyczące treści modelki, z uzasadnieniem i informacją o odwołaniu
+pub async fn show_moderation_notices(
+    current: CurrentErika,
+    State(state): State<AppState>,
+) -> Result<Html<String>, AppError> {
+    let decisions =
+        ModerationDecision::find_restrictions_by_erika_id(current.id(), &state.db).await?;
+    let galleries = Gallery::find_by_erika_id(current.id(), &state.db).await?;
+
+    let content = maud::html! {
+        div class="max-w-4xl mx-auto" {
+            a href="/panel" class="inline-block mb-6 text-blue-400 hover:text-blue-300 transition-colors" {
+                "← Wróć do panelu"
+            }
+            h1 class="text-3xl font-bold text-white mb-2" { "Decyzje moderacji" }
+            p class="text-gray-400 mb-6" {
+                "Tu znajdziesz uzasadnienie każdej decyzji ograniczającej Twoje treści. "
+                "Jeśli się z nią nie zgadzasz, możesz złożyć odwołanie do administracji serwisu "
+                "lub skorzystać z pozasądowego rozstrzygania sporów albo drogi sądowej."
+            }
+            @if decisions.is_empty() {
+                p class="text-gray-400" { "Brak decyzji dotyczących Twoich treści." }
+            } @else {
+                div class="space-y-4" {
+                    @for decision in &decisions {
+                        div class="bg-gray-800 p-6 rounded-lg shadow-lg" {
+                            p class="text-white font-semibold" {
+                                (decision.action.label()) ": "
+                                @match decision.target_kind {
+                                    ReportTarget::Profile => "profil",
+                                    ReportTarget::Gallery => {
+                                        @match galleries.iter().find(|g| g.id == decision.target_id) {
+                                            Some(gallery) => { "galeria " (gallery.name) }
+                                            None => "usunięta galeria",
+                                        }
+                                    }
+                                    ReportTarget::Photo => "zdjęcie",
+                                }
+                            }
+                            p class="text-gray-400 text-sm mb-2" {
+                                (layout::format_datetime(decision.created_at))
+                                " · podstawa: " (decision.ground.label())
+                                @if decision.ground.is_legal_ground() {
+                                    " (treść niezgodna z prawem)"
+                                } @else {
+                                    " (naruszenie regulaminu)"
+                                }
+                                " · decyzja podjęta przez moderatora, nie automatycznie"
+                            }
+                            p class="text-gray-300 whitespace-pre-line" { (decision.statement) }
+                            p class="text-gray-500 text-xs mt-2" { "Numer decyzji (podaj go w odwołaniu): " (decision.id) }
+                        }
+                    }
+                }
+            }
+        }
+    };
+    Ok(Html(
+        layout::page("Decyzje moderacji", content).into_string(),
+    ))
+}
@@ -0,0 +1,159 @@
+// src/handlers/security_handlers.rs
+
+use super::layout;
+use crate::auth::{self, CurrentErika};
+use crate::models::user_session::UserSession;
+use crate::{app_state::AppState, errors::AppError};
+use axum::extract::Path as AxumPath;
+use axum::{
+    extract::State,
+    response::{Html, Redirect},
+};
+use tower_sessions::Session;
+use tracing::info;
+use uuid::Uuid;
+
+// Strona "Bezpieczeństwo" - lista aktywnych sesji z możliwością wylogowania
+pub async fn show_security_page(
+    current: CurrentErika,
+    session: Session,
+    State(state): State<AppState>,
+) -> Result<Html<String>, AppError> {
+    let sessions = UserSession::find_active_by_erika_id(
+        current.id(),
+        auth::SESSION_INACTIVITY_DAYS,
+        &state.db,
+    )
+    .await?;
+    let current_session_id = session.id().map(|id| id.to_string());
+
+    let content = maud::html! {
+        div class="max-w-4xl mx-auto" {
+            a href="/panel" class="inline-block mb-6 text-blue-400 hover:text-blue-300 transition-colors" {
+                "← Wróć do panelu"
+            }
+            h1 class="text-3xl font-bold text-white mb-6" { "Bezpieczeństwo" }
+
+            div class="bg-gray-800 p-6 rounded-lg shadow-lg" {
+                div class="flex justify-between items-center mb-4" {
+                    h2 class="text-xl font-semibold text-white" { "Aktywne sesje" }
+                    @if sessions.len() > 1 {
+                        form action="/panel/security/sessions/revoke-others" method="post" {
+                            button type="submit" class="bg-red-600 hover:bg-red-700 text-white font-bold py-1 px-3 rounded-md text-sm" {
+                                "Wyloguj wszystkie inne"
+                            }
+                        }
+                    }
+                }
+                ul {
+                    @for user_session in &sessions {
+                        @let is_current = current_session_id.as_deref() == Some(user_session.session_id.as_str());
+                        li class="py-4 border-b border-gray-700 flex justify-between items-center" {
+                            div {
+                                p class="text-white" {
+                                    (describe_device(user_session.user_agent.as_deref()))
+                                    @if is_current {
+                                        span class="ml-3 text-xs font-semibold bg-green-500 text-white px-2 py-1 rounded-full" { "Ta sesja" }
+                                    }
+                                }
+                                p class="text-sm text-gray-400" {
+                                    "IP: " (user_session.ip_address.as_deref().unwrap_or("nieznane"))
+                                    " · Ostatnio aktywna: " (layout::format_datetime(user_session.last_seen_at))
+                                    " · Zalogowano: " (layout::format_datetime(user_session.created_at))
+                                }
+                            }
+                            @if !is_current {
+                                form action=(format!("/panel/security/sessions/{}/revoke", user_session.id)) method="post" {
+                                    button type="submit" class="bg-gray-600 hover:bg-gray-700 text-white font-bold py-1 px-3 rounded-md text-sm" {
+                                        "Wyloguj"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    };
+    Ok(Html(layout::page("Bezpieczeństwo", content).into_string()))
+}
+
+// Wylogowuje jedną wybraną sesję
+pub async fn revoke_session(
+    AxumPath(user_session_id): AxumPath<Uuid>,
+    current: CurrentErika,
+    State(state): State<AppState>,
+) -> Result<Redirect, AppError> {
+    let user_session =
+        UserSession::find_by_id_and_erika_id(user_session_id, current.id(), &state.db)
+            .await?
+            .ok_or(AppError::NotFound)?;
+
+    auth::revoke_sessions(&state, &[user_session]).await?;
+    info!(
+        "Erika {} wylogowała sesję {}",
+        current.id(),
+        user_session_id
+    );
+
+    Ok(Redirect::to("/panel/security"))
+}
+
+// Wylogowuje wszystkie sesje poza bieżącą
+pub async fn revoke_other_sessions(
+    current: CurrentErika,
+    session: Session,
+    State(state): State<AppState>,
+) -> Result<Redirect, AppError> {
+    let current_session_id = session.id().map(|id| id.to_string());
+    let others =
+        UserSession::find_all_except(current.id(), current_session_id.as_deref(), &state.db)
+            .await?;
+
+    auth::revoke_sessions(&state, &others).await?;
+    info!(
+        "Erika {} wylogowała {} innych sesji",
+        current.id(),
+        others.len()
+    );
+
+    Ok(Redirect::to("/panel/security"))
+}
+
+/// Zamienia User-Agent na czytelny opis w stylu "Firefox na Windows".
+fn describe_device(user_agent: Option<&str>) -> String {
+    let Some(ua) = user_agent else {
+        return "Nieznane urządzenie".to_string();
+    };
+
+    // Kolejność ma znaczenie: Edge i Opera udają Chrome, a Chrome udaje Safari
+    let browser = [
+        ("Edg/", "Edge"),
+        ("OPR/", "Opera"),
+        ("Firefox/", "Firefox"),
+        ("Chrome/", "Chrome"),
+        ("Safari/", "Safari"),
+        ("curl/", "curl"),
+    ]
+    .iter()
+    .find(|(needle, _)| ua.contains(needle))
+    .map(|(_, name)| *name)
+    .unwrap_or("Przeglądarka");
+
+    let os = [
+        ("Android", "Android"),
+        ("iPhone", "iPhone"),
+        ("iPad", "iPad"),
+        ("Windows", "Windows"),
+        ("Mac OS X", "macOS"),
+        ("Linux", "Linux"),
+    ]
+    .iter()
+    .find(|(needle, _)| ua.contains(needle))
+    .map(|(_, name)| *name);
+
+    match os {
+        Some(os) => format!("{} na {}", browser, os),
+        None => browser.to_string(),
+    }
+}
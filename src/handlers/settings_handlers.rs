@@ -0,0 +1,255 @@
+// src/handlers/settings_handlers.rs
+
+// Edytor ustawień serwisu. Po zapisie ustawienia działają od razu -
+// podmieniamy kopię trzymaną w `AppState`.
+
+use super::erika_handlers::form_errors_response;
+use super::layout;
+use crate::auth::{ClientInfo, CurrentStaff};
+use crate::models::audit::{self, AuditAction};
+use crate::models::site_settings::{
+    MAX_PRESENCE_TIMEOUT_MINUTES, MAX_UPLOAD_CEILING_MB, SiteSettings,
+};
+use crate::validation::{self, FormErrors, Validate};
+use crate::{app_state::AppState, errors::AppError};
+use axum::{
+    Form,
+    extract::State,
+    response::{Html, IntoResponse, Redirect, Response},
+};
+use maud::Markup;
+use serde::Deserialize;
+use serde_json::json;
+use tracing::info;
+
+#[derive(Deserialize, Default)]
+pub struct SettingsPayload {
+    pub commission_percent: String,
+    pub max_upload_mb: String,
+    pub registration_open: Option<String>,
+    pub homepage_headline: String,
+    #[serde(default)]
+    pub banner: String,
+    pub maintenance_mode: Option<String>,
+    pub maintenance_message: String,
+    pub presence_timeout_minutes: String,
+}
+
+impl Validate for SettingsPayload {
+    fn normalize(&mut self) {
+        self.commission_percent = self.commission_percent.trim().to_string();
+        self.max_upload_mb = self.max_upload_mb.trim().to_string();
+        self.homepage_headline = self.homepage_headline.trim().to_string();
+        self.banner = self.banner.trim().to_string();
+        self.maintenance_message = self.maintenance_message.trim().to_string();
+        self.presence_timeout_minutes = self.presence_timeout_minutes.trim().to_string();
+    }
+
+    fn validate(&self) -> Result<(), FormErrors> {
+        let mut errors = FormErrors::default();
+        if !matches!(self.commission_percent.parse::<u8>(), Ok(0..=100)) {
+            errors.add(
+                "commission_percent",
+                "Prowizja musi być liczbą całkowitą od 0 do 100.",
+            );
+        }
+        if !matches!(
+            self.max_upload_mb.parse::<u32>(),
+            Ok(1..=MAX_UPLOAD_CEILING_MB)
+        ) {
+            errors.add(
+                "max_upload_mb",
+                format!(
+                    "Limit musi być liczbą od 1 do {} MB.",
+                    MAX_UPLOAD_CEILING_MB
+                ),
+            );
+        }
+        if !matches!(
+            self.presence_timeout_minutes.parse::<u32>(),
+            Ok(1..=MAX_PRESENCE_TIMEOUT_MINUTES)
+        ) {
+            errors.add(
+                "presence_timeout_minutes",
+                format!(
+                    "Czas musi być liczbą od 1 do {} minut.",
+                    MAX_PRESENCE_TIMEOUT_MINUTES
+                ),
+            );
+        }
+        let headline_len = self.homepage_headline.chars().count();
+        if headline_len == 0 || headline_len > validation::HEADLINE_MAX_LEN {
+            errors.add(
+                "homepage_headline",
+                format!(
+                    "Nagłówek musi mieć od 1 do {} znaków.",
+                    validation::HEADLINE_MAX_LEN
+                ),
+            );
+        }
+        if self.banner.chars().count() > validation::BANNER_MAX_LEN {
+            errors.add(
+                "banner",
+                format!(
+                    "Komunikat może mieć najwyżej {} znaków.",
+                    validation::BANNER_MAX_LEN
+                ),
+            );
+        }
+        let message_len = self.maintenance_message.chars().count();
+        if message_len == 0 || message_len > validation::MAINTENANCE_MESSAGE_MAX_LEN {
+            errors.add(
+                "maintenance_message",
+                format!(
+                    "Komunikat musi mieć od 1 do {} znaków.",
+                    validation::MAINTENANCE_MESSAGE_MAX_LEN
+                ),
+            );
+        }
+        errors.into_result()
+    }
+}
+
+impl SettingsPayload {
+    fn from_settings(settings: &SiteSettings) -> Self {
+        SettingsPayload {
+            commission_percent: settings.commission_percent.to_string(),
+            max_upload_mb: settings.max_upload_mb.to_string(),
+            registration_open: settings.registration_open.then(|| "1".to_string()),
+            homepage_headline: settings.homepage_headline.clone(),
+            banner: settings.banner.clone(),
+            maintenance_mode: settings.maintenance_mode.then(|| "1".to_string()),
+            maintenance_message: settings.maintenance_message.clone(),
+            presence_timeout_minutes: settings.presence_timeout_minutes.to_string(),
+        }
+    }
+
+    /// Ustawienia po walidacji - liczby są już sprawdzone.
+    fn settings(&self) -> SiteSettings {
+        SiteSettings {
+            commission_percent: self.commission_percent.parse().unwrap_or_default(),
+            max_upload_mb: self.max_upload_mb.parse().unwrap_or_default(),
+            registration_open: self.registration_open.is_some(),
+            homepage_headline: self.homepage_headline.clone(),
+            banner: self.banner.clone(),
+            maintenance_mode: self.maintenance_mode.is_some(),
+            maintenance_message: self.maintenance_message.clone(),
+            presence_timeout_minutes: self.presence_timeout_minutes.parse().unwrap_or_default(),
+        }
+    }
+}
+
+pub async fn show_settings(State(state): State<AppState>) -> Html<String> {
+    let values = SettingsPayload::from_settings(&state.settings());
+    Html(render_settings_page(&values, &FormErrors::default()).into_string())
+}
+
+fn render_settings_page(values: &SettingsPayload, errors: &FormErrors) -> Markup {
+    let input_class = "w-full px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white focus:outline-none focus:ring-2 focus:ring-blue-500";
+    let label_class = "block text-gray-300 text-sm font-bold mb-2";
+    let content = maud::html! {
+        a href="/admin" class="inline-block mb-6 text-blue-400 hover:text-blue-300 transition-colors" {
+            "← Wróć do panelu"
+        }
+        h1 class="text-3xl font-bold text-white mb-6" { "Ustawienia serwisu" }
+
+        form action="/admin/settings" method="post" class="max-w-2xl space-y-6" {
+            div class="bg-gray-800 p-6 rounded-lg shadow-lg space-y-4" {
+                h2 class="text-xl font-semibold text-white" { "Sprzedaż i pliki" }
+                div {
+                    label for="commission_percent" class=(label_class) { "Prowizja serwisu (%):" }
+                    input type="number" id="commission_percent" name="commission_percent" min="0" max="100"
+                          value=(values.commission_percent) required class=(input_class);
+                    (layout::field_error(errors, "commission_percent"))
+                }
+                div {
+                    label for="max_upload_mb" class=(label_class) { "Maksymalny rozmiar zdjęcia (MB):" }
+                    input type="number" id="max_upload_mb" name="max_upload_mb" min="1" max=(MAX_UPLOAD_CEILING_MB)
+                          value=(values.max_upload_mb) required class=(input_class);
+                    (layout::field_error(errors, "max_upload_mb"))
+                }
+            }
+
+            div class="bg-gray-800 p-6 rounded-lg shadow-lg space-y-4" {
+                h2 class="text-xl font-semibold text-white" { "Strona" }
+                label class="flex items-center gap-2 text-gray-300" {
+                    input type="checkbox" name="registration_open" value="1" checked[values.registration_open.is_some()];
+                    "Rejestracja nowych kont otwarta"
+                }
+                div {
+                    label for="homepage_headline" class=(label_class) { "Nagłówek strony głównej:" }
+                    input type="text" id="homepage_headline" name="homepage_headline" value=(values.homepage_headline)
+                          required maxlength=(validation::HEADLINE_MAX_LEN) class=(input_class);
+                    (layout::field_error(errors, "homepage_headline"))
+                }
+                div {
+                    label for="presence_timeout_minutes" class=(label_class) { "Modelka jest offline po (minut bez aktywności w panelu kamerki):" }
+                    input type="number" id="presence_timeout_minutes" name="presence_timeout_minutes" min="1" max=(MAX_PRESENCE_TIMEOUT_MINUTES)
+                          value=(values.presence_timeout_minutes) required class=(input_class);
+                    (layout::field_error(errors, "presence_timeout_minutes"))
+                }
+                div {
+                    label for="banner" class=(label_class) { "Komunikat na górze każdej strony (puste - brak):" }
+                    input type="text" id="banner" name="banner" value=(values.banner)
+                          maxlength=(validation::BANNER_MAX_LEN) class=(input_class);
+                    (layout::field_error(errors, "banner"))
+                }
+            }
+
+            div class="bg-gray-800 p-6 rounded-lg shadow-lg space-y-4" {
+                h2 class="text-xl font-semibold text-white" { "Tryb konserwacji" }
+                label class="flex items-center gap-2 text-gray-300" {
+                    input type="checkbox" name="maintenance_mode" value="1" checked[values.maintenance_mode.is_some()];
+                    "Włączony - serwis widzą tylko administratorzy"
+                }
+                div {
+                    label for="maintenance_message" class=(label_class) { "Komunikat dla odwiedzających:" }
+                    textarea id="maintenance_message" name="maintenance_message" rows="3" required
+                             maxlength=(validation::MAINTENANCE_MESSAGE_MAX_LEN) class=(input_class) { (values.maintenance_message) }
+                    (layout::field_error(errors, "maintenance_message"))
+                }
+            }
+
+            button type="submit" class="bg-blue-600 hover:bg-blue-700 text-white font-bold py-2 px-4 rounded-md" { "Zapisz ustawienia" }
+        }
+    };
+    layout::page("Ustawienia", content)
+}
+
+pub async fn update_settings(
+    admin: CurrentStaff,
+    client: ClientInfo,
+    State(state): State<AppState>,
+    Form(mut payload): Form<SettingsPayload>,
+) -> Result<Response, AppError> {
+    payload.normalize();
+    if let Err(errors) = payload.validate() {
+        return Ok(form_errors_response(render_settings_page(
+            &payload, &errors,
+        )));
+    }
+
+    let before = state.settings();
+    let after = payload.settings();
+    after.save(&state.db).await?;
+    state.replace_settings(after.clone());
+
+    let fields: Vec<_> = before
+        .pairs()
+        .into_iter()
+        .zip(after.pairs())
+        .map(|((key, old), (_, new))| (key, json!(old), json!(new)))
+        .collect();
+    admin
+        .audit(
+            AuditAction::SettingsUpdated,
+            "settings",
+            None,
+            audit::diff(&fields),
+            &client,
+            &state.db,
+        )
+        .await?;
+    info!("Admin {} zmienił ustawienia serwisu", admin.0.username);
+    Ok(Redirect::to("/admin/settings").into_response())
+}
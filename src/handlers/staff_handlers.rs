@@ -0,0 +1,205 @@
+// src/handlers/staff_handlers.rs
+
+// Zespół panelu admina: kto ma rolę Admin lub Moderator i co każda rola może.
+// Rolę nadaje się po nazwie użytkownika; każda zmiana trafia do audytu.
+
+use super::erika_handlers::form_errors_response;
+use super::layout;
+use crate::auth::{ClientInfo, CurrentStaff};
+use crate::models::audit::AuditAction;
+use crate::models::erika::{Erika, ErikaQuery, ROLES};
+use crate::models::permission::{self, Permission, STAFF_ROLES};
+use crate::validation::FormErrors;
+use crate::{app_state::AppState, errors::AppError};
+use axum::{
+    Form,
+    extract::State,
+    response::{Html, IntoResponse, Redirect, Response},
+};
+use maud::Markup;
+use serde::Deserialize;
+use serde_json::json;
+use strum::IntoEnumIterator;
+use tracing::info;
+
+/// Górny limit osób jednej roli na liście zespołu.
+const STAFF_LIST_LIMIT: i64 = 200;
+
+#[derive(Deserialize, Default)]
+pub struct AssignRolePayload {
+    pub username: String,
+    pub role: String,
+}
+
+pub async fn show_staff(State(state): State<AppState>) -> Result<Html<String>, AppError> {
+    let values = AssignRolePayload {
+        role: "Moderator".to_string(),
+        ..AssignRolePayload::default()
+    };
+    let page = render_staff_page(&values, &FormErrors::default(), &state).await?;
+    Ok(Html(page.into_string()))
+}
+
+fn role_select(selected: &str) -> Markup {
+    maud::html! {
+        select name="role" class="px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white" {
+            @for role in ROLES {
+                option value=(role) selected[*role == selected] { (role) }
+            }
+        }
+    }
+}
+
+async fn render_staff_page(
+    values: &AssignRolePayload,
+    errors: &FormErrors,
+    state: &AppState,
+) -> Result<Markup, AppError> {
+    let mut members = Vec::new();
+    for role in STAFF_ROLES {
+        let query = ErikaQuery {
+            role: Some(role.to_string()),
+            ..ErikaQuery::default()
+        };
+        members.extend(Erika::find_filtered(&query, STAFF_LIST_LIMIT, &state.db).await?);
+    }
+
+    let content = maud::html! {
+        a href="/admin" class="inline-block mb-6 text-blue-400 hover:text-blue-300 transition-colors" {
+            "← Wróć do panelu"
+        }
+        h1 class="text-3xl font-bold text-white mb-6" { "Zespół" }
+
+        div class="bg-gray-800 p-6 rounded-lg shadow-lg mb-6" {
+            h2 class="text-xl font-semibold text-white mb-4" { "Nadaj rolę" }
+            form action="/admin/staff" method="post" class="flex flex-wrap gap-4 items-start" {
+                div {
+                    input type="text" name="username" value=(values.username) required placeholder="nazwa użytkownika"
+                          class="px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white focus:outline-none focus:ring-2 focus:ring-blue-500";
+                    (layout::field_error(errors, "username"))
+                }
+                div {
+                    (role_select(&values.role))
+                    (layout::field_error(errors, "role"))
+                }
+                button type="submit" class="bg-blue-600 hover:bg-blue-700 text-white font-bold py-2 px-4 rounded-md" { "Zapisz" }
+            }
+        }
+
+        div class="bg-gray-800 rounded-lg shadow-lg mb-6" {
+            table class="w-full text-left text-sm" {
+                thead class="text-gray-400 border-b border-gray-700" {
+                    tr {
+                        th class="p-3" { "Nazwa" }
+                        th class="p-3" { "Email" }
+                        th class="p-3" { "Rola" }
+                    }
+                }
+                tbody {
+                    @for member in &members {
+                        tr class="border-b border-gray-700" {
+                            td class="p-3 text-white" {
+                                a href=(format!("/admin/erika/{}", member.id)) class="hover:underline" { (member.username) }
+                            }
+                            td class="p-3 text-gray-300" { (member.email) }
+                            td class="p-3" {
+                                form action="/admin/staff" method="post" class="flex gap-2" {
+                                    input type="hidden" name="username" value=(member.username);
+                                    (role_select(&member.role))
+                                    button type="submit" class="text-blue-400 hover:text-blue-300" { "Zmień" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        h2 class="text-xl font-semibold text-white mb-4" { "Uprawnienia ról" }
+        div class="bg-gray-800 rounded-lg shadow-lg overflow-x-auto" {
+            table class="w-full text-left text-sm" {
+                thead class="text-gray-400 border-b border-gray-700" {
+                    tr {
+                        th class="p-3" { "Uprawnienie" }
+                        @for role in STAFF_ROLES {
+                            th class="p-3" { (role) }
+                        }
+                    }
+                }
+                tbody {
+                    @for permission in Permission::iter() {
+                        tr class="border-b border-gray-700" {
+                            td class="p-3 text-white" {
+                                (permission.label())
+                                code class="ml-2 text-gray-500" { (permission.to_string()) }
+                            }
+                            @for role in STAFF_ROLES {
+                                td class="p-3" {
+                                    @if permission::permissions_for(role).contains(&permission) {
+                                        span class="text-green-400" { "✓" }
+                                    } @else {
+                                        span class="text-gray-600" { "—" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    };
+    Ok(layout::page("Zespół", content))
+}
+
+pub async fn assign_role(
+    admin: CurrentStaff,
+    client: ClientInfo,
+    State(state): State<AppState>,
+    Form(payload): Form<AssignRolePayload>,
+) -> Result<Response, AppError> {
+    let mut errors = FormErrors::default();
+    if !ROLES.contains(&payload.role.as_str()) {
+        errors.add("role", "Nieznana rola.");
+    }
+    let erika = Erika::find_by_username(payload.username.trim(), &state.db).await?;
+    if erika.is_none() {
+        errors.add("username", "Nie ma konta o tej nazwie.");
+    }
+    let (Some(erika), true) = (erika, errors.is_empty()) else {
+        let page = render_staff_page(&payload, &errors, &state).await?;
+        return Ok(form_errors_response(page));
+    };
+
+    let before = Erika::find_role(erika.id, &state.db)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    if before == payload.role {
+        return Ok(Redirect::to("/admin/staff").into_response());
+    }
+
+    if !Erika::change_role(erika.id, &payload.role, &state.db).await? {
+        errors.add(
+            "role",
+            "To ostatni administrator - najpierw nadaj rolę Admin innemu kontu.",
+        );
+        let page = render_staff_page(&payload, &errors, &state).await?;
+        return Ok(form_errors_response(page));
+    }
+
+    // Sesje konta same wymienią ID przy następnym żądaniu (zmienił się odcisk uprawnień)
+    admin
+        .audit(
+            AuditAction::RoleChanged,
+            "erika",
+            Some(erika.id),
+            json!({ "role": { "before": before, "after": payload.role } }),
+            &client,
+            &state.db,
+        )
+        .await?;
+    info!(
+        "Admin {} zmienił rolę {} z {} na {}",
+        admin.0.username, erika.username, before, payload.role
+    );
+    Ok(Redirect::to("/admin/staff").into_response())
+}
@@ -0,0 +1,653 @@
+// src/handlers/stream_handlers.rs
+
+// Panel kamerki: zapowiedzi transmisji, start i koniec transmisji, historia
+// oraz ustawienia napiwków (`tip_handlers`) i pokazów prywatnych (`private_show_handlers`).
+// Fani widzą zapowiedzi na profilu i mogą je subskrybować jako kalendarz (.ics).
+
+use super::erika_handlers::form_errors_response;
+use super::layout;
+use super::private_show_handlers::{self, PrivateRatePayload};
+use super::tip_handlers::{self, TipGoalPayload, TipMenuItemPayload};
+use crate::auth::CurrentErika;
+use crate::events::CreatorEvent;
+use crate::models::erika::Erika;
+use crate::models::private_show::{PrivateShow, PrivateShowEndReason, PrivateShowRate};
+use crate::models::stream_session::StreamSession;
+use crate::models::tip::{TipGoal, TipMenuItem};
+use crate::presence;
+use crate::validation::{self, FormErrors, Validate};
+use crate::{app_state::AppState, errors::AppError};
+use axum::{
+    Form,
+    extract::{Path, State},
+    http::header,
+    response::{Html, IntoResponse, Redirect, Response},
+};
+use maud::Markup;
+use serde::Deserialize;
+use time::{Duration, OffsetDateTime, macros::format_description};
+use tracing::info;
+use uuid::Uuid;
+
+/// Ile zakończonych transmisji pokazujemy w historii.
+const HISTORY_LIMIT: i64 = 50;
+
+/// Tytuł transmisji rozpoczętej bez podania własnego.
+const DEFAULT_LIVE_TITLE: &str = "Transmisja na żywo";
+
+#[derive(Deserialize)]
+pub struct StreamSchedulePayload {
+    pub title: String,
+    /// Pole `datetime-local` w UTC.
+    pub scheduled_at: String,
+    pub planned_minutes: String,
+}
+
+impl Validate for StreamSchedulePayload {
+    fn normalize(&mut self) {
+        self.title = self.title.trim().to_string();
+        self.scheduled_at = self.scheduled_at.trim().to_string();
+        self.planned_minutes = self.planned_minutes.trim().to_string();
+    }
+
+    fn validate(&self) -> Result<(), FormErrors> {
+        let mut errors = FormErrors::default();
+        let title_len = self.title.chars().count();
+        if title_len == 0 || title_len > validation::STREAM_TITLE_MAX_LEN {
+            errors.add(
+                "title",
+                format!(
+                    "Tytuł musi mieć od 1 do {} znaków.",
+                    validation::STREAM_TITLE_MAX_LEN
+                ),
+            );
+        }
+        match layout::parse_datetime_input(&self.scheduled_at) {
+            None => errors.add("scheduled_at", "Podaj datę i godzinę startu."),
+            Some(scheduled_at) if scheduled_at <= OffsetDateTime::now_utc() => {
+                errors.add("scheduled_at", "Termin musi być w przyszłości.")
+            }
+            Some(_) => {}
+        }
+        match self.planned_minutes.parse::<i32>() {
+            Ok(minutes)
+                if (validation::STREAM_MIN_MINUTES..=validation::STREAM_MAX_MINUTES)
+                    .contains(&minutes) => {}
+            _ => errors.add(
+                "planned_minutes",
+                format!(
+                    "Podaj czas od {} do {} minut.",
+                    validation::STREAM_MIN_MINUTES,
+                    validation::STREAM_MAX_MINUTES
+                ),
+            ),
+        }
+        errors.into_result()
+    }
+}
+
+impl Default for StreamSchedulePayload {
+    fn default() -> Self {
+        StreamSchedulePayload {
+            title: String::new(),
+            scheduled_at: String::new(),
+            planned_minutes: "60".to_string(),
+        }
+    }
+}
+
+#[derive(Deserialize, Default)]
+pub struct StartStreamPayload {
+    #[serde(default)]
+    pub live_title: String,
+}
+
+impl Validate for StartStreamPayload {
+    fn normalize(&mut self) {
+        self.live_title = self.live_title.trim().to_string();
+    }
+
+    fn validate(&self) -> Result<(), FormErrors> {
+        let mut errors = FormErrors::default();
+        // Pusty tytuł jest dozwolony - wtedy transmisja dostaje domyślny
+        if self.live_title.chars().count() > validation::STREAM_TITLE_MAX_LEN {
+            errors.add(
+                "live_title",
+                format!(
+                    "Tytuł może mieć najwyżej {} znaków.",
+                    validation::STREAM_TITLE_MAX_LEN
+                ),
+            );
+        }
+        errors.into_result()
+    }
+}
+
+/// Wartości formularzy panelu kamerki - po błędzie walidacji wraca
+/// ten, który modelka wysłała, a pozostałe są puste.
+#[derive(Default)]
+pub struct StreamPanelForms {
+    pub schedule: StreamSchedulePayload,
+    pub start: StartStreamPayload,
+    pub menu_item: TipMenuItemPayload,
+    pub goal: TipGoalPayload,
+    pub private_rate: PrivateRatePayload,
+}
+
+pub async fn show_stream_panel(
+    current: CurrentErika,
+    State(state): State<AppState>,
+) -> Result<Html<String>, AppError> {
+    // Streamować mogą tylko zaakceptowane modelki
+    current.ensure_approved()?;
+    let page = render_stream_panel(
+        &current,
+        &StreamPanelForms::default(),
+        &FormErrors::default(),
+        &state,
+    )
+    .await?;
+    Ok(Html(page.into_string()))
+}
+
+pub async fn render_stream_panel(
+    current: &CurrentErika,
+    forms: &StreamPanelForms,
+    errors: &FormErrors,
+    state: &AppState,
+) -> Result<Markup, AppError> {
+    let live = StreamSession::find_live(current.id(), &state.db).await?;
+    let menu = TipMenuItem::find_by_erika_id(current.id(), &state.db).await?;
+    let goal = TipGoal::find_by_erika_id(current.id(), &state.db).await?;
+    let private_rate = PrivateShowRate::find(current.id(), &state.db).await?;
+    let private_requests = private_show_handlers::render_requests(current.id(), &state.db).await?;
+    let feed = tip_handlers::render_live_feed(current.id(), &state.db).await?;
+    let planned = StreamSession::find_planned_by_erika_id(current.id(), &state.db).await?;
+    let history =
+        StreamSession::find_history_by_erika_id(current.id(), HISTORY_LIMIT, &state.db).await?;
+    let now = OffsetDateTime::now_utc();
+    let input_class = "w-full px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white focus:outline-none focus:ring-2 focus:ring-blue-500";
+    let label_class = "block text-gray-300 text-sm font-bold mb-2";
+
+    let content = maud::html! {
+        // Rozszerzenie SSE dla HTMX - czat odświeża się po każdym napiwku
+        script src="https://cdn.jsdelivr.net/npm/htmx-ext-sse@2.2.3/dist/sse.js" defer {}
+        div class="max-w-7xl mx-auto" {
+            a href="/panel" class="inline-block mb-6 text-blue-400 hover:text-blue-300 transition-colors" {
+                "← Wróć do głównego panelu"
+            }
+            h1 class="text-3xl font-bold text-white mb-6" { "Panel Twojej Kamerki" }
+
+            // Dopóki ta strona jest otwarta, modelka jest online
+            div hx-post="/panel/presence" hx-trigger=(format!("load, every {}s", presence::HEARTBEAT_INTERVAL_SECS)) hx-swap="none" {}
+
+            div class="bg-gray-800 p-4 rounded-lg shadow-lg mb-6" {
+                @if let Some(live) = &live {
+                    form action="/panel/stream/end" method="post" class="flex flex-wrap justify-between items-center gap-4" {
+                        div {
+                            p class="text-red-400 font-semibold" {
+                                @if live.is_private { "● Pokaz prywatny" } @else { "● Na żywo: " (live.title) }
+                            }
+                            @if let Some(started_at) = live.started_at {
+                                p class="text-gray-400 text-sm" { "Od " (layout::format_datetime(started_at)) }
+                            }
+                        }
+                        button type="submit" class="bg-red-600 hover:bg-red-700 text-white font-bold py-2 px-4 rounded-md" {
+                            @if live.is_private { "Zakończ pokaz" } @else { "Zakończ transmisję" }
+                        }
+                    }
+                } @else {
+                    form action="/panel/stream/start" method="post" class="flex flex-col sm:flex-row gap-4 sm:items-start" {
+                        div class="flex-grow" {
+                            input type="text" name="live_title" value=(forms.start.live_title) placeholder=(DEFAULT_LIVE_TITLE)
+                                  maxlength=(validation::STREAM_TITLE_MAX_LEN) class=(input_class);
+                            (layout::field_error(errors, "live_title"))
+                        }
+                        button type="submit" class="bg-green-600 hover:bg-green-700 text-white font-bold py-2 px-4 rounded-md whitespace-nowrap" { "Rozpocznij transmisję teraz" }
+                    }
+                }
+            }
+
+            // Domyślnie flex-col (pionowo), na dużych ekranach (lg) flex-row (poziomo)
+            div class="flex flex-col lg:flex-row gap-6 mb-6" {
+                // Kolumna z wideo
+                div class="lg:w-2/3 bg-gray-800 p-4 rounded-lg shadow-lg" {
+                    // Główny widok
+                    div class="bg-black aspect-video w-full mb-4 rounded flex items-center justify-center" {
+                        p class="text-gray-500" { "Oczekiwanie na połączenie..." }
+                    }
+                    // Miniaturka
+                    div class="w-1/2 md:w-1/3 lg:w-1/4 bg-black aspect-video rounded ml-auto flex items-center justify-center" {
+                        p class="text-gray-500 text-sm" { "Twój podgląd" }
+                    }
+                }
+                // Kolumna z czatem i kontrolkami
+                div class="lg:w-1/3 bg-gray-800 p-4 rounded-lg shadow-lg" {
+                    h2 class="text-xl font-semibold text-white mb-4" { "Czat" }
+                    div class="h-96 overflow-y-auto bg-gray-700 rounded p-2 mb-4" {
+                        (tip_handlers::live_feed_container(current.id(), feed))
+                    }
+                    input type="text" placeholder="Napisz wiadomość..." class="w-full px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white";
+                }
+            }
+
+            div class="grid grid-cols-1 lg:grid-cols-2 gap-6 mb-6" {
+                div class="bg-gray-800 p-6 rounded-lg shadow-lg" {
+                    h2 class="text-xl font-semibold text-white mb-4" { "Zapowiedz transmisję" }
+                    form action="/panel/stream/schedule" method="post" class="space-y-4" {
+                        div {
+                            label for="title" class=(label_class) { "Tytuł:" }
+                            input type="text" id="title" name="title" value=(forms.schedule.title) required
+                                  maxlength=(validation::STREAM_TITLE_MAX_LEN) class=(input_class);
+                            (layout::field_error(errors, "title"))
+                        }
+                        div class="grid grid-cols-1 sm:grid-cols-2 gap-4" {
+                            div {
+                                label for="scheduled_at" class=(label_class) { "Start (UTC):" }
+                                input type="datetime-local" id="scheduled_at" name="scheduled_at" value=(forms.schedule.scheduled_at) required class=(input_class);
+                                (layout::field_error(errors, "scheduled_at"))
+                            }
+                            div {
+                                label for="planned_minutes" class=(label_class) { "Planowany czas (minut):" }
+                                input type="number" id="planned_minutes" name="planned_minutes" value=(forms.schedule.planned_minutes) required
+                                      min=(validation::STREAM_MIN_MINUTES) max=(validation::STREAM_MAX_MINUTES) class=(input_class);
+                                (layout::field_error(errors, "planned_minutes"))
+                            }
+                        }
+                        button type="submit" class="bg-blue-600 hover:bg-blue-700 text-white font-bold py-2 px-4 rounded-md" { "Dodaj do grafiku" }
+                    }
+                }
+
+                div class="bg-gray-800 p-6 rounded-lg shadow-lg" {
+                    div class="flex justify-between items-center mb-4" {
+                        h2 class="text-xl font-semibold text-white" { "Grafik" }
+                        a href=(format!("/erika/{}/schedule.ics", current.0.username)) class="text-sm text-blue-400 hover:underline" { "Kalendarz .ics" }
+                    }
+                    @if planned.is_empty() {
+                        p class="text-gray-400" { "Brak zapowiedzianych transmisji." }
+                    } @else {
+                        div class="divide-y divide-gray-700" {
+                            @for session in &planned {
+                                div class="flex flex-wrap justify-between items-center gap-2 py-3" {
+                                    div {
+                                        p class="text-white" { (session.title) }
+                                        @if let Some(scheduled_at) = session.scheduled_at {
+                                            p class={ "text-sm " @if scheduled_at < now { "text-yellow-400" } @else { "text-gray-400" } } {
+                                                (layout::format_datetime(scheduled_at)) " · " (session.planned_minutes) " min"
+                                                @if scheduled_at < now { " · po terminie" }
+                                            }
+                                        }
+                                    }
+                                    div class="flex items-center gap-3" {
+                                        @if live.is_none() {
+                                            form action=(format!("/panel/stream/{}/start", session.id)) method="post" {
+                                                button type="submit" class="text-green-400 hover:text-green-300" { "Rozpocznij" }
+                                            }
+                                        }
+                                        form action=(format!("/panel/stream/{}/delete", session.id)) method="post" {
+                                            button type="submit" class="text-red-400 hover:text-red-300" { "Odwołaj" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            div class="grid grid-cols-1 lg:grid-cols-2 gap-6 mb-6" {
+                (tip_handlers::render_tip_settings(&menu, goal.as_ref(), forms, errors))
+                (private_show_handlers::render_private_settings(private_rate.as_ref(), private_requests, forms, errors))
+            }
+
+            div class="bg-gray-800 p-6 rounded-lg shadow-lg" {
+                h2 class="text-xl font-semibold text-white mb-4" { "Historia transmisji" }
+                @if history.is_empty() {
+                    p class="text-gray-400" { "Nie masz jeszcze za sobą żadnej transmisji." }
+                } @else {
+                    table class="w-full text-left text-sm" {
+                        thead class="text-gray-400 border-b border-gray-700" {
+                            tr {
+                                th class="p-2" { "Tytuł" }
+                                th class="p-2" { "Start" }
+                                th class="p-2" { "Czas trwania" }
+                                th class="p-2" { "Szczyt widzów" }
+                                th class="p-2 text-right" { "Zarobek" }
+                            }
+                        }
+                        tbody {
+                            @for session in &history {
+                                tr class="border-b border-gray-700" {
+                                    td class="p-2 text-white" {
+                                        (session.title)
+                                        @if session.is_private {
+                                            span class="block text-purple-400 text-xs" { "pokaz prywatny" }
+                                        } @else if session.scheduled_at.is_none() {
+                                            span class="block text-gray-500 text-xs" { "bez zapowiedzi" }
+                                        }
+                                    }
+                                    td class="p-2 text-gray-300 whitespace-nowrap" {
+                                        @if let Some(started_at) = session.started_at { (layout::format_datetime(started_at)) }
+                                    }
+                                    td class="p-2 text-gray-300" {
+                                        @if let Some(duration) = session.duration() { (format_duration(duration)) }
+                                    }
+                                    td class="p-2 text-gray-300" { (session.peak_viewers) }
+                                    td class="p-2 text-green-400 text-right whitespace-nowrap" { (session.earnings_pln.with_scale(2)) " PLN" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    };
+    Ok(layout::page("Panel Kamerki", content))
+}
+
+pub async fn schedule_stream(
+    current: CurrentErika,
+    State(state): State<AppState>,
+    Form(mut payload): Form<StreamSchedulePayload>,
+) -> Result<Response, AppError> {
+    current.ensure_approved()?;
+    payload.normalize();
+    if let Err(errors) = payload.validate() {
+        let forms = StreamPanelForms {
+            schedule: payload,
+            ..StreamPanelForms::default()
+        };
+        let page = render_stream_panel(&current, &forms, &errors, &state).await?;
+        return Ok(form_errors_response(page));
+    }
+
+    // Wartości są już zwalidowane
+    let scheduled_at =
+        layout::parse_datetime_input(&payload.scheduled_at).unwrap_or_else(OffsetDateTime::now_utc);
+    let planned_minutes = payload.planned_minutes.parse().unwrap_or(60);
+    let session_id = StreamSession::schedule(
+        current.id(),
+        &payload.title,
+        scheduled_at,
+        planned_minutes,
+        &state.db,
+    )
+    .await?;
+    info!(
+        "Modelka {} zapowiedziała transmisję {}",
+        current.id(),
+        session_id
+    );
+    Ok(Redirect::to("/panel/stream").into_response())
+}
+
+/// Start transmisji bez zapowiedzi.
+pub async fn start_stream_now(
+    current: CurrentErika,
+    State(state): State<AppState>,
+    Form(mut payload): Form<StartStreamPayload>,
+) -> Result<Response, AppError> {
+    current.ensure_approved()?;
+    payload.normalize();
+    let mut errors = match payload.validate() {
+        Ok(()) => FormErrors::default(),
+        Err(errors) => errors,
+    };
+    if errors.is_empty()
+        && StreamSession::find_live(current.id(), &state.db)
+            .await?
+            .is_some()
+    {
+        errors.add("live_title", "Najpierw zakończ trwającą transmisję.");
+    }
+    if !errors.is_empty() {
+        let forms = StreamPanelForms {
+            start: payload,
+            ..StreamPanelForms::default()
+        };
+        let page = render_stream_panel(&current, &forms, &errors, &state).await?;
+        return Ok(form_errors_response(page));
+    }
+
+    let title = if payload.live_title.is_empty() {
+        DEFAULT_LIVE_TITLE
+    } else {
+        &payload.live_title
+    };
+    let session_id = StreamSession::start_unscheduled(current.id(), title, &state.db).await?;
+    info!(
+        "Modelka {} rozpoczęła transmisję {}",
+        current.id(),
+        session_id
+    );
+    Ok(Redirect::to("/panel/stream").into_response())
+}
+
+/// Start zapowiedzianej transmisji.
+pub async fn start_scheduled_stream(
+    Path(session_id): Path<Uuid>,
+    current: CurrentErika,
+    State(state): State<AppState>,
+) -> Result<Response, AppError> {
+    current.ensure_approved()?;
+    if StreamSession::find_live(current.id(), &state.db)
+        .await?
+        .is_some()
+    {
+        let mut errors = FormErrors::default();
+        errors.add("live_title", "Najpierw zakończ trwającą transmisję.");
+        let page =
+            render_stream_panel(&current, &StreamPanelForms::default(), &errors, &state).await?;
+        return Ok(form_errors_response(page));
+    }
+    if !StreamSession::start(session_id, current.id(), &state.db).await? {
+        return Err(AppError::NotFound);
+    }
+    info!(
+        "Modelka {} rozpoczęła transmisję {}",
+        current.id(),
+        session_id
+    );
+    Ok(Redirect::to("/panel/stream").into_response())
+}
+
+pub async fn end_stream(
+    current: CurrentErika,
+    State(state): State<AppState>,
+) -> Result<Redirect, AppError> {
+    // Pokaz prywatny kończy się z rozliczeniem minut, a nie samym zamknięciem transmisji
+    let commission_percent = state.settings().commission_percent;
+    if let Some(show_id) = PrivateShow::end_for_erika(
+        current.id(),
+        PrivateShowEndReason::Creator,
+        commission_percent,
+        &state.db,
+    )
+    .await?
+    {
+        state.publish(CreatorEvent::PrivateShow {
+            erika_id: current.id(),
+            show_id,
+        });
+        info!(
+            "Modelka {} zakończyła pokaz prywatny {}",
+            current.id(),
+            show_id
+        );
+    } else if let Some(session_id) = StreamSession::end_live(current.id(), &state.db).await? {
+        info!(
+            "Modelka {} zakończyła transmisję {}",
+            current.id(),
+            session_id
+        );
+    }
+    Ok(Redirect::to("/panel/stream"))
+}
+
+pub async fn cancel_stream(
+    Path(session_id): Path<Uuid>,
+    current: CurrentErika,
+    State(state): State<AppState>,
+) -> Result<Redirect, AppError> {
+    if !StreamSession::cancel(session_id, current.id(), &state.db).await? {
+        return Err(AppError::NotFound);
+    }
+    info!(
+        "Modelka {} odwołała transmisję {}",
+        current.id(),
+        session_id
+    );
+    Ok(Redirect::to("/panel/stream"))
+}
+
+/// Nadchodzące transmisje na profilu modelki, z odliczaniem w przeglądarce.
+pub fn render_upcoming_shows(username: &str, sessions: &[StreamSession]) -> Markup {
+    maud::html! {
+        div class="bg-gray-800 p-6 rounded-lg shadow-lg mb-8" {
+            div class="flex flex-wrap justify-between items-center gap-2 mb-4" {
+                h2 class="text-2xl font-bold text-white" { "Nadchodzące transmisje" }
+                a href=(format!("/erika/{}/schedule.ics", username)) class="text-sm text-blue-400 hover:underline"
+                  title="Dodaj ten adres jako subskrypcję w swoim kalendarzu" {
+                    "Subskrybuj kalendarz (.ics)"
+                }
+            }
+            @if sessions.is_empty() {
+                p class="text-gray-400" { "Brak zapowiedzianych transmisji." }
+            } @else {
+                div class="divide-y divide-gray-700" {
+                    @for session in sessions {
+                        @if let Some(scheduled_at) = session.scheduled_at {
+                            div class="flex flex-wrap justify-between items-center gap-2 py-3" {
+                                div {
+                                    p class="text-white font-semibold" { (session.title) }
+                                    p class="text-gray-400 text-sm" { (layout::format_datetime(scheduled_at)) " · " (session.planned_minutes) " min" }
+                                }
+                                (countdown(scheduled_at))
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Odliczanie do startu. Bez JavaScriptu zostaje sama data z serwera.
+fn countdown(at: OffsetDateTime) -> Markup {
+    let at_ms = at.unix_timestamp() * 1000;
+    maud::html! {
+        span class="text-purple-400 font-mono"
+             x-data=(format!("{{ left: 0, tick() {{ this.left = Math.max(0, Math.floor(({} - Date.now()) / 1000)) }} }}", at_ms))
+             x-init="tick(); setInterval(() => tick(), 1000)"
+             x-text="left === 0 ? 'zaraz start' : (left >= 86400 ? Math.floor(left / 86400) + ' d ' : '') + Math.floor(left % 86400 / 3600) + ' godz. ' + Math.floor(left % 3600 / 60) + ' min ' + left % 60 + ' s'" {
+            (layout::format_datetime(at))
+        }
+    }
+}
+
+// Czas trwania transmisji, np. "1 godz. 25 min"
+fn format_duration(duration: Duration) -> String {
+    let minutes = duration.whole_minutes();
+    match minutes / 60 {
+        0 => format!("{} min", minutes),
+        hours => format!("{} godz. {} min", hours, minutes % 60),
+    }
+}
+
+/// Kanał iCalendar z zapowiedziami modelki do subskrypcji w kalendarzu fana.
+pub async fn schedule_feed(
+    Path(username): Path<String>,
+    State(state): State<AppState>,
+) -> Result<Response, AppError> {
+    let erika = Erika::find_by_public_username(&username, &state.db)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    let sessions = StreamSession::find_for_feed(erika.id, &state.db).await?;
+
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//Erika//Transmisje//PL".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        format!(
+            "X-WR-CALNAME:{}",
+            ics_text(&format!("Transmisje: {}", erika.username))
+        ),
+        "REFRESH-INTERVAL;VALUE=DURATION:PT1H".to_string(),
+        "X-PUBLISHED-TTL:PT1H".to_string(),
+    ];
+    for session in &sessions {
+        let Some(scheduled_at) = session.scheduled_at else {
+            continue;
+        };
+        lines.extend([
+            "BEGIN:VEVENT".to_string(),
+            format!("UID:{}@erika", session.id),
+            format!("DTSTAMP:{}", ics_datetime(session.created_at)),
+            format!("DTSTART:{}", ics_datetime(scheduled_at)),
+            format!("DURATION:PT{}M", session.planned_minutes),
+            format!(
+                "SUMMARY:{}",
+                ics_text(&format!("{}: {}", erika.username, session.title))
+            ),
+            format!(
+                "DESCRIPTION:{}",
+                ics_text(&format!("Profil: /erika/{}", erika.username))
+            ),
+            "END:VEVENT".to_string(),
+        ]);
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    let body: String = lines.iter().map(|line| ics_fold(line)).collect();
+    Ok((
+        [
+            (
+                header::CONTENT_TYPE,
+                "text/calendar; charset=utf-8".to_string(),
+            ),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("inline; filename=\"{}.ics\"", erika.username),
+            ),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+// Czas w formacie iCalendar (UTC), np. "20250822T190000Z"
+fn ics_datetime(value: OffsetDateTime) -> String {
+    value
+        .format(format_description!(
+            "[year][month][day]T[hour][minute][second]Z"
+        ))
+        .unwrap_or_default()
+}
+
+// Tekst z ucieczką znaków specjalnych iCalendar (RFC 5545, 3.3.11)
+fn ics_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+        .replace('\r', "")
+}
+
+// Zawija linię co 75 bajtów (bez dzielenia znaków UTF-8) i kończy ją CRLF
+fn ics_fold(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 8);
+    let mut line_len = 0;
+    for c in line.chars() {
+        if line_len + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            line_len = 1;
+        }
+        folded.push(c);
+        line_len += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
@@ -0,0 +1,549 @@
+// src/handlers/tip_handlers.rs
+
+// Napiwki w czasie transmisji. Modelka ustawia w panelu kamerki menu napiwków
+// i cel zbiórki; widz płaci z przedpłaconego salda na profilu modelki.
+// Każdy napiwek trafia do księgi z prowizją serwisu i do czatu transmisji (SSE).
+
+use super::erika_handlers::form_errors_response;
+use super::layout;
+use super::stream_handlers::{self, StreamPanelForms};
+use crate::auth::{CurrentAccount, CurrentErika};
+use crate::events::CreatorEvent;
+use crate::models::erika::Erika;
+use crate::models::stream_session::StreamSession;
+use crate::models::tip::{MAX_MENU_ITEMS, NewTip, Tip, TipEntry, TipGoal, TipMenuItem};
+use crate::validation::{self, FormErrors, Validate};
+use crate::{app_state::AppState, errors::AppError};
+use axum::{
+    Form,
+    extract::{Path, State},
+    response::{
+        Html, IntoResponse, Redirect, Response,
+        sse::{Event, KeepAlive, Sse},
+    },
+};
+use bigdecimal::BigDecimal;
+use maud::Markup;
+use serde::Deserialize;
+use sqlx::PgPool;
+use std::convert::Infallible;
+use tokio_stream::{Stream, StreamExt, wrappers::BroadcastStream};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Nazwa zdarzenia SSE z odświeżonym czatem transmisji.
+const TIPS_EVENT: &str = "tips";
+
+/// Ile ostatnich napiwków pokazuje czat.
+const FEED_LIMIT: i64 = 30;
+
+#[derive(Deserialize, Default)]
+pub struct TipMenuItemPayload {
+    pub menu_label: String,
+    pub menu_price: String,
+}
+
+impl Validate for TipMenuItemPayload {
+    fn normalize(&mut self) {
+        self.menu_label = self.menu_label.trim().to_string();
+        self.menu_price = self.menu_price.trim().to_string();
+    }
+
+    fn validate(&self) -> Result<(), FormErrors> {
+        let mut errors = FormErrors::default();
+        let label_len = self.menu_label.chars().count();
+        if label_len == 0 || label_len > validation::TIP_LABEL_MAX_LEN {
+            errors.add(
+                "menu_label",
+                format!(
+                    "Nazwa musi mieć od 1 do {} znaków.",
+                    validation::TIP_LABEL_MAX_LEN
+                ),
+            );
+        }
+        if !validation::pln_in_range(
+            &self.menu_price,
+            validation::TIP_MIN_PLN,
+            validation::TIP_MAX_PLN,
+        ) {
+            errors.add(
+                "menu_price",
+                format!(
+                    "Podaj cenę od {} do {} PLN.",
+                    validation::TIP_MIN_PLN,
+                    validation::TIP_MAX_PLN
+                ),
+            );
+        }
+        errors.into_result()
+    }
+}
+
+#[derive(Deserialize, Default)]
+pub struct TipGoalPayload {
+    pub goal_title: String,
+    pub goal_target: String,
+}
+
+impl Validate for TipGoalPayload {
+    fn normalize(&mut self) {
+        self.goal_title = self.goal_title.trim().to_string();
+        self.goal_target = self.goal_target.trim().to_string();
+    }
+
+    fn validate(&self) -> Result<(), FormErrors> {
+        let mut errors = FormErrors::default();
+        let title_len = self.goal_title.chars().count();
+        if title_len == 0 || title_len > validation::TIP_LABEL_MAX_LEN {
+            errors.add(
+                "goal_title",
+                format!(
+                    "Opis celu musi mieć od 1 do {} znaków.",
+                    validation::TIP_LABEL_MAX_LEN
+                ),
+            );
+        }
+        if !validation::pln_in_range(
+            &self.goal_target,
+            validation::TIP_MIN_PLN,
+            validation::GOAL_MAX_PLN,
+        ) {
+            errors.add(
+                "goal_target",
+                format!(
+                    "Podaj kwotę od {} do {} PLN.",
+                    validation::TIP_MIN_PLN,
+                    validation::GOAL_MAX_PLN
+                ),
+            );
+        }
+        errors.into_result()
+    }
+}
+
+#[derive(Deserialize, Default)]
+pub struct TipPayload {
+    /// Pozycja z menu; pusta oznacza dowolną kwotę z pola `amount`.
+    #[serde(default)]
+    pub menu_item_id: String,
+    #[serde(default)]
+    pub amount: String,
+    #[serde(default)]
+    pub message: String,
+}
+
+impl Validate for TipPayload {
+    fn normalize(&mut self) {
+        self.menu_item_id = self.menu_item_id.trim().to_string();
+        self.amount = self.amount.trim().to_string();
+        self.message = self.message.trim().to_string();
+    }
+
+    fn validate(&self) -> Result<(), FormErrors> {
+        let mut errors = FormErrors::default();
+        // Przy pozycji z menu cenę bierzemy z menu, a nie z formularza
+        if self.menu_item_id.is_empty()
+            && !validation::pln_in_range(
+                &self.amount,
+                validation::TIP_MIN_PLN,
+                validation::TIP_MAX_PLN,
+            )
+        {
+            errors.add(
+                "amount",
+                format!(
+                    "Podaj kwotę od {} do {} PLN.",
+                    validation::TIP_MIN_PLN,
+                    validation::TIP_MAX_PLN
+                ),
+            );
+        }
+        if self.message.chars().count() > validation::TIP_MESSAGE_MAX_LEN {
+            errors.add(
+                "message",
+                format!(
+                    "Wiadomość może mieć najwyżej {} znaków.",
+                    validation::TIP_MESSAGE_MAX_LEN
+                ),
+            );
+        }
+        errors.into_result()
+    }
+}
+
+/// Menu napiwków i cel zbiórki w panelu kamerki.
+pub fn render_tip_settings(
+    menu: &[TipMenuItem],
+    goal: Option<&TipGoal>,
+    forms: &StreamPanelForms,
+    errors: &FormErrors,
+) -> Markup {
+    let input_class = "w-full px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white focus:outline-none focus:ring-2 focus:ring-blue-500";
+    let label_class = "block text-gray-300 text-sm font-bold mb-2";
+    maud::html! {
+        div class="bg-gray-800 p-6 rounded-lg shadow-lg" {
+            h2 class="text-xl font-semibold text-white mb-4" { "Menu napiwków" }
+            @if menu.is_empty() {
+                p class="text-gray-400 mb-4" { "Dodaj akcje, za które widzowie mogą dać napiwek." }
+            } @else {
+                div class="divide-y divide-gray-700 mb-4" {
+                    @for item in menu {
+                        div class="flex justify-between items-center gap-2 py-2" {
+                            span class="text-white" { (item.label) }
+                            div class="flex items-center gap-3" {
+                                span class="text-green-400 whitespace-nowrap" { (item.price_pln.with_scale(2)) " PLN" }
+                                form action=(format!("/panel/stream/menu/{}/delete", item.id)) method="post" {
+                                    button type="submit" class="text-red-400 hover:text-red-300 text-sm" { "Usuń" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            @if (menu.len() as i64) < MAX_MENU_ITEMS {
+                form action="/panel/stream/menu" method="post" class="grid grid-cols-1 sm:grid-cols-3 gap-4 items-start" {
+                    div class="sm:col-span-2" {
+                        input type="text" name="menu_label" value=(forms.menu_item.menu_label) placeholder="Nazwa akcji" required
+                              maxlength=(validation::TIP_LABEL_MAX_LEN) class=(input_class);
+                        (layout::field_error(errors, "menu_label"))
+                    }
+                    div {
+                        input type="number" name="menu_price" value=(forms.menu_item.menu_price) placeholder="PLN" required
+                              min=(validation::TIP_MIN_PLN) max=(validation::TIP_MAX_PLN) step="0.01" class=(input_class);
+                        (layout::field_error(errors, "menu_price"))
+                    }
+                    button type="submit" class="sm:col-span-3 bg-blue-600 hover:bg-blue-700 text-white font-bold py-2 px-4 rounded-md" { "Dodaj do menu" }
+                }
+            }
+        }
+
+        div class="bg-gray-800 p-6 rounded-lg shadow-lg" {
+            h2 class="text-xl font-semibold text-white mb-4" { "Cel zbiórki" }
+            @if let Some(goal) = goal {
+                div class="mb-4" {
+                    (render_goal(goal))
+                    form action="/panel/stream/goal/delete" method="post" class="mt-2 text-right" {
+                        button type="submit" class="text-red-400 hover:text-red-300 text-sm" { "Usuń cel" }
+                    }
+                }
+            }
+            form action="/panel/stream/goal" method="post" class="space-y-4" {
+                div {
+                    label for="goal_title" class=(label_class) { "Na co zbierasz:" }
+                    input type="text" id="goal_title" name="goal_title" value=(forms.goal.goal_title) required
+                          maxlength=(validation::TIP_LABEL_MAX_LEN) class=(input_class);
+                    (layout::field_error(errors, "goal_title"))
+                }
+                div {
+                    label for="goal_target" class=(label_class) { "Kwota (PLN):" }
+                    input type="number" id="goal_target" name="goal_target" value=(forms.goal.goal_target) required
+                          min=(validation::TIP_MIN_PLN) max=(validation::GOAL_MAX_PLN) step="0.01" class=(input_class);
+                    (layout::field_error(errors, "goal_target"))
+                }
+                p class="text-gray-500 text-sm" { "Nowy cel zastępuje obecny, a postęp liczy się od zera." }
+                button type="submit" class="bg-blue-600 hover:bg-blue-700 text-white font-bold py-2 px-4 rounded-md" {
+                    @if goal.is_some() { "Ustaw nowy cel" } @else { "Ustaw cel" }
+                }
+            }
+        }
+    }
+}
+
+async fn stream_panel_errors(
+    current: &CurrentErika,
+    forms: &StreamPanelForms,
+    errors: &FormErrors,
+    state: &AppState,
+) -> Result<Response, AppError> {
+    let page = stream_handlers::render_stream_panel(current, forms, errors, state).await?;
+    Ok(form_errors_response(page))
+}
+
+pub async fn add_menu_item(
+    current: CurrentErika,
+    State(state): State<AppState>,
+    Form(mut payload): Form<TipMenuItemPayload>,
+) -> Result<Response, AppError> {
+    current.ensure_approved()?;
+    payload.normalize();
+    let mut errors = match payload.validate() {
+        Ok(()) => FormErrors::default(),
+        Err(errors) => errors,
+    };
+    if TipMenuItem::count_by_erika_id(current.id(), &state.db).await? >= MAX_MENU_ITEMS {
+        errors.add(
+            "menu_label",
+            format!("Menu może mieć najwyżej {} pozycji.", MAX_MENU_ITEMS),
+        );
+    }
+    if !errors.is_empty() {
+        let forms = StreamPanelForms {
+            menu_item: payload,
+            ..StreamPanelForms::default()
+        };
+        return stream_panel_errors(&current, &forms, &errors, &state).await;
+    }
+
+    // Kwota jest już zwalidowana
+    let price = validation::parse_pln(&payload.menu_price).unwrap_or_default();
+    TipMenuItem::create(current.id(), &payload.menu_label, &price, &state.db).await?;
+    info!("Modelka {} dodała pozycję menu napiwków", current.id());
+    Ok(Redirect::to("/panel/stream").into_response())
+}
+
+pub async fn delete_menu_item(
+    Path(item_id): Path<Uuid>,
+    current: CurrentErika,
+    State(state): State<AppState>,
+) -> Result<Redirect, AppError> {
+    if !TipMenuItem::delete(item_id, current.id(), &state.db).await? {
+        return Err(AppError::NotFound);
+    }
+    Ok(Redirect::to("/panel/stream"))
+}
+
+pub async fn set_goal(
+    current: CurrentErika,
+    State(state): State<AppState>,
+    Form(mut payload): Form<TipGoalPayload>,
+) -> Result<Response, AppError> {
+    current.ensure_approved()?;
+    payload.normalize();
+    if let Err(errors) = payload.validate() {
+        let forms = StreamPanelForms {
+            goal: payload,
+            ..StreamPanelForms::default()
+        };
+        return stream_panel_errors(&current, &forms, &errors, &state).await;
+    }
+
+    let target = validation::parse_pln(&payload.goal_target).unwrap_or_default();
+    TipGoal::set(current.id(), &payload.goal_title, &target, &state.db).await?;
+    info!("Modelka {} ustawiła cel zbiórki", current.id());
+    Ok(Redirect::to("/panel/stream").into_response())
+}
+
+pub async fn clear_goal(
+    current: CurrentErika,
+    State(state): State<AppState>,
+) -> Result<Redirect, AppError> {
+    TipGoal::clear(current.id(), &state.db).await?;
+    Ok(Redirect::to("/panel/stream"))
+}
+
+/// Napiwek od widza. Odpowiedź to fragment HTML z wynikiem pod formularzem
+/// (HTMX); czat i cel odświeżają się wszystkim przez SSE.
+pub async fn send_tip(
+    Path(username): Path<String>,
+    viewer: CurrentAccount,
+    State(state): State<AppState>,
+    Form(mut payload): Form<TipPayload>,
+) -> Result<Html<String>, AppError> {
+    let erika = Erika::find_by_public_username(&username, &state.db)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    if erika.id == viewer.0.id {
+        return Ok(tip_result(false, "Nie możesz dać napiwku samej sobie."));
+    }
+    let Some(session) = StreamSession::find_live(erika.id, &state.db).await? else {
+        return Ok(tip_result(false, "Transmisja już się zakończyła."));
+    };
+    if session.is_private {
+        return Ok(tip_result(false, "Modelka jest teraz w pokazie prywatnym."));
+    }
+
+    payload.normalize();
+    if let Err(errors) = payload.validate() {
+        let message = errors
+            .get("amount")
+            .or_else(|| errors.get("message"))
+            .unwrap_or("Niepoprawny napiwek.");
+        return Ok(tip_result(false, message));
+    }
+
+    // Pozycja z menu ma ustaloną cenę - kwoty z formularza wtedy nie bierzemy pod uwagę
+    let (label, amount) = match Uuid::parse_str(&payload.menu_item_id) {
+        Ok(item_id) => {
+            let item = TipMenuItem::find_by_id_and_erika_id(item_id, erika.id, &state.db)
+                .await?
+                .ok_or(AppError::NotFound)?;
+            (Some(item.label), item.price_pln)
+        }
+        Err(_) => (
+            None,
+            validation::parse_pln(&payload.amount).unwrap_or_default(),
+        ),
+    };
+
+    let new_tip = NewTip {
+        erika_id: erika.id,
+        tipper_id: viewer.0.id,
+        stream_session_id: session.id,
+        label: label.as_deref(),
+        message: Some(payload.message.as_str()).filter(|m| !m.is_empty()),
+        amount_pln: &amount,
+        commission_percent: state.settings().commission_percent,
+    };
+    let Some(tip_id) = Tip::create(new_tip, &state.db).await? else {
+        return Ok(tip_result(false, "Za mało środków na saldzie."));
+    };
+
+    state.publish(CreatorEvent::Tip {
+        erika_id: erika.id,
+        tip_id,
+    });
+    info!(
+        "Konto {} dało napiwek {} modelce {}",
+        viewer.0.id, tip_id, erika.id
+    );
+    Ok(tip_result(
+        true,
+        &format!("Dziękujemy! Wysłano napiwek {} PLN.", amount.with_scale(2)),
+    ))
+}
+
+fn tip_result(ok: bool, message: &str) -> Html<String> {
+    Html(
+        maud::html! {
+            p class={ "text-sm mt-2 " @if ok { "text-green-400" } @else { "text-red-400" } } { (message) }
+        }
+        .into_string(),
+    )
+}
+
+/// Czat transmisji: cel zbiórki i ostatnie napiwki. Bez trwającej transmisji pusty.
+pub async fn render_live_feed(erika_id: Uuid, db: &PgPool) -> Result<Markup, sqlx::Error> {
+    let goal = TipGoal::find_by_erika_id(erika_id, db).await?;
+    let tips = match StreamSession::find_live(erika_id, db).await? {
+        Some(session) => Tip::find_recent_for_session(session.id, FEED_LIMIT, db).await?,
+        None => Vec::new(),
+    };
+    Ok(maud::html! {
+        @if let Some(goal) = &goal {
+            div class="mb-3" { (render_goal(goal)) }
+        }
+        @if tips.is_empty() {
+            p class="text-gray-400 text-sm" { "Tu pojawią się napiwki od widzów." }
+        } @else {
+            ul class="space-y-2" {
+                @for tip in &tips {
+                    (render_tip_entry(tip))
+                }
+            }
+        }
+    })
+}
+
+fn render_tip_entry(tip: &TipEntry) -> Markup {
+    maud::html! {
+        li class="text-sm" {
+            span class="text-purple-300 font-semibold" { (tip.tipper_username.as_deref().unwrap_or("usunięte konto")) }
+            " dał(a) napiwek "
+            span class="text-green-400 font-semibold" { (tip.amount_pln.with_scale(2)) " PLN" }
+            @if let Some(label) = &tip.label {
+                " - " span class="text-white" { (label) }
+            }
+            @if let Some(message) = &tip.message {
+                span class="block text-gray-300" { (message) }
+            }
+        }
+    }
+}
+
+fn render_goal(goal: &TipGoal) -> Markup {
+    let percent = goal.percent();
+    maud::html! {
+        div {
+            div class="flex justify-between text-sm mb-1" {
+                span class="text-white font-semibold" { "Cel: " (goal.title) }
+                span class="text-gray-300" {
+                    (goal.raised_pln.with_scale(2)) " / " (goal.target_pln.with_scale(2)) " PLN"
+                }
+            }
+            div class="w-full bg-gray-600 rounded-full h-3" {
+                div class="bg-purple-500 h-3 rounded-full" style=(format!("width: {}%", percent)) {}
+            }
+        }
+    }
+}
+
+/// Czat transmisji podłączony do SSE - w panelu kamerki i na profilu modelki.
+pub fn live_feed_container(erika_id: Uuid, feed: Markup) -> Markup {
+    maud::html! {
+        div hx-ext="sse" sse-connect=(format!("/events/tips/{}", erika_id)) {
+            div sse-swap=(TIPS_EVENT) { (feed) }
+        }
+    }
+}
+
+/// Okienko napiwków na profilu modelki w czasie transmisji.
+pub fn render_tip_box(
+    username: &str,
+    menu: &[TipMenuItem],
+    balance: Option<&BigDecimal>,
+) -> Markup {
+    let tip_url = format!("/pay/tip/{}", username);
+    let input_class = "w-full px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white focus:outline-none focus:ring-2 focus:ring-blue-500";
+    maud::html! {
+        @match balance {
+            None => p class="text-gray-400" {
+                a href="/login" class="text-blue-400 hover:underline" { "Zaloguj się" } ", aby dać napiwek."
+            },
+            Some(balance) => div {
+                p class="text-gray-400 text-sm mb-3" { "Twoje saldo: " span class="text-white" { (balance.with_scale(2)) " PLN" } }
+                @if !menu.is_empty() {
+                    div class="flex flex-wrap gap-2 mb-4" {
+                        @for item in menu {
+                            form hx-post=(tip_url) hx-target="#tip-result" {
+                                input type="hidden" name="menu_item_id" value=(item.id);
+                                button type="submit" class="bg-purple-600 hover:bg-purple-700 text-white text-sm py-1 px-3 rounded-md" {
+                                    (item.label) " · " (item.price_pln.with_scale(2)) " PLN"
+                                }
+                            }
+                        }
+                    }
+                }
+                form hx-post=(tip_url) hx-target="#tip-result" class="grid grid-cols-1 sm:grid-cols-4 gap-2" {
+                    input type="number" name="amount" placeholder="PLN" required
+                          min=(validation::TIP_MIN_PLN) max=(validation::TIP_MAX_PLN) step="0.01" class=(input_class);
+                    input type="text" name="message" placeholder="Wiadomość (opcjonalnie)"
+                          maxlength=(validation::TIP_MESSAGE_MAX_LEN) class={ (input_class) " sm:col-span-2" };
+                    button type="submit" class="bg-green-600 hover:bg-green-700 text-white font-bold py-2 px-4 rounded-md" { "Daj napiwek" }
+                }
+                div id="tip-result" {}
+            },
+        }
+    }
+}
+
+// Strumień SSE czatu transmisji: po każdym napiwku dla tej modelki wysyła
+// świeżo wyrenderowany czat z celem zbiórki.
+pub async fn tip_events(
+    Path(erika_id): Path<Uuid>,
+    State(state): State<AppState>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let events = BroadcastStream::new(state.creator_events.subscribe());
+    let stream = events
+        // `Lagged` też odświeża czat - pominięte napiwki i tak są w bazie
+        .filter(move |event| match event {
+            Ok(CreatorEvent::Tip {
+                erika_id: tipped, ..
+            }) => *tipped == erika_id,
+            Ok(_) => false,
+            Err(_) => true,
+        })
+        .then(move |_event| {
+            let state = state.clone();
+            async move {
+                match render_live_feed(erika_id, &state.db).await {
+                    Ok(feed) => Some(Event::default().event(TIPS_EVENT).data(feed.into_string())),
+                    Err(e) => {
+                        warn!("Nie udało się odświeżyć czatu transmisji: {}", e);
+                        None
+                    }
+                }
+            }
+        })
+        .filter_map(|event| event.map(Ok));
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
@@ -0,0 +1,88 @@
+// src/handlers/upload_handlers.rs
+
+// Pliki z `/uploads`. Nie wystawiamy katalogu w całości: zdjęcie galerii
+// dostaje każdy dopiero, gdy jest publicznie widoczne (po akceptacji, bez
+// ukrycia decyzją moderacji), wcześniej tylko autorka i zespół moderacji.
+// Zdjęcia profilowe są publiczne.
+
+use crate::auth::OptionalViewer;
+use crate::models::erika::Erika;
+use crate::models::permission::{self, Permission};
+use crate::models::photo::{self, Photo};
+use crate::{app_state::AppState, errors::AppError};
+use axum::{
+    extract::{Path, State},
+    http::header,
+    response::{IntoResponse, Response},
+};
+use tokio::fs;
+use tracing::warn;
+
+pub async fn serve_upload(
+    Path(file_name): Path<String>,
+    OptionalViewer(viewer): OptionalViewer,
+    State(state): State<AppState>,
+) -> Result<Response, AppError> {
+    if file_name.contains(['/', '\\']) || file_name.starts_with('.') {
+        return Err(AppError::NotFound);
+    }
+    let file_url = format!("/uploads/{}", file_name);
+
+    let (path, cache_control) = match Photo::find_access(&file_url, &state.db).await? {
+        Some(access) if access.is_public => (
+            photo::disk_path(&file_url, access.status),
+            "public, max-age=3600",
+        ),
+        Some(access) => {
+            let allowed = viewer.is_some_and(|viewer| {
+                viewer.id == access.erika_id
+                    || permission::permissions_for(&viewer.role)
+                        .contains(&Permission::ModerateContent)
+            });
+            if !allowed {
+                return Err(AppError::NotFound);
+            }
+            (
+                photo::disk_path(&file_url, access.status),
+                "private, no-store",
+            )
+        }
+        None if Erika::is_avatar(&file_url, &state.db).await? => (
+            std::path::Path::new(photo::UPLOADS_DIR).join(&file_name),
+            "public, max-age=3600",
+        ),
+        None => return Err(AppError::NotFound),
+    };
+
+    let bytes = fs::read(&path).await.map_err(|e| {
+        warn!("Brak pliku {}: {}", path.display(), e);
+        AppError::NotFound
+    })?;
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type(&file_name)),
+            (header::CACHE_CONTROL, cache_control),
+            (header::X_CONTENT_TYPE_OPTIONS, "nosniff"),
+        ],
+        bytes,
+    )
+        .into_response())
+}
+
+/// Typ pliku po rozszerzeniu. Nieznane wysyłamy jako dane binarne, żeby
+/// przeglądarka nie wykonała np. wgranego HTML-a.
+fn content_type(file_name: &str) -> &'static str {
+    let extension = file_name
+        .rsplit('.')
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    match extension.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "avif" => "image/avif",
+        _ => "application/octet-stream",
+    }
+}
@@ -0,0 +1,513 @@
+// src/handlers/verification_handlers.rs
+
+// Weryfikacja tożsamości i wieku (KYC). Skany dokumentów i selfie trafiają
+// do katalogu prywatnego, którego nie serwuje `/uploads` - podgląd ma tylko
+// admin, przez handler sprawdzający uprawnienia.
+
+use super::erika_handlers::form_errors_response;
+use super::layout;
+use crate::auth::{ClientInfo, CurrentErika, CurrentStaff};
+use crate::models::audit::AuditAction;
+use crate::models::erika::Erika;
+use crate::models::verification::{
+    self, IdentityVerification, MIN_AGE, NewVerification, VerificationFile, VerificationStatus,
+};
+use crate::validation::FormErrors;
+use crate::{app_state::AppState, errors::AppError};
+use axum::extract::{Multipart, Path};
+use axum::{
+    Form,
+    body::Bytes,
+    extract::State,
+    http::header,
+    response::{Html, IntoResponse, Redirect, Response},
+};
+use maud::Markup;
+use serde::Deserialize;
+use serde_json::json;
+use std::path::Path as FsPath;
+use time::{Date, OffsetDateTime, macros::format_description};
+use tokio::fs;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Katalog na dokumenty. Celowo poza `uploads`.
+pub const PRIVATE_DIR: &str = "private/kyc";
+
+/// Limit rozmiaru całego formularza (trzy zdjęcia z telefonu).
+pub const MAX_UPLOAD_BYTES: usize = 25 * 1024 * 1024;
+
+const REASON_MAX_LEN: usize = 500;
+
+// Strona weryfikacji w panelu modelki
+pub async fn show_verification_page(
+    current: CurrentErika,
+    State(state): State<AppState>,
+) -> Result<Html<String>, AppError> {
+    let page = render_verification_page(current.id(), &FormErrors::default(), &state).await?;
+    Ok(Html(page.into_string()))
+}
+
+async fn render_verification_page(
+    erika_id: Uuid,
+    errors: &FormErrors,
+    state: &AppState,
+) -> Result<Markup, AppError> {
+    let submissions = IdentityVerification::find_by_erika_id(erika_id, &state.db).await?;
+    let latest = submissions.first();
+    // Nowe zgłoszenie można wysłać tylko, gdy nic nie czeka i nic nie jest zaakceptowane
+    let can_submit = latest.is_none_or(|v| v.status == VerificationStatus::Rejected);
+    let file_class = "w-full text-sm text-gray-400 file:mr-4 file:py-2 file:px-4 file:rounded-full file:border-0 file:text-sm file:font-semibold file:bg-blue-600 file:text-white hover:file:bg-blue-700";
+
+    let content = maud::html! {
+        div class="max-w-2xl mx-auto" {
+            a href="/panel" class="inline-block mb-6 text-blue-400 hover:text-blue-300 transition-colors" {
+                "← Wróć do panelu"
+            }
+            h1 class="text-3xl font-bold text-white mb-2" { "Weryfikacja tożsamości" }
+            p class="text-gray-400 mb-6" {
+                "Zanim profil zostanie opublikowany, musimy potwierdzić, że masz ukończone "
+                (MIN_AGE) " lat i jesteś osobą z profilu. Dokumenty widzi tylko administrator."
+            }
+
+            @if let Some(latest) = latest {
+                div class="bg-gray-800 p-6 rounded-lg shadow-lg mb-6" {
+                    p class="text-white" { "Status: " strong { (latest.status.label()) } }
+                    p class="text-gray-400 text-sm" { "Wysłano: " (layout::format_datetime(latest.created_at)) }
+                    @if let Some(reason) = &latest.rejection_reason {
+                        p class="text-red-300 text-sm mt-2" { "Powód odrzucenia: " (reason) }
+                    }
+                }
+            }
+
+            @if can_submit {
+                form action="/panel/verification" method="post" enctype="multipart/form-data"
+                     class="bg-gray-800 p-6 rounded-lg shadow-lg space-y-5" {
+                    div {
+                        label for="date_of_birth" class="block text-gray-300 text-sm font-bold mb-2" { "Data urodzenia (jak w dokumencie):" }
+                        input type="date" id="date_of_birth" name="date_of_birth" required
+                              class="w-full px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white focus:outline-none focus:ring-2 focus:ring-blue-500";
+                        (layout::field_error(errors, "date_of_birth"))
+                    }
+                    div {
+                        label class="block text-gray-300 text-sm font-bold mb-2" { "Dokument tożsamości - przód:" }
+                        input type="file" name="document_front" required accept="image/png, image/jpeg" class=(file_class);
+                        (layout::field_error(errors, "document_front"))
+                    }
+                    div {
+                        label class="block text-gray-300 text-sm font-bold mb-2" { "Dokument tożsamości - tył (dowód osobisty):" }
+                        input type="file" name="document_back" accept="image/png, image/jpeg" class=(file_class);
+                        (layout::field_error(errors, "document_back"))
+                    }
+                    div {
+                        label class="block text-gray-300 text-sm font-bold mb-2" { "Selfie z dokumentem w dłoni:" }
+                        input type="file" name="selfie" required accept="image/png, image/jpeg" class=(file_class);
+                        (layout::field_error(errors, "selfie"))
+                    }
+                    button type="submit" class="w-full bg-green-600 hover:bg-green-700 text-white font-bold py-2 px-4 rounded-md" {
+                        "Wyślij do weryfikacji"
+                    }
+                }
+            }
+        }
+    };
+    Ok(layout::page("Weryfikacja tożsamości", content))
+}
+
+// Przyjmuje zgłoszenie z dokumentami
+pub async fn submit_verification(
+    current: CurrentErika,
+    State(state): State<AppState>,
+    mut multipart: Multipart,
+) -> Result<Response, AppError> {
+    let erika_id = current.id();
+
+    let mut date_of_birth = String::new();
+    let mut files: Vec<(VerificationFile, String, Bytes)> = Vec::new();
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|_| AppError::InternalServerError)?
+    {
+        let name = field.name().unwrap_or("").to_string();
+        let file_name = field.file_name().unwrap_or("").to_string();
+        let data = field
+            .bytes()
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        let kind = match name.as_str() {
+            "date_of_birth" => {
+                date_of_birth = String::from_utf8(data.to_vec()).unwrap_or_default();
+                continue;
+            }
+            "document_front" => VerificationFile::DocumentFront,
+            "document_back" => VerificationFile::DocumentBack,
+            "selfie" => VerificationFile::Selfie,
+            _ => continue,
+        };
+        if !data.is_empty() {
+            let extension = FsPath::new(&file_name)
+                .extension()
+                .and_then(|s| s.to_str())
+                .unwrap_or("")
+                .to_lowercase();
+            files.push((kind, extension, data));
+        }
+    }
+
+    let mut errors = FormErrors::default();
+    let parsed_date = Date::parse(
+        date_of_birth.trim(),
+        format_description!("[year]-[month]-[day]"),
+    )
+    .ok();
+    match parsed_date {
+        None => errors.add("date_of_birth", "Podaj poprawną datę urodzenia."),
+        Some(date) if verification::age_on(date, OffsetDateTime::now_utc().date()) < MIN_AGE => {
+            errors.add(
+                "date_of_birth",
+                format!("Musisz mieć ukończone {} lat.", MIN_AGE),
+            )
+        }
+        Some(_) => {}
+    }
+    for (kind, field) in [
+        (VerificationFile::DocumentFront, "document_front"),
+        (VerificationFile::Selfie, "selfie"),
+    ] {
+        if !files.iter().any(|(k, _, _)| *k == kind) {
+            errors.add(field, "To zdjęcie jest wymagane.");
+        }
+    }
+    for (kind, extension, _) in &files {
+        if !matches!(extension.as_str(), "png" | "jpg" | "jpeg") {
+            let field = match kind {
+                VerificationFile::DocumentFront => "document_front",
+                VerificationFile::DocumentBack => "document_back",
+                VerificationFile::Selfie => "selfie",
+            };
+            errors.add(field, "Zdjęcie musi być w formacie PNG lub JPG.");
+        }
+    }
+    let has_open = IdentityVerification::find_by_erika_id(erika_id, &state.db)
+        .await?
+        .first()
+        .is_some_and(|v| v.status != VerificationStatus::Rejected);
+    if has_open {
+        errors.add(
+            "document_front",
+            "Masz już zgłoszenie w trakcie sprawdzania lub zaakceptowane.",
+        );
+    }
+    let Some(date_of_birth) = parsed_date.filter(|_| errors.is_empty()) else {
+        let page = render_verification_page(erika_id, &errors, &state).await?;
+        return Ok(form_errors_response(page));
+    };
+
+    // Zapisujemy pliki dopiero po udanej walidacji
+    let dir = format!("{}/{}", PRIVATE_DIR, erika_id);
+    fs::create_dir_all(&dir)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    let mut paths: Vec<(VerificationFile, String)> = Vec::new();
+    for (kind, extension, data) in files {
+        let path = format!("{}/{}_{}.{}", dir, kind.slug(), Uuid::new_v4(), extension);
+        fs::write(&path, &data)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+        paths.push((kind, path));
+    }
+    let path_of = |kind: VerificationFile| {
+        paths
+            .iter()
+            .find(|(k, _)| *k == kind)
+            .map(|(_, p)| p.as_str())
+    };
+
+    let created = IdentityVerification::create(
+        NewVerification {
+            erika_id,
+            date_of_birth,
+            document_front_path: path_of(VerificationFile::DocumentFront).unwrap_or_default(),
+            document_back_path: path_of(VerificationFile::DocumentBack),
+            selfie_path: path_of(VerificationFile::Selfie).unwrap_or_default(),
+        },
+        &state.db,
+    )
+    .await;
+
+    if let Err(e) = created {
+        // Np. równoległe drugie zgłoszenie - nie zostawiamy osieroconych plików
+        for (_, path) in &paths {
+            let _ = fs::remove_file(path).await;
+        }
+        return Err(e.into());
+    }
+
+    info!("Erika {} wysłała zgłoszenie weryfikacyjne", erika_id);
+    Ok(Redirect::to("/panel/verification").into_response())
+}
+
+// Kolejka zgłoszeń dla admina
+pub async fn show_verification_queue(
+    State(state): State<AppState>,
+) -> Result<Html<String>, AppError> {
+    let pending = IdentityVerification::find_pending(&state.db).await?;
+    let today = OffsetDateTime::now_utc().date();
+
+    let content = maud::html! {
+        a href="/admin" class="inline-block mb-6 text-blue-400 hover:text-blue-300 transition-colors" {
+            "← Wróć do panelu"
+        }
+        h1 class="text-3xl font-bold text-white mb-6" { "Weryfikacja tożsamości" }
+        @if pending.is_empty() {
+            p class="text-gray-400" { "Brak zgłoszeń do sprawdzenia." }
+        } @else {
+            div class="bg-gray-800 rounded-lg shadow-lg" {
+                ul {
+                    @for item in &pending {
+                        li class="p-4 border-b border-gray-700 flex justify-between items-center" {
+                            div {
+                                span class="text-white" { (item.username) }
+                                span class="ml-4 text-gray-400 text-sm" {
+                                    "wiek: " (verification::age_on(item.date_of_birth, today))
+                                    " · wysłano " (layout::format_datetime(item.created_at))
+                                }
+                            }
+                            a href=(format!("/admin/verifications/{}", item.id)) class="bg-blue-600 hover:bg-blue-700 text-white font-bold py-1 px-3 rounded-md text-sm" {
+                                "Sprawdź"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    };
+    Ok(Html(
+        layout::page("Weryfikacja tożsamości", content).into_string(),
+    ))
+}
+
+// Ekran porównania: dokument i selfie obok siebie
+pub async fn show_verification_review(
+    Path(verification_id): Path<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Html<String>, AppError> {
+    let page = render_review_page(verification_id, &FormErrors::default(), &state).await?;
+    Ok(Html(page.into_string()))
+}
+
+async fn render_review_page(
+    verification_id: Uuid,
+    errors: &FormErrors,
+    state: &AppState,
+) -> Result<Markup, AppError> {
+    let verification = IdentityVerification::find_by_id(verification_id, &state.db)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    let erika = Erika::find_by_id(verification.erika_id, &state.db)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    let age = verification.age();
+    let file_url = |file: VerificationFile| {
+        format!(
+            "/admin/verifications/{}/files/{}",
+            verification.id,
+            file.slug()
+        )
+    };
+
+    let content = maud::html! {
+        a href="/admin/verifications" class="inline-block mb-6 text-blue-400 hover:text-blue-300 transition-colors" {
+            "← Wróć do kolejki"
+        }
+        h1 class="text-3xl font-bold text-white mb-2" {
+            "Weryfikacja: "
+            a href=(format!("/admin/erika/{}", erika.id)) class="text-blue-400 hover:underline" { (erika.username) }
+        }
+        p class="text-gray-300 mb-6" {
+            "Data urodzenia: " strong { (verification.date_of_birth.to_string()) }
+            " · wiek: "
+            @if age >= MIN_AGE {
+                strong class="text-green-400" { (age) }
+            } @else {
+                strong class="text-red-400" { (age) " - niepełnoletnia" }
+            }
+            " · status: " (verification.status.label())
+        }
+
+        div class="grid grid-cols-1 md:grid-cols-3 gap-4 mb-6" {
+            @for (file, title) in [
+                (VerificationFile::DocumentFront, "Dokument - przód"),
+                (VerificationFile::DocumentBack, "Dokument - tył"),
+                (VerificationFile::Selfie, "Selfie z dokumentem"),
+            ] {
+                div class="bg-gray-800 rounded-lg p-2" {
+                    p class="text-gray-400 text-sm mb-2" { (title) }
+                    @if verification.file_path(file).is_some() {
+                        a href=(file_url(file)) target="_blank" {
+                            img src=(file_url(file)) alt=(title) class="w-full max-h-[32rem] object-contain bg-black rounded";
+                        }
+                    } @else {
+                        p class="text-gray-500 text-sm" { "Nie dołączono." }
+                    }
+                }
+            }
+        }
+
+        @if verification.status == VerificationStatus::Pending {
+            div class="flex flex-col md:flex-row gap-6" {
+                form action=(format!("/admin/verifications/{}/approve", verification.id)) method="post" {
+                    button type="submit" disabled[age < MIN_AGE]
+                           class="bg-green-600 hover:bg-green-700 disabled:opacity-50 text-white font-bold py-2 px-4 rounded-md" {
+                        "Tożsamość i wiek potwierdzone"
+                    }
+                    (layout::field_error(errors, "approve"))
+                }
+                form action=(format!("/admin/verifications/{}/reject", verification.id)) method="post" class="flex-1 flex gap-2 items-start" {
+                    div class="flex-1" {
+                        input type="text" name="reason" required maxlength=(REASON_MAX_LEN) placeholder="Powód odrzucenia (widoczny dla modelki)"
+                              class="w-full px-3 py-2 bg-gray-700 border border-gray-600 rounded-md text-white focus:outline-none focus:ring-2 focus:ring-red-500";
+                        (layout::field_error(errors, "reason"))
+                    }
+                    button type="submit" class="bg-red-600 hover:bg-red-700 text-white font-bold py-2 px-4 rounded-md" { "Odrzuć" }
+                }
+            }
+        } @else if let Some(reviewed_at) = verification.reviewed_at {
+            p class="text-gray-400" { "Rozpatrzono: " (layout::format_datetime(reviewed_at)) }
+        }
+    };
+    Ok(layout::page("Weryfikacja tożsamości", content))
+}
+
+// Podgląd pliku ze zgłoszenia - tylko dla admina, bez cache w przeglądarce
+pub async fn show_verification_file(
+    Path((verification_id, slug)): Path<(Uuid, String)>,
+    State(state): State<AppState>,
+) -> Result<Response, AppError> {
+    let file = VerificationFile::from_slug(&slug).ok_or(AppError::NotFound)?;
+    let verification = IdentityVerification::find_by_id(verification_id, &state.db)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    let path = verification.file_path(file).ok_or(AppError::NotFound)?;
+    let bytes = fs::read(path).await.map_err(|e| {
+        warn!("Brak pliku weryfikacji {}: {}", path, e);
+        AppError::NotFound
+    })?;
+
+    let content_type = if path.ends_with(".png") {
+        "image/png"
+    } else {
+        "image/jpeg"
+    };
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type),
+            (header::CACHE_CONTROL, "no-store"),
+        ],
+        bytes,
+    )
+        .into_response())
+}
+
+#[derive(Deserialize)]
+pub struct RejectVerificationPayload {
+    pub reason: String,
+}
+
+pub async fn approve_verification(
+    Path(verification_id): Path<Uuid>,
+    admin: CurrentStaff,
+    client: ClientInfo,
+    State(state): State<AppState>,
+) -> Result<Response, AppError> {
+    let verification = IdentityVerification::find_by_id(verification_id, &state.db)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    if verification.age() < MIN_AGE {
+        let mut errors = FormErrors::default();
+        errors.add(
+            "approve",
+            "Osoba niepełnoletnia - zgłoszenie trzeba odrzucić.",
+        );
+        let page = render_review_page(verification_id, &errors, &state).await?;
+        return Ok(form_errors_response(page));
+    }
+
+    if IdentityVerification::review(
+        verification_id,
+        VerificationStatus::Verified,
+        None,
+        admin.id(),
+        &state.db,
+    )
+    .await?
+    {
+        admin
+            .audit(
+                AuditAction::VerificationApproved,
+                "erika",
+                Some(verification.erika_id),
+                json!({ "verification_id": verification_id }),
+                &client,
+                &state.db,
+            )
+            .await?;
+        info!(
+            "Admin {} potwierdził tożsamość konta {}",
+            admin.0.username, verification.erika_id
+        );
+    }
+    Ok(Redirect::to(&format!("/admin/erika/{}", verification.erika_id)).into_response())
+}
+
+pub async fn reject_verification(
+    Path(verification_id): Path<Uuid>,
+    admin: CurrentStaff,
+    client: ClientInfo,
+    State(state): State<AppState>,
+    Form(payload): Form<RejectVerificationPayload>,
+) -> Result<Response, AppError> {
+    let verification = IdentityVerification::find_by_id(verification_id, &state.db)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    let reason = payload.reason.trim();
+    if reason.is_empty() || reason.chars().count() > REASON_MAX_LEN {
+        let mut errors = FormErrors::default();
+        errors.add(
+            "reason",
+            format!("Podaj powód (najwyżej {} znaków).", REASON_MAX_LEN),
+        );
+        let page = render_review_page(verification_id, &errors, &state).await?;
+        return Ok(form_errors_response(page));
+    }
+
+    if IdentityVerification::review(
+        verification_id,
+        VerificationStatus::Rejected,
+        Some(reason),
+        admin.id(),
+        &state.db,
+    )
+    .await?
+    {
+        admin
+            .audit(
+                AuditAction::VerificationRejected,
+                "erika",
+                Some(verification.erika_id),
+                json!({ "verification_id": verification_id, "reason": reason }),
+                &client,
+                &state.db,
+            )
+            .await?;
+        info!(
+            "Admin {} odrzucił weryfikację konta {}",
+            admin.0.username, verification.erika_id
+        );
+    }
+    Ok(Redirect::to("/admin/verifications").into_response())
+}
@@ -0,0 +1,457 @@
+// src/jobs.rs
+
+// Worker zadań w tle dla wniosków RODO (eksport danych, usunięcie konta).
+// Zadania leżą w tabeli `account_jobs`, więc przetrwają restart serwera.
+
+use crate::{
+    app_state::AppState,
+    auth,
+    events::CreatorEvent,
+    models::{
+        account_job::{AccountJob, AccountJobKind},
+        admin_notice::AdminNotice,
+        audit::{AuditAction, AuditEvent, NewAuditEvent},
+        erika::Erika,
+        gallery::Gallery,
+        gallery_purchase::GalleryPurchase,
+        photo::Photo,
+        private_show::PrivateShow,
+        stream_session::StreamSession,
+        tip::Tip,
+        user_session::UserSession,
+        verification::IdentityVerification,
+        wallet::Wallet,
+    },
+};
+use serde_json::json;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::{fs, task};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+use zip::{ZipWriter, write::SimpleFileOptions};
+
+/// Katalog z gotowymi eksportami. Celowo poza `uploads`, który jest serwowany publicznie.
+pub const EXPORTS_DIR: &str = "exports";
+
+/// Co ile sprawdzamy, czy w kolejce czekają nowe zadania.
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Uruchamia worker w tle. Wywoływane raz przy starcie serwera.
+pub fn spawn_worker(state: AppState) {
+    tokio::spawn(async move {
+        match AccountJob::requeue_interrupted(&state.db).await {
+            Ok(0) => {}
+            Ok(n) => info!("Przywrócono do kolejki {} przerwanych zadań", n),
+            Err(e) => error!("Nie udało się przywrócić przerwanych zadań: {}", e),
+        }
+
+        loop {
+            match AccountJob::claim_next(&state.db).await {
+                Ok(Some(job)) => run_job(&state, job).await,
+                Ok(None) => tokio::time::sleep(POLL_INTERVAL).await,
+                Err(e) => {
+                    error!("Nie udało się pobrać zadania z kolejki: {}", e);
+                    tokio::time::sleep(POLL_INTERVAL).await;
+                }
+            }
+        }
+    });
+}
+
+async fn run_job(state: &AppState, job: AccountJob) {
+    info!("Start zadania {} ({})", job.id, job.kind);
+
+    let Some(erika_id) = job.erika_id else {
+        let _ = AccountJob::mark_failed(job.id, "Konto już nie istnieje", &state.db).await;
+        return;
+    };
+
+    let result = match job.kind {
+        AccountJobKind::Export => build_export(state, job.id, erika_id).await.map(Some),
+        AccountJobKind::Deletion => delete_account(state, erika_id).await.map(|_| None),
+    };
+
+    let saved = match result {
+        Ok(file_path) => {
+            info!("Zadanie {} zakończone", job.id);
+            AccountJob::mark_done(job.id, file_path.as_deref(), &state.db).await
+        }
+        Err(e) => {
+            error!("Zadanie {} nie powiodło się: {}", job.id, e);
+            AccountJob::mark_failed(job.id, &e, &state.db).await
+        }
+    };
+    if let Err(e) = saved {
+        error!("Nie udało się zapisać stanu zadania {}: {}", job.id, e);
+    }
+}
+
+/// Zamienia publiczny URL (`/uploads/plik.jpg`) na ścieżkę na dysku.
+fn upload_path(file_url: &str) -> PathBuf {
+    PathBuf::from(file_url.strip_prefix('/').unwrap_or(file_url))
+}
+
+/// Buduje archiwum ZIP ze wszystkimi danymi konta i zwraca ścieżkę do pliku.
+async fn build_export(state: &AppState, job_id: Uuid, erika_id: Uuid) -> Result<String, String> {
+    let db_err = |e: sqlx::Error| format!("Błąd bazy danych: {}", e);
+
+    let profile = Erika::export_profile(erika_id, &state.db)
+        .await
+        .map_err(db_err)?
+        .ok_or("Konto nie istnieje")?;
+
+    // Pliki do dołączenia: (ścieżka na dysku, ścieżka w archiwum)
+    let mut files: Vec<(PathBuf, String)> = Vec::new();
+    if let Some(avatar_url) = profile["profile_image_url"].as_str() {
+        let path = upload_path(avatar_url);
+        let name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("avatar");
+        files.push((path.clone(), format!("files/avatar/{}", name)));
+    }
+
+    let mut galleries = Vec::new();
+    for gallery in Gallery::find_by_erika_id(erika_id, &state.db)
+        .await
+        .map_err(db_err)?
+    {
+        let photos = Photo::find_by_gallery_id(gallery.id, &state.db)
+            .await
+            .map_err(db_err)?;
+        let mut photo_entries = Vec::new();
+        for photo in photos {
+            let path = photo.disk_path();
+            let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("photo");
+            let archive_path = format!("files/galleries/{}/{}", gallery.id, name);
+            files.push((path.clone(), archive_path.clone()));
+            photo_entries.push(json!({ "photo": photo, "archive_path": archive_path }));
+        }
+        galleries.push(json!({ "gallery": gallery, "photos": photo_entries }));
+    }
+
+    let sessions: Vec<_> =
+        UserSession::find_active_by_erika_id(erika_id, auth::SESSION_INACTIVITY_DAYS, &state.db)
+            .await
+            .map_err(db_err)?
+            .into_iter()
+            .map(|s| {
+                json!({
+                    "user_agent": s.user_agent,
+                    "ip_address": s.ip_address,
+                    "created_at": s.created_at.to_string(),
+                    "last_seen_at": s.last_seen_at.to_string(),
+                })
+            })
+            .collect();
+
+    let mut verifications = Vec::new();
+    for verification in IdentityVerification::find_by_erika_id(erika_id, &state.db)
+        .await
+        .map_err(db_err)?
+    {
+        for path in verification.file_paths() {
+            let path = PathBuf::from(path);
+            let name = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("document");
+            files.push((path.clone(), format!("files/verification/{}", name)));
+        }
+        verifications.push(json!({
+            "status": verification.status.to_string(),
+            "date_of_birth": verification.date_of_birth.to_string(),
+            "rejection_reason": verification.rejection_reason,
+            "created_at": verification.created_at.to_string(),
+        }));
+    }
+
+    let streams: Vec<_> = StreamSession::find_by_erika_id(erika_id, &state.db)
+        .await
+        .map_err(db_err)?
+        .into_iter()
+        .map(|s| {
+            json!({
+                "title": s.title,
+                "scheduled_at": s.scheduled_at.map(|t| t.to_string()),
+                "planned_minutes": s.planned_minutes,
+                "started_at": s.started_at.map(|t| t.to_string()),
+                "ended_at": s.ended_at.map(|t| t.to_string()),
+                "is_private": s.is_private,
+                "peak_viewers": s.peak_viewers,
+                "earnings_pln": s.earnings_pln.with_scale(2).to_string(),
+            })
+        })
+        .collect();
+
+    let tips: Vec<_> = Tip::find_by_account(erika_id, &state.db)
+        .await
+        .map_err(db_err)?
+        .into_iter()
+        .map(|t| {
+            json!({
+                "tipper": t.tipper_username,
+                "label": t.label,
+                "message": t.message,
+                "amount_pln": t.amount_pln.with_scale(2).to_string(),
+                "created_at": t.created_at.to_string(),
+            })
+        })
+        .collect();
+    let balance = Wallet::balance(erika_id, &state.db).await.map_err(db_err)?;
+    let transactions: Vec<_> = Wallet::find_transactions(erika_id, &state.db)
+        .await
+        .map_err(db_err)?
+        .into_iter()
+        .map(|t| {
+            json!({
+                "kind": t.kind.to_string(),
+                "amount_pln": t.amount_pln.with_scale(2).to_string(),
+                "balance_after_pln": t.balance_after_pln.with_scale(2).to_string(),
+                "note": t.note,
+                "created_at": t.created_at.to_string(),
+            })
+        })
+        .collect();
+    let wallet = json!({
+        "balance_pln": balance.with_scale(2).to_string(),
+        "transactions": transactions,
+        "tips": tips,
+    });
+
+    let private_shows: Vec<_> = PrivateShow::find_by_account(erika_id, &state.db)
+        .await
+        .map_err(db_err)?
+        .into_iter()
+        .map(|p| {
+            json!({
+                "creator": p.erika_username,
+                "viewer": p.viewer_username,
+                "status": p.status.to_string(),
+                "rate_pln": p.rate_pln.with_scale(2).to_string(),
+                "minutes_billed": p.minutes_billed,
+                "billed_pln": p.billed_pln.with_scale(2).to_string(),
+                "end_reason": p.end_reason.map(|r| r.to_string()),
+                "requested_at": p.requested_at.to_string(),
+                "started_at": p.started_at.map(|t| t.to_string()),
+                "ended_at": p.ended_at.map(|t| t.to_string()),
+            })
+        })
+        .collect();
+
+    let purchases: Vec<_> = GalleryPurchase::find_by_account(erika_id, &state.db)
+        .await
+        .map_err(db_err)?
+        .into_iter()
+        .map(|p| {
+            json!({
+                "role": if p.is_buyer { "buyer" } else { "seller" },
+                "gallery": p.gallery_name,
+                "seller": p.seller_username,
+                "buyer": p.buyer_username,
+                "amount_pln": p.amount_pln.with_scale(2).to_string(),
+                "commission_pln": p.commission_pln.with_scale(2).to_string(),
+                "paid_at": p.paid_at.to_string(),
+            })
+        })
+        .collect();
+
+    let messages: Vec<_> = AdminNotice::find_by_erika_id(erika_id, &state.db)
+        .await
+        .map_err(db_err)?
+        .into_iter()
+        .map(|n| {
+            json!({
+                "sender": n.sender_username,
+                "subject": n.subject,
+                "body": n.body,
+                "created_at": n.created_at.to_string(),
+                "read_at": n.read_at.map(|t| t.to_string()),
+            })
+        })
+        .collect();
+
+    let documents = vec![
+        ("profile.json", profile),
+        ("galleries.json", json!(galleries)),
+        ("sessions.json", json!(sessions)),
+        ("verification.json", json!(verifications)),
+        ("streams.json", json!(streams)),
+        ("wallet.json", wallet),
+        ("private_shows.json", json!(private_shows)),
+        ("purchases.json", json!(purchases)),
+        ("messages.json", json!(messages)),
+    ];
+
+    fs::create_dir_all(EXPORTS_DIR)
+        .await
+        .map_err(|e| format!("Nie można utworzyć katalogu eksportów: {}", e))?;
+    let zip_path = format!("{}/{}.zip", EXPORTS_DIR, job_id);
+
+    // Pakowanie jest blokujące (std::io), więc robimy je w osobnym wątku
+    let target = zip_path.clone();
+    task::spawn_blocking(move || write_zip(&target, &documents, &files))
+        .await
+        .map_err(|e| format!("Zadanie pakowania przerwane: {}", e))??;
+
+    Ok(zip_path)
+}
+
+fn write_zip(
+    zip_path: &str,
+    documents: &[(&str, serde_json::Value)],
+    files: &[(PathBuf, String)],
+) -> Result<(), String> {
+    let file = std::fs::File::create(zip_path)
+        .map_err(|e| format!("Nie można utworzyć archiwum: {}", e))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default();
+    let zip_err = |e: zip::result::ZipError| format!("Błąd archiwum: {}", e);
+    let io_err = |e: std::io::Error| format!("Błąd zapisu archiwum: {}", e);
+
+    zip.start_file("README.txt", options).map_err(zip_err)?;
+    zip.write_all(
+        "Eksport danych konta (art. 15 i 20 RODO).\n\
+         profile.json - dane konta\n\
+         galleries.json - galerie i zdjęcia\n\
+         sessions.json - aktywne sesje logowania\n\
+         verification.json - zgłoszenia weryfikacji tożsamości\n\
+         streams.json - transmisje i ich zarobki\n\
+         wallet.json - saldo, dziennik salda i napiwki\n\
+         private_shows.json - pokazy prywatne\n\
+         purchases.json - kupione i sprzedane galerie\n\
+         messages.json - wiadomości od administracji\n\
+         files/ - wgrane pliki\n"
+            .as_bytes(),
+    )
+    .map_err(io_err)?;
+
+    for (name, value) in documents {
+        let body = serde_json::to_vec_pretty(value).map_err(|e| e.to_string())?;
+        zip.start_file(*name, options).map_err(zip_err)?;
+        zip.write_all(&body).map_err(io_err)?;
+    }
+
+    for (path, archive_path) in files {
+        // Brakujący plik nie przerywa eksportu - dane w JSON-ie są ważniejsze
+        match std::fs::read(path) {
+            Ok(bytes) => {
+                zip.start_file(archive_path.as_str(), options)
+                    .map_err(zip_err)?;
+                zip.write_all(&bytes).map_err(io_err)?;
+            }
+            Err(e) => warn!("Pomijam brakujący plik {}: {}", path.display(), e),
+        }
+    }
+
+    zip.finish().map_err(zip_err)?;
+    Ok(())
+}
+
+/// Usuwa konto wraz z galeriami, zdjęciami, plikami i sesjami. Dokumenty
+/// weryfikacji i zgody osób współwystępujących zostają zastrzeżone na okres
+/// przechowywania.
+async fn delete_account(state: &AppState, erika_id: Uuid) -> Result<(), String> {
+    let db_err = |e: sqlx::Error| format!("Błąd bazy danych: {}", e);
+
+    let erika = Erika::find_by_id(erika_id, &state.db)
+        .await
+        .map_err(db_err)?
+        .ok_or("Konto nie istnieje")?;
+
+    // Najpierw zbieramy pliki, bo po usunięciu wierszy nie będziemy ich znać
+    let mut files: Vec<PathBuf> = erika
+        .profile_image_url
+        .as_deref()
+        .map(upload_path)
+        .into_iter()
+        .collect();
+    for gallery in Gallery::find_by_erika_id(erika_id, &state.db)
+        .await
+        .map_err(db_err)?
+    {
+        for photo in Photo::find_by_gallery_id(gallery.id, &state.db)
+            .await
+            .map_err(db_err)?
+        {
+            files.push(photo.disk_path());
+        }
+    }
+    // Pliki weryfikacji tożsamości i zgód osób współwystępujących zostają
+    // na okres przechowywania - usunie je `retention`
+    for job in AccountJob::find_by_erika_id(erika_id, &state.db)
+        .await
+        .map_err(db_err)?
+    {
+        if let Some(path) = job.file_path {
+            files.push(PathBuf::from(path));
+        }
+    }
+
+    // Dodatnie saldo to pieniądze widza - wniosek czeka na ich zwrot.
+    // Pusty portfel znika, a jego dziennik zostaje bez powiązania z kontem.
+    if !Wallet::delete_if_empty(erika_id, &state.db)
+        .await
+        .map_err(db_err)?
+    {
+        return Err("Na saldzie konta są środki - najpierw trzeba je zwrócić".to_string());
+    }
+
+    auth::revoke_all_sessions(state, erika_id)
+        .await
+        .map_err(|_| "Nie udało się unieważnić sesji".to_string())?;
+
+    // Ślad wykonania wniosku - bez danych osobowych poza ID
+    AuditEvent::record(
+        NewAuditEvent {
+            actor_id: Some(erika_id),
+            actor_username: "self-service",
+            action: AuditAction::AccountDeleted,
+            target_type: "erika",
+            target_id: Some(erika_id),
+            changes: json!({ "files_removed": files.len() }),
+            ip_address: None,
+        },
+        &state.db,
+    )
+    .await
+    .map_err(db_err)?;
+
+    // Trwający pokaz prywatny rozliczamy, zanim zniknie konto i jego transmisje
+    let commission_percent = state.settings().commission_percent;
+    for (show_id, show_erika_id) in
+        PrivateShow::close_for_account(erika_id, commission_percent, &state.db)
+            .await
+            .map_err(db_err)?
+    {
+        state.publish(CreatorEvent::PrivateShow {
+            erika_id: show_erika_id,
+            show_id,
+        });
+    }
+
+    // Rekordy finansowe zostają zanonimizowane, reszta znika z kontem
+    Erika::delete(erika_id, &state.db).await.map_err(db_err)?;
+    state.publish(CreatorEvent::Listing {
+        erika_id,
+        listed: false,
+    });
+
+    for path in files {
+        if let Err(e) = fs::remove_file(&path).await {
+            warn!("Nie udało się usunąć pliku {}: {}", path.display(), e);
+        }
+    }
+
+    info!("Usunięto konto {} na wniosek użytkownika", erika_id);
+    Ok(())
+}
+
+/// Sprawdza, czy ścieżka wskazuje na plik w katalogu eksportów.
+pub fn is_export_path(path: &str) -> bool {
+    let path = Path::new(path);
+    path.parent() == Some(Path::new(EXPORTS_DIR))
+        && path.extension().and_then(|e| e.to_str()) == Some("zip")
+}
@@ -0,0 +1,6 @@
+// src/lib.rs
+
+// Kod współdzielony między serwerem a narzędziami z `src/bin`.
+
+pub mod models;
+pub mod password;
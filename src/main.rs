@@ -1,14 +1,25 @@
 mod app_state;
+mod auth;
 mod errors;
+mod events;
 mod handlers;
+mod jobs;
+mod metering;
 mod middleware;
-mod models;
+mod presence;
+mod retention;
 mod router;
+mod validation;
 
 use app_state::AppState;
+use erika_system::models;
+use models::site_settings::SiteSettings;
+use models::user_session::UserSession;
 use sqlx::postgres::PgPoolOptions;
 use std::net::SocketAddr;
+use std::sync::{Arc, RwLock};
 use tokio::net::TcpListener;
+use tokio::sync::broadcast;
 use tracing::info;
 
 use time::Duration;
@@ -25,6 +36,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let database_url = std::env::var("DATABASE_URL").expect("Brak DATABASE_URL w .env");
 
+    // Wczytujemy parametry Argon2 od razu, żeby błędna konfiguracja wyszła przy starcie
+    let argon2_params = erika_system::password::params();
+    info!(
+        "Parametry Argon2: m={} KiB, t={}, p={}",
+        argon2_params.m_cost(),
+        argon2_params.t_cost(),
+        argon2_params.p_cost()
+    );
+    // Hash zastępczy dla nieznanych nazw liczymy teraz, a nie przy pierwszym logowaniu
+    erika_system::password::dummy_hash();
+
     let pool = PgPoolOptions::new()
         .max_connections(5)
         .connect(&database_url)
@@ -36,13 +58,38 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let session_store = PostgresStore::new(pool.clone());
     session_store.migrate().await?; // Automatycznie tworzy tabelę, jeśli nie istnieje
 
+    // Sprzątamy metadane sesji, które wygasły, gdy serwer nie działał
+    let stale = UserSession::delete_stale(auth::SESSION_INACTIVITY_DAYS, &pool).await?;
+    info!("Usunięto {} wygasłych wpisów sesji", stale);
+
     // Konfiguracja warstwy sesji - sesja wygasa po 1 dniu
-    let session_layer = SessionManagerLayer::new(session_store)
+    let session_layer = SessionManagerLayer::new(session_store.clone())
         .with_secure(false) // Ustaw na 'true' gdy przejdziesz na HTTPS
-        .with_expiry(Expiry::OnInactivity(Duration::days(1)));
+        .with_expiry(Expiry::OnInactivity(Duration::days(
+            auth::SESSION_INACTIVITY_DAYS,
+        )));
+
+    let settings = SiteSettings::load(&pool).await?;
+    if settings.maintenance_mode {
+        info!("Serwis startuje w trybie konserwacji");
+    }
 
     // Tworzymy router i dodajemy do niego warstwę sesji
-    let app_state = AppState { db: pool };
+    let app_state = AppState {
+        db: pool,
+        sessions: session_store,
+        settings: Arc::new(RwLock::new(settings)),
+        creator_events: broadcast::channel(events::CHANNEL_CAPACITY).0,
+    };
+
+    // Worker eksportów i usuwania kont (RODO)
+    jobs::spawn_worker(app_state.clone());
+    // Gaszenie statusu online modelek, które zamknęły panel kamerki
+    presence::spawn_sweeper(app_state.clone());
+    // Naliczanie minut pokazów prywatnych
+    metering::spawn_meter(app_state.clone());
+    // Usuwanie dokumentów po okresie przechowywania
+    retention::spawn_purger(app_state.clone());
 
     let app = router::create_router(app_state).layer(session_layer);
     let addr = SocketAddr::from(([0, 0, 0, 0], 3000));
@@ -50,6 +97,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let listener = TcpListener::bind(addr).await?;
     info!("Serwer nasłuchuje na http://{}", addr);
 
-    axum::serve(listener, app).await?;
+    // ConnectInfo daje nam adres IP klienta (lista sesji, audyt)
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await?;
     Ok(())
 }
@@ -0,0 +1,69 @@
+// src/metering.rs
+
+// Naliczanie pokazów prywatnych. Minuta jest płatna z góry: co chwilę task
+// pobiera z salda widza kolejną minutę trwających pokazów, a gdy środków
+// zabraknie albo widz zamknie stronę pokazu - kończy pokaz i go rozlicza.
+
+use crate::models::private_show::{MinuteCharge, PrivateShow, PrivateShowEndReason};
+use crate::{app_state::AppState, events::CreatorEvent};
+use std::time::Duration;
+use tracing::{error, info};
+use uuid::Uuid;
+
+/// Co ile sprawdzamy, którym pokazom zaczęła się kolejna minuta.
+const METER_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Uruchamia task w tle. Wywoływane raz przy starcie serwera.
+pub fn spawn_meter(state: AppState) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(METER_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = meter(&state).await {
+                error!("Błąd naliczania pokazów prywatnych: {}", e);
+            }
+        }
+    });
+}
+
+async fn meter(state: &AppState) -> Result<(), sqlx::Error> {
+    for show_id in PrivateShow::find_due(&state.db).await? {
+        match PrivateShow::charge_next_minute(show_id, &state.db).await? {
+            MinuteCharge::Charged => publish(state, show_id).await?,
+            MinuteCharge::InsufficientFunds => {
+                end(state, show_id, PrivateShowEndReason::Funds).await?
+            }
+            MinuteCharge::NotDue => {}
+        }
+    }
+
+    let timeout_secs = state.settings().presence_timeout_secs();
+    for show_id in PrivateShow::find_viewer_gone(timeout_secs, &state.db).await? {
+        end(state, show_id, PrivateShowEndReason::ViewerGone).await?;
+    }
+    Ok(())
+}
+
+async fn end(
+    state: &AppState,
+    show_id: Uuid,
+    reason: PrivateShowEndReason,
+) -> Result<(), sqlx::Error> {
+    let commission_percent = state.settings().commission_percent;
+    if let Some(erika_id) = PrivateShow::end(show_id, reason, commission_percent, &state.db).await?
+    {
+        info!("Zakończono pokaz prywatny {} ({})", show_id, reason);
+        state.publish(CreatorEvent::PrivateShow { erika_id, show_id });
+    }
+    Ok(())
+}
+
+async fn publish(state: &AppState, show_id: Uuid) -> Result<(), sqlx::Error> {
+    if let Some(erika_id) = PrivateShow::find_by_id(show_id, &state.db)
+        .await?
+        .and_then(|show| show.erika_id)
+    {
+        state.publish(CreatorEvent::PrivateShow { erika_id, show_id });
+    }
+    Ok(())
+}
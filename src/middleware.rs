@@ -1,40 +1,219 @@
 // src/middleware.rs
-use crate::{app_state::AppState, errors::AppError, models::erika::Erika};
+use crate::{
+    app_state::AppState,
+    auth::{self, ClientInfo, CurrentStaff, OptionalViewer},
+    errors::AppError,
+    handlers::layout,
+    models::{announcement::Announcement, audit::AuditAction, permission::Permission},
+};
 use axum::{
     body::Body,
     extract::{FromRequestParts, State},
-    http::Request,
+    http::{Method, Request, StatusCode},
     middleware::Next,
-    response::Response,
+    response::{Html, IntoResponse, Response},
 };
+use serde_json::json;
+use tracing::warn;
 
-use tower_sessions::Session;
-use uuid::Uuid;
-
-pub async fn require_admin(
+pub async fn require_staff(
     State(state): State<AppState>,
     request: Request<Body>, // <-- Zmiana na konkretny typ Body
     next: Next,
-) -> Result<Response, AppError> {
+) -> Result<Response, Response> {
+    let (mut parts, body) = request.into_parts();
+    // Cała logika (sesja, rola, przekierowanie na /login) siedzi w ekstraktorze
+    CurrentStaff::from_request_parts(&mut parts, &state).await?;
+
+    let request = Request::from_parts(parts, body);
+    Ok(next.run(request).await)
+}
+
+/// Wpuszcza tylko członków zespołu, których rola daje `permission`.
+/// Stan to para (stan aplikacji, uprawnienie), patrz `router::guarded`.
+pub async fn require_permission(
+    State((state, permission)): State<(AppState, Permission)>,
+    request: Request<Body>,
+    next: Next,
+) -> Result<Response, Response> {
+    let (mut parts, body) = request.into_parts();
+    let staff = CurrentStaff::from_request_parts(&mut parts, &state).await?;
+    if !staff.can(permission) {
+        return Err(AppError::Forbidden.into_response());
+    }
+
+    let request = Request::from_parts(parts, body);
+    Ok(next.run(request).await)
+}
+
+/// Ścieżki dostępne w trybie konserwacji - admin musi móc się zalogować.
+const MAINTENANCE_OPEN_PATHS: &[&str] = &["/login", "/logout", "/impersonation/stop"];
+
+/// Pasek komunikatu z ustawień i tryb konserwacji: wszyscy poza osobami
+/// z `ManageSettings` dostają stronę z informacją o pracach technicznych.
+pub async fn site_settings(
+    State(state): State<AppState>,
+    request: Request<Body>,
+    next: Next,
+) -> Result<Response, Response> {
+    let settings = state.settings();
+    let banner = (!settings.banner.is_empty()).then(|| settings.banner.clone());
+    if !settings.maintenance_mode || MAINTENANCE_OPEN_PATHS.contains(&request.uri().path()) {
+        return Ok(layout::with_banner(banner, next.run(request)).await);
+    }
+
     let (mut parts, body) = request.into_parts();
-    let session = Session::from_request_parts(&mut parts, &state)
+    let OptionalViewer(viewer) = OptionalViewer::from_request_parts(&mut parts, &state).await?;
+    if viewer.is_some_and(|viewer| viewer.can(Permission::ManageSettings)) {
+        let banner = Some(
+            "Tryb konserwacji jest włączony - serwis widzą tylko administratorzy.".to_string(),
+        );
+        let request = Request::from_parts(parts, body);
+        return Ok(layout::with_banner(banner, next.run(request)).await);
+    }
+
+    let page = layout::info_page("Przerwa techniczna", &settings.maintenance_message, None);
+    Err((StatusCode::SERVICE_UNAVAILABLE, Html(page.into_string())).into_response())
+}
+
+/// Ścieżki zablokowane w podglądzie konta nawet do odczytu: płatności (także
+/// płatne pokazy prywatne po obu stronach), dane osobowe, weryfikacja tożsamości
+/// i sesje oglądanego konta. Blokujemy też samą ścieżkę bez końcowego `/`.
+const IMPERSONATION_BLOCKED_PREFIXES: &[&str] = &[
+    "/pay/",
+    "/panel/private/",
+    "/private-shows/",
+    "/panel/privacy/",
+    "/panel/security/",
+    "/panel/verification/",
+];
+
+/// Jedyne zmiany (POST) dozwolone w podglądzie konta: zakończenie podglądu,
+/// wylogowanie i sygnał obecności, który w podglądzie niczego nie zapisuje.
+/// Każda inna zmiana działałaby w imieniu modelki, więc lista jest zamknięta.
+const IMPERSONATION_ALLOWED_POSTS: &[&str] = &["/impersonation/stop", "/logout", "/panel/presence"];
+
+/// Czy żądanie wykracza poza przeglądanie konta.
+fn blocked_while_impersonating(method: &Method, path: &str) -> bool {
+    let read_only = method == Method::GET || method == Method::HEAD;
+    IMPERSONATION_BLOCKED_PREFIXES
+        .iter()
+        .any(|prefix| path == prefix.trim_end_matches('/') || path.starts_with(prefix))
+        || (!read_only && !IMPERSONATION_ALLOWED_POSTS.contains(&path))
+}
+
+/// Podgląd konta przez admina: żądanie obsługujemy w imieniu oglądanego konta,
+/// każde zapisujemy w dzienniku audytu, a wszystko poza przeglądaniem blokujemy.
+pub async fn impersonation(
+    State(state): State<AppState>,
+    request: Request<Body>,
+    next: Next,
+) -> Result<Response, Response> {
+    let (mut parts, body) = request.into_parts();
+    let Some(impersonation) = auth::load_impersonation(&mut parts, &state).await? else {
+        let request = Request::from_parts(parts, body);
+        return Ok(next.run(request).await);
+    };
+
+    let Ok(client) = ClientInfo::from_request_parts(&mut parts, &state).await;
+    let path = parts.uri.path();
+    let blocked = blocked_while_impersonating(&parts.method, path);
+    let target = &impersonation.target;
+    CurrentStaff(impersonation.admin.clone())
+        .audit(
+            AuditAction::ImpersonationRequest,
+            "erika",
+            Some(target.id),
+            json!({
+                "method": parts.method.as_str(),
+                "path": parts.uri.path_and_query().map_or(path, |value| value.as_str()),
+                "blocked": blocked,
+            }),
+            &client,
+            &state.db,
+        )
         .await
-        .map_err(|_| AppError::Unauthorized)?;
+        .map_err(IntoResponse::into_response)?;
+
+    // Strona blokady też ma pasek podglądu z przyciskiem zakończenia
+    let username = target.username.clone();
+    if blocked {
+        return Err(layout::with_impersonation(Some(username), async {
+            let page = layout::info_page(
+                "Niedostępne w podglądzie",
+                "Podgląd konta służy tylko do przeglądania - zmian, płatności i usuwania nie można w nim wykonać.",
+                None,
+            );
+            (StatusCode::FORBIDDEN, Html(page.into_string())).into_response()
+        })
+        .await);
+    }
 
-    let erika_id = session
-        .get::<Uuid>("erika_id")
+    parts.extensions.insert(impersonation);
+    let request = Request::from_parts(parts, body);
+    Ok(layout::with_impersonation(Some(username), next.run(request)).await)
+}
+
+/// Trwające ogłoszenia dla odwiedzającego - `layout::page` pokazuje je jako paski.
+/// Błąd bazy nie blokuje strony, najwyżej nie będzie ogłoszeń.
+pub async fn announcements(
+    State(state): State<AppState>,
+    request: Request<Body>,
+    next: Next,
+) -> Result<Response, Response> {
+    let (mut parts, body) = request.into_parts();
+    let OptionalViewer(viewer) = OptionalViewer::from_request_parts(&mut parts, &state).await?;
+    let creator = viewer.as_ref().is_some_and(auth::has_creator_panel);
+    let fan = viewer.is_some() && !creator;
+    let announcements = Announcement::find_active(creator, fan, &state.db)
         .await
-        .unwrap_or(None)
-        .ok_or(AppError::Unauthorized)?;
+        .unwrap_or_else(|e| {
+            warn!("Nie udało się wczytać ogłoszeń: {}", e);
+            Vec::new()
+        });
 
-    let erika = Erika::find_by_id_for_auth(erika_id, &state.db)
-        .await?
-        .ok_or(AppError::Unauthorized)?;
+    let request = Request::from_parts(parts, body);
+    Ok(layout::with_announcements(announcements, next.run(request)).await)
+}
 
-    if erika.role == "Admin" {
-        let request = Request::from_parts(parts, body);
-        Ok(next.run(request).await)
-    } else {
-        Err(AppError::Unauthorized)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn impersonation_blocks_sensitive_pages_with_and_without_trailing_slash() {
+        for path in [
+            "/panel/security",
+            "/panel/security/",
+            "/panel/privacy",
+            "/panel/privacy/export/1",
+            "/panel/verification",
+            "/panel/verification/",
+            "/pay/abc",
+        ] {
+            assert!(blocked_while_impersonating(&Method::GET, path), "{}", path);
+        }
+    }
+
+    #[test]
+    fn impersonation_allows_browsing_and_listed_posts() {
+        assert!(!blocked_while_impersonating(&Method::GET, "/panel"));
+        assert!(!blocked_while_impersonating(
+            &Method::GET,
+            "/panel/galleries"
+        ));
+        // Podobny przedrostek nie może blokować innej strony
+        assert!(!blocked_while_impersonating(
+            &Method::GET,
+            "/panel/privacy-policy"
+        ));
+        assert!(!blocked_while_impersonating(
+            &Method::POST,
+            "/impersonation/stop"
+        ));
+        assert!(blocked_while_impersonating(
+            &Method::POST,
+            "/panel/status-toggle"
+        ));
     }
 }
@@ -0,0 +1,162 @@
+// src/models/account_job.rs
+use serde::Serialize;
+use sqlx::PgPool;
+use strum::Display;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, sqlx::Type, Display)]
+#[sqlx(type_name = "account_job_kind")]
+pub enum AccountJobKind {
+    Export,
+    Deletion,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, sqlx::Type, Display)]
+#[sqlx(type_name = "account_job_status")]
+pub enum AccountJobStatus {
+    Pending,
+    Running,
+    Done,
+    Failed,
+}
+
+impl AccountJobStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            AccountJobStatus::Pending => "Oczekuje",
+            AccountJobStatus::Running => "W trakcie",
+            AccountJobStatus::Done => "Gotowe",
+            AccountJobStatus::Failed => "Błąd",
+        }
+    }
+}
+
+#[derive(sqlx::FromRow, Clone)]
+pub struct AccountJob {
+    pub id: Uuid,
+    pub erika_id: Option<Uuid>,
+    pub kind: AccountJobKind,
+    pub status: AccountJobStatus,
+    pub file_path: Option<String>,
+    pub error: Option<String>,
+    pub created_at: OffsetDateTime,
+    pub finished_at: Option<OffsetDateTime>,
+}
+
+impl AccountJob {
+    pub async fn create(
+        erika_id: Uuid,
+        kind: AccountJobKind,
+        db: &PgPool,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            AccountJob,
+            r#"INSERT INTO account_jobs (erika_id, kind) VALUES ($1, $2)
+               RETURNING id, erika_id, kind as "kind: _", status as "status: _", file_path, error, created_at, finished_at"#,
+            erika_id,
+            kind as AccountJobKind
+        )
+        .fetch_one(db)
+        .await
+    }
+
+    pub async fn find_by_erika_id(erika_id: Uuid, db: &PgPool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AccountJob,
+            r#"SELECT id, erika_id, kind as "kind: _", status as "status: _", file_path, error, created_at, finished_at
+               FROM account_jobs WHERE erika_id = $1 ORDER BY created_at DESC"#,
+            erika_id
+        )
+        .fetch_all(db)
+        .await
+    }
+
+    pub async fn find_by_id_and_erika_id(
+        id: Uuid,
+        erika_id: Uuid,
+        db: &PgPool,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AccountJob,
+            r#"SELECT id, erika_id, kind as "kind: _", status as "status: _", file_path, error, created_at, finished_at
+               FROM account_jobs WHERE id = $1 AND erika_id = $2"#,
+            id,
+            erika_id
+        )
+        .fetch_optional(db)
+        .await
+    }
+
+    /// Czy konto ma już niezakończone zadanie danego rodzaju.
+    pub async fn has_open(
+        erika_id: Uuid,
+        kind: AccountJobKind,
+        db: &PgPool,
+    ) -> Result<bool, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"SELECT EXISTS(
+                 SELECT 1 FROM account_jobs
+                 WHERE erika_id = $1 AND kind = $2 AND status IN ('Pending', 'Running')
+               ) as "exists!""#,
+            erika_id,
+            kind as AccountJobKind
+        )
+        .fetch_one(db)
+        .await?;
+        Ok(row.exists)
+    }
+
+    /// Pobiera najstarsze oczekujące zadanie i oznacza je jako "w trakcie".
+    /// `SKIP LOCKED` pozwala uruchomić kilka workerów bez podwójnego wykonania.
+    pub async fn claim_next(db: &PgPool) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AccountJob,
+            r#"UPDATE account_jobs SET status = 'Running'
+               WHERE id = (
+                   SELECT id FROM account_jobs WHERE status = 'Pending'
+                   ORDER BY created_at
+                   FOR UPDATE SKIP LOCKED
+                   LIMIT 1
+               )
+               RETURNING id, erika_id, kind as "kind: _", status as "status: _", file_path, error, created_at, finished_at"#
+        )
+        .fetch_optional(db)
+        .await
+    }
+
+    pub async fn mark_done(
+        id: Uuid,
+        file_path: Option<&str>,
+        db: &PgPool,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE account_jobs SET status = 'Done', file_path = $1, finished_at = NOW() WHERE id = $2",
+            file_path,
+            id
+        )
+        .execute(db)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn mark_failed(id: Uuid, error: &str, db: &PgPool) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE account_jobs SET status = 'Failed', error = $1, finished_at = NOW() WHERE id = $2",
+            error,
+            id
+        )
+        .execute(db)
+        .await?;
+        Ok(())
+    }
+
+    /// Zadania przerwane restartem serwera wracają do kolejki.
+    pub async fn requeue_interrupted(db: &PgPool) -> Result<u64, sqlx::Error> {
+        let result =
+            sqlx::query!("UPDATE account_jobs SET status = 'Pending' WHERE status = 'Running'")
+                .execute(db)
+                .await?;
+        Ok(result.rows_affected())
+    }
+}
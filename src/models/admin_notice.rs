@@ -0,0 +1,95 @@
+// src/models/admin_notice.rs
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// Wiadomość od administracji do jednej modelki.
+#[derive(sqlx::FromRow, Clone)]
+pub struct AdminNotice {
+    pub id: Uuid,
+    pub erika_id: Uuid,
+    /// Nazwa nadawcy; `None`, jeśli jego konto już nie istnieje.
+    pub sender_username: Option<String>,
+    pub subject: String,
+    pub body: String,
+    pub created_at: OffsetDateTime,
+    pub read_at: Option<OffsetDateTime>,
+}
+
+impl AdminNotice {
+    pub async fn create(
+        erika_id: Uuid,
+        sender_id: Uuid,
+        subject: &str,
+        body: &str,
+        db: &PgPool,
+    ) -> Result<Uuid, sqlx::Error> {
+        let row = sqlx::query!(
+            "INSERT INTO admin_notices (erika_id, sender_id, subject, body)
+             VALUES ($1, $2, $3, $4)
+             RETURNING id",
+            erika_id,
+            sender_id,
+            subject,
+            body
+        )
+        .fetch_one(db)
+        .await?;
+        Ok(row.id)
+    }
+
+    pub async fn find_by_erika_id(erika_id: Uuid, db: &PgPool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AdminNotice,
+            r#"SELECT n.id, n.erika_id, e.username as "sender_username?", n.subject, n.body,
+                      n.created_at, n.read_at
+               FROM admin_notices n LEFT JOIN erikas e ON e.id = n.sender_id
+               WHERE n.erika_id = $1
+               ORDER BY n.created_at DESC"#,
+            erika_id
+        )
+        .fetch_all(db)
+        .await
+    }
+
+    /// Wiadomość tylko jeśli jest zaadresowana do tej modelki.
+    pub async fn find_for_erika(
+        id: Uuid,
+        erika_id: Uuid,
+        db: &PgPool,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AdminNotice,
+            r#"SELECT n.id, n.erika_id, e.username as "sender_username?", n.subject, n.body,
+                      n.created_at, n.read_at
+               FROM admin_notices n LEFT JOIN erikas e ON e.id = n.sender_id
+               WHERE n.id = $1 AND n.erika_id = $2"#,
+            id,
+            erika_id
+        )
+        .fetch_optional(db)
+        .await
+    }
+
+    pub async fn count_unread(erika_id: Uuid, db: &PgPool) -> Result<i64, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"SELECT COUNT(*) as "count!" FROM admin_notices
+               WHERE erika_id = $1 AND read_at IS NULL"#,
+            erika_id
+        )
+        .fetch_one(db)
+        .await?;
+        Ok(row.count)
+    }
+
+    /// Potwierdzenie odczytu - zapisujemy tylko pierwsze otwarcie.
+    pub async fn mark_read(id: Uuid, db: &PgPool) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE admin_notices SET read_at = NOW() WHERE id = $1 AND read_at IS NULL",
+            id
+        )
+        .execute(db)
+        .await?;
+        Ok(())
+    }
+}
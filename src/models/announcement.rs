@@ -0,0 +1,112 @@
+// src/models/announcement.rs
+use sqlx::PgPool;
+use strum::{Display, EnumIter, EnumString};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// Kto widzi ogłoszenie.
+#[derive(Debug, Clone, Copy, PartialEq, sqlx::Type, EnumString, EnumIter, Display)]
+#[sqlx(type_name = "announcement_audience")]
+pub enum AnnouncementAudience {
+    Creators,
+    Fans,
+    Everyone,
+}
+
+impl AnnouncementAudience {
+    pub fn label(&self) -> &'static str {
+        match self {
+            AnnouncementAudience::Creators => "Modelki",
+            AnnouncementAudience::Fans => "Fani",
+            AnnouncementAudience::Everyone => "Wszyscy",
+        }
+    }
+}
+
+#[derive(sqlx::FromRow, Clone)]
+pub struct Announcement {
+    pub id: Uuid,
+    pub message: String,
+    pub audience: AnnouncementAudience,
+    pub starts_at: OffsetDateTime,
+    pub ends_at: Option<OffsetDateTime>,
+    pub created_at: OffsetDateTime,
+}
+
+impl Announcement {
+    pub async fn create(
+        message: &str,
+        audience: AnnouncementAudience,
+        starts_at: OffsetDateTime,
+        ends_at: Option<OffsetDateTime>,
+        created_by: Uuid,
+        db: &PgPool,
+    ) -> Result<Uuid, sqlx::Error> {
+        let row = sqlx::query!(
+            "INSERT INTO announcements (message, audience, starts_at, ends_at, created_by)
+             VALUES ($1, $2, $3, $4, $5)
+             RETURNING id",
+            message,
+            audience as AnnouncementAudience,
+            starts_at,
+            ends_at,
+            created_by
+        )
+        .fetch_one(db)
+        .await?;
+        Ok(row.id)
+    }
+
+    pub async fn find_by_id(id: Uuid, db: &PgPool) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Announcement,
+            r#"SELECT id, message, audience as "audience: _", starts_at, ends_at, created_at
+               FROM announcements WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(db)
+        .await
+    }
+
+    /// Wszystkie ogłoszenia dla panelu admina, najnowsze na górze.
+    pub async fn find_all(db: &PgPool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Announcement,
+            r#"SELECT id, message, audience as "audience: _", starts_at, ends_at, created_at
+               FROM announcements
+               ORDER BY starts_at DESC"#
+        )
+        .fetch_all(db)
+        .await
+    }
+
+    /// Ogłoszenia trwające w tej chwili, widoczne dla odwiedzającego.
+    /// `creator` i `fan` mówią, do której grupy należy zalogowane konto.
+    pub async fn find_active(
+        creator: bool,
+        fan: bool,
+        db: &PgPool,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Announcement,
+            r#"SELECT id, message, audience as "audience: _", starts_at, ends_at, created_at
+               FROM announcements
+               WHERE starts_at <= NOW() AND (ends_at IS NULL OR ends_at > NOW())
+                 AND (audience = 'Everyone'
+                      OR (audience = 'Creators' AND $1)
+                      OR (audience = 'Fans' AND $2))
+               ORDER BY starts_at"#,
+            creator,
+            fan
+        )
+        .fetch_all(db)
+        .await
+    }
+
+    pub async fn delete(id: Uuid, db: &PgPool) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM announcements WHERE id = $1", id)
+            .execute(db)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
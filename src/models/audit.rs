@@ -0,0 +1,250 @@
+// src/models/audit.rs
+use serde_json::{Map, Value, json};
+use sqlx::{PgConnection, PgPool};
+use strum::{Display, EnumIter, EnumString};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// Rodzaje działań zapisywanych w dzienniku audytu.
+/// W bazie trzymamy wartość tekstową (np. "erika.approved").
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, EnumIter, Display)]
+pub enum AuditAction {
+    #[strum(serialize = "erika.profile_updated")]
+    ProfileUpdated,
+    /// Tylko historyczne wpisy - akceptacja jest teraz jedną ze zmian statusu.
+    #[strum(serialize = "erika.approved")]
+    AccountApproved,
+    #[strum(serialize = "erika.status_changed")]
+    StatusChanged,
+    #[strum(serialize = "erika.sessions_revoked")]
+    SessionsRevoked,
+    #[strum(serialize = "erika.role_changed")]
+    RoleChanged,
+    #[strum(serialize = "erika.created")]
+    AccountCreated,
+    #[strum(serialize = "erika.password_reset_issued")]
+    PasswordResetIssued,
+    #[strum(serialize = "erika.password_reset")]
+    PasswordReset,
+    #[strum(serialize = "erika.deletion_requested")]
+    DeletionRequested,
+    #[strum(serialize = "erika.deleted")]
+    AccountDeleted,
+    #[strum(serialize = "verification.approved")]
+    VerificationApproved,
+    #[strum(serialize = "verification.rejected")]
+    VerificationRejected,
+    #[strum(serialize = "co_performer.approved")]
+    CoPerformerApproved,
+    #[strum(serialize = "co_performer.rejected")]
+    CoPerformerRejected,
+    #[strum(serialize = "co_performer.exported")]
+    CoPerformersExported,
+    #[strum(serialize = "category.created")]
+    CategoryCreated,
+    #[strum(serialize = "category.updated")]
+    CategoryUpdated,
+    #[strum(serialize = "category.deleted")]
+    CategoryDeleted,
+    #[strum(serialize = "report.resolved")]
+    ReportResolved,
+    #[strum(serialize = "gallery.updated")]
+    GalleryUpdated,
+    #[strum(serialize = "gallery.unpublished")]
+    GalleryUnpublished,
+    #[strum(serialize = "photo.deleted")]
+    PhotoDeleted,
+    #[strum(serialize = "photo.approved")]
+    PhotoApproved,
+    #[strum(serialize = "photo.rejected")]
+    PhotoRejected,
+    #[strum(serialize = "settings.updated")]
+    SettingsUpdated,
+    #[strum(serialize = "impersonation.started")]
+    ImpersonationStarted,
+    #[strum(serialize = "impersonation.ended")]
+    ImpersonationEnded,
+    #[strum(serialize = "impersonation.request")]
+    ImpersonationRequest,
+    #[strum(serialize = "announcement.created")]
+    AnnouncementCreated,
+    #[strum(serialize = "announcement.deleted")]
+    AnnouncementDeleted,
+    #[strum(serialize = "notice.sent")]
+    NoticeSent,
+    #[strum(serialize = "wallet.credited")]
+    WalletCredited,
+}
+
+impl AuditAction {
+    /// Czytelna nazwa do przeglądarki dziennika.
+    pub fn label(&self) -> &'static str {
+        match self {
+            AuditAction::ProfileUpdated => "Edycja profilu",
+            AuditAction::AccountApproved => "Akceptacja konta",
+            AuditAction::StatusChanged => "Zmiana statusu konta",
+            AuditAction::SessionsRevoked => "Wymuszone wylogowanie",
+            AuditAction::RoleChanged => "Zmiana roli",
+            AuditAction::AccountCreated => "Założenie konta",
+            AuditAction::PasswordResetIssued => "Wydanie linku do zmiany hasła",
+            AuditAction::PasswordReset => "Zmiana hasła z linku",
+            AuditAction::DeletionRequested => "Wniosek o usunięcie konta",
+            AuditAction::AccountDeleted => "Usunięcie konta",
+            AuditAction::VerificationApproved => "Potwierdzenie tożsamości",
+            AuditAction::VerificationRejected => "Odrzucenie weryfikacji",
+            AuditAction::CoPerformerApproved => "Potwierdzenie osoby współwystępującej",
+            AuditAction::CoPerformerRejected => "Odrzucenie osoby współwystępującej",
+            AuditAction::CoPerformersExported => "Eksport rekordów zgód",
+            AuditAction::CategoryCreated => "Dodanie kategorii",
+            AuditAction::CategoryUpdated => "Edycja kategorii",
+            AuditAction::CategoryDeleted => "Usunięcie kategorii",
+            AuditAction::ReportResolved => "Rozpatrzenie zgłoszenia",
+            AuditAction::GalleryUpdated => "Edycja galerii",
+            AuditAction::GalleryUnpublished => "Cofnięcie publikacji galerii",
+            AuditAction::PhotoDeleted => "Usunięcie zdjęcia",
+            AuditAction::PhotoApproved => "Akceptacja zdjęcia",
+            AuditAction::PhotoRejected => "Odrzucenie zdjęcia",
+            AuditAction::SettingsUpdated => "Zmiana ustawień serwisu",
+            AuditAction::ImpersonationStarted => "Rozpoczęcie podglądu konta",
+            AuditAction::ImpersonationEnded => "Zakończenie podglądu konta",
+            AuditAction::ImpersonationRequest => "Żądanie w podglądzie konta",
+            AuditAction::AnnouncementCreated => "Dodanie ogłoszenia",
+            AuditAction::AnnouncementDeleted => "Usunięcie ogłoszenia",
+            AuditAction::NoticeSent => "Wiadomość do modelki",
+            AuditAction::WalletCredited => "Doładowanie salda",
+        }
+    }
+}
+
+#[derive(sqlx::FromRow, Clone)]
+pub struct AuditEvent {
+    pub id: i64,
+    pub actor_id: Option<Uuid>,
+    pub actor_username: String,
+    pub action: String,
+    pub target_type: String,
+    pub target_id: Option<Uuid>,
+    pub changes: Value,
+    pub ip_address: Option<String>,
+    pub created_at: OffsetDateTime,
+}
+
+/// Dane nowego wpisu w dzienniku.
+pub struct NewAuditEvent<'a> {
+    pub actor_id: Option<Uuid>,
+    pub actor_username: &'a str,
+    pub action: AuditAction,
+    pub target_type: &'a str,
+    pub target_id: Option<Uuid>,
+    pub changes: Value,
+    pub ip_address: Option<&'a str>,
+}
+
+/// Filtry przeglądarki dziennika. `None` oznacza "bez filtra".
+#[derive(Default)]
+pub struct AuditQuery {
+    pub action: Option<String>,
+    pub actor_username: Option<String>,
+    pub target_id: Option<Uuid>,
+    /// Daty w formacie RRRR-MM-DD (już zwalidowane).
+    pub date_from: Option<String>,
+    pub date_to: Option<String>,
+    /// Stronicowanie: tylko wpisy starsze niż podane ID.
+    pub before_id: Option<i64>,
+}
+
+/// Znacznik w miejscu usuniętych wartości danych osobowych.
+pub const REDACTED: &str = "[usunięte]";
+
+impl AuditEvent {
+    pub async fn record(event: NewAuditEvent<'_>, db: &PgPool) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "INSERT INTO audit_events (actor_id, actor_username, action, target_type, target_id, changes, ip_address)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)",
+            event.actor_id,
+            event.actor_username,
+            event.action.to_string(),
+            event.target_type,
+            event.target_id,
+            event.changes,
+            event.ip_address
+        )
+        .execute(db)
+        .await?;
+        Ok(())
+    }
+
+    /// Przy usunięciu konta: wartości przed i po we wpisach o edycji jego profilu
+    /// zastępujemy znacznikiem, a nazwy zmienionych pól zostają. To jedyna zmiana,
+    /// na którą pozwala trigger dziennika - i tylko w tej transakcji.
+    pub async fn redact_profile_values(
+        target_id: Uuid,
+        conn: &mut PgConnection,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!("SELECT set_config('audit.redaction', 'on', true)")
+            .fetch_one(&mut *conn)
+            .await?;
+        sqlx::query!(
+            "UPDATE audit_events
+             SET changes = (
+                 SELECT jsonb_object_agg(field, jsonb_build_object('before', $3::TEXT, 'after', $3::TEXT))
+                 FROM jsonb_object_keys(changes) AS field
+             )
+             WHERE action = $1 AND target_id = $2 AND changes <> '{}'::jsonb",
+            AuditAction::ProfileUpdated.to_string(),
+            target_id,
+            REDACTED
+        )
+        .execute(&mut *conn)
+        .await?;
+        sqlx::query!("SELECT set_config('audit.redaction', 'off', true)")
+            .fetch_one(&mut *conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Zwraca najwyżej `limit` wpisów pasujących do filtrów, od najnowszych.
+    pub async fn find_filtered(
+        query: &AuditQuery,
+        limit: i64,
+        db: &PgPool,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AuditEvent,
+            "SELECT * FROM audit_events
+             WHERE ($1::TEXT IS NULL OR action = $1)
+               AND ($2::TEXT IS NULL OR LOWER(actor_username) = LOWER($2))
+               AND ($3::UUID IS NULL OR target_id = $3)
+               AND ($4::TEXT IS NULL OR created_at >= $4::TEXT::DATE)
+               AND ($5::TEXT IS NULL OR created_at < $5::TEXT::DATE + 1)
+               AND ($6::BIGINT IS NULL OR id < $6)
+             ORDER BY id DESC
+             LIMIT $7",
+            query.action,
+            query.actor_username,
+            query.target_id,
+            query.date_from,
+            query.date_to,
+            query.before_id,
+            limit
+        )
+        .fetch_all(db)
+        .await
+    }
+}
+
+/// Buduje opis zmian w postaci `{"pole": {"before": .., "after": ..}}`,
+/// pomijając pola, które się nie zmieniły.
+pub fn diff(fields: &[(&str, Value, Value)]) -> Value {
+    let changes: Map<String, Value> = fields
+        .iter()
+        .filter(|(_, before, after)| before != after)
+        .map(|(name, before, after)| {
+            (
+                name.to_string(),
+                json!({ "before": before, "after": after }),
+            )
+        })
+        .collect();
+    Value::Object(changes)
+}
@@ -0,0 +1,117 @@
+// src/models/category.rs
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Kategoria galerii. Nieaktywnej nie można wybrać dla nowej galerii,
+/// ale istniejące galerie ją zachowują.
+#[derive(sqlx::FromRow, Clone)]
+pub struct Category {
+    pub id: Uuid,
+    pub slug: String,
+    pub name: String,
+    pub sort_order: i32,
+    pub is_active: bool,
+}
+
+/// Kategoria z liczbą galerii - lista w panelu admina.
+#[derive(sqlx::FromRow, Clone)]
+pub struct CategoryWithUsage {
+    pub id: Uuid,
+    pub slug: String,
+    pub name: String,
+    pub sort_order: i32,
+    pub is_active: bool,
+    pub gallery_count: i64,
+}
+
+/// Dane z formularza admina - ten sam zestaw pól przy tworzeniu i edycji.
+pub struct CategoryFields<'a> {
+    pub slug: &'a str,
+    pub name: &'a str,
+    pub sort_order: i32,
+    pub is_active: bool,
+}
+
+impl Category {
+    /// Kategorie do wyboru przy tworzeniu galerii.
+    pub async fn find_active(db: &PgPool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Category,
+            "SELECT id, slug, name, sort_order, is_active
+             FROM categories WHERE is_active
+             ORDER BY sort_order, name"
+        )
+        .fetch_all(db)
+        .await
+    }
+
+    pub async fn find_by_id(id: Uuid, db: &PgPool) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Category,
+            "SELECT id, slug, name, sort_order, is_active
+             FROM categories WHERE id = $1",
+            id
+        )
+        .fetch_optional(db)
+        .await
+    }
+
+    pub async fn find_all_with_usage(db: &PgPool) -> Result<Vec<CategoryWithUsage>, sqlx::Error> {
+        sqlx::query_as!(
+            CategoryWithUsage,
+            r#"SELECT c.id, c.slug, c.name, c.sort_order, c.is_active,
+                      COUNT(g.id) as "gallery_count!"
+               FROM categories c LEFT JOIN galleries g ON g.category_id = c.id
+               GROUP BY c.id
+               ORDER BY c.sort_order, c.name"#
+        )
+        .fetch_all(db)
+        .await
+    }
+
+    pub async fn create(fields: &CategoryFields<'_>, db: &PgPool) -> Result<Uuid, sqlx::Error> {
+        let row = sqlx::query!(
+            "INSERT INTO categories (slug, name, sort_order, is_active)
+             VALUES ($1, $2, $3, $4)
+             RETURNING id",
+            fields.slug,
+            fields.name,
+            fields.sort_order,
+            fields.is_active
+        )
+        .fetch_one(db)
+        .await?;
+        Ok(row.id)
+    }
+
+    pub async fn update(
+        id: Uuid,
+        fields: &CategoryFields<'_>,
+        db: &PgPool,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "UPDATE categories SET slug = $1, name = $2, sort_order = $3, is_active = $4
+             WHERE id = $5",
+            fields.slug,
+            fields.name,
+            fields.sort_order,
+            fields.is_active,
+            id
+        )
+        .execute(db)
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Usuwa kategorię, o ile żadna galeria jej nie używa. Używaną można tylko wyłączyć.
+    pub async fn delete_unused(id: Uuid, db: &PgPool) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM categories
+             WHERE id = $1 AND NOT EXISTS (SELECT 1 FROM galleries WHERE category_id = $1)",
+            id
+        )
+        .execute(db)
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+}
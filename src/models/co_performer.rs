@@ -0,0 +1,305 @@
+// src/models/co_performer.rs
+use super::verification::{self, VerificationStatus};
+use sqlx::PgPool;
+use time::{Date, OffsetDateTime};
+use uuid::Uuid;
+
+/// Rodzaj pliku w rekordzie - także fragment URL-a podglądu dla admina.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CoPerformerFile {
+    IdDocument,
+    Release,
+}
+
+impl CoPerformerFile {
+    pub fn from_slug(slug: &str) -> Option<Self> {
+        match slug {
+            "document" => Some(CoPerformerFile::IdDocument),
+            "release" => Some(CoPerformerFile::Release),
+            _ => None,
+        }
+    }
+
+    pub fn slug(&self) -> &'static str {
+        match self {
+            CoPerformerFile::IdDocument => "document",
+            CoPerformerFile::Release => "release",
+        }
+    }
+}
+
+/// Osoba współwystępująca w galerii wraz z dokumentami.
+#[derive(sqlx::FromRow, Clone)]
+pub struct CoPerformer {
+    pub id: Uuid,
+    pub gallery_id: Uuid,
+    pub legal_name: String,
+    pub date_of_birth: Date,
+    pub id_document_path: String,
+    pub release_path: String,
+    pub status: VerificationStatus,
+    pub rejection_reason: Option<String>,
+    pub reviewed_at: Option<OffsetDateTime>,
+    pub created_at: OffsetDateTime,
+}
+
+/// Rekord w kolejce admina.
+#[derive(sqlx::FromRow, Clone)]
+pub struct CoPerformerQueueItem {
+    pub id: Uuid,
+    pub legal_name: String,
+    pub date_of_birth: Date,
+    pub created_at: OffsetDateTime,
+    pub gallery_name: String,
+    pub username: String,
+}
+
+/// Wiersz eksportu dla celów zgodności - rekord z danymi galerii i osoby sprawdzającej.
+#[derive(sqlx::FromRow, Clone)]
+pub struct ComplianceRecord {
+    pub id: Uuid,
+    pub gallery_id: Uuid,
+    pub gallery_name: String,
+    pub owner_username: String,
+    pub legal_name: String,
+    pub date_of_birth: Date,
+    pub status: VerificationStatus,
+    pub id_document_path: String,
+    pub release_path: String,
+    pub rejection_reason: Option<String>,
+    pub reviewed_by: Option<String>,
+    pub reviewed_at: Option<OffsetDateTime>,
+    pub created_at: OffsetDateTime,
+}
+
+/// Dane nowego rekordu - ścieżki do już zapisanych plików.
+pub struct NewCoPerformer<'a> {
+    pub gallery_id: Uuid,
+    pub legal_name: &'a str,
+    pub date_of_birth: Date,
+    pub id_document_path: &'a str,
+    pub release_path: &'a str,
+}
+
+impl CoPerformer {
+    pub fn age(&self) -> i32 {
+        verification::age_on(self.date_of_birth, OffsetDateTime::now_utc().date())
+    }
+
+    pub fn file_path(&self, file: CoPerformerFile) -> &str {
+        match file {
+            CoPerformerFile::IdDocument => &self.id_document_path,
+            CoPerformerFile::Release => &self.release_path,
+        }
+    }
+
+    /// Dodaje osobę do galerii. Opublikowana galeria wraca do szkicu, bo nowa
+    /// osoba nie jest jeszcze zweryfikowana.
+    pub async fn create(new: NewCoPerformer<'_>, db: &PgPool) -> Result<Uuid, sqlx::Error> {
+        let mut tx = db.begin().await?;
+        let row = sqlx::query!(
+            "INSERT INTO co_performers
+                 (gallery_id, legal_name, date_of_birth, id_document_path, release_path)
+             VALUES ($1, $2, $3, $4, $5)
+             RETURNING id",
+            new.gallery_id,
+            new.legal_name,
+            new.date_of_birth,
+            new.id_document_path,
+            new.release_path
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        sqlx::query!(
+            "UPDATE galleries SET published_at = NULL WHERE id = $1",
+            new.gallery_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(row.id)
+    }
+
+    pub async fn find_by_id(id: Uuid, db: &PgPool) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            CoPerformer,
+            r#"SELECT id, gallery_id as "gallery_id!", legal_name, date_of_birth, id_document_path, release_path,
+                      status as "status: _", rejection_reason, reviewed_at, created_at
+               FROM co_performers WHERE id = $1 AND restricted_at IS NULL"#,
+            id
+        )
+        .fetch_optional(db)
+        .await
+    }
+
+    pub async fn find_by_gallery_id(
+        gallery_id: Uuid,
+        db: &PgPool,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            CoPerformer,
+            r#"SELECT id, gallery_id as "gallery_id!", legal_name, date_of_birth, id_document_path, release_path,
+                      status as "status: _", rejection_reason, reviewed_at, created_at
+               FROM co_performers WHERE gallery_id = $1
+               ORDER BY created_at ASC"#,
+            gallery_id
+        )
+        .fetch_all(db)
+        .await
+    }
+
+    pub async fn find_pending(db: &PgPool) -> Result<Vec<CoPerformerQueueItem>, sqlx::Error> {
+        sqlx::query_as!(
+            CoPerformerQueueItem,
+            r#"SELECT c.id, c.legal_name, c.date_of_birth, c.created_at,
+                      cat.name as gallery_name, e.username
+               FROM co_performers c
+               JOIN galleries g ON g.id = c.gallery_id
+               JOIN categories cat ON cat.id = g.category_id
+               JOIN erikas e ON e.id = g.erika_id
+               WHERE c.status = 'Pending'
+               ORDER BY c.created_at ASC"#
+        )
+        .fetch_all(db)
+        .await
+    }
+
+    /// Wszystkie rekordy do eksportu, pogrupowane po właścicielce i galerii.
+    pub async fn find_all_for_export(db: &PgPool) -> Result<Vec<ComplianceRecord>, sqlx::Error> {
+        sqlx::query_as!(
+            ComplianceRecord,
+            r#"SELECT c.id, c.gallery_id as "gallery_id!", cat.name as gallery_name, e.username as owner_username,
+                      c.legal_name, c.date_of_birth, c.status as "status: _",
+                      c.id_document_path, c.release_path, c.rejection_reason,
+                      r.username as "reviewed_by?", c.reviewed_at, c.created_at
+               FROM co_performers c
+               JOIN galleries g ON g.id = c.gallery_id
+               JOIN categories cat ON cat.id = g.category_id
+               JOIN erikas e ON e.id = g.erika_id
+               LEFT JOIN erikas r ON r.id = c.reviewed_by
+               ORDER BY e.username, c.gallery_id, c.created_at"#
+        )
+        .fetch_all(db)
+        .await
+    }
+
+    /// Zapisuje decyzję admina. Działa tylko na rekordzie, który wciąż czeka.
+    pub async fn review(
+        id: Uuid,
+        status: VerificationStatus,
+        rejection_reason: Option<&str>,
+        reviewer_id: Uuid,
+        db: &PgPool,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "UPDATE co_performers
+             SET status = $1, rejection_reason = $2, reviewed_by = $3, reviewed_at = NOW()
+             WHERE id = $4 AND status = 'Pending' AND restricted_at IS NULL",
+            status as VerificationStatus,
+            rejection_reason,
+            reviewer_id,
+            id
+        )
+        .execute(db)
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Usuwa rekordy, którym minął okres przechowywania, i zwraca ścieżki
+    /// ich plików do usunięcia z dysku.
+    pub async fn purge_expired(db: &PgPool) -> Result<Vec<String>, sqlx::Error> {
+        let rows = sqlx::query!(
+            "DELETE FROM co_performers
+             WHERE retain_until < NOW()
+             RETURNING id_document_path, release_path",
+        )
+        .fetch_all(db)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .flat_map(|r| [r.id_document_path, r.release_path])
+            .collect())
+    }
+
+    /// Usuwa rekord, który nie został zweryfikowany. Zweryfikowanych nie
+    /// usuwamy - są dowodem zgody dla opublikowanych treści.
+    pub async fn delete_unverified(
+        id: Uuid,
+        gallery_id: Uuid,
+        db: &PgPool,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            CoPerformer,
+            r#"DELETE FROM co_performers
+               WHERE id = $1 AND gallery_id = $2 AND status <> 'Verified'
+               RETURNING id, gallery_id as "gallery_id!", legal_name, date_of_birth, id_document_path, release_path,
+                         status as "status: _", rejection_reason, reviewed_at, created_at"#,
+            id,
+            gallery_id
+        )
+        .fetch_optional(db)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::erika::Erika;
+    use time::macros::date;
+
+    #[sqlx::test]
+    async fn deleted_account_keeps_restricted_release_until_purge(db: PgPool) {
+        let gallery_id = sqlx::query!(
+            "WITH erika AS (
+                 INSERT INTO erikas (username, email, password_hash) VALUES ('anna', 'anna@example.com', 'x')
+                 RETURNING id
+             ), category AS (
+                 INSERT INTO categories (name, slug) VALUES ('Plener', 'plener') RETURNING id
+             )
+             INSERT INTO galleries (erika_id, category_id)
+             SELECT erika.id, category.id FROM erika, category
+             RETURNING id, erika_id"
+        )
+        .fetch_one(&db)
+        .await
+        .unwrap();
+        let id = CoPerformer::create(
+            NewCoPerformer {
+                gallery_id: gallery_id.id,
+                legal_name: "Jan Kowalski",
+                date_of_birth: date!(1990 - 01 - 01),
+                id_document_path: "private/co_performers/id.jpg",
+                release_path: "private/co_performers/release.pdf",
+            },
+            &db,
+        )
+        .await
+        .unwrap();
+
+        Erika::delete(gallery_id.erika_id, &db).await.unwrap();
+
+        // Rekord zostaje, ale panel admina go nie widzi
+        assert!(CoPerformer::find_by_id(id, &db).await.unwrap().is_none());
+        assert!(
+            CoPerformer::find_all_for_export(&db)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert!(CoPerformer::purge_expired(&db).await.unwrap().is_empty());
+
+        sqlx::query!("UPDATE co_performers SET retain_until = NOW() - INTERVAL '1 day'")
+            .execute(&db)
+            .await
+            .unwrap();
+        let files = CoPerformer::purge_expired(&db).await.unwrap();
+        assert_eq!(
+            files,
+            [
+                "private/co_performers/id.jpg",
+                "private/co_performers/release.pdf"
+            ]
+        );
+    }
+}
@@ -1,16 +1,98 @@
-use argon2::{
-    Argon2,
-    password_hash::{PasswordHasher, PasswordVerifier, SaltString, rand_core::OsRng},
-};
+use crate::models::audit::AuditEvent;
+use crate::models::permission::{self, Permission};
+use crate::models::verification;
+use crate::password;
 use serde::Serialize;
 use sqlx::PgPool;
+use strum::{Display, EnumIter, EnumString};
+use time::format_description::well_known::Rfc3339;
+use time::{Date, OffsetDateTime};
 use tokio::task;
 use tracing::debug;
 use uuid::Uuid;
 
-#[derive(sqlx::FromRow)]
+/// Status konta w cyklu życia: rejestracja -> weryfikacja -> aktywne / zawieszone / zbanowane.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, sqlx::Type, EnumString, EnumIter, Display)]
+#[sqlx(type_name = "account_status")]
+pub enum AccountStatus {
+    Pending,
+    Approved,
+    Rejected,
+    Suspended,
+    Banned,
+}
+
+impl AccountStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            AccountStatus::Pending => "Oczekuje",
+            AccountStatus::Approved => "Zaakceptowane",
+            AccountStatus::Rejected => "Odrzucone",
+            AccountStatus::Suspended => "Zawieszone",
+            AccountStatus::Banned => "Zbanowane",
+        }
+    }
+
+    /// Nazwa akcji admina prowadzącej do tego statusu (przyciski w panelu).
+    pub fn action_label(&self) -> &'static str {
+        match self {
+            AccountStatus::Pending => "Cofnij do weryfikacji",
+            AccountStatus::Approved => "Akceptuj / przywróć",
+            AccountStatus::Rejected => "Odrzuć",
+            AccountStatus::Suspended => "Zawieś",
+            AccountStatus::Banned => "Zbanuj",
+        }
+    }
+
+    /// Dozwolone przejścia między statusami.
+    pub fn allowed_transitions(&self) -> &'static [AccountStatus] {
+        use AccountStatus::*;
+        match self {
+            Pending => &[Approved, Rejected, Banned],
+            Approved => &[Pending, Suspended, Banned],
+            Rejected => &[Pending, Approved, Banned],
+            Suspended => &[Approved, Banned],
+            Banned => &[Approved],
+        }
+    }
+
+    pub fn can_transition_to(&self, next: AccountStatus) -> bool {
+        self.allowed_transitions().contains(&next)
+    }
+
+    /// Czy konto może się zalogować. Zawieszona modelka loguje się,
+    /// żeby zobaczyć powód, ale nie ma dostępu do funkcji zarobkowych.
+    pub fn can_log_in(&self) -> bool {
+        *self != AccountStatus::Banned
+    }
+}
+
+/// Wartości typu `user_role` w bazie.
+pub const ROLES: &[&str] = &["Admin", "Erika", "Moderator", "User"];
+
+#[derive(sqlx::FromRow, Clone)]
 pub struct ErikaAuth {
+    pub id: Uuid,
+    pub username: String,
     pub role: String,
+    pub status: AccountStatus,
+}
+
+impl ErikaAuth {
+    /// Rola i status, z którymi wydano sesję. Gdy się zmienią, `auth` wymienia ID sesji.
+    pub fn privileges(&self) -> String {
+        format!("{}:{}", self.role, self.status)
+    }
+
+    /// Czy rola konta daje to uprawnienie w panelu admina.
+    pub fn can(&self, permission: Permission) -> bool {
+        permission::permissions_for(&self.role).contains(&permission)
+    }
+
+    /// Członek zespołu, czyli konto z co najmniej jednym uprawnieniem.
+    pub fn is_staff(&self) -> bool {
+        !permission::permissions_for(&self.role).is_empty()
+    }
 }
 
 #[derive(sqlx::FromRow, Clone, Serialize)]
@@ -22,35 +104,167 @@ pub struct Erika {
     pub profile_image_url: Option<String>,
     pub bio: Option<String>,
     pub is_online: bool,
-    pub is_approved: bool,
+    pub status: AccountStatus,
+    pub status_reason: Option<String>,
+    pub status_changed_at: Option<OffsetDateTime>,
+    /// Ostatni sygnał z panelu kamerki.
+    pub last_seen_at: Option<OffsetDateTime>,
+    /// "Pokazuj mnie jako offline" - ukrywa też `last_seen_at` przed odwiedzającymi.
+    pub appear_offline: bool,
 }
 
-impl Erika {
-    pub async fn create(
-        payload: &super::super::handlers::erika_handlers::RegisterErikaPayload,
-        db: &PgPool,
-    ) -> Result<(), sqlx::Error> {
-        let password_to_hash = payload.password.clone();
+/// Wiersz listy kont (CLI admina).
+#[derive(sqlx::FromRow, Clone)]
+pub struct ErikaSummary {
+    pub id: Uuid,
+    pub username: String,
+    pub email: String,
+    pub role: String,
+    pub status: AccountStatus,
+    pub created_at: OffsetDateTime,
+}
 
-        // **OPTYMALIZACJA**: Nadal wykonujemy hashowanie w osobnym wątku.
-        let password_hash = task::spawn_blocking(move || -> String {
-            // 1. Generujemy unikalną, losową sól dla każdego hasła.
-            let salt = SaltString::generate(&mut OsRng);
+/// Filtry listy kont. `None` oznacza "bez filtra".
+#[derive(Default)]
+pub struct ErikaQuery {
+    pub status: Option<AccountStatus>,
+    pub role: Option<String>,
+    /// Fragment nazwy użytkownika lub adresu email.
+    pub search: Option<String>,
+}
 
-            // 2. Używamy domyślnej, bezpiecznej konfiguracji Argon2.
-            let argon2 = Argon2::default();
+/// Kolumna sortowania listy kont w panelu admina.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum CreatorSort {
+    #[default]
+    Username,
+    Registered,
+    Status,
+}
 
-            // 3. Hashujemy hasło z użyciem nowej soli.
-            // Wynikiem jest kompletny hash zawierający wszystkie potrzebne informacje.
-            let hash = argon2
-                .hash_password(password_to_hash.as_bytes(), &salt)
-                .expect("Hashowanie hasła nie powiodło się");
+impl CreatorSort {
+    pub fn from_slug(slug: &str) -> Option<Self> {
+        match slug {
+            "username" => Some(CreatorSort::Username),
+            "registered" => Some(CreatorSort::Registered),
+            "status" => Some(CreatorSort::Status),
+            _ => None,
+        }
+    }
 
-            // 4. Zwracamy hash jako string do zapisu w bazie.
-            hash.to_string()
+    pub fn slug(&self) -> &'static str {
+        match self {
+            CreatorSort::Username => "username",
+            CreatorSort::Registered => "registered",
+            CreatorSort::Status => "status",
+        }
+    }
+}
+
+/// Wiersz listy kont w panelu admina.
+#[derive(sqlx::FromRow, Clone)]
+pub struct CreatorListItem {
+    pub id: Uuid,
+    pub username: String,
+    pub email: String,
+    pub status: AccountStatus,
+    pub is_online: bool,
+    pub created_at: OffsetDateTime,
+}
+
+/// Wyszukiwanie, filtry i sortowanie listy kont w panelu admina.
+#[derive(Default)]
+pub struct CreatorQuery {
+    /// Fragment nazwy użytkownika lub adresu email.
+    pub search: Option<String>,
+    pub status: Option<AccountStatus>,
+    pub online: Option<bool>,
+    pub registered_from: Option<Date>,
+    /// Włącznie z tym dniem.
+    pub registered_to: Option<Date>,
+    pub sort: CreatorSort,
+    pub descending: bool,
+    /// Stronicowanie: klucz ostatniego wiersza poprzedniej strony.
+    pub after: Option<CreatorCursor>,
+}
+
+/// Kursor listy kont: wartości kluczy sortowania ostatniego wiersza strony.
+/// Trzymamy wartości, a nie samo ID, żeby usunięcie tego konta nie urywało listy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreatorCursor {
+    pub id: Uuid,
+    pub username: String,
+    pub status: AccountStatus,
+    pub created_at: OffsetDateTime,
+}
+
+impl CreatorCursor {
+    pub fn from_item(item: &CreatorListItem) -> Self {
+        CreatorCursor {
+            id: item.id,
+            username: item.username.clone(),
+            status: item.status,
+            created_at: item.created_at,
+        }
+    }
+
+    /// Zapis do URL-a: `nanosekundy~status~id~nazwa`. Nazwa jest ostatnia,
+    /// więc jej znaki nie psują podziału.
+    pub fn encode(&self) -> String {
+        format!(
+            "{}~{}~{}~{}",
+            self.created_at.unix_timestamp_nanos(),
+            self.status,
+            self.id,
+            self.username
+        )
+    }
+
+    pub fn decode(value: &str) -> Option<Self> {
+        let mut parts = value.splitn(4, '~');
+        let created_at = parts.next()?.parse().ok()?;
+        let status = parts.next()?.parse().ok()?;
+        let id = parts.next()?.parse().ok()?;
+        let username = parts.next()?.to_string();
+        Some(CreatorCursor {
+            id,
+            username,
+            status,
+            created_at: OffsetDateTime::from_unix_timestamp_nanos(created_at).ok()?,
         })
-        .await
-        .expect("Zadanie hashowania w tle nie powiodło się");
+    }
+}
+
+/// Wzorzec ILIKE "zawiera": `%` i `_` z wyszukiwanej frazy mają znaczyć same siebie.
+fn like_pattern(search: &str) -> String {
+    let mut pattern = String::with_capacity(search.len() + 2);
+    pattern.push('%');
+    for c in search.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            pattern.push('\\');
+        }
+        pattern.push(c);
+    }
+    pattern.push('%');
+    pattern
+}
+
+impl Erika {
+    /// Zakłada konto (rejestracja, CLI). Zwraca ID nowego konta.
+    pub async fn create(
+        username: &str,
+        email: &str,
+        password: &str,
+        db: &PgPool,
+    ) -> Result<Uuid, sqlx::Error> {
+        let password_to_hash = password.to_string();
+
+        // Hashowanie jest kosztowne, więc robimy je w osobnym wątku.
+        // Parametry Argon2 pochodzą z konfiguracji (`password::params`).
+        let password_hash = task::spawn_blocking(move || password::hash(&password_to_hash))
+            .await
+            .expect("Zadanie hashowania w tle nie powiodło się")
+            .expect("Hashowanie hasła nie powiodło się");
 
         let new_id = Uuid::new_v4();
 
@@ -58,14 +272,14 @@ impl Erika {
         sqlx::query!(
             "INSERT INTO erikas (id, username, email, password_hash) VALUES ($1, $2, $3, $4)",
             new_id, // <-- Przekazujemy wygenerowane ID
-            payload.username,
-            payload.email,
+            username,
+            email,
             password_hash
         )
         .execute(db)
         .await?;
 
-        Ok(())
+        Ok(new_id)
     }
 
     /// NOWA METODA: Wyszukuje profil publiczny po nazwie użytkownika (wrażliwe na wielkość liter)
@@ -75,8 +289,8 @@ impl Erika {
     ) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Erika,
-            "SELECT id, username, email, password_hash, profile_image_url, bio, is_online, is_approved FROM erikas
-             WHERE username = $1 AND is_approved = TRUE",
+            r#"SELECT id, username, email, password_hash, profile_image_url, bio, is_online, status as "status: _", status_reason, status_changed_at, last_seen_at, appear_offline FROM erikas
+               WHERE username = $1 AND status = 'Approved'"#,
             username
         )
         .fetch_optional(db)
@@ -90,52 +304,73 @@ impl Erika {
     ) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Erika,
-            "SELECT id, username, email, password_hash, bio, is_online, profile_image_url, is_approved FROM erikas WHERE LOWER(username) = LOWER($1)",
+            r#"SELECT id, username, email, password_hash, profile_image_url, bio, is_online, status as "status: _", status_reason, status_changed_at, last_seen_at, appear_offline FROM erikas WHERE LOWER(username) = LOWER($1)"#,
             username
         )
         .fetch_optional(db)
         .await
     }
 
-    /// Weryfikuje podane hasło z hashem zapisanym w bazie.
-    pub fn verify_password(&self, password: &str) -> bool {
+    /// Weryfikuje podane hasło z hashem zapisanym w bazie. Argon2 jest
+    /// kosztowny, więc liczymy go w `spawn_blocking`, poza executorem.
+    pub async fn verify_password(&self, password: &str) -> bool {
         debug!(
             "Rozpoczynam weryfikację hasła dla użytkownika: {}",
             self.username
-        ); // <-- LOG A
+        );
 
-        // Parsujemy hash zapisany w bazie
-        let parsed_hash = match argon2::password_hash::PasswordHash::new(&self.password_hash) {
-            Ok(hash) => {
-                debug!("Hash z bazy poprawnie sparsowany."); // <-- LOG B (sukces)
-                hash
-            }
-            Err(e) => {
-                debug!("BŁĄD parsowania hasha z bazy: {}", e); // <-- LOG B (błąd)
-                return false;
+        let (password, hash) = (password.to_owned(), self.password_hash.clone());
+        match task::spawn_blocking(move || password::verify(&password, &hash)).await {
+            Ok(Ok(valid)) => {
+                debug!("WYNIK weryfikacji hasła: {}", valid);
+                valid
             }
-        };
-
-        // Weryfikujemy hasło
-        let result = Argon2::default().verify_password(password.as_bytes(), &parsed_hash);
-
-        match result {
-            Ok(_) => {
-                debug!("WYNIK: Hasło jest poprawne."); // <-- LOG C (sukces)
-                true
+            Ok(Err(e)) => {
+                debug!("BŁĄD weryfikacji hasha z bazy: {}", e);
+                false
             }
             Err(e) => {
-                debug!("WYNIK: Hasło jest niepoprawne. Błąd weryfikacji: {}", e); // <-- LOG C (błąd)
+                debug!("Zadanie weryfikacji hasła przerwane: {}", e);
                 false
             }
         }
     }
 
+    /// Gdy konta nie ma: liczymy Argon2 na hashu zastępczym tak samo jak
+    /// przy weryfikacji, żeby czas odpowiedzi nie zdradzał istnienia konta.
+    pub async fn simulate_password_check(password: &str) {
+        let password = password.to_owned();
+        let _ =
+            task::spawn_blocking(move || password::verify(&password, password::dummy_hash())).await;
+    }
+
+    /// Przelicza hash hasła na aktualne parametry Argon2.
+    /// Wołane po udanym logowaniu, gdy `password::needs_rehash` zwróci `true`.
+    /// Aktualizacja dotyczy tylko starego hasha - jeśli w międzyczasie
+    /// hasło zostało zmienione, niczego nie nadpisujemy.
+    pub async fn rehash_password(&self, password: &str, db: &PgPool) -> Result<bool, sqlx::Error> {
+        let password = password.to_string();
+        let new_hash = match task::spawn_blocking(move || password::hash(&password)).await {
+            Ok(Ok(hash)) => hash,
+            _ => return Ok(false),
+        };
+
+        let result = sqlx::query!(
+            "UPDATE erikas SET password_hash = $1 WHERE id = $2 AND password_hash = $3",
+            new_hash,
+            self.id,
+            self.password_hash
+        )
+        .execute(db)
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
     /// Wyszukuje Erikę po jej unikalnym ID (pobranym z sesji).
     pub async fn find_by_id(id: Uuid, db: &PgPool) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Erika,
-            "SELECT id, username, email, password_hash, profile_image_url, bio, is_online, is_approved FROM erikas WHERE id = $1",
+            r#"SELECT id, username, email, password_hash, profile_image_url, bio, is_online, status as "status: _", status_reason, status_changed_at, last_seen_at, appear_offline FROM erikas WHERE id = $1"#,
             id
         )
         .fetch_optional(db)
@@ -146,9 +381,9 @@ impl Erika {
     pub async fn find_active(db: &PgPool) -> Result<Vec<Self>, sqlx::Error> {
         sqlx::query_as!(
             Erika,
-            "SELECT id, username, email, password_hash, profile_image_url, bio, is_online, is_approved FROM erikas
-             WHERE is_approved = TRUE
-             ORDER BY is_online DESC, username"
+            r#"SELECT id, username, email, password_hash, profile_image_url, bio, is_online, status as "status: _", status_reason, status_changed_at, last_seen_at, appear_offline FROM erikas
+               WHERE status = 'Approved'
+               ORDER BY is_online DESC, username"#
         )
         .fetch_all(db).await
     }
@@ -176,36 +411,70 @@ impl Erika {
         Ok(())
     }
 
-    // NOWA METODA: Przełącza status is_online i zwraca nowy status
-    pub async fn toggle_online_status(id: Uuid, db: &PgPool) -> Result<bool, sqlx::Error> {
+    /// Przełącza "pokazuj mnie jako offline" i zwraca nową wartość.
+    /// Włączenie od razu chowa modelkę; wyłączenie pokaże ją przy następnym sygnale.
+    pub async fn toggle_appear_offline(id: Uuid, db: &PgPool) -> Result<bool, sqlx::Error> {
         let result = sqlx::query!(
-            "UPDATE erikas SET is_online = NOT is_online WHERE id = $1 RETURNING is_online",
+            "UPDATE erikas
+             SET appear_offline = NOT appear_offline,
+                 is_online = is_online AND appear_offline
+             WHERE id = $1
+             RETURNING appear_offline",
             id
         )
         .fetch_one(db)
         .await?;
-        Ok(result.is_online)
+        Ok(result.appear_offline)
     }
 
-    // NOWA METODA: Pobiera tylko rolę do weryfikacji w middleware
+    /// Sygnał obecności z panelu kamerki. Online jest tylko zaakceptowane konto,
+    /// które nie wybrało "pokazuj mnie jako offline". Zwraca `true`, jeśli ten
+    /// sygnał właśnie przełączył modelkę na online.
+    pub async fn record_heartbeat(id: Uuid, db: &PgPool) -> Result<bool, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"WITH before AS (SELECT id, is_online FROM erikas WHERE id = $1 FOR UPDATE)
+               UPDATE erikas e
+               SET last_seen_at = NOW(),
+                   is_online = NOT e.appear_offline AND e.status = 'Approved'
+               FROM before
+               WHERE e.id = before.id
+               RETURNING e.is_online, before.is_online as "was_online!""#,
+            id
+        )
+        .fetch_optional(db)
+        .await?;
+        Ok(row.is_some_and(|row| row.is_online && !row.was_online))
+    }
+
+    /// Oznacza jako offline modelki bez sygnału od `timeout_secs` sekund
+    /// i zwraca ich ID.
+    pub async fn expire_presence(timeout_secs: i64, db: &PgPool) -> Result<Vec<Uuid>, sqlx::Error> {
+        let rows = sqlx::query!(
+            "UPDATE erikas SET is_online = FALSE
+             WHERE is_online
+               AND (last_seen_at IS NULL OR last_seen_at < NOW() - make_interval(secs => $1))
+             RETURNING id",
+            timeout_secs as f64
+        )
+        .fetch_all(db)
+        .await?;
+        Ok(rows.into_iter().map(|row| row.id).collect())
+    }
+
+    /// Pobiera tylko dane potrzebne do uwierzytelnienia (ekstraktory w `auth`).
     pub async fn find_by_id_for_auth(
         id: Uuid,
         db: &PgPool,
     ) -> Result<Option<ErikaAuth>, sqlx::Error> {
         sqlx::query_as!(
             ErikaAuth,
-            r#"SELECT role::TEXT as "role!" FROM erikas WHERE id = $1"#,
+            r#"SELECT id, username, role::TEXT as "role!", status as "status: _" FROM erikas WHERE id = $1"#,
             id
         )
         .fetch_optional(db)
         .await
     }
 
-    pub async fn find_all(db: &PgPool) -> Result<Vec<Self>, sqlx::Error> {
-        sqlx::query_as!(Erika, "SELECT id, username, email, password_hash, profile_image_url, bio, is_online, is_approved FROM erikas ORDER BY username")
-            .fetch_all(db).await
-    }
-
     // NOWA METODA: Aktualizuje tylko dane tekstowe (dla admina)
     pub async fn update_profile_details(
         id: Uuid,
@@ -226,11 +495,459 @@ impl Erika {
         Ok(())
     }
 
-    // NOWA METODA: Akceptuje profil Eriki
-    pub async fn approve(id: Uuid, db: &PgPool) -> Result<(), sqlx::Error> {
-        sqlx::query!("UPDATE erikas SET is_approved = TRUE WHERE id = $1", id)
-            .execute(db)
+    /// Zmienia status konta, o ile obecny status wciąż jest równy `from`.
+    /// Zwraca `false`, jeśli ktoś zmienił status w międzyczasie albo konto
+    /// bez potwierdzonej tożsamości (KYC) miałoby zostać zaakceptowane.
+    pub async fn change_status(
+        id: Uuid,
+        from: AccountStatus,
+        to: AccountStatus,
+        reason: &str,
+        db: &PgPool,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "UPDATE erikas
+             SET status = $1, status_reason = $2, status_changed_at = NOW(),
+                 is_online = is_online AND $1 = 'Approved'::account_status
+             WHERE id = $3 AND status = $4
+               AND ($1 <> 'Approved'::account_status OR EXISTS(
+                   SELECT 1 FROM identity_verifications v
+                   WHERE v.erika_id = $3 AND v.status = 'Verified'
+               ))",
+            to as AccountStatus,
+            reason,
+            id,
+            from as AccountStatus
+        )
+        .execute(db)
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Czy to publiczny URL czyjegoś zdjęcia profilowego.
+    pub async fn is_avatar(file_url: &str, db: &PgPool) -> Result<bool, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"SELECT EXISTS(SELECT 1 FROM erikas WHERE profile_image_url = $1) AS "exists!""#,
+            file_url
+        )
+        .fetch_one(db)
+        .await?;
+        Ok(row.exists)
+    }
+
+    /// Dane konta do eksportu RODO - bez hasha hasła.
+    pub async fn export_profile(
+        id: Uuid,
+        db: &PgPool,
+    ) -> Result<Option<serde_json::Value>, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"SELECT id, username, email, role::TEXT as "role!", bio, profile_image_url, is_online,
+                      last_seen_at, appear_offline, status::TEXT as "status!", status_reason, created_at
+               FROM erikas WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(db)
+        .await?;
+
+        Ok(row.map(|row| {
+            serde_json::json!({
+                "id": row.id,
+                "username": row.username,
+                "email": row.email,
+                "role": row.role,
+                "bio": row.bio,
+                "profile_image_url": row.profile_image_url,
+                "is_online": row.is_online,
+                "last_seen_at": row.last_seen_at.and_then(|value| value.format(&Rfc3339).ok()),
+                "appear_offline": row.appear_offline,
+                "status": row.status,
+                "status_reason": row.status_reason,
+                "created_at": row.created_at.format(&Rfc3339).unwrap_or_default(),
+            })
+        }))
+    }
+
+    /// Trwale usuwa konto. Galerie, zdjęcia i sesje znikają kaskadowo.
+    /// Rekordy finansowe zostają do archiwizacji - najpierw odpinamy je od
+    /// konta i czyścimy wiadomości dołączone przez to konto do napiwków.
+    /// Dokumenty weryfikacji i zgody osób współwystępujących zostają
+    /// zastrzeżone na okres przechowywania.
+    pub async fn delete(id: Uuid, db: &PgPool) -> Result<(), sqlx::Error> {
+        let mut tx = db.begin().await?;
+        sqlx::query!(
+            "UPDATE identity_verifications
+             SET erika_id = NULL, restricted_at = NOW(), retain_until = NOW() + make_interval(years => $2)
+             WHERE erika_id = $1",
+            id,
+            verification::RETENTION_YEARS
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            "UPDATE co_performers
+             SET gallery_id = NULL, restricted_at = NOW(), retain_until = NOW() + make_interval(years => $2)
+             WHERE gallery_id IN (SELECT id FROM galleries WHERE erika_id = $1)",
+            id,
+            verification::RETENTION_YEARS
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            "UPDATE gallery_purchases
+             SET erika_id = NULLIF(erika_id, $1), buyer_id = NULLIF(buyer_id, $1)
+             WHERE erika_id = $1 OR buyer_id = $1",
+            id
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            "UPDATE tips
+             SET message = CASE WHEN tipper_id = $1 THEN NULL ELSE message END,
+                 erika_id = NULLIF(erika_id, $1), tipper_id = NULLIF(tipper_id, $1)
+             WHERE erika_id = $1 OR tipper_id = $1",
+            id
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            "UPDATE private_shows
+             SET erika_id = NULLIF(erika_id, $1), viewer_id = NULLIF(viewer_id, $1)
+             WHERE erika_id = $1 OR viewer_id = $1",
+            id
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            "UPDATE wallet_transactions SET erika_id = NULL WHERE erika_id = $1",
+            id
+        )
+        .execute(&mut *tx)
+        .await?;
+        AuditEvent::redact_profile_values(id, &mut tx).await?;
+        sqlx::query!("DELETE FROM erikas WHERE id = $1", id)
+            .execute(&mut *tx)
             .await?;
-        Ok(())
+        tx.commit().await
+    }
+
+    /// Rola konta jako tekst (`None`, jeśli konto nie istnieje).
+    pub async fn find_role(id: Uuid, db: &PgPool) -> Result<Option<String>, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"SELECT role::TEXT as "role!" FROM erikas WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(db)
+        .await?;
+        Ok(row.map(|row| row.role))
+    }
+
+    /// Zmienia rolę konta. Nie odbiera roli ostatniemu adminowi - zwraca wtedy `false`.
+    /// `role` musi być jedną z `ROLES`.
+    pub async fn change_role(id: Uuid, role: &str, db: &PgPool) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "UPDATE erikas SET role = $1::TEXT::user_role
+             WHERE id = $2
+               AND ($1 = 'Admin' OR role <> 'Admin'
+                    OR (SELECT COUNT(*) FROM erikas WHERE role = 'Admin') > 1)",
+            role,
+            id
+        )
+        .execute(db)
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Lista kont pasujących do filtrów, alfabetycznie.
+    pub async fn find_filtered(
+        query: &ErikaQuery,
+        limit: i64,
+        db: &PgPool,
+    ) -> Result<Vec<ErikaSummary>, sqlx::Error> {
+        sqlx::query_as!(
+            ErikaSummary,
+            r#"SELECT id, username, email, role::TEXT as "role!", status as "status: _", created_at
+               FROM erikas
+               WHERE ($1::account_status IS NULL OR status = $1)
+                 AND ($2::TEXT IS NULL OR role::TEXT = $2)
+                 AND ($3::TEXT IS NULL OR username ILIKE $3 OR email ILIKE $3)
+               ORDER BY username
+               LIMIT $4"#,
+            query.status as Option<AccountStatus>,
+            query.role,
+            query.search.as_deref().map(like_pattern),
+            limit
+        )
+        .fetch_all(db)
+        .await
+    }
+
+    /// Liczba kont w każdym statusie.
+    pub async fn count_by_status(db: &PgPool) -> Result<Vec<(AccountStatus, i64)>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"SELECT status as "status: AccountStatus", COUNT(*) as "count!"
+               FROM erikas GROUP BY status ORDER BY status"#
+        )
+        .fetch_all(db)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.status, row.count))
+            .collect())
+    }
+
+    /// Liczba kont w każdej roli.
+    pub async fn count_by_role(db: &PgPool) -> Result<Vec<(String, i64)>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"SELECT role::TEXT as "role!", COUNT(*) as "count!"
+               FROM erikas GROUP BY role ORDER BY role"#
+        )
+        .fetch_all(db)
+        .await?;
+        Ok(rows.into_iter().map(|row| (row.role, row.count)).collect())
+    }
+
+    /// Strona listy kont modelek dla panelu admina (bez kont zespołu i widzów).
+    /// Stronicowanie po kluczu: następna strona
+    /// zaczyna się za kursorem `after` według bieżącego sortowania, więc dopisane
+    /// w międzyczasie konta nie przesuwają listy. Każde sortowanie ma własne
+    /// zapytanie, z kluczem zgodnym z ORDER BY i z indeksem.
+    pub async fn find_page(
+        query: &CreatorQuery,
+        limit: i64,
+        db: &PgPool,
+    ) -> Result<Vec<CreatorListItem>, sqlx::Error> {
+        let pattern = query.search.as_deref().map(like_pattern);
+        let after = query.after.as_ref();
+        let (username, created_at, status, id) = (
+            after.map(|c| c.username.as_str()),
+            after.map(|c| c.created_at),
+            after.map(|c| c.status),
+            after.map(|c| c.id),
+        );
+
+        // Filtry są wspólne, różni się tylko warunek kursora i ORDER BY
+        macro_rules! page {
+            ($keyset:literal, $order:literal, $($cursor:expr),+) => {
+                sqlx::query_as!(
+                    CreatorListItem,
+                    r#"SELECT id, username, email, status as "status: _", is_online, created_at
+                       FROM erikas
+                       WHERE role = 'Erika'
+                         AND ($1::TEXT IS NULL OR username ILIKE $1 OR email ILIKE $1)
+                         AND ($2::account_status IS NULL OR status = $2)
+                         AND ($3::BOOLEAN IS NULL OR is_online = $3)
+                         AND ($4::DATE IS NULL OR created_at >= $4)
+                         AND ($5::DATE IS NULL OR created_at < $5 + 1)
+                         AND "# + $keyset + r#"
+                       ORDER BY "# + $order + r#"
+                       LIMIT $6"#,
+                    pattern,
+                    query.status as Option<AccountStatus>,
+                    query.online,
+                    query.registered_from,
+                    query.registered_to,
+                    limit,
+                    $($cursor),+
+                )
+                .fetch_all(db)
+                .await
+            };
+        }
+
+        // Nazwa użytkownika jest unikalna, więc przy sortowaniu po niej ID nie jest potrzebne
+        match (query.sort, query.descending) {
+            (CreatorSort::Username, false) => {
+                page!("($7::TEXT IS NULL OR username > $7)", "username", username)
+            }
+            (CreatorSort::Username, true) => {
+                page!(
+                    "($7::TEXT IS NULL OR username < $7)",
+                    "username DESC",
+                    username
+                )
+            }
+            (CreatorSort::Registered, false) => page!(
+                "($7::TIMESTAMPTZ IS NULL OR (created_at, id) > ($7, $8))",
+                "created_at, id",
+                created_at,
+                id
+            ),
+            (CreatorSort::Registered, true) => page!(
+                "($7::TIMESTAMPTZ IS NULL OR (created_at, id) < ($7, $8))",
+                "created_at DESC, id DESC",
+                created_at,
+                id
+            ),
+            (CreatorSort::Status, false) => page!(
+                "($7::account_status IS NULL OR (status, username) > ($7, $8))",
+                "status, username",
+                status as Option<AccountStatus>,
+                username
+            ),
+            (CreatorSort::Status, true) => page!(
+                "($7::account_status IS NULL OR (status, username) < ($7, $8))",
+                "status DESC, username DESC",
+                status as Option<AccountStatus>,
+                username
+            ),
+        }
+    }
+
+    /// Liczba kont modelek pasujących do filtrów (bez stronicowania).
+    pub async fn count_filtered(query: &CreatorQuery, db: &PgPool) -> Result<i64, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"SELECT COUNT(*) as "count!" FROM erikas
+               WHERE role = 'Erika'
+                 AND ($1::TEXT IS NULL OR username ILIKE $1 OR email ILIKE $1)
+                 AND ($2::account_status IS NULL OR status = $2)
+                 AND ($3::BOOLEAN IS NULL OR is_online = $3)
+                 AND ($4::DATE IS NULL OR created_at >= $4)
+                 AND ($5::DATE IS NULL OR created_at < $5 + 1)"#,
+            query.search.as_deref().map(like_pattern),
+            query.status as Option<AccountStatus>,
+            query.online,
+            query.registered_from,
+            query.registered_to
+        )
+        .fetch_one(db)
+        .await?;
+        Ok(row.count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn account(username: &str, db: &PgPool) -> Uuid {
+        sqlx::query!(
+            "INSERT INTO erikas (username, email, password_hash) VALUES ($1, $2, 'x') RETURNING id",
+            username,
+            format!("{}@example.com", username)
+        )
+        .fetch_one(db)
+        .await
+        .unwrap()
+        .id
+    }
+
+    async fn usernames(query: &CreatorQuery, db: &PgPool) -> Vec<String> {
+        Erika::find_page(query, 10, db)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|item| item.username)
+            .collect()
+    }
+
+    async fn first(sort: CreatorSort, db: &PgPool) -> CreatorCursor {
+        let query = CreatorQuery {
+            sort,
+            ..Default::default()
+        };
+        CreatorCursor::from_item(&Erika::find_page(&query, 1, db).await.unwrap()[0])
+    }
+
+    #[test]
+    fn like_pattern_escapes_wildcards() {
+        assert_eq!(like_pattern("ann"), "%ann%");
+        assert_eq!(like_pattern("a_n%n\\"), "%a\\_n\\%n\\\\%");
+    }
+
+    #[sqlx::test]
+    async fn search_treats_wildcards_literally(db: PgPool) {
+        account("anna_k", &db).await;
+        account("annaxk", &db).await;
+
+        let query = CreatorQuery {
+            search: Some("a_k".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(usernames(&query, &db).await, ["anna_k"]);
+    }
+
+    #[sqlx::test]
+    async fn creator_list_skips_staff_accounts(db: PgPool) {
+        account("anna", &db).await;
+        let boss = account("boss", &db).await;
+        Erika::change_role(boss, "Admin", &db).await.unwrap();
+
+        let query = CreatorQuery::default();
+        assert_eq!(usernames(&query, &db).await, ["anna"]);
+        assert_eq!(Erika::count_filtered(&query, &db).await.unwrap(), 1);
+    }
+
+    #[sqlx::test]
+    async fn page_continues_after_deleted_cursor_row(db: PgPool) {
+        for username in ["ala", "basia", "celina"] {
+            account(username, &db).await;
+        }
+        for sort in [CreatorSort::Username, CreatorSort::Status] {
+            let cursor = first(sort, &db).await;
+            assert_eq!(
+                CreatorCursor::decode(&cursor.encode()).as_ref(),
+                Some(&cursor)
+            );
+            let query = CreatorQuery {
+                sort,
+                after: Some(cursor),
+                ..Default::default()
+            };
+            assert_eq!(usernames(&query, &db).await, ["basia", "celina"]);
+        }
+
+        // Kursor trzyma wartości kluczy, więc usunięcie wiersza nie urywa listy
+        let cursor = first(CreatorSort::Registered, &db).await;
+        sqlx::query!("DELETE FROM erikas WHERE id = $1", cursor.id)
+            .execute(&db)
+            .await
+            .unwrap();
+        let query = CreatorQuery {
+            sort: CreatorSort::Registered,
+            after: Some(cursor),
+            ..Default::default()
+        };
+        assert_eq!(usernames(&query, &db).await, ["basia", "celina"]);
+    }
+
+    #[sqlx::test]
+    async fn deleting_account_redacts_profile_audit_values(db: PgPool) {
+        use crate::models::audit::{self, AuditAction, NewAuditEvent};
+        use serde_json::json;
+
+        let id = account("anna", &db).await;
+        let event = NewAuditEvent {
+            actor_id: None,
+            actor_username: "boss",
+            action: AuditAction::ProfileUpdated,
+            target_type: "erika",
+            target_id: Some(id),
+            changes: audit::diff(&[
+                ("email", json!("anna@example.com"), json!("anna@poczta.pl")),
+                ("bio", json!(""), json!("")),
+            ]),
+            ip_address: None,
+        };
+        AuditEvent::record(event, &db).await.unwrap();
+
+        // Poza usunięciem konta dziennika nie da się zmienić
+        let update = sqlx::query!("UPDATE audit_events SET changes = '{}'::jsonb")
+            .execute(&db)
+            .await;
+        assert!(update.is_err());
+
+        Erika::delete(id, &db).await.unwrap();
+        let row = sqlx::query!(
+            "SELECT actor_username, changes FROM audit_events WHERE target_id = $1",
+            id
+        )
+        .fetch_one(&db)
+        .await
+        .unwrap();
+        assert_eq!(row.actor_username, "boss");
+        assert_eq!(
+            row.changes,
+            json!({ "email": { "before": audit::REDACTED, "after": audit::REDACTED } })
+        );
     }
 }
@@ -3,47 +3,16 @@
 use bigdecimal::BigDecimal;
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
-use std::str::FromStr;
-use strum::{Display, EnumIter, EnumString};
 use time::OffsetDateTime;
 use uuid::Uuid;
 
-// NOWY ENUM: Musi odpowiadać temu w bazie danych
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, EnumString, EnumIter, Display)]
-#[sqlx(type_name = "gallery_category", rename_all = "PascalCase")]
-#[strum(serialize_all = "PascalCase")]
-pub enum GalleryCategory {
-    Piersi,
-    Tyłek,
-    Cipka,
-    #[strum(serialize = "Całe Ciało")]
-    CałeCiało,
-    #[strum(serialize = "Otwieram Cipkę dla Ciebie")]
-    OtwieramCipkęDlaCiebie,
-    Analne,
-    #[strum(serialize = "Zabawy wibratorem")]
-    ZabawyWibratorem,
-    Orgazm,
-}
-
-// --- DODAJ TEN BLOK KODU ---
-// Ręcznie implementujemy konwersję ze String, delegując do istniejącej implementacji z &str.
-impl TryFrom<String> for GalleryCategory {
-    type Error = strum::ParseError;
-
-    fn try_from(value: String) -> Result<Self, Self::Error> {
-        // Używamy `from_str` z cechy `EnumString`, którą już zaimplementowaliśmy
-        GalleryCategory::from_str(&value)
-    }
-}
-// --- KONIEC NOWEGO BLOKU ---
-
 #[derive(sqlx::FromRow, Clone, Serialize, Deserialize)]
 pub struct Gallery {
     pub id: Uuid,
     pub erika_id: Uuid,
-    #[sqlx(try_from = "String")]
-    pub name: GalleryCategory,
+    pub category_id: Uuid,
+    /// Nazwa kategorii - galeria nie ma własnej nazwy, wyświetlamy kategorię.
+    pub name: String,
     pub description: Option<String>,
     pub price_pln: Option<BigDecimal>,
 
@@ -51,30 +20,36 @@ pub struct Gallery {
     // Atrybut `serde(with ...)` mówi, jak serializować ten typ (to ważne dla API/sesji)
     #[serde(with = "time::serde::rfc3339")]
     pub created_at: OffsetDateTime,
+    /// `None` - szkic, widoczny tylko dla właścicielki.
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub published_at: Option<OffsetDateTime>,
+    /// Ustawione, gdy moderacja ukryła galerię po zgłoszeniu - wtedy nie da się jej opublikować.
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub blocked_at: Option<OffsetDateTime>,
 }
 
 impl Gallery {
     pub async fn create(
         erika_id: Uuid,
-        name: GalleryCategory,
+        category_id: Uuid,
         db: &PgPool,
     ) -> Result<Self, sqlx::Error> {
         let new_id = Uuid::new_v4();
 
         sqlx::query!(
-            "INSERT INTO galleries (id, erika_id, name) VALUES ($1, $2, $3)",
+            "INSERT INTO galleries (id, erika_id, category_id) VALUES ($1, $2, $3)",
             new_id,
             erika_id,
-            // Rzutujemy nasz enum na typ, który sqlx rozumie
-            name as GalleryCategory
+            category_id
         )
         .execute(db)
         .await?;
 
-        // Używamy `query_as!` z jawnym typowaniem kolumny
         let new_gallery = sqlx::query_as!(
             Gallery,
-            r#"SELECT id, erika_id, name as "name: _", description, price_pln, created_at FROM galleries WHERE id = $1"#,
+            "SELECT g.id, g.erika_id, g.category_id, c.name, g.description, g.price_pln, g.created_at, g.published_at, g.blocked_at
+             FROM galleries g JOIN categories c ON c.id = g.category_id
+             WHERE g.id = $1",
             new_id
         )
         .fetch_one(db)
@@ -86,7 +61,9 @@ impl Gallery {
     pub async fn find_by_erika_id(erika_id: Uuid, db: &PgPool) -> Result<Vec<Self>, sqlx::Error> {
         let galleries = sqlx::query_as!(
             Gallery,
-            r#"SELECT id, erika_id, name as "name: _", description, price_pln, created_at FROM galleries WHERE erika_id = $1 ORDER BY created_at DESC"#,
+            "SELECT g.id, g.erika_id, g.category_id, c.name, g.description, g.price_pln, g.created_at, g.published_at, g.blocked_at
+             FROM galleries g JOIN categories c ON c.id = g.category_id
+             WHERE g.erika_id = $1 ORDER BY g.created_at DESC",
             erika_id
         )
         .fetch_all(db)
@@ -94,17 +71,58 @@ impl Gallery {
         Ok(galleries)
     }
 
+    /// Galerie widoczne na publicznym profilu - tylko opublikowane.
+    pub async fn find_published_by_erika_id(
+        erika_id: Uuid,
+        db: &PgPool,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Gallery,
+            "SELECT g.id, g.erika_id, g.category_id, c.name, g.description, g.price_pln, g.created_at, g.published_at, g.blocked_at
+             FROM galleries g JOIN categories c ON c.id = g.category_id
+             WHERE g.erika_id = $1 AND g.published_at IS NOT NULL
+             ORDER BY g.created_at DESC",
+            erika_id
+        )
+        .fetch_all(db)
+        .await
+    }
+
+    /// Publikuje galerię. Warunek w SQL-u, a nie tylko w handlerze: każda
+    /// osoba współwystępująca musi mieć zweryfikowaną zgodę i wiek, a galeria
+    /// nie może być ukryta decyzją moderacji.
+    pub async fn publish(id: Uuid, db: &PgPool) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "UPDATE galleries SET published_at = NOW()
+             WHERE id = $1 AND published_at IS NULL AND blocked_at IS NULL
+               AND NOT EXISTS (
+                   SELECT 1 FROM co_performers WHERE gallery_id = $1 AND status <> 'Verified'
+               )",
+            id
+        )
+        .execute(db)
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    pub async fn unpublish(id: Uuid, db: &PgPool) -> Result<(), sqlx::Error> {
+        sqlx::query!("UPDATE galleries SET published_at = NULL WHERE id = $1", id)
+            .execute(db)
+            .await?;
+        Ok(())
+    }
+
     // NOWA METODA: Aktualizuje szczegóły galerii
     pub async fn update_details(
         id: Uuid,
-        name: GalleryCategory, // Zmieniamy typ na enum
+        category_id: Uuid,
         description: &str,
         price_pln: Option<BigDecimal>,
         db: &PgPool,
     ) -> Result<(), sqlx::Error> {
         sqlx::query!(
-            "UPDATE galleries SET name = $1, description = $2, price_pln = $3 WHERE id = $4",
-            name as GalleryCategory, // Rzutujemy enum
+            "UPDATE galleries SET category_id = $1, description = $2, price_pln = $3 WHERE id = $4",
+            category_id,
             description,
             price_pln,
             id
@@ -114,10 +132,14 @@ impl Gallery {
         Ok(())
     }
 
-    pub async fn find_by_id(id: Uuid, db: &PgPool) -> Result<Option<Self>, sqlx::Error> {
+    /// Galeria widoczna publicznie - opublikowana, a konto właścicielki zaakceptowane.
+    pub async fn find_public_by_id(id: Uuid, db: &PgPool) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Gallery,
-            r#"SELECT id, erika_id, name as "name: _", description, price_pln, created_at FROM galleries WHERE id = $1"#,
+            "SELECT g.id, g.erika_id, g.category_id, c.name, g.description, g.price_pln, g.created_at, g.published_at, g.blocked_at
+             FROM galleries g JOIN categories c ON c.id = g.category_id
+             JOIN erikas e ON e.id = g.erika_id
+             WHERE g.id = $1 AND g.published_at IS NOT NULL AND e.status = 'Approved'",
             id
         )
         .fetch_optional(db)
@@ -132,11 +154,26 @@ impl Gallery {
     ) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Gallery,
-            r#"SELECT id, erika_id, name as "name: _", description, price_pln, created_at FROM galleries WHERE id = $1 AND erika_id = $2"#,
+            "SELECT g.id, g.erika_id, g.category_id, c.name, g.description, g.price_pln, g.created_at, g.published_at, g.blocked_at
+             FROM galleries g JOIN categories c ON c.id = g.category_id
+             WHERE g.id = $1 AND g.erika_id = $2",
             id,
             erika_id
         )
         .fetch_optional(db)
         .await
     }
+
+    /// Dowolna galeria, bez sprawdzania właściciela - tylko dla panelu admina.
+    pub async fn find_by_id(id: Uuid, db: &PgPool) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Gallery,
+            "SELECT g.id, g.erika_id, g.category_id, c.name, g.description, g.price_pln, g.created_at, g.published_at, g.blocked_at
+             FROM galleries g JOIN categories c ON c.id = g.category_id
+             WHERE g.id = $1",
+            id
+        )
+        .fetch_optional(db)
+        .await
+    }
 }
@@ -0,0 +1,46 @@
+// src/models/gallery_purchase.rs
+use bigdecimal::BigDecimal;
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// Zakup galerii widziany z konta kupującego albo sprzedającej - do eksportu danych.
+#[derive(sqlx::FromRow, Clone)]
+pub struct PurchaseEntry {
+    /// `None`, jeśli galeria została usunięta.
+    pub gallery_name: Option<String>,
+    pub seller_username: Option<String>,
+    pub buyer_username: Option<String>,
+    /// Czy to konto kupiło galerię (a nie ją sprzedało).
+    pub is_buyer: bool,
+    pub amount_pln: BigDecimal,
+    pub commission_pln: BigDecimal,
+    pub paid_at: OffsetDateTime,
+}
+
+pub struct GalleryPurchase;
+
+impl GalleryPurchase {
+    /// Zakupy i sprzedaże konta, od najstarszej.
+    pub async fn find_by_account(
+        erika_id: Uuid,
+        db: &PgPool,
+    ) -> Result<Vec<PurchaseEntry>, sqlx::Error> {
+        sqlx::query_as!(
+            PurchaseEntry,
+            r#"SELECT c.name AS "gallery_name?", s.username AS "seller_username?",
+                      b.username AS "buyer_username?", COALESCE(p.buyer_id = $1, false) AS "is_buyer!",
+                      p.amount_pln, p.commission_pln, p.paid_at
+               FROM gallery_purchases p
+               LEFT JOIN galleries g ON g.id = p.gallery_id
+               LEFT JOIN categories c ON c.id = g.category_id
+               LEFT JOIN erikas s ON s.id = p.erika_id
+               LEFT JOIN erikas b ON b.id = p.buyer_id
+               WHERE p.erika_id = $1 OR p.buyer_id = $1
+               ORDER BY p.paid_at"#,
+            erika_id
+        )
+        .fetch_all(db)
+        .await
+    }
+}
@@ -0,0 +1,175 @@
+// src/models/metrics.rs
+
+// Wskaźniki do dashboardu admina. Dni liczymy w strefie czasowej bazy danych;
+// zakres dat jest zawsze domknięty z obu stron. Sprzedaż to galerie, napiwki
+// i rozliczone pokazy prywatne (każdy pokaz to jedna sprzedaż w dniu zakończenia).
+
+use bigdecimal::BigDecimal;
+use sqlx::PgPool;
+use time::Date;
+use uuid::Uuid;
+
+/// Sprzedaż i rejestracje modelek jednego dnia.
+#[derive(sqlx::FromRow, Clone)]
+pub struct DailyMetrics {
+    pub day: Date,
+    pub sales: i64,
+    pub gross_pln: BigDecimal,
+    pub commission_pln: BigDecimal,
+    pub signups: i64,
+}
+
+/// Sprzedaż w jednym miesiącu (przycięta do wybranego zakresu).
+#[derive(sqlx::FromRow, Clone)]
+pub struct MonthlySales {
+    pub month: Date,
+    pub sales: i64,
+    pub gross_pln: BigDecimal,
+    pub commission_pln: BigDecimal,
+}
+
+#[derive(sqlx::FromRow, Clone)]
+pub struct TopGallery {
+    pub gallery_id: Uuid,
+    pub name: String,
+    pub owner: String,
+    pub sales: i64,
+    pub gross_pln: BigDecimal,
+}
+
+/// Stan "na teraz", niezależny od zakresu dat.
+pub struct Snapshot {
+    /// Konta modelek czekające na akceptację.
+    pub pending_creators: i64,
+    pub pending_verifications: i64,
+    pub pending_photos: i64,
+    pub online_creators: i64,
+}
+
+pub struct Metrics;
+
+impl Metrics {
+    /// Jeden wiersz na każdy dzień zakresu, także dni bez sprzedaży.
+    pub async fn daily(
+        from: Date,
+        to: Date,
+        db: &PgPool,
+    ) -> Result<Vec<DailyMetrics>, sqlx::Error> {
+        sqlx::query_as!(
+            DailyMetrics,
+            r#"WITH revenue AS (
+                   SELECT paid_at AS at, amount_pln, commission_pln FROM gallery_purchases
+                   UNION ALL
+                   SELECT created_at, amount_pln, commission_pln FROM tips
+                   UNION ALL
+                   SELECT ended_at, billed_pln, commission_pln FROM private_shows
+                   WHERE status = 'Ended' AND billed_pln > 0
+               )
+               SELECT d::DATE as "day!",
+                      COALESCE(s.sales, 0) as "sales!",
+                      COALESCE(s.gross_pln, 0) as "gross_pln!",
+                      COALESCE(s.commission_pln, 0) as "commission_pln!",
+                      COALESCE(r.signups, 0) as "signups!"
+               FROM generate_series($1::DATE, $2::DATE, INTERVAL '1 day') d
+               LEFT JOIN (
+                   SELECT at::DATE as day, COUNT(*) as sales,
+                          SUM(amount_pln) as gross_pln, SUM(commission_pln) as commission_pln
+                   FROM revenue
+                   WHERE at >= $1::DATE AND at < $2::DATE + 1
+                   GROUP BY 1
+               ) s ON s.day = d::DATE
+               LEFT JOIN (
+                   SELECT created_at::DATE as day, COUNT(*) as signups
+                   FROM erikas
+                   WHERE role = 'Erika' AND created_at >= $1::DATE AND created_at < $2::DATE + 1
+                   GROUP BY 1
+               ) r ON r.day = d::DATE
+               ORDER BY d"#,
+            from,
+            to
+        )
+        .fetch_all(db)
+        .await
+    }
+
+    /// Sprzedaż w miesiącach kalendarzowych, które obejmuje zakres.
+    pub async fn monthly(
+        from: Date,
+        to: Date,
+        db: &PgPool,
+    ) -> Result<Vec<MonthlySales>, sqlx::Error> {
+        sqlx::query_as!(
+            MonthlySales,
+            r#"WITH revenue AS (
+                   SELECT paid_at AS at, amount_pln, commission_pln FROM gallery_purchases
+                   UNION ALL
+                   SELECT created_at, amount_pln, commission_pln FROM tips
+                   UNION ALL
+                   SELECT ended_at, billed_pln, commission_pln FROM private_shows
+                   WHERE status = 'Ended' AND billed_pln > 0
+               )
+               SELECT m::DATE as "month!",
+                      COALESCE(s.sales, 0) as "sales!",
+                      COALESCE(s.gross_pln, 0) as "gross_pln!",
+                      COALESCE(s.commission_pln, 0) as "commission_pln!"
+               FROM generate_series(date_trunc('month', $1::DATE), $2::DATE, INTERVAL '1 month') m
+               LEFT JOIN (
+                   SELECT date_trunc('month', at)::DATE as month, COUNT(*) as sales,
+                          SUM(amount_pln) as gross_pln, SUM(commission_pln) as commission_pln
+                   FROM revenue
+                   WHERE at >= $1::DATE AND at < $2::DATE + 1
+                   GROUP BY 1
+               ) s ON s.month = m::DATE
+               ORDER BY m"#,
+            from,
+            to
+        )
+        .fetch_all(db)
+        .await
+    }
+
+    /// Galerie z najwyższą sprzedażą w zakresie. Sprzedaż usuniętych galerii pomijamy.
+    pub async fn top_galleries(
+        from: Date,
+        to: Date,
+        limit: i64,
+        db: &PgPool,
+    ) -> Result<Vec<TopGallery>, sqlx::Error> {
+        sqlx::query_as!(
+            TopGallery,
+            r#"SELECT g.id as "gallery_id!", c.name as "name!", e.username as "owner!",
+                      COUNT(*) as "sales!", SUM(p.amount_pln) as "gross_pln!"
+               FROM gallery_purchases p
+               JOIN galleries g ON g.id = p.gallery_id
+               JOIN categories c ON c.id = g.category_id
+               JOIN erikas e ON e.id = g.erika_id
+               WHERE p.paid_at >= $1::DATE AND p.paid_at < $2::DATE + 1
+               GROUP BY g.id, c.name, e.username
+               ORDER BY 5 DESC, 4 DESC
+               LIMIT $3"#,
+            from,
+            to,
+            limit
+        )
+        .fetch_all(db)
+        .await
+    }
+
+    pub async fn snapshot(db: &PgPool) -> Result<Snapshot, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"SELECT
+                 (SELECT COUNT(*) FROM erikas WHERE role = 'Erika' AND status = 'Pending') as "pending_creators!",
+                 (SELECT COUNT(*) FROM identity_verifications WHERE status = 'Pending') as "pending_verifications!",
+                 (SELECT COUNT(*) FROM photos WHERE status = 'Pending') as "pending_photos!",
+                 (SELECT COUNT(*) FROM erikas WHERE role = 'Erika' AND is_online) as "online_creators!""#
+        )
+        .fetch_one(db)
+        .await?;
+        Ok(Snapshot {
+            pending_creators: row.pending_creators,
+            pending_verifications: row.pending_verifications,
+            pending_photos: row.pending_photos,
+            online_creators: row.online_creators,
+        })
+    }
+}
@@ -1,3 +1,21 @@
+pub mod account_job;
+pub mod admin_notice;
+pub mod announcement;
+pub mod audit;
+pub mod category;
+pub mod co_performer;
 pub mod erika;
 pub mod gallery;
+pub mod gallery_purchase;
+pub mod metrics;
+pub mod password_reset;
+pub mod permission;
 pub mod photo;
+pub mod private_show;
+pub mod report;
+pub mod site_settings;
+pub mod stream_session;
+pub mod tip;
+pub mod user_session;
+pub mod verification;
+pub mod wallet;
@@ -0,0 +1,103 @@
+// src/models/password_reset.rs
+use crate::password;
+use sqlx::PgPool;
+use time::Duration;
+use tokio::task;
+use uuid::Uuid;
+
+/// Jak długo link do zmiany hasła jest ważny.
+pub const RESET_TTL: Duration = Duration::hours(24);
+
+/// Konto, do którego prowadzi ważny link.
+#[derive(sqlx::FromRow, Clone)]
+pub struct PasswordResetTarget {
+    pub erika_id: Uuid,
+    pub username: String,
+}
+
+pub struct PasswordReset;
+
+impl PasswordReset {
+    /// Wydaje nowy token i unieważnia wcześniejsze, niewykorzystane.
+    /// Zwraca token w postaci jawnej - w bazie zostaje tylko jego skrót SHA-256.
+    pub async fn issue(erika_id: Uuid, db: &PgPool) -> Result<String, sqlx::Error> {
+        // Dwa UUID v4 to 244 losowe bity z generatora systemowego
+        let token = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+        let expires_in = RESET_TTL.whole_seconds() as f64;
+
+        let mut tx = db.begin().await?;
+        sqlx::query!(
+            "DELETE FROM password_resets WHERE erika_id = $1 AND used_at IS NULL",
+            erika_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            "INSERT INTO password_resets (erika_id, token_hash, expires_at)
+             VALUES ($1, sha256(convert_to($2, 'UTF8')), NOW() + make_interval(secs => $3))",
+            erika_id,
+            token,
+            expires_in
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(token)
+    }
+
+    /// Konto przypisane do tokenu, o ile link nie wygasł i nie został użyty.
+    pub async fn find_valid(
+        token: &str,
+        db: &PgPool,
+    ) -> Result<Option<PasswordResetTarget>, sqlx::Error> {
+        sqlx::query_as!(
+            PasswordResetTarget,
+            "SELECT r.erika_id, e.username
+             FROM password_resets r JOIN erikas e ON e.id = r.erika_id
+             WHERE r.token_hash = sha256(convert_to($1, 'UTF8'))
+               AND r.used_at IS NULL AND r.expires_at > NOW()",
+            token
+        )
+        .fetch_optional(db)
+        .await
+    }
+
+    /// Zużywa token i ustawia nowe hasło w jednej transakcji.
+    /// Zwraca `None`, jeśli token jest nieważny (np. użyty w innej karcie).
+    pub async fn redeem(
+        token: &str,
+        new_password: &str,
+        db: &PgPool,
+    ) -> Result<Option<Uuid>, sqlx::Error> {
+        let new_password = new_password.to_string();
+        let password_hash = task::spawn_blocking(move || password::hash(&new_password))
+            .await
+            .expect("Zadanie hashowania w tle nie powiodło się")
+            .expect("Hashowanie hasła nie powiodło się");
+
+        let mut tx = db.begin().await?;
+        let used = sqlx::query!(
+            "UPDATE password_resets SET used_at = NOW()
+             WHERE token_hash = sha256(convert_to($1, 'UTF8'))
+               AND used_at IS NULL AND expires_at > NOW()
+             RETURNING erika_id",
+            token
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(used) = used else {
+            tx.rollback().await?;
+            return Ok(None);
+        };
+
+        sqlx::query!(
+            "UPDATE erikas SET password_hash = $1 WHERE id = $2",
+            password_hash,
+            used.erika_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(Some(used.erika_id))
+    }
+}
@@ -0,0 +1,83 @@
+// src/models/permission.rs
+
+// Macierz rola -> uprawnienia jest celowo stała, w kodzie: każda zmiana tego,
+// co wolno danej roli, przechodzi przez przegląd kodu. Ekran "Zespół" nadaje
+// tylko role i pokazuje macierz do odczytu; zmiany ról trafiają do dziennika audytu.
+
+use strum::{Display, EnumIter, EnumString};
+
+/// Uprawnienia zespołu. Trasy panelu admina sprawdzają uprawnienie,
+/// a nie nazwę roli - rola to tylko zestaw uprawnień z `ROLE_PERMISSIONS`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, EnumIter, Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum Permission {
+    /// Kolejka zdjęć i zgłoszenia.
+    ModerateContent,
+    /// Weryfikacja tożsamości, osoby współwystępujące, status konta.
+    ApproveCreators,
+    /// Lista kont, edycja profilu i galerii, wylogowanie ze wszystkich urządzeń.
+    EditProfiles,
+    /// Saldo napiwków modelek: doładowania i wypłaty.
+    ManagePayouts,
+    /// Kategorie i ustawienia serwisu.
+    ManageSettings,
+    /// Nadawanie ról zespołu.
+    ManageStaff,
+    ViewAuditLog,
+    /// Sprzedaż, prowizje i wskaźniki na stronie głównej panelu.
+    ViewMetrics,
+    /// Podgląd serwisu "jako" wybrana modelka - dla pomocy technicznej.
+    Impersonate,
+    /// Ogłoszenia serwisu i wiadomości do modelek.
+    SendAnnouncements,
+}
+
+impl Permission {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Permission::ModerateContent => "Moderacja treści",
+            Permission::ApproveCreators => "Akceptacja modelek",
+            Permission::EditProfiles => "Edycja profili",
+            Permission::ManagePayouts => "Saldo i wypłaty",
+            Permission::ManageSettings => "Ustawienia serwisu",
+            Permission::ManageStaff => "Role zespołu",
+            Permission::ViewAuditLog => "Dziennik audytu",
+            Permission::ViewMetrics => "Statystyki i sprzedaż",
+            Permission::Impersonate => "Podgląd konta jako modelka",
+            Permission::SendAnnouncements => "Ogłoszenia i wiadomości",
+        }
+    }
+}
+
+/// Role, które daje się nadać w ekranie "Zespół". Pozostałe (`Erika`, `User`)
+/// nie mają żadnych uprawnień panelu admina.
+pub const STAFF_ROLES: &[&str] = &["Admin", "Moderator"];
+
+/// Macierz uprawnień: rola -> co wolno jej robić w panelu admina.
+pub const ROLE_PERMISSIONS: &[(&str, &[Permission])] = &[
+    (
+        "Admin",
+        &[
+            Permission::ModerateContent,
+            Permission::ApproveCreators,
+            Permission::EditProfiles,
+            Permission::ManagePayouts,
+            Permission::ManageSettings,
+            Permission::ManageStaff,
+            Permission::ViewAuditLog,
+            Permission::ViewMetrics,
+            Permission::Impersonate,
+            Permission::SendAnnouncements,
+        ],
+    ),
+    ("Moderator", &[Permission::ModerateContent]),
+];
+
+/// Uprawnienia danej roli; nieznana rola nie ma żadnych.
+pub fn permissions_for(role: &str) -> &'static [Permission] {
+    ROLE_PERMISSIONS
+        .iter()
+        .find(|(name, _)| *name == role)
+        .map(|(_, permissions)| *permissions)
+        .unwrap_or(&[])
+}
@@ -1,9 +1,36 @@
 // src/models/photo.rs
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
+use std::path::{Path, PathBuf};
+use strum::Display;
 use time::OffsetDateTime;
 use uuid::Uuid;
 
+/// Katalog publicznych plików (`/uploads/...`).
+pub const UPLOADS_DIR: &str = "uploads";
+
+/// Katalog poza `uploads`, do którego trafiają pliki odrzuconych zdjęć.
+pub const QUARANTINE_DIR: &str = "private/rejected";
+
+/// Status moderacji zdjęcia. Publicznie widoczne są tylko `Approved`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type, Display)]
+#[sqlx(type_name = "photo_status")]
+pub enum PhotoStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+impl PhotoStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PhotoStatus::Pending => "Czeka na moderację",
+            PhotoStatus::Approved => "Zaakceptowane",
+            PhotoStatus::Rejected => "Odrzucone",
+        }
+    }
+}
+
 #[derive(sqlx::FromRow, Clone, Serialize, Deserialize)]
 pub struct Photo {
     pub id: Uuid,
@@ -12,10 +39,51 @@ pub struct Photo {
     pub description: Option<String>,
     #[serde(with = "time::serde::rfc3339")]
     pub created_at: OffsetDateTime,
+    pub status: PhotoStatus,
+    pub rejection_reason: Option<String>,
+}
+
+/// Co trzeba wiedzieć o zdjęciu, żeby zdecydować, komu wydać jego plik.
+pub struct PhotoAccess {
+    pub erika_id: Uuid,
+    pub status: PhotoStatus,
+    /// Zaakceptowane, nieukryte, w opublikowanej galerii zaakceptowanego konta.
+    pub is_public: bool,
+}
+
+/// Zdjęcie zmienione decyzją moderacji.
+pub struct ModeratedPhoto {
+    pub id: Uuid,
+    pub file_url: String,
+}
+
+/// Zdjęcie w kolejce moderacji, razem z autorką i galerią.
+#[derive(sqlx::FromRow, Clone)]
+pub struct ModerationItem {
+    pub id: Uuid,
+    pub file_url: String,
+    pub created_at: OffsetDateTime,
+    pub erika_id: Uuid,
+    pub username: String,
+    pub gallery_name: String,
+}
+
+/// Ścieżka pliku zdjęcia na dysku. Odrzucone leżą w kwarantannie, poza `/uploads`.
+pub fn disk_path(file_url: &str, status: PhotoStatus) -> PathBuf {
+    let name = file_url.rsplit('/').next().unwrap_or(file_url);
+    match status {
+        PhotoStatus::Rejected => Path::new(QUARANTINE_DIR).join(name),
+        PhotoStatus::Pending | PhotoStatus::Approved => Path::new(UPLOADS_DIR).join(name),
+    }
 }
 
 impl Photo {
+    pub fn disk_path(&self) -> PathBuf {
+        disk_path(&self.file_url, self.status)
+    }
+
     /// Zapisuje informacje o nowym zdjęciu w bazie danych.
+    /// Nowe zdjęcie trafia do kolejki moderacji (status `Pending`).
     pub async fn create(
         gallery_id: Uuid,
         file_url: &str,
@@ -23,7 +91,8 @@ impl Photo {
     ) -> Result<Self, sqlx::Error> {
         let photo = sqlx::query_as!(
             Photo,
-            "INSERT INTO photos (gallery_id, file_url) VALUES ($1, $2) RETURNING *",
+            r#"INSERT INTO photos (gallery_id, file_url) VALUES ($1, $2)
+               RETURNING id, gallery_id, file_url, description, created_at, status as "status: _", rejection_reason"#,
             gallery_id,
             file_url
         )
@@ -32,14 +101,16 @@ impl Photo {
         Ok(photo)
     }
 
-    /// Pobiera wszystkie zdjęcia dla danej galerii.
+    /// Pobiera wszystkie zdjęcia dla danej galerii (także niezaakceptowane -
+    /// dla właścicielki galerii i eksportu danych).
     pub async fn find_by_gallery_id(
         gallery_id: Uuid,
         db: &PgPool,
     ) -> Result<Vec<Self>, sqlx::Error> {
         let photos = sqlx::query_as!(
             Photo,
-            "SELECT * FROM photos WHERE gallery_id = $1 ORDER BY created_at ASC",
+            r#"SELECT id, gallery_id, file_url, description, created_at, status as "status: _", rejection_reason
+               FROM photos WHERE gallery_id = $1 ORDER BY created_at ASC"#,
             gallery_id
         )
         .fetch_all(db)
@@ -47,6 +118,24 @@ impl Photo {
         Ok(photos)
     }
 
+    /// Liczba zaakceptowanych zdjęć w każdej galerii danej Eriki.
+    /// Widoki publiczne i kupujących liczą tylko zdjęcia po moderacji.
+    pub async fn count_approved_by_erika_id(
+        erika_id: Uuid,
+        db: &PgPool,
+    ) -> Result<Vec<(Uuid, i64)>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"SELECT p.gallery_id, COUNT(*) as "count!"
+               FROM photos p JOIN galleries g ON g.id = p.gallery_id
+               WHERE g.erika_id = $1 AND p.status = 'Approved'
+               GROUP BY p.gallery_id"#,
+            erika_id
+        )
+        .fetch_all(db)
+        .await?;
+        Ok(rows.into_iter().map(|r| (r.gallery_id, r.count)).collect())
+    }
+
     pub async fn delete(id: Uuid, db: &PgPool) -> Result<(), sqlx::Error> {
         sqlx::query!("DELETE FROM photos WHERE id = $1", id)
             .execute(db)
@@ -55,8 +144,100 @@ impl Photo {
     }
 
     pub async fn find_by_id(id: Uuid, db: &PgPool) -> Result<Option<Self>, sqlx::Error> {
-        sqlx::query_as!(Photo, "SELECT * FROM photos WHERE id = $1", id)
-            .fetch_optional(db)
-            .await
+        sqlx::query_as!(
+            Photo,
+            r#"SELECT id, gallery_id, file_url, description, created_at, status as "status: _", rejection_reason
+               FROM photos WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(db)
+        .await
+    }
+
+    /// Autorka, status i widoczność zdjęcia o podanym publicznym URL-u.
+    pub async fn find_access(
+        file_url: &str,
+        db: &PgPool,
+    ) -> Result<Option<PhotoAccess>, sqlx::Error> {
+        sqlx::query_as!(
+            PhotoAccess,
+            r#"SELECT g.erika_id, p.status as "status: _",
+                      (p.status = 'Approved' AND p.hidden_at IS NULL
+                       AND g.published_at IS NOT NULL AND g.blocked_at IS NULL
+                       AND e.status = 'Approved') as "is_public!"
+               FROM photos p
+               JOIN galleries g ON g.id = p.gallery_id
+               JOIN erikas e ON e.id = g.erika_id
+               WHERE p.file_url = $1"#,
+            file_url
+        )
+        .fetch_optional(db)
+        .await
+    }
+
+    /// Autorka publicznie widocznego zdjęcia - tylko takie można zgłosić.
+    pub async fn find_public_erika_id(id: Uuid, db: &PgPool) -> Result<Option<Uuid>, sqlx::Error> {
+        let row = sqlx::query!(
+            "SELECT g.erika_id
+             FROM photos p JOIN galleries g ON g.id = p.gallery_id
+             WHERE p.id = $1 AND p.status = 'Approved' AND p.hidden_at IS NULL
+               AND g.published_at IS NOT NULL AND g.blocked_at IS NULL",
+            id
+        )
+        .fetch_optional(db)
+        .await?;
+        Ok(row.map(|r| r.erika_id))
+    }
+
+    /// Kolejka moderacji: najstarsze oczekujące zdjęcia.
+    pub async fn find_pending(limit: i64, db: &PgPool) -> Result<Vec<ModerationItem>, sqlx::Error> {
+        sqlx::query_as!(
+            ModerationItem,
+            r#"SELECT p.id, p.file_url, p.created_at,
+                      e.id as erika_id, e.username, c.name as gallery_name
+               FROM photos p
+               JOIN galleries g ON g.id = p.gallery_id
+               JOIN categories c ON c.id = g.category_id
+               JOIN erikas e ON e.id = g.erika_id
+               WHERE p.status = 'Pending'
+               ORDER BY p.created_at ASC
+               LIMIT $1"#,
+            limit
+        )
+        .fetch_all(db)
+        .await
+    }
+
+    pub async fn count_pending(db: &PgPool) -> Result<i64, sqlx::Error> {
+        let row =
+            sqlx::query!(r#"SELECT COUNT(*) as "count!" FROM photos WHERE status = 'Pending'"#)
+                .fetch_one(db)
+                .await?;
+        Ok(row.count)
+    }
+
+    /// Ustawia status moderacji dla podanych zdjęć, które wciąż czekają w kolejce.
+    /// Zwraca faktycznie zmienione zdjęcia - dwóch moderatorów nie
+    /// nadpisze nawzajem swoich decyzji.
+    pub async fn moderate(
+        ids: &[Uuid],
+        status: PhotoStatus,
+        rejection_reason: Option<&str>,
+        moderator_id: Uuid,
+        db: &PgPool,
+    ) -> Result<Vec<ModeratedPhoto>, sqlx::Error> {
+        sqlx::query_as!(
+            ModeratedPhoto,
+            "UPDATE photos
+             SET status = $1, rejection_reason = $2, moderated_by = $3, moderated_at = NOW()
+             WHERE id = ANY($4) AND status = 'Pending'
+             RETURNING id, file_url",
+            status as PhotoStatus,
+            rejection_reason,
+            moderator_id,
+            ids
+        )
+        .fetch_all(db)
+        .await
     }
 }
@@ -0,0 +1,732 @@
+// src/models/private_show.rs
+use crate::models::wallet::{Wallet, WalletDebit};
+use bigdecimal::BigDecimal;
+use sqlx::PgPool;
+use strum::Display;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// Tytuł transmisji zakładanej dla zaakceptowanego pokazu.
+pub const PRIVATE_SESSION_TITLE: &str = "Pokaz prywatny";
+
+#[derive(Debug, Clone, Copy, PartialEq, sqlx::Type, Display)]
+#[sqlx(type_name = "private_show_status")]
+pub enum PrivateShowStatus {
+    Requested,
+    Declined,
+    Cancelled,
+    Active,
+    Ended,
+}
+
+impl PrivateShowStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PrivateShowStatus::Requested => "Czeka na odpowiedź",
+            PrivateShowStatus::Declined => "Odrzucona",
+            PrivateShowStatus::Cancelled => "Anulowana",
+            PrivateShowStatus::Active => "Trwa",
+            PrivateShowStatus::Ended => "Zakończony",
+        }
+    }
+}
+
+/// Dlaczego pokaz się skończył.
+#[derive(Debug, Clone, Copy, PartialEq, sqlx::Type, Display)]
+#[sqlx(type_name = "private_show_end_reason")]
+pub enum PrivateShowEndReason {
+    Creator,
+    Viewer,
+    /// Na saldzie widza zabrakło na kolejną minutę.
+    Funds,
+    /// Strona pokazu u widza przestała wysyłać sygnał obecności.
+    ViewerGone,
+    /// Modelka przestała być online (panel kamerki zamknięty).
+    CreatorOffline,
+}
+
+impl PrivateShowEndReason {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PrivateShowEndReason::Creator => "zakończony przez modelkę",
+            PrivateShowEndReason::Viewer => "zakończony przez widza",
+            PrivateShowEndReason::Funds => "skończyły się środki na saldzie",
+            PrivateShowEndReason::ViewerGone => "widz opuścił pokaz",
+            PrivateShowEndReason::CreatorOffline => "modelka rozłączyła się",
+        }
+    }
+}
+
+#[derive(sqlx::FromRow, Clone)]
+pub struct PrivateShow {
+    pub id: Uuid,
+    pub erika_id: Option<Uuid>,
+    pub viewer_id: Option<Uuid>,
+    pub erika_username: Option<String>,
+    pub viewer_username: Option<String>,
+    pub status: PrivateShowStatus,
+    pub rate_pln: BigDecimal,
+    pub minutes_billed: i32,
+    pub billed_pln: BigDecimal,
+    pub commission_pln: BigDecimal,
+    pub end_reason: Option<PrivateShowEndReason>,
+    pub requested_at: OffsetDateTime,
+    pub started_at: Option<OffsetDateTime>,
+    pub ended_at: Option<OffsetDateTime>,
+}
+
+/// Wynik akceptacji prośby przez modelkę.
+pub enum AcceptOutcome {
+    /// Pokaz trwa; `ended_public` to transmisja publiczna zakończona przy starcie.
+    Started {
+        ended_public: Option<Uuid>,
+    },
+    /// Modelka ma już trwający pokaz prywatny.
+    Busy,
+    /// Widza nie stać na pierwszą minutę - prośba została anulowana.
+    InsufficientFunds,
+    NotFound,
+}
+
+/// Wynik pobrania kolejnej minuty pokazu.
+#[derive(Debug, PartialEq)]
+pub enum MinuteCharge {
+    Charged,
+    /// Pokaz się nie toczy albo kolejna minuta jeszcze się nie zaczęła.
+    NotDue,
+    /// Na saldzie widza zabrakło na kolejną minutę - pokaz trzeba zakończyć.
+    InsufficientFunds,
+}
+
+/// Stawka modelki za minutę pokazu prywatnego.
+pub struct PrivateShowRate;
+
+impl PrivateShowRate {
+    pub async fn find(erika_id: Uuid, db: &PgPool) -> Result<Option<BigDecimal>, sqlx::Error> {
+        let row = sqlx::query!(
+            "SELECT rate_pln FROM private_show_rates WHERE erika_id = $1",
+            erika_id
+        )
+        .fetch_optional(db)
+        .await?;
+        Ok(row.map(|row| row.rate_pln))
+    }
+
+    pub async fn set(
+        erika_id: Uuid,
+        rate_pln: &BigDecimal,
+        db: &PgPool,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "INSERT INTO private_show_rates (erika_id, rate_pln) VALUES ($1, $2)
+             ON CONFLICT (erika_id) DO UPDATE SET rate_pln = EXCLUDED.rate_pln, updated_at = NOW()",
+            erika_id,
+            rate_pln
+        )
+        .execute(db)
+        .await?;
+        Ok(())
+    }
+
+    /// Wyłącza przyjmowanie nowych próśb. Trwający pokaz zostaje przy starej stawce.
+    pub async fn clear(erika_id: Uuid, db: &PgPool) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "DELETE FROM private_show_rates WHERE erika_id = $1",
+            erika_id
+        )
+        .execute(db)
+        .await?;
+        Ok(())
+    }
+}
+
+impl PrivateShow {
+    /// Prośba widza o pokaz po aktualnej stawce modelki. Zwraca `None`, jeśli
+    /// widz ma już otwartą prośbę albo trwający pokaz.
+    pub async fn request(
+        erika_id: Uuid,
+        viewer_id: Uuid,
+        rate_pln: &BigDecimal,
+        db: &PgPool,
+    ) -> Result<Option<Uuid>, sqlx::Error> {
+        let row = sqlx::query!(
+            "INSERT INTO private_shows (erika_id, viewer_id, rate_pln) VALUES ($1, $2, $3)
+             ON CONFLICT (viewer_id) WHERE status IN ('Requested', 'Active') DO NOTHING
+             RETURNING id",
+            erika_id,
+            viewer_id,
+            rate_pln
+        )
+        .fetch_optional(db)
+        .await?;
+        Ok(row.map(|row| row.id))
+    }
+
+    pub async fn find_by_id(id: Uuid, db: &PgPool) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            PrivateShow,
+            r#"SELECT p.id, p.erika_id, p.viewer_id, e.username AS "erika_username?", v.username AS "viewer_username?",
+                      p.status AS "status: _", p.rate_pln, p.minutes_billed, p.billed_pln, p.commission_pln,
+                      p.end_reason AS "end_reason: _", p.requested_at, p.started_at, p.ended_at
+               FROM private_shows p
+               LEFT JOIN erikas e ON e.id = p.erika_id
+               LEFT JOIN erikas v ON v.id = p.viewer_id
+               WHERE p.id = $1"#,
+            id
+        )
+        .fetch_optional(db)
+        .await
+    }
+
+    /// Otwarta prośba albo trwający pokaz widza.
+    pub async fn find_open_for_viewer(
+        viewer_id: Uuid,
+        db: &PgPool,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            PrivateShow,
+            r#"SELECT p.id, p.erika_id, p.viewer_id, e.username AS "erika_username?", v.username AS "viewer_username?",
+                      p.status AS "status: _", p.rate_pln, p.minutes_billed, p.billed_pln, p.commission_pln,
+                      p.end_reason AS "end_reason: _", p.requested_at, p.started_at, p.ended_at
+               FROM private_shows p
+               LEFT JOIN erikas e ON e.id = p.erika_id
+               LEFT JOIN erikas v ON v.id = p.viewer_id
+               WHERE p.viewer_id = $1 AND p.status IN ('Requested', 'Active')"#,
+            viewer_id
+        )
+        .fetch_optional(db)
+        .await
+    }
+
+    /// Prośby i trwający pokaz modelki - do panelu kamerki.
+    pub async fn find_open_for_erika(
+        erika_id: Uuid,
+        db: &PgPool,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            PrivateShow,
+            r#"SELECT p.id, p.erika_id, p.viewer_id, e.username AS "erika_username?", v.username AS "viewer_username?",
+                      p.status AS "status: _", p.rate_pln, p.minutes_billed, p.billed_pln, p.commission_pln,
+                      p.end_reason AS "end_reason: _", p.requested_at, p.started_at, p.ended_at
+               FROM private_shows p
+               LEFT JOIN erikas e ON e.id = p.erika_id
+               LEFT JOIN erikas v ON v.id = p.viewer_id
+               WHERE p.erika_id = $1 AND p.status IN ('Requested', 'Active')
+               ORDER BY p.requested_at"#,
+            erika_id
+        )
+        .fetch_all(db)
+        .await
+    }
+
+    /// Pokazy, w których konto było modelką albo widzem - do eksportu danych.
+    pub async fn find_by_account(erika_id: Uuid, db: &PgPool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            PrivateShow,
+            r#"SELECT p.id, p.erika_id, p.viewer_id, e.username AS "erika_username?", v.username AS "viewer_username?",
+                      p.status AS "status: _", p.rate_pln, p.minutes_billed, p.billed_pln, p.commission_pln,
+                      p.end_reason AS "end_reason: _", p.requested_at, p.started_at, p.ended_at
+               FROM private_shows p
+               LEFT JOIN erikas e ON e.id = p.erika_id
+               LEFT JOIN erikas v ON v.id = p.viewer_id
+               WHERE p.erika_id = $1 OR p.viewer_id = $1
+               ORDER BY p.requested_at"#,
+            erika_id
+        )
+        .fetch_all(db)
+        .await
+    }
+
+    pub async fn decline(id: Uuid, erika_id: Uuid, db: &PgPool) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "UPDATE private_shows SET status = 'Declined', ended_at = NOW()
+             WHERE id = $1 AND erika_id = $2 AND status = 'Requested'",
+            id,
+            erika_id
+        )
+        .execute(db)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn cancel(id: Uuid, viewer_id: Uuid, db: &PgPool) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "UPDATE private_shows SET status = 'Cancelled', ended_at = NOW()
+             WHERE id = $1 AND viewer_id = $2 AND status = 'Requested'",
+            id,
+            viewer_id
+        )
+        .execute(db)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Akceptuje prośbę: kończy trwającą transmisję publiczną, pobiera
+    /// z salda widza pierwszą minutę i zakłada prywatną transmisję.
+    pub async fn accept(
+        id: Uuid,
+        erika_id: Uuid,
+        db: &PgPool,
+    ) -> Result<AcceptOutcome, sqlx::Error> {
+        let mut tx = db.begin().await?;
+        let Some(show) = sqlx::query!(
+            "SELECT viewer_id, rate_pln FROM private_shows
+             WHERE id = $1 AND erika_id = $2 AND status = 'Requested'
+             FOR UPDATE",
+            id,
+            erika_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        else {
+            return Ok(AcceptOutcome::NotFound);
+        };
+
+        let busy = sqlx::query!(
+            r#"SELECT EXISTS(
+                 SELECT 1 FROM private_shows WHERE erika_id = $1 AND status = 'Active'
+               ) AS "busy!""#,
+            erika_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        if busy.busy {
+            return Ok(AcceptOutcome::Busy);
+        }
+
+        let charged = match show.viewer_id {
+            Some(viewer_id) => {
+                Wallet::debit(
+                    viewer_id,
+                    &show.rate_pln,
+                    WalletDebit::PrivateShow(id),
+                    &mut tx,
+                )
+                .await?
+            }
+            None => false,
+        };
+        if !charged {
+            sqlx::query!(
+                "UPDATE private_shows SET status = 'Cancelled', end_reason = 'Funds', ended_at = NOW()
+                 WHERE id = $1",
+                id
+            )
+            .execute(&mut *tx)
+            .await?;
+            tx.commit().await?;
+            return Ok(AcceptOutcome::InsufficientFunds);
+        }
+
+        let ended_public = sqlx::query!(
+            "UPDATE stream_sessions SET ended_at = NOW()
+             WHERE erika_id = $1 AND started_at IS NOT NULL AND ended_at IS NULL
+             RETURNING id",
+            erika_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        .map(|row| row.id);
+
+        let session = sqlx::query!(
+            "INSERT INTO stream_sessions (erika_id, title, started_at, is_private)
+             VALUES ($1, $2, NOW(), TRUE)
+             RETURNING id",
+            erika_id,
+            PRIVATE_SESSION_TITLE
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            "UPDATE private_shows
+             SET status = 'Active', stream_session_id = $2, started_at = NOW(), viewer_seen_at = NOW(),
+                 minutes_billed = 1, billed_pln = rate_pln
+             WHERE id = $1",
+            id,
+            session.id
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(AcceptOutcome::Started { ended_public })
+    }
+
+    /// Trwające pokazy, którym zaczyna się kolejna (jeszcze nieopłacona) minuta.
+    pub async fn find_due(db: &PgPool) -> Result<Vec<Uuid>, sqlx::Error> {
+        let rows = sqlx::query!(
+            "SELECT id FROM private_shows
+             WHERE status = 'Active' AND erika_id IS NOT NULL
+               AND started_at + make_interval(mins => minutes_billed) <= NOW()"
+        )
+        .fetch_all(db)
+        .await?;
+        Ok(rows.into_iter().map(|row| row.id).collect())
+    }
+
+    /// Trwające pokazy, od których widza sygnał nie przyszedł od `timeout_secs` sekund.
+    pub async fn find_viewer_gone(
+        timeout_secs: i64,
+        db: &PgPool,
+    ) -> Result<Vec<Uuid>, sqlx::Error> {
+        let rows = sqlx::query!(
+            "SELECT id FROM private_shows
+             WHERE status = 'Active' AND viewer_seen_at < NOW() - make_interval(secs => $1)",
+            timeout_secs as f64
+        )
+        .fetch_all(db)
+        .await?;
+        Ok(rows.into_iter().map(|row| row.id).collect())
+    }
+
+    /// Pobiera z salda widza kolejną minutę, jeśli już się zaczęła. Wiersz pokazu
+    /// jest blokowany do końca transakcji, więc równoległe `end` albo drugi
+    /// przebieg naliczania nie pobiorą tej samej minuty ani nie obciążą
+    /// pokazu, który właśnie się rozliczył.
+    pub async fn charge_next_minute(id: Uuid, db: &PgPool) -> Result<MinuteCharge, sqlx::Error> {
+        let mut tx = db.begin().await?;
+        let Some(show) = sqlx::query!(
+            "SELECT viewer_id, rate_pln FROM private_shows
+             WHERE id = $1 AND status = 'Active' AND erika_id IS NOT NULL
+               AND started_at + make_interval(mins => minutes_billed) <= NOW()
+             FOR UPDATE",
+            id
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        else {
+            return Ok(MinuteCharge::NotDue);
+        };
+
+        let charged = match show.viewer_id {
+            Some(viewer_id) => {
+                Wallet::debit(
+                    viewer_id,
+                    &show.rate_pln,
+                    WalletDebit::PrivateShow(id),
+                    &mut tx,
+                )
+                .await?
+            }
+            None => false,
+        };
+        if !charged {
+            return Ok(MinuteCharge::InsufficientFunds);
+        }
+        sqlx::query!(
+            "UPDATE private_shows
+             SET minutes_billed = minutes_billed + 1, billed_pln = billed_pln + rate_pln
+             WHERE id = $1",
+            id
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(MinuteCharge::Charged)
+    }
+
+    /// Odnotowuje, że strona pokazu jest otwarta u widza.
+    pub async fn record_viewer_heartbeat(
+        id: Uuid,
+        viewer_id: Uuid,
+        db: &PgPool,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "UPDATE private_shows SET viewer_seen_at = NOW()
+             WHERE id = $1 AND viewer_id = $2 AND status = 'Active'",
+            id,
+            viewer_id
+        )
+        .execute(db)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Kończy pokaz i rozlicza go: prowizja serwisu od całej pobranej kwoty,
+    /// reszta trafia do zarobku prywatnej transmisji modelki.
+    /// Zwraca id modelki albo `None`, jeśli pokaz już się nie toczył.
+    pub async fn end(
+        id: Uuid,
+        reason: PrivateShowEndReason,
+        commission_percent: u8,
+        db: &PgPool,
+    ) -> Result<Option<Uuid>, sqlx::Error> {
+        let mut tx = db.begin().await?;
+        // Ta sama blokada co przy naliczaniu - rozliczamy dopiero po ostatniej pobranej minucie
+        let locked = sqlx::query!(
+            "SELECT id FROM private_shows WHERE id = $1 AND status = 'Active' FOR UPDATE",
+            id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        if locked.is_none() {
+            return Ok(None);
+        }
+        let Some(show) = sqlx::query!(
+            r#"UPDATE private_shows
+               SET status = 'Ended', end_reason = $2, ended_at = NOW(),
+                   commission_pln = ROUND(billed_pln * $3::INT / 100, 2)
+               WHERE id = $1 AND status = 'Active'
+               RETURNING erika_id, stream_session_id, billed_pln - commission_pln AS "net_pln!""#,
+            id,
+            reason as PrivateShowEndReason,
+            commission_percent as i32
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        else {
+            return Ok(None);
+        };
+
+        sqlx::query!(
+            "UPDATE stream_sessions SET ended_at = NOW(), earnings_pln = earnings_pln + $2
+             WHERE id = $1 AND ended_at IS NULL",
+            show.stream_session_id,
+            show.net_pln
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(show.erika_id)
+    }
+
+    /// Kończy trwający pokaz modelki, jeśli jakiś trwa. Zwraca jego id.
+    pub async fn end_for_erika(
+        erika_id: Uuid,
+        reason: PrivateShowEndReason,
+        commission_percent: u8,
+        db: &PgPool,
+    ) -> Result<Option<Uuid>, sqlx::Error> {
+        let active = sqlx::query!(
+            "SELECT id FROM private_shows WHERE erika_id = $1 AND status = 'Active'",
+            erika_id
+        )
+        .fetch_optional(db)
+        .await?;
+        let Some(active) = active else {
+            return Ok(None);
+        };
+        Ok(Self::end(active.id, reason, commission_percent, db)
+            .await?
+            .map(|_| active.id))
+    }
+
+    /// Zamyka pokazy konta, które jest usuwane: trwające rozlicza, prośby anuluje.
+    /// Zwraca pary (pokaz, modelka) do powiadomienia stron pokazów.
+    pub async fn close_for_account(
+        account_id: Uuid,
+        commission_percent: u8,
+        db: &PgPool,
+    ) -> Result<Vec<(Uuid, Uuid)>, sqlx::Error> {
+        let active = sqlx::query!(
+            "SELECT id, erika_id FROM private_shows
+             WHERE (erika_id = $1 OR viewer_id = $1) AND status = 'Active'",
+            account_id
+        )
+        .fetch_all(db)
+        .await?;
+        let mut closed = Vec::new();
+        for show in active {
+            let reason = if show.erika_id == Some(account_id) {
+                PrivateShowEndReason::CreatorOffline
+            } else {
+                PrivateShowEndReason::ViewerGone
+            };
+            if let Some(erika_id) = Self::end(show.id, reason, commission_percent, db).await? {
+                closed.push((show.id, erika_id));
+            }
+        }
+
+        let cancelled = sqlx::query!(
+            r#"UPDATE private_shows SET status = 'Cancelled', ended_at = NOW()
+               WHERE (erika_id = $1 OR viewer_id = $1) AND status = 'Requested'
+               RETURNING id, erika_id"#,
+            account_id
+        )
+        .fetch_all(db)
+        .await?;
+        closed.extend(
+            cancelled
+                .into_iter()
+                .filter_map(|row| Some((row.id, row.erika_id?))),
+        );
+        Ok(closed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn pln(value: &str) -> BigDecimal {
+        BigDecimal::from_str(value).unwrap()
+    }
+
+    async fn account(username: &str, db: &PgPool) -> Uuid {
+        sqlx::query!(
+            "INSERT INTO erikas (username, email, password_hash) VALUES ($1, $2, 'x') RETURNING id",
+            username,
+            format!("{}@example.com", username)
+        )
+        .fetch_one(db)
+        .await
+        .unwrap()
+        .id
+    }
+
+    /// Pokaz trwający od `minutes` minut i kilku sekund - kolejna minuta jest do pobrania.
+    async fn rewind(id: Uuid, minutes: i32, db: &PgPool) {
+        sqlx::query!(
+            "UPDATE private_shows SET started_at = NOW() - make_interval(mins => $2, secs => 5) WHERE id = $1",
+            id,
+            minutes
+        )
+        .execute(db)
+        .await
+        .unwrap();
+    }
+
+    async fn requested_show(
+        erika_id: Uuid,
+        viewer: &str,
+        balance: &str,
+        db: &PgPool,
+    ) -> (Uuid, Uuid) {
+        let viewer_id = account(viewer, db).await;
+        Wallet::credit(viewer_id, &pln(balance), "test", db)
+            .await
+            .unwrap();
+        let show_id = PrivateShow::request(erika_id, viewer_id, &pln("10"), db)
+            .await
+            .unwrap()
+            .unwrap();
+        (show_id, viewer_id)
+    }
+
+    #[sqlx::test]
+    async fn accept_charges_first_minute_and_second_show_is_busy(db: PgPool) {
+        let erika_id = account("anna", &db).await;
+        let (first, first_viewer) = requested_show(erika_id, "fan1", "30", &db).await;
+        let (second, _) = requested_show(erika_id, "fan2", "30", &db).await;
+
+        let outcome = PrivateShow::accept(first, erika_id, &db).await.unwrap();
+        assert!(matches!(
+            outcome,
+            AcceptOutcome::Started { ended_public: None }
+        ));
+        assert_eq!(Wallet::balance(first_viewer, &db).await.unwrap(), pln("20"));
+
+        let outcome = PrivateShow::accept(second, erika_id, &db).await.unwrap();
+        assert!(matches!(outcome, AcceptOutcome::Busy));
+        let show = PrivateShow::find_by_id(second, &db).await.unwrap().unwrap();
+        assert_eq!(show.status, PrivateShowStatus::Requested);
+    }
+
+    #[sqlx::test]
+    async fn accept_without_funds_cancels_request(db: PgPool) {
+        let erika_id = account("anna", &db).await;
+        let (show_id, viewer_id) = requested_show(erika_id, "fan1", "9.99", &db).await;
+
+        let outcome = PrivateShow::accept(show_id, erika_id, &db).await.unwrap();
+        assert!(matches!(outcome, AcceptOutcome::InsufficientFunds));
+        let show = PrivateShow::find_by_id(show_id, &db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(show.status, PrivateShowStatus::Cancelled);
+        assert_eq!(show.end_reason, Some(PrivateShowEndReason::Funds));
+        assert_eq!(Wallet::balance(viewer_id, &db).await.unwrap(), pln("9.99"));
+    }
+
+    #[sqlx::test]
+    async fn charge_bills_each_minute_once(db: PgPool) {
+        let erika_id = account("anna", &db).await;
+        let (show_id, viewer_id) = requested_show(erika_id, "fan1", "25", &db).await;
+        PrivateShow::accept(show_id, erika_id, &db).await.unwrap();
+
+        // Pierwsza minuta jest opłacona przy akceptacji
+        assert_eq!(
+            PrivateShow::charge_next_minute(show_id, &db).await.unwrap(),
+            MinuteCharge::NotDue
+        );
+        rewind(show_id, 1, &db).await;
+        assert_eq!(
+            PrivateShow::charge_next_minute(show_id, &db).await.unwrap(),
+            MinuteCharge::Charged
+        );
+        assert_eq!(
+            PrivateShow::charge_next_minute(show_id, &db).await.unwrap(),
+            MinuteCharge::NotDue
+        );
+        rewind(show_id, 2, &db).await;
+        assert_eq!(
+            PrivateShow::charge_next_minute(show_id, &db).await.unwrap(),
+            MinuteCharge::InsufficientFunds
+        );
+
+        let show = PrivateShow::find_by_id(show_id, &db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!((show.minutes_billed, show.billed_pln), (2, pln("20")));
+        assert_eq!(Wallet::balance(viewer_id, &db).await.unwrap(), pln("5"));
+    }
+
+    #[sqlx::test]
+    async fn end_settles_billed_amount_once(db: PgPool) {
+        let erika_id = account("anna", &db).await;
+        let (show_id, viewer_id) = requested_show(erika_id, "fan1", "100", &db).await;
+        sqlx::query!(
+            "UPDATE private_shows SET rate_pln = 3.33 WHERE id = $1",
+            show_id
+        )
+        .execute(&db)
+        .await
+        .unwrap();
+        PrivateShow::accept(show_id, erika_id, &db).await.unwrap();
+        rewind(show_id, 1, &db).await;
+        PrivateShow::charge_next_minute(show_id, &db).await.unwrap();
+
+        let ended = PrivateShow::end(show_id, PrivateShowEndReason::Viewer, 15, &db)
+            .await
+            .unwrap();
+        assert_eq!(ended, Some(erika_id));
+        assert_eq!(
+            PrivateShow::end(show_id, PrivateShowEndReason::Creator, 15, &db)
+                .await
+                .unwrap(),
+            None
+        );
+
+        // Po rozliczeniu naliczanie nie rusza już salda
+        rewind(show_id, 5, &db).await;
+        assert_eq!(
+            PrivateShow::charge_next_minute(show_id, &db).await.unwrap(),
+            MinuteCharge::NotDue
+        );
+        assert_eq!(Wallet::balance(viewer_id, &db).await.unwrap(), pln("93.34"));
+
+        let show = PrivateShow::find_by_id(show_id, &db)
+            .await
+            .unwrap()
+            .unwrap();
+        let session = sqlx::query!(
+            r#"SELECT s.earnings_pln, s.ended_at IS NOT NULL AS "ended!"
+               FROM stream_sessions s JOIN private_shows p ON p.stream_session_id = s.id
+               WHERE p.id = $1"#,
+            show_id
+        )
+        .fetch_one(&db)
+        .await
+        .unwrap();
+        // 15% z 6.66 to 0.999 - po zaokrągleniu 1.00
+        assert_eq!(show.billed_pln, pln("6.66"));
+        assert_eq!(show.commission_pln, pln("1.00"));
+        assert_eq!(
+            &session.earnings_pln + &show.commission_pln,
+            show.billed_pln
+        );
+        assert!(session.ended);
+    }
+}
@@ -0,0 +1,380 @@
+// src/models/report.rs
+use sqlx::PgPool;
+use strum::{Display, EnumIter, EnumString};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// Czego dotyczy zgłoszenie. Slug trafia do URL-a formularza (`/report/profile/...`).
+#[derive(Debug, Clone, Copy, PartialEq, sqlx::Type, Display)]
+#[sqlx(type_name = "report_target")]
+pub enum ReportTarget {
+    Profile,
+    Gallery,
+    Photo,
+}
+
+impl ReportTarget {
+    pub fn from_slug(slug: &str) -> Option<Self> {
+        match slug {
+            "profile" => Some(ReportTarget::Profile),
+            "gallery" => Some(ReportTarget::Gallery),
+            "photo" => Some(ReportTarget::Photo),
+            _ => None,
+        }
+    }
+
+    pub fn slug(&self) -> &'static str {
+        match self {
+            ReportTarget::Profile => "profile",
+            ReportTarget::Gallery => "gallery",
+            ReportTarget::Photo => "photo",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReportTarget::Profile => "Profil",
+            ReportTarget::Gallery => "Galeria",
+            ReportTarget::Photo => "Zdjęcie",
+        }
+    }
+
+    /// Czy treść da się ukryć decyzją moderacji. Profil ogranicza się
+    /// przez zawieszenie konta.
+    pub fn can_be_hidden(&self) -> bool {
+        *self != ReportTarget::Profile
+    }
+}
+
+/// Kategoria zgłoszenia - w decyzji służy też jako podstawa ograniczenia.
+#[derive(Debug, Clone, Copy, PartialEq, sqlx::Type, EnumString, EnumIter, Display)]
+#[sqlx(type_name = "report_reason")]
+pub enum ReportReason {
+    IllegalContent,
+    Minor,
+    NonConsensual,
+    Copyright,
+    Impersonation,
+    Spam,
+    Other,
+}
+
+impl ReportReason {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReportReason::IllegalContent => "Treści niezgodne z prawem",
+            ReportReason::Minor => "Osoba niepełnoletnia",
+            ReportReason::NonConsensual => "Publikacja bez zgody osoby na zdjęciach",
+            ReportReason::Copyright => "Naruszenie praw autorskich",
+            ReportReason::Impersonation => "Podszywanie się pod inną osobę",
+            ReportReason::Spam => "Spam lub oszustwo",
+            ReportReason::Other => "Inne naruszenie regulaminu",
+        }
+    }
+
+    /// Czy ograniczenie z tego powodu wynika z prawa, a nie tylko z regulaminu
+    /// (rozróżnienie wymagane w uzasadnieniu decyzji).
+    pub fn is_legal_ground(&self) -> bool {
+        !matches!(
+            self,
+            ReportReason::Impersonation | ReportReason::Spam | ReportReason::Other
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, sqlx::Type, EnumString, EnumIter, Display)]
+#[sqlx(type_name = "report_status")]
+pub enum ReportStatus {
+    Open,
+    Actioned,
+    Dismissed,
+}
+
+impl ReportStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReportStatus::Open => "Otwarte",
+            ReportStatus::Actioned => "Podjęto działanie",
+            ReportStatus::Dismissed => "Odrzucone",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, sqlx::Type, EnumString, EnumIter, Display)]
+#[sqlx(type_name = "moderation_action")]
+pub enum ModerationAction {
+    NoAction,
+    HideContent,
+    SuspendCreator,
+}
+
+impl ModerationAction {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ModerationAction::NoAction => "Bez działania",
+            ModerationAction::HideContent => "Ukrycie treści",
+            ModerationAction::SuspendCreator => "Zawieszenie konta",
+        }
+    }
+}
+
+#[derive(sqlx::FromRow, Clone)]
+pub struct Report {
+    pub id: Uuid,
+    pub target_kind: ReportTarget,
+    pub target_id: Uuid,
+    pub erika_id: Uuid,
+    pub reason: ReportReason,
+    pub details: String,
+    pub reporter_email: Option<String>,
+    pub ip_address: Option<String>,
+    pub status: ReportStatus,
+    pub decision_id: Option<Uuid>,
+    pub created_at: OffsetDateTime,
+}
+
+/// Zgłoszenie na liście admina, z nazwą konta, którego dotyczy.
+#[derive(sqlx::FromRow, Clone)]
+pub struct ReportQueueItem {
+    pub id: Uuid,
+    pub target_kind: ReportTarget,
+    pub reason: ReportReason,
+    pub status: ReportStatus,
+    pub username: String,
+    pub created_at: OffsetDateTime,
+}
+
+pub struct NewReport<'a> {
+    pub target_kind: ReportTarget,
+    pub target_id: Uuid,
+    pub erika_id: Uuid,
+    pub reason: ReportReason,
+    pub details: &'a str,
+    pub reporter_id: Option<Uuid>,
+    pub reporter_email: Option<&'a str>,
+    pub ip_address: Option<&'a str>,
+}
+
+/// Decyzja moderacyjna z uzasadnieniem, które widzi modelka.
+#[derive(sqlx::FromRow, Clone)]
+pub struct ModerationDecision {
+    pub id: Uuid,
+    pub target_kind: ReportTarget,
+    pub target_id: Uuid,
+    pub action: ModerationAction,
+    pub ground: ReportReason,
+    pub statement: String,
+    pub created_at: OffsetDateTime,
+}
+
+pub struct NewDecision<'a> {
+    pub erika_id: Uuid,
+    pub target_kind: ReportTarget,
+    pub target_id: Uuid,
+    pub action: ModerationAction,
+    pub ground: ReportReason,
+    pub statement: &'a str,
+    pub decided_by: Uuid,
+}
+
+impl Report {
+    pub async fn create(new: NewReport<'_>, db: &PgPool) -> Result<Uuid, sqlx::Error> {
+        let row = sqlx::query!(
+            "INSERT INTO reports
+                 (target_kind, target_id, erika_id, reason, details, reporter_id, reporter_email, ip_address)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+             RETURNING id",
+            new.target_kind as ReportTarget,
+            new.target_id,
+            new.erika_id,
+            new.reason as ReportReason,
+            new.details,
+            new.reporter_id,
+            new.reporter_email,
+            new.ip_address
+        )
+        .fetch_one(db)
+        .await?;
+        Ok(row.id)
+    }
+
+    /// Czy z tego adresu IP zgłoszono już tę treść w ciągu ostatniej doby.
+    /// Chroni kolejkę przed wielokrotnym wysłaniem tego samego formularza.
+    pub async fn is_duplicate(
+        target_kind: ReportTarget,
+        target_id: Uuid,
+        ip_address: &str,
+        db: &PgPool,
+    ) -> Result<bool, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"SELECT EXISTS(
+                 SELECT 1 FROM reports
+                 WHERE target_kind = $1 AND target_id = $2 AND ip_address = $3
+                   AND created_at > NOW() - INTERVAL '1 day'
+               ) as "exists!""#,
+            target_kind as ReportTarget,
+            target_id,
+            ip_address
+        )
+        .fetch_one(db)
+        .await?;
+        Ok(row.exists)
+    }
+
+    pub async fn find_by_id(id: Uuid, db: &PgPool) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Report,
+            r#"SELECT id, target_kind as "target_kind: _", target_id, erika_id, reason as "reason: _",
+                      details, reporter_email, ip_address, status as "status: _", decision_id, created_at
+               FROM reports WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(db)
+        .await
+    }
+
+    /// Wszystkie zgłoszenia tej samej treści, od najstarszego.
+    pub async fn find_by_target(
+        target_kind: ReportTarget,
+        target_id: Uuid,
+        db: &PgPool,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Report,
+            r#"SELECT id, target_kind as "target_kind: _", target_id, erika_id, reason as "reason: _",
+                      details, reporter_email, ip_address, status as "status: _", decision_id, created_at
+               FROM reports WHERE target_kind = $1 AND target_id = $2
+               ORDER BY created_at ASC"#,
+            target_kind as ReportTarget,
+            target_id
+        )
+        .fetch_all(db)
+        .await
+    }
+
+    /// Lista zgłoszeń o danym statusie. Otwarte od najstarszych, żeby nic nie czekało w nieskończoność.
+    pub async fn find_by_status(
+        status: ReportStatus,
+        limit: i64,
+        db: &PgPool,
+    ) -> Result<Vec<ReportQueueItem>, sqlx::Error> {
+        sqlx::query_as!(
+            ReportQueueItem,
+            r#"SELECT r.id, r.target_kind as "target_kind: _", r.reason as "reason: _",
+                      r.status as "status: _", e.username, r.created_at
+               FROM reports r JOIN erikas e ON e.id = r.erika_id
+               WHERE r.status = $1
+               ORDER BY CASE WHEN r.status = 'Open' THEN r.created_at END ASC,
+                        r.created_at DESC
+               LIMIT $2"#,
+            status as ReportStatus,
+            limit
+        )
+        .fetch_all(db)
+        .await
+    }
+
+    pub async fn count_open(db: &PgPool) -> Result<i64, sqlx::Error> {
+        let row = sqlx::query!(r#"SELECT COUNT(*) as "count!" FROM reports WHERE status = 'Open'"#)
+            .fetch_one(db)
+            .await?;
+        Ok(row.count)
+    }
+}
+
+impl ModerationDecision {
+    /// Zapisuje decyzję i zamyka wszystkie otwarte zgłoszenia tej treści.
+    /// Ukrycie galerii zdejmuje ją z publikacji i blokuje ponowne opublikowanie,
+    /// ukrycie zdjęcia wyłącza publiczny dostęp do pliku.
+    /// Zwraca `None`, jeśli nie było już otwartych zgłoszeń (ktoś rozpatrzył je wcześniej).
+    pub async fn record(new: NewDecision<'_>, db: &PgPool) -> Result<Option<Uuid>, sqlx::Error> {
+        let mut tx = db.begin().await?;
+        let decision = sqlx::query!(
+            "INSERT INTO moderation_decisions
+                 (erika_id, target_kind, target_id, action, ground, statement, decided_by)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)
+             RETURNING id",
+            new.erika_id,
+            new.target_kind as ReportTarget,
+            new.target_id,
+            new.action as ModerationAction,
+            new.ground as ReportReason,
+            new.statement,
+            new.decided_by
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let status = match new.action {
+            ModerationAction::NoAction => ReportStatus::Dismissed,
+            _ => ReportStatus::Actioned,
+        };
+        let closed = sqlx::query!(
+            "UPDATE reports SET status = $1, decision_id = $2
+             WHERE target_kind = $3 AND target_id = $4 AND status = 'Open'",
+            status as ReportStatus,
+            decision.id,
+            new.target_kind as ReportTarget,
+            new.target_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        if closed.rows_affected() == 0 {
+            tx.rollback().await?;
+            return Ok(None);
+        }
+
+        match (new.action, new.target_kind) {
+            (ModerationAction::HideContent, ReportTarget::Gallery) => {
+                sqlx::query!(
+                    "UPDATE galleries SET published_at = NULL, blocked_at = NOW() WHERE id = $1",
+                    new.target_id
+                )
+                .execute(&mut *tx)
+                .await?;
+            }
+            (ModerationAction::HideContent, ReportTarget::Photo) => {
+                sqlx::query!(
+                    "UPDATE photos SET hidden_at = NOW() WHERE id = $1",
+                    new.target_id
+                )
+                .execute(&mut *tx)
+                .await?;
+            }
+            _ => {}
+        }
+
+        tx.commit().await?;
+        Ok(Some(decision.id))
+    }
+
+    pub async fn find_by_id(id: Uuid, db: &PgPool) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ModerationDecision,
+            r#"SELECT id, target_kind as "target_kind: _", target_id, action as "action: _",
+                      ground as "ground: _", statement, created_at
+               FROM moderation_decisions WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(db)
+        .await
+    }
+
+    /// Decyzje ograniczające treści konta - to, co modelka widzi w panelu.
+    pub async fn find_restrictions_by_erika_id(
+        erika_id: Uuid,
+        db: &PgPool,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ModerationDecision,
+            r#"SELECT id, target_kind as "target_kind: _", target_id, action as "action: _",
+                      ground as "ground: _", statement, created_at
+               FROM moderation_decisions
+               WHERE erika_id = $1 AND action <> 'NoAction'
+               ORDER BY created_at DESC"#,
+            erika_id
+        )
+        .fetch_all(db)
+        .await
+    }
+}
@@ -0,0 +1,123 @@
+// src/models/site_settings.rs
+use sqlx::PgPool;
+use tracing::warn;
+
+/// Twardy górny limit wgrywanego zdjęcia - ustawienie może go tylko obniżyć.
+pub const MAX_UPLOAD_CEILING_MB: u32 = 100;
+
+/// Najdłuższy dopuszczalny czas bez sygnału, zanim modelka zniknie z listy online.
+pub const MAX_PRESENCE_TIMEOUT_MINUTES: u32 = 60;
+
+/// Ustawienia serwisu. W bazie każde pole to osobny wiersz `site_settings`;
+/// aplikacja trzyma wczytaną kopię w `AppState`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SiteSettings {
+    /// Prowizja serwisu od sprzedaży, w procentach.
+    pub commission_percent: u8,
+    /// Maksymalny rozmiar jednego zdjęcia w galerii.
+    pub max_upload_mb: u32,
+    pub registration_open: bool,
+    pub homepage_headline: String,
+    /// Komunikat nad treścią każdej strony (pusty - brak paska).
+    pub banner: String,
+    /// W trybie konserwacji serwis widzą tylko admini.
+    pub maintenance_mode: bool,
+    pub maintenance_message: String,
+    /// Po ilu minutach bez sygnału z panelu kamerki modelka jest offline.
+    pub presence_timeout_minutes: u32,
+}
+
+impl Default for SiteSettings {
+    fn default() -> Self {
+        SiteSettings {
+            commission_percent: 20,
+            max_upload_mb: 10,
+            registration_open: true,
+            homepage_headline: "Poznaj nasze modelki".to_string(),
+            banner: String::new(),
+            maintenance_mode: false,
+            maintenance_message: "Trwają prace techniczne. Wróć za chwilę.".to_string(),
+            presence_timeout_minutes: 2,
+        }
+    }
+}
+
+impl SiteSettings {
+    /// Wczytuje ustawienia. Nieznane klucze i wartości, których nie da się
+    /// odczytać, są pomijane - zostaje wtedy wartość domyślna.
+    pub async fn load(db: &PgPool) -> Result<Self, sqlx::Error> {
+        let rows = sqlx::query!("SELECT key, value FROM site_settings")
+            .fetch_all(db)
+            .await?;
+
+        let mut settings = SiteSettings::default();
+        for row in rows {
+            if !settings.apply(&row.key, &row.value) {
+                warn!("Pominięto ustawienie {}={:?}", row.key, row.value);
+            }
+        }
+        Ok(settings)
+    }
+
+    fn apply(&mut self, key: &str, value: &str) -> bool {
+        fn parse<T: std::str::FromStr>(value: &str, field: &mut T) -> bool {
+            value.parse().map(|parsed| *field = parsed).is_ok()
+        }
+        match key {
+            "commission_percent" => parse(value, &mut self.commission_percent),
+            "max_upload_mb" => parse(value, &mut self.max_upload_mb),
+            "registration_open" => parse(value, &mut self.registration_open),
+            "homepage_headline" => parse(value, &mut self.homepage_headline),
+            "banner" => parse(value, &mut self.banner),
+            "maintenance_mode" => parse(value, &mut self.maintenance_mode),
+            "maintenance_message" => parse(value, &mut self.maintenance_message),
+            "presence_timeout_minutes" => parse(value, &mut self.presence_timeout_minutes),
+            _ => false,
+        }
+    }
+
+    /// Pary klucz-wartość w postaci zapisywanej w bazie.
+    pub fn pairs(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("commission_percent", self.commission_percent.to_string()),
+            ("max_upload_mb", self.max_upload_mb.to_string()),
+            ("registration_open", self.registration_open.to_string()),
+            ("homepage_headline", self.homepage_headline.clone()),
+            ("banner", self.banner.clone()),
+            ("maintenance_mode", self.maintenance_mode.to_string()),
+            ("maintenance_message", self.maintenance_message.clone()),
+            (
+                "presence_timeout_minutes",
+                self.presence_timeout_minutes.to_string(),
+            ),
+        ]
+    }
+
+    /// Zapisuje wszystkie ustawienia w jednej transakcji.
+    pub async fn save(&self, db: &PgPool) -> Result<(), sqlx::Error> {
+        let mut tx = db.begin().await?;
+        for (key, value) in self.pairs() {
+            sqlx::query!(
+                "INSERT INTO site_settings (key, value) VALUES ($1, $2)
+                 ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value, updated_at = NOW()
+                 WHERE site_settings.value <> EXCLUDED.value",
+                key,
+                value
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await
+    }
+
+    pub fn max_upload_bytes(&self) -> usize {
+        self.max_upload_mb.min(MAX_UPLOAD_CEILING_MB) as usize * 1024 * 1024
+    }
+
+    pub fn presence_timeout_secs(&self) -> i64 {
+        i64::from(
+            self.presence_timeout_minutes
+                .clamp(1, MAX_PRESENCE_TIMEOUT_MINUTES),
+        ) * 60
+    }
+}
@@ -0,0 +1,214 @@
+// src/models/stream_session.rs
+use bigdecimal::BigDecimal;
+use sqlx::PgPool;
+use time::{Duration, OffsetDateTime};
+use uuid::Uuid;
+
+/// Ile dni wstecz kanał kalendarza (.ics) trzyma minione zapowiedzi.
+pub const FEED_HISTORY_DAYS: i64 = 30;
+
+#[derive(sqlx::FromRow, Clone)]
+pub struct StreamSession {
+    pub id: Uuid,
+    pub erika_id: Uuid,
+    pub title: String,
+    pub scheduled_at: Option<OffsetDateTime>,
+    pub planned_minutes: i32,
+    pub started_at: Option<OffsetDateTime>,
+    pub ended_at: Option<OffsetDateTime>,
+    pub peak_viewers: i32,
+    pub earnings_pln: BigDecimal,
+    /// Pokaz prywatny (`private_shows`) - nie pokazujemy go widzom profilu.
+    pub is_private: bool,
+    pub created_at: OffsetDateTime,
+}
+
+impl StreamSession {
+    /// Czas trwania zakończonej transmisji.
+    pub fn duration(&self) -> Option<Duration> {
+        Some(self.ended_at? - self.started_at?)
+    }
+
+    /// Zapowiada transmisję na podany termin.
+    pub async fn schedule(
+        erika_id: Uuid,
+        title: &str,
+        scheduled_at: OffsetDateTime,
+        planned_minutes: i32,
+        db: &PgPool,
+    ) -> Result<Uuid, sqlx::Error> {
+        let row = sqlx::query!(
+            "INSERT INTO stream_sessions (erika_id, title, scheduled_at, planned_minutes)
+             VALUES ($1, $2, $3, $4)
+             RETURNING id",
+            erika_id,
+            title,
+            scheduled_at,
+            planned_minutes
+        )
+        .fetch_one(db)
+        .await?;
+        Ok(row.id)
+    }
+
+    /// Zapowiedzi, których modelka jeszcze nie rozpoczęła - także te po terminie.
+    pub async fn find_planned_by_erika_id(
+        erika_id: Uuid,
+        db: &PgPool,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            StreamSession,
+            "SELECT id, erika_id, title, scheduled_at, planned_minutes, started_at, ended_at,
+                    peak_viewers, earnings_pln, is_private, created_at
+             FROM stream_sessions
+             WHERE erika_id = $1 AND started_at IS NULL
+             ORDER BY scheduled_at",
+            erika_id
+        )
+        .fetch_all(db)
+        .await
+    }
+
+    /// Nadchodzące transmisje do pokazania na profilu.
+    pub async fn find_upcoming_by_erika_id(
+        erika_id: Uuid,
+        db: &PgPool,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            StreamSession,
+            "SELECT id, erika_id, title, scheduled_at, planned_minutes, started_at, ended_at,
+                    peak_viewers, earnings_pln, is_private, created_at
+             FROM stream_sessions
+             WHERE erika_id = $1 AND started_at IS NULL AND scheduled_at > NOW()
+             ORDER BY scheduled_at",
+            erika_id
+        )
+        .fetch_all(db)
+        .await
+    }
+
+    /// Zapowiedzi do kanału kalendarza: nadchodzące i te z ostatnich
+    /// `FEED_HISTORY_DAYS` dni, żeby nie znikały z kalendarza fana od razu.
+    pub async fn find_for_feed(erika_id: Uuid, db: &PgPool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            StreamSession,
+            "SELECT id, erika_id, title, scheduled_at, planned_minutes, started_at, ended_at,
+                    peak_viewers, earnings_pln, is_private, created_at
+             FROM stream_sessions
+             WHERE erika_id = $1 AND scheduled_at > NOW() - make_interval(days => $2)
+             ORDER BY scheduled_at",
+            erika_id,
+            FEED_HISTORY_DAYS as i32
+        )
+        .fetch_all(db)
+        .await
+    }
+
+    /// Trwająca transmisja modelki, jeśli jest.
+    pub async fn find_live(erika_id: Uuid, db: &PgPool) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            StreamSession,
+            "SELECT id, erika_id, title, scheduled_at, planned_minutes, started_at, ended_at,
+                    peak_viewers, earnings_pln, is_private, created_at
+             FROM stream_sessions
+             WHERE erika_id = $1 AND started_at IS NOT NULL AND ended_at IS NULL",
+            erika_id
+        )
+        .fetch_optional(db)
+        .await
+    }
+
+    /// Zakończone transmisje, najnowsze na górze.
+    pub async fn find_history_by_erika_id(
+        erika_id: Uuid,
+        limit: i64,
+        db: &PgPool,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            StreamSession,
+            "SELECT id, erika_id, title, scheduled_at, planned_minutes, started_at, ended_at,
+                    peak_viewers, earnings_pln, is_private, created_at
+             FROM stream_sessions
+             WHERE erika_id = $1 AND ended_at IS NOT NULL
+             ORDER BY started_at DESC
+             LIMIT $2",
+            erika_id,
+            limit
+        )
+        .fetch_all(db)
+        .await
+    }
+
+    /// Wszystkie transmisje modelki do eksportu danych.
+    pub async fn find_by_erika_id(erika_id: Uuid, db: &PgPool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            StreamSession,
+            "SELECT id, erika_id, title, scheduled_at, planned_minutes, started_at, ended_at,
+                    peak_viewers, earnings_pln, is_private, created_at
+             FROM stream_sessions
+             WHERE erika_id = $1
+             ORDER BY created_at",
+            erika_id
+        )
+        .fetch_all(db)
+        .await
+    }
+
+    /// Rozpoczyna zapowiedzianą transmisję. Zwraca `false`, jeśli zapowiedź
+    /// nie należy do modelki albo już się odbyła.
+    pub async fn start(id: Uuid, erika_id: Uuid, db: &PgPool) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "UPDATE stream_sessions SET started_at = NOW()
+             WHERE id = $1 AND erika_id = $2 AND started_at IS NULL",
+            id,
+            erika_id
+        )
+        .execute(db)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Rozpoczyna transmisję bez wcześniejszej zapowiedzi.
+    pub async fn start_unscheduled(
+        erika_id: Uuid,
+        title: &str,
+        db: &PgPool,
+    ) -> Result<Uuid, sqlx::Error> {
+        let row = sqlx::query!(
+            "INSERT INTO stream_sessions (erika_id, title, started_at)
+             VALUES ($1, $2, NOW())
+             RETURNING id",
+            erika_id,
+            title
+        )
+        .fetch_one(db)
+        .await?;
+        Ok(row.id)
+    }
+
+    /// Kończy trwającą transmisję modelki. Zwraca jej id albo `None`,
+    /// jeśli żadna nie trwała.
+    pub async fn end_live(erika_id: Uuid, db: &PgPool) -> Result<Option<Uuid>, sqlx::Error> {
+        let row = sqlx::query!(
+            "UPDATE stream_sessions SET ended_at = NOW()
+             WHERE erika_id = $1 AND started_at IS NOT NULL AND ended_at IS NULL
+             RETURNING id",
+            erika_id
+        )
+        .fetch_optional(db)
+        .await?;
+        Ok(row.map(|row| row.id))
+    }
+
+    /// Odwołuje zapowiedź, która jeszcze się nie rozpoczęła.
+    pub async fn cancel(id: Uuid, erika_id: Uuid, db: &PgPool) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM stream_sessions WHERE id = $1 AND erika_id = $2 AND started_at IS NULL",
+            id,
+            erika_id
+        )
+        .execute(db)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,431 @@
+// src/models/tip.rs
+use crate::models::wallet::{Wallet, WalletDebit};
+use bigdecimal::BigDecimal;
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// Ile pozycji może mieć menu napiwków jednej modelki.
+pub const MAX_MENU_ITEMS: i64 = 20;
+
+#[derive(sqlx::FromRow, Clone)]
+pub struct TipMenuItem {
+    pub id: Uuid,
+    pub erika_id: Uuid,
+    pub label: String,
+    pub price_pln: BigDecimal,
+    pub created_at: OffsetDateTime,
+}
+
+impl TipMenuItem {
+    pub async fn create(
+        erika_id: Uuid,
+        label: &str,
+        price_pln: &BigDecimal,
+        db: &PgPool,
+    ) -> Result<Uuid, sqlx::Error> {
+        let row = sqlx::query!(
+            "INSERT INTO tip_menu_items (erika_id, label, price_pln) VALUES ($1, $2, $3) RETURNING id",
+            erika_id,
+            label,
+            price_pln
+        )
+        .fetch_one(db)
+        .await?;
+        Ok(row.id)
+    }
+
+    /// Menu modelki od najtańszej pozycji.
+    pub async fn find_by_erika_id(erika_id: Uuid, db: &PgPool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TipMenuItem,
+            "SELECT id, erika_id, label, price_pln, created_at
+             FROM tip_menu_items WHERE erika_id = $1
+             ORDER BY price_pln, created_at",
+            erika_id
+        )
+        .fetch_all(db)
+        .await
+    }
+
+    pub async fn find_by_id_and_erika_id(
+        id: Uuid,
+        erika_id: Uuid,
+        db: &PgPool,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TipMenuItem,
+            "SELECT id, erika_id, label, price_pln, created_at
+             FROM tip_menu_items WHERE id = $1 AND erika_id = $2",
+            id,
+            erika_id
+        )
+        .fetch_optional(db)
+        .await
+    }
+
+    pub async fn count_by_erika_id(erika_id: Uuid, db: &PgPool) -> Result<i64, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"SELECT COUNT(*) AS "count!" FROM tip_menu_items WHERE erika_id = $1"#,
+            erika_id
+        )
+        .fetch_one(db)
+        .await?;
+        Ok(row.count)
+    }
+
+    pub async fn delete(id: Uuid, erika_id: Uuid, db: &PgPool) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM tip_menu_items WHERE id = $1 AND erika_id = $2",
+            id,
+            erika_id
+        )
+        .execute(db)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+/// Cel zbiórki z postępem liczonym od jego ustawienia.
+#[derive(sqlx::FromRow, Clone)]
+pub struct TipGoal {
+    pub title: String,
+    pub target_pln: BigDecimal,
+    pub raised_pln: BigDecimal,
+    pub created_at: OffsetDateTime,
+}
+
+impl TipGoal {
+    /// Postęp w procentach, najwyżej 100.
+    pub fn percent(&self) -> u8 {
+        use bigdecimal::ToPrimitive;
+        let ratio = (&self.raised_pln * BigDecimal::from(100) / &self.target_pln)
+            .to_u64()
+            .unwrap_or(0);
+        ratio.min(100) as u8
+    }
+
+    pub async fn find_by_erika_id(
+        erika_id: Uuid,
+        db: &PgPool,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TipGoal,
+            r#"SELECT g.title, g.target_pln, g.created_at,
+                      COALESCE((SELECT SUM(t.amount_pln) FROM tips t
+                                WHERE t.erika_id = g.erika_id AND t.created_at >= g.created_at), 0) AS "raised_pln!"
+               FROM tip_goals g WHERE g.erika_id = $1"#,
+            erika_id
+        )
+        .fetch_optional(db)
+        .await
+    }
+
+    /// Ustawia nowy cel w miejsce poprzedniego - postęp liczy się od zera.
+    pub async fn set(
+        erika_id: Uuid,
+        title: &str,
+        target_pln: &BigDecimal,
+        db: &PgPool,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "INSERT INTO tip_goals (erika_id, title, target_pln) VALUES ($1, $2, $3)
+             ON CONFLICT (erika_id) DO UPDATE
+             SET title = EXCLUDED.title, target_pln = EXCLUDED.target_pln, created_at = NOW()",
+            erika_id,
+            title,
+            target_pln
+        )
+        .execute(db)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn clear(erika_id: Uuid, db: &PgPool) -> Result<(), sqlx::Error> {
+        sqlx::query!("DELETE FROM tip_goals WHERE erika_id = $1", erika_id)
+            .execute(db)
+            .await?;
+        Ok(())
+    }
+}
+
+/// Napiwek do zapisania w księdze.
+pub struct NewTip<'a> {
+    pub erika_id: Uuid,
+    pub tipper_id: Uuid,
+    pub stream_session_id: Uuid,
+    pub label: Option<&'a str>,
+    pub message: Option<&'a str>,
+    pub amount_pln: &'a BigDecimal,
+    pub commission_percent: u8,
+}
+
+/// Napiwek w czacie transmisji.
+#[derive(sqlx::FromRow, Clone)]
+pub struct TipEntry {
+    pub tipper_username: Option<String>,
+    pub label: Option<String>,
+    pub message: Option<String>,
+    pub amount_pln: BigDecimal,
+    pub created_at: OffsetDateTime,
+}
+
+pub struct Tip;
+
+impl Tip {
+    /// Pobiera kwotę z salda widza, zapisuje napiwek z prowizją serwisu
+    /// i dolicza modelce zarobek netto do trwającej transmisji.
+    /// Zwraca `None`, jeśli saldo widza jest za małe.
+    pub async fn create(new: NewTip<'_>, db: &PgPool) -> Result<Option<Uuid>, sqlx::Error> {
+        let mut tx = db.begin().await?;
+        let tip = sqlx::query!(
+            r#"INSERT INTO tips
+                   (erika_id, tipper_id, stream_session_id, label, message, amount_pln, commission_pln)
+               VALUES ($1, $2, $3, $4, $5, $6::NUMERIC, ROUND($6::NUMERIC * $7::INT / 100, 2))
+               RETURNING id, amount_pln - commission_pln AS "net_pln!""#,
+            new.erika_id,
+            new.tipper_id,
+            new.stream_session_id,
+            new.label,
+            new.message,
+            new.amount_pln,
+            new.commission_percent as i32
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        // Bez środków transakcja się wycofuje razem z zapisanym napiwkiem
+        if !Wallet::debit(
+            new.tipper_id,
+            new.amount_pln,
+            WalletDebit::Tip(tip.id),
+            &mut tx,
+        )
+        .await?
+        {
+            return Ok(None);
+        }
+
+        sqlx::query!(
+            "UPDATE stream_sessions SET earnings_pln = earnings_pln + $1 WHERE id = $2",
+            tip.net_pln,
+            new.stream_session_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(Some(tip.id))
+    }
+
+    /// Ostatnie napiwki transmisji, najnowsze na górze.
+    pub async fn find_recent_for_session(
+        stream_session_id: Uuid,
+        limit: i64,
+        db: &PgPool,
+    ) -> Result<Vec<TipEntry>, sqlx::Error> {
+        sqlx::query_as!(
+            TipEntry,
+            r#"SELECT e.username AS "tipper_username?", t.label, t.message, t.amount_pln, t.created_at
+               FROM tips t
+               LEFT JOIN erikas e ON e.id = t.tipper_id
+               WHERE t.stream_session_id = $1
+               ORDER BY t.created_at DESC
+               LIMIT $2"#,
+            stream_session_id,
+            limit
+        )
+        .fetch_all(db)
+        .await
+    }
+
+    /// Napiwki dane i otrzymane przez konto - do eksportu danych.
+    pub async fn find_by_account(
+        erika_id: Uuid,
+        db: &PgPool,
+    ) -> Result<Vec<TipEntry>, sqlx::Error> {
+        sqlx::query_as!(
+            TipEntry,
+            r#"SELECT e.username AS "tipper_username?", t.label, t.message, t.amount_pln, t.created_at
+               FROM tips t
+               LEFT JOIN erikas e ON e.id = t.tipper_id
+               WHERE t.erika_id = $1 OR t.tipper_id = $1
+               ORDER BY t.created_at"#,
+            erika_id
+        )
+        .fetch_all(db)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::erika::Erika;
+    use crate::models::stream_session::StreamSession;
+    use std::str::FromStr;
+
+    fn pln(value: &str) -> BigDecimal {
+        BigDecimal::from_str(value).unwrap()
+    }
+
+    fn goal(raised: &str, target: &str) -> TipGoal {
+        TipGoal {
+            title: "Cel".to_string(),
+            target_pln: pln(target),
+            raised_pln: pln(raised),
+            created_at: OffsetDateTime::now_utc(),
+        }
+    }
+
+    async fn account(username: &str, db: &PgPool) -> Uuid {
+        sqlx::query!(
+            "INSERT INTO erikas (username, email, password_hash) VALUES ($1, $2, 'x') RETURNING id",
+            username,
+            format!("{}@example.com", username)
+        )
+        .fetch_one(db)
+        .await
+        .unwrap()
+        .id
+    }
+
+    #[test]
+    fn goal_percent_rounds_down_and_caps_at_100() {
+        assert_eq!(goal("0", "100").percent(), 0);
+        assert_eq!(goal("33.33", "100").percent(), 33);
+        assert_eq!(goal("99.99", "100").percent(), 99);
+        assert_eq!(goal("100", "100").percent(), 100);
+        assert_eq!(goal("250", "100").percent(), 100);
+    }
+
+    #[sqlx::test]
+    async fn create_rounds_commission_and_credits_net_to_session(db: PgPool) {
+        let erika_id = account("anna", &db).await;
+        let tipper_id = account("fan", &db).await;
+        let session_id = StreamSession::start_unscheduled(erika_id, "Live", &db)
+            .await
+            .unwrap();
+        Wallet::credit(tipper_id, &pln("20"), "test", &db)
+            .await
+            .unwrap();
+
+        let tip_id = Tip::create(
+            NewTip {
+                erika_id,
+                tipper_id,
+                stream_session_id: session_id,
+                label: None,
+                message: None,
+                amount_pln: &pln("9.99"),
+                commission_percent: 15,
+            },
+            &db,
+        )
+        .await
+        .unwrap()
+        .expect("saldo wystarcza");
+
+        // 15% z 9.99 to 1.4985 - zaokrąglamy do groszy
+        let tip = sqlx::query!("SELECT commission_pln FROM tips WHERE id = $1", tip_id)
+            .fetch_one(&db)
+            .await
+            .unwrap();
+        assert_eq!(tip.commission_pln, pln("1.50"));
+        let session = sqlx::query!(
+            "SELECT earnings_pln FROM stream_sessions WHERE id = $1",
+            session_id
+        )
+        .fetch_one(&db)
+        .await
+        .unwrap();
+        assert_eq!(session.earnings_pln, pln("8.49"));
+        assert_eq!(Wallet::balance(tipper_id, &db).await.unwrap(), pln("10.01"));
+    }
+
+    #[sqlx::test]
+    async fn create_without_funds_changes_nothing(db: PgPool) {
+        let erika_id = account("anna", &db).await;
+        let tipper_id = account("fan", &db).await;
+        let session_id = StreamSession::start_unscheduled(erika_id, "Live", &db)
+            .await
+            .unwrap();
+        Wallet::credit(tipper_id, &pln("5"), "test", &db)
+            .await
+            .unwrap();
+
+        let tip = Tip::create(
+            NewTip {
+                erika_id,
+                tipper_id,
+                stream_session_id: session_id,
+                label: None,
+                message: None,
+                amount_pln: &pln("10"),
+                commission_percent: 20,
+            },
+            &db,
+        )
+        .await
+        .unwrap();
+
+        assert!(tip.is_none());
+        assert_eq!(Wallet::balance(tipper_id, &db).await.unwrap(), pln("5"));
+        let counts = sqlx::query!(
+            r#"SELECT (SELECT COUNT(*) FROM tips) AS "tips!",
+                      (SELECT COUNT(*) FROM wallet_transactions WHERE kind = 'Tip') AS "debits!""#
+        )
+        .fetch_one(&db)
+        .await
+        .unwrap();
+        assert_eq!((counts.tips, counts.debits), (0, 0));
+    }
+
+    #[sqlx::test]
+    async fn deleting_tipper_keeps_tip_without_personal_data(db: PgPool) {
+        let erika_id = account("anna", &db).await;
+        let tipper_id = account("fan", &db).await;
+        let session_id = StreamSession::start_unscheduled(erika_id, "Live", &db)
+            .await
+            .unwrap();
+        Wallet::credit(tipper_id, &pln("10"), "test", &db)
+            .await
+            .unwrap();
+        let tip_id = Tip::create(
+            NewTip {
+                erika_id,
+                tipper_id,
+                stream_session_id: session_id,
+                label: None,
+                message: Some("Pozdrowienia od Janka"),
+                amount_pln: &pln("10"),
+                commission_percent: 20,
+            },
+            &db,
+        )
+        .await
+        .unwrap()
+        .expect("saldo wystarcza");
+
+        assert!(Wallet::delete_if_empty(tipper_id, &db).await.unwrap());
+        Erika::delete(tipper_id, &db).await.unwrap();
+
+        let tip = sqlx::query!(
+            "SELECT erika_id, tipper_id, message, amount_pln FROM tips WHERE id = $1",
+            tip_id
+        )
+        .fetch_one(&db)
+        .await
+        .unwrap();
+        assert_eq!(
+            (tip.erika_id, tip.tipper_id, tip.message),
+            (Some(erika_id), None, None)
+        );
+        assert_eq!(tip.amount_pln, pln("10"));
+        let ledger = sqlx::query!(
+            r#"SELECT COUNT(*) AS "count!" FROM wallet_transactions WHERE erika_id IS NULL"#
+        )
+        .fetch_one(&db)
+        .await
+        .unwrap();
+        assert_eq!(ledger.count, 2);
+    }
+}
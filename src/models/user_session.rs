@@ -0,0 +1,133 @@
+// src/models/user_session.rs
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// Metadane jednej aktywnej sesji logowania.
+#[derive(sqlx::FromRow, Clone)]
+pub struct UserSession {
+    pub id: Uuid,
+    pub session_id: String,
+    pub erika_id: Uuid,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at: OffsetDateTime,
+    pub last_seen_at: OffsetDateTime,
+}
+
+impl UserSession {
+    /// Zapisuje nową sesję albo odświeża `last_seen_at` istniejącej. Wołane
+    /// przy każdym żądaniu, więc istniejący wiersz zapisujemy najwyżej raz na
+    /// minutę - chyba że zmienił się adres IP.
+    pub async fn touch(
+        session_id: &str,
+        erika_id: Uuid,
+        user_agent: Option<&str>,
+        ip_address: Option<&str>,
+        db: &PgPool,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "INSERT INTO user_sessions (session_id, erika_id, user_agent, ip_address)
+             VALUES ($1, $2, $3, $4)
+             ON CONFLICT (session_id) DO UPDATE
+             SET last_seen_at = NOW(), ip_address = EXCLUDED.ip_address
+             WHERE user_sessions.last_seen_at < NOW() - INTERVAL '1 minute'
+                OR user_sessions.ip_address IS DISTINCT FROM EXCLUDED.ip_address",
+            session_id,
+            erika_id,
+            user_agent,
+            ip_address
+        )
+        .execute(db)
+        .await?;
+        Ok(())
+    }
+
+    /// Przepina metadane na nowe ID sesji (po `cycle_id`).
+    pub async fn rename(
+        old_session_id: &str,
+        new_session_id: &str,
+        db: &PgPool,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE user_sessions SET session_id = $1, last_seen_at = NOW() WHERE session_id = $2",
+            new_session_id,
+            old_session_id
+        )
+        .execute(db)
+        .await?;
+        Ok(())
+    }
+
+    /// Aktywne sesje danej Eriki, od ostatnio używanej.
+    pub async fn find_active_by_erika_id(
+        erika_id: Uuid,
+        inactivity_days: i64,
+        db: &PgPool,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            UserSession,
+            "SELECT * FROM user_sessions
+             WHERE erika_id = $1 AND last_seen_at > NOW() - make_interval(days => $2::INT)
+             ORDER BY last_seen_at DESC",
+            erika_id,
+            inactivity_days as i32
+        )
+        .fetch_all(db)
+        .await
+    }
+
+    /// Znajduje sesję po publicznym ID, tylko jeśli należy do wskazanej Eriki.
+    pub async fn find_by_id_and_erika_id(
+        id: Uuid,
+        erika_id: Uuid,
+        db: &PgPool,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            UserSession,
+            "SELECT * FROM user_sessions WHERE id = $1 AND erika_id = $2",
+            id,
+            erika_id
+        )
+        .fetch_optional(db)
+        .await
+    }
+
+    /// Wszystkie sesje Eriki poza wskazaną (`None` = wszystkie).
+    pub async fn find_all_except(
+        erika_id: Uuid,
+        keep_session_id: Option<&str>,
+        db: &PgPool,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            UserSession,
+            "SELECT * FROM user_sessions
+             WHERE erika_id = $1 AND ($2::TEXT IS NULL OR session_id <> $2)",
+            erika_id,
+            keep_session_id
+        )
+        .fetch_all(db)
+        .await
+    }
+
+    pub async fn delete_by_session_id(session_id: &str, db: &PgPool) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "DELETE FROM user_sessions WHERE session_id = $1",
+            session_id
+        )
+        .execute(db)
+        .await?;
+        Ok(())
+    }
+
+    /// Usuwa metadane sesji, które i tak już wygasły w magazynie sesji.
+    pub async fn delete_stale(inactivity_days: i64, db: &PgPool) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM user_sessions WHERE last_seen_at < NOW() - make_interval(days => $1::INT)",
+            inactivity_days as i32
+        )
+        .execute(db)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,312 @@
+// src/models/verification.rs
+use sqlx::PgPool;
+use strum::Display;
+use time::{Date, OffsetDateTime};
+use uuid::Uuid;
+
+/// Minimalny wiek modelki w latach.
+pub const MIN_AGE: i32 = 18;
+
+/// Ile lat po usunięciu konta przechowujemy dokumenty weryfikacji
+/// (i zgody osób współwystępujących) jako dowód pełnoletności.
+pub const RETENTION_YEARS: i32 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, sqlx::Type, Display)]
+#[sqlx(type_name = "verification_status")]
+pub enum VerificationStatus {
+    Pending,
+    Verified,
+    Rejected,
+}
+
+impl VerificationStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            VerificationStatus::Pending => "W trakcie sprawdzania",
+            VerificationStatus::Verified => "Zweryfikowana",
+            VerificationStatus::Rejected => "Odrzucona",
+        }
+    }
+}
+
+/// Rodzaj pliku w zgłoszeniu - także fragment URL-a podglądu dla admina.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VerificationFile {
+    DocumentFront,
+    DocumentBack,
+    Selfie,
+}
+
+impl VerificationFile {
+    pub fn from_slug(slug: &str) -> Option<Self> {
+        match slug {
+            "front" => Some(VerificationFile::DocumentFront),
+            "back" => Some(VerificationFile::DocumentBack),
+            "selfie" => Some(VerificationFile::Selfie),
+            _ => None,
+        }
+    }
+
+    pub fn slug(&self) -> &'static str {
+        match self {
+            VerificationFile::DocumentFront => "front",
+            VerificationFile::DocumentBack => "back",
+            VerificationFile::Selfie => "selfie",
+        }
+    }
+}
+
+#[derive(sqlx::FromRow, Clone)]
+pub struct IdentityVerification {
+    pub id: Uuid,
+    pub erika_id: Uuid,
+    pub status: VerificationStatus,
+    pub date_of_birth: Date,
+    pub document_front_path: String,
+    pub document_back_path: Option<String>,
+    pub selfie_path: String,
+    pub rejection_reason: Option<String>,
+    pub reviewed_at: Option<OffsetDateTime>,
+    pub created_at: OffsetDateTime,
+}
+
+/// Zgłoszenie w kolejce admina.
+#[derive(sqlx::FromRow, Clone)]
+pub struct VerificationQueueItem {
+    pub id: Uuid,
+    pub username: String,
+    pub date_of_birth: Date,
+    pub created_at: OffsetDateTime,
+}
+
+/// Dane nowego zgłoszenia - ścieżki do już zapisanych plików.
+pub struct NewVerification<'a> {
+    pub erika_id: Uuid,
+    pub date_of_birth: Date,
+    pub document_front_path: &'a str,
+    pub document_back_path: Option<&'a str>,
+    pub selfie_path: &'a str,
+}
+
+/// Wiek w pełnych latach w dniu `today`.
+pub fn age_on(date_of_birth: Date, today: Date) -> i32 {
+    let mut age = today.year() - date_of_birth.year();
+    if (today.month() as u8, today.day()) < (date_of_birth.month() as u8, date_of_birth.day()) {
+        age -= 1;
+    }
+    age
+}
+
+impl IdentityVerification {
+    pub fn age(&self) -> i32 {
+        age_on(self.date_of_birth, OffsetDateTime::now_utc().date())
+    }
+
+    pub fn file_path(&self, file: VerificationFile) -> Option<&str> {
+        match file {
+            VerificationFile::DocumentFront => Some(&self.document_front_path),
+            VerificationFile::DocumentBack => self.document_back_path.as_deref(),
+            VerificationFile::Selfie => Some(&self.selfie_path),
+        }
+    }
+
+    /// Wszystkie pliki zgłoszenia (do eksportu i usuwania konta).
+    pub fn file_paths(&self) -> Vec<&str> {
+        [
+            Some(self.document_front_path.as_str()),
+            self.document_back_path.as_deref(),
+            Some(self.selfie_path.as_str()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    pub async fn create(new: NewVerification<'_>, db: &PgPool) -> Result<Uuid, sqlx::Error> {
+        let row = sqlx::query!(
+            "INSERT INTO identity_verifications
+                 (erika_id, date_of_birth, document_front_path, document_back_path, selfie_path)
+             VALUES ($1, $2, $3, $4, $5)
+             RETURNING id",
+            new.erika_id,
+            new.date_of_birth,
+            new.document_front_path,
+            new.document_back_path,
+            new.selfie_path
+        )
+        .fetch_one(db)
+        .await?;
+        Ok(row.id)
+    }
+
+    pub async fn find_by_id(id: Uuid, db: &PgPool) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            IdentityVerification,
+            r#"SELECT id, erika_id as "erika_id!", status as "status: _", date_of_birth, document_front_path,
+                      document_back_path, selfie_path, rejection_reason, reviewed_at, created_at
+               FROM identity_verifications WHERE id = $1 AND restricted_at IS NULL"#,
+            id
+        )
+        .fetch_optional(db)
+        .await
+    }
+
+    /// Wszystkie zgłoszenia konta, od najnowszego.
+    pub async fn find_by_erika_id(erika_id: Uuid, db: &PgPool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            IdentityVerification,
+            r#"SELECT id, erika_id as "erika_id!", status as "status: _", date_of_birth, document_front_path,
+                      document_back_path, selfie_path, rejection_reason, reviewed_at, created_at
+               FROM identity_verifications WHERE erika_id = $1
+               ORDER BY created_at DESC"#,
+            erika_id
+        )
+        .fetch_all(db)
+        .await
+    }
+
+    pub async fn find_pending(db: &PgPool) -> Result<Vec<VerificationQueueItem>, sqlx::Error> {
+        sqlx::query_as!(
+            VerificationQueueItem,
+            "SELECT v.id, e.username, v.date_of_birth, v.created_at
+             FROM identity_verifications v JOIN erikas e ON e.id = v.erika_id
+             WHERE v.status = 'Pending'
+             ORDER BY v.created_at ASC"
+        )
+        .fetch_all(db)
+        .await
+    }
+
+    pub async fn count_pending(db: &PgPool) -> Result<i64, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"SELECT COUNT(*) as "count!" FROM identity_verifications
+               WHERE status = 'Pending' AND restricted_at IS NULL"#
+        )
+        .fetch_one(db)
+        .await?;
+        Ok(row.count)
+    }
+
+    pub async fn has_verified(erika_id: Uuid, db: &PgPool) -> Result<bool, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"SELECT EXISTS(
+                 SELECT 1 FROM identity_verifications WHERE erika_id = $1 AND status = 'Verified'
+               ) as "exists!""#,
+            erika_id
+        )
+        .fetch_one(db)
+        .await?;
+        Ok(row.exists)
+    }
+
+    /// Usuwa zgłoszenia, którym minął okres przechowywania, i zwraca ścieżki
+    /// ich plików do usunięcia z dysku.
+    pub async fn purge_expired(db: &PgPool) -> Result<Vec<String>, sqlx::Error> {
+        let rows = sqlx::query!(
+            "DELETE FROM identity_verifications
+             WHERE retain_until < NOW()
+             RETURNING document_front_path, document_back_path, selfie_path",
+        )
+        .fetch_all(db)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .flat_map(|r| {
+                [
+                    Some(r.document_front_path),
+                    r.document_back_path,
+                    Some(r.selfie_path),
+                ]
+            })
+            .flatten()
+            .collect())
+    }
+
+    /// Zapisuje decyzję admina. Działa tylko na zgłoszeniu, które wciąż czeka.
+    pub async fn review(
+        id: Uuid,
+        status: VerificationStatus,
+        rejection_reason: Option<&str>,
+        reviewer_id: Uuid,
+        db: &PgPool,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "UPDATE identity_verifications
+             SET status = $1, rejection_reason = $2, reviewed_by = $3, reviewed_at = NOW()
+             WHERE id = $4 AND status = 'Pending' AND restricted_at IS NULL",
+            status as VerificationStatus,
+            rejection_reason,
+            reviewer_id,
+            id
+        )
+        .execute(db)
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::erika::Erika;
+    use time::macros::date;
+
+    async fn account(username: &str, db: &PgPool) -> Uuid {
+        sqlx::query!(
+            "INSERT INTO erikas (username, email, password_hash) VALUES ($1, $2, 'x') RETURNING id",
+            username,
+            format!("{}@example.com", username)
+        )
+        .fetch_one(db)
+        .await
+        .unwrap()
+        .id
+    }
+
+    #[sqlx::test]
+    async fn deleted_account_keeps_restricted_verification_until_purge(db: PgPool) {
+        let erika_id = account("anna", &db).await;
+        let id = IdentityVerification::create(
+            NewVerification {
+                erika_id,
+                date_of_birth: date!(1990 - 01 - 01),
+                document_front_path: "private/verifications/front.jpg",
+                document_back_path: None,
+                selfie_path: "private/verifications/selfie.jpg",
+            },
+            &db,
+        )
+        .await
+        .unwrap();
+
+        Erika::delete(erika_id, &db).await.unwrap();
+
+        // Wiersz zostaje, ale panel admina go nie widzi
+        assert!(
+            IdentityVerification::find_by_id(id, &db)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert_eq!(IdentityVerification::count_pending(&db).await.unwrap(), 0);
+        assert!(
+            IdentityVerification::purge_expired(&db)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        sqlx::query!("UPDATE identity_verifications SET retain_until = NOW() - INTERVAL '1 day'")
+            .execute(&db)
+            .await
+            .unwrap();
+        let files = IdentityVerification::purge_expired(&db).await.unwrap();
+        assert_eq!(
+            files,
+            [
+                "private/verifications/front.jpg",
+                "private/verifications/selfie.jpg"
+            ]
+        );
+    }
+}
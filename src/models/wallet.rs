@@ -0,0 +1,141 @@
+// src/models/wallet.rs
+use bigdecimal::BigDecimal;
+use sqlx::{PgConnection, PgPool};
+use strum::Display;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// Przedpłacone saldo konta. Konto bez wpisu ma saldo zero.
+/// Każda zmiana salda ma wpis w dzienniku `wallet_transactions`.
+pub struct Wallet;
+
+#[derive(Debug, Clone, Copy, PartialEq, sqlx::Type, Display)]
+#[sqlx(type_name = "wallet_transaction_kind")]
+pub enum WalletTransactionKind {
+    Credit,
+    Tip,
+    PrivateShow,
+}
+
+/// Za co pobieramy pieniądze z salda.
+#[derive(Debug, Clone, Copy)]
+pub enum WalletDebit {
+    Tip(Uuid),
+    /// Kolejna minuta pokazu prywatnego.
+    PrivateShow(Uuid),
+}
+
+/// Wpis dziennika salda.
+#[derive(sqlx::FromRow, Clone)]
+pub struct WalletTransaction {
+    pub kind: WalletTransactionKind,
+    pub amount_pln: BigDecimal,
+    pub balance_after_pln: BigDecimal,
+    pub note: Option<String>,
+    pub created_at: OffsetDateTime,
+}
+
+impl Wallet {
+    pub async fn balance(erika_id: Uuid, db: &PgPool) -> Result<BigDecimal, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"SELECT COALESCE((SELECT balance_pln FROM wallets WHERE erika_id = $1), 0) AS "balance!""#,
+            erika_id
+        )
+        .fetch_one(db)
+        .await?;
+        Ok(row.balance)
+    }
+
+    /// Doładowuje saldo po potwierdzonej wpłacie i zwraca nowe saldo.
+    /// `note` trafia do dziennika (np. numer przelewu).
+    pub async fn credit(
+        erika_id: Uuid,
+        amount_pln: &BigDecimal,
+        note: &str,
+        db: &PgPool,
+    ) -> Result<BigDecimal, sqlx::Error> {
+        let mut tx = db.begin().await?;
+        let row = sqlx::query!(
+            "INSERT INTO wallets (erika_id, balance_pln) VALUES ($1, $2)
+             ON CONFLICT (erika_id) DO UPDATE
+             SET balance_pln = wallets.balance_pln + EXCLUDED.balance_pln, updated_at = NOW()
+             RETURNING balance_pln",
+            erika_id,
+            amount_pln
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        sqlx::query!(
+            "INSERT INTO wallet_transactions (erika_id, kind, amount_pln, balance_after_pln, note)
+             VALUES ($1, 'Credit', $2, $3, $4)",
+            erika_id,
+            amount_pln,
+            row.balance_pln,
+            note
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(row.balance_pln)
+    }
+
+    /// Pobiera kwotę z salda w transakcji wywołującego i zapisuje to w dzienniku.
+    /// `false` - saldo jest za małe i nic nie zostało pobrane.
+    pub async fn debit(
+        erika_id: Uuid,
+        amount_pln: &BigDecimal,
+        debit: WalletDebit,
+        conn: &mut PgConnection,
+    ) -> Result<bool, sqlx::Error> {
+        let (kind, tip_id, private_show_id) = match debit {
+            WalletDebit::Tip(id) => (WalletTransactionKind::Tip, Some(id), None),
+            WalletDebit::PrivateShow(id) => (WalletTransactionKind::PrivateShow, None, Some(id)),
+        };
+        let result = sqlx::query!(
+            "WITH debited AS (
+                 UPDATE wallets SET balance_pln = balance_pln - $2, updated_at = NOW()
+                 WHERE erika_id = $1 AND balance_pln >= $2
+                 RETURNING erika_id, balance_pln
+             )
+             INSERT INTO wallet_transactions (erika_id, kind, amount_pln, balance_after_pln, tip_id, private_show_id)
+             SELECT erika_id, $3, -$2::NUMERIC, balance_pln, $4, $5 FROM debited",
+            erika_id,
+            amount_pln,
+            kind as WalletTransactionKind,
+            tip_id,
+            private_show_id
+        )
+        .execute(conn)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Usuwa pusty portfel przed usunięciem konta (dziennik zostaje).
+    /// `false` - na saldzie są jeszcze pieniądze i portfel zostaje.
+    pub async fn delete_if_empty(erika_id: Uuid, db: &PgPool) -> Result<bool, sqlx::Error> {
+        sqlx::query!(
+            "DELETE FROM wallets WHERE erika_id = $1 AND balance_pln = 0",
+            erika_id
+        )
+        .execute(db)
+        .await?;
+        Ok(Self::balance(erika_id, db).await? == BigDecimal::from(0))
+    }
+
+    /// Dziennik salda konta, od najstarszego wpisu - do eksportu danych.
+    pub async fn find_transactions(
+        erika_id: Uuid,
+        db: &PgPool,
+    ) -> Result<Vec<WalletTransaction>, sqlx::Error> {
+        sqlx::query_as!(
+            WalletTransaction,
+            r#"SELECT kind AS "kind: _", amount_pln, balance_after_pln, note, created_at
+               FROM wallet_transactions
+               WHERE erika_id = $1
+               ORDER BY created_at"#,
+            erika_id
+        )
+        .fetch_all(db)
+        .await
+    }
+}
@@ -0,0 +1,107 @@
+// src/password.rs
+
+// Hashowanie haseł Argon2id z parametrami z konfiguracji (.env).
+// Hash w formacie PHC zapisuje własne parametry, więc stare hashe dalej
+// się weryfikują po podniesieniu kosztów - przy logowaniu przeliczamy je
+// na nowe (`needs_rehash`).
+
+use argon2::{
+    Algorithm, Argon2, Params, PasswordHash, PasswordHasher, PasswordVerifier, Version,
+    password_hash::{self, SaltString, rand_core::OsRng},
+};
+use std::{env, sync::OnceLock};
+
+pub const MEMORY_KIB_VAR: &str = "ARGON2_MEMORY_KIB";
+pub const TIME_COST_VAR: &str = "ARGON2_TIME_COST";
+pub const PARALLELISM_VAR: &str = "ARGON2_PARALLELISM";
+
+static PARAMS: OnceLock<Params> = OnceLock::new();
+static DUMMY_HASH: OnceLock<String> = OnceLock::new();
+
+/// Aktualne parametry Argon2. Brakujące zmienne środowiskowe
+/// zastępujemy domyślnymi wartościami biblioteki.
+pub fn params() -> &'static Params {
+    PARAMS.get_or_init(|| {
+        let read = |key: &str, default: u32| match env::var(key) {
+            Ok(value) => value
+                .parse()
+                .unwrap_or_else(|_| panic!("{} musi być liczbą, jest '{}'", key, value)),
+            Err(_) => default,
+        };
+        Params::new(
+            read(MEMORY_KIB_VAR, Params::DEFAULT_M_COST),
+            read(TIME_COST_VAR, Params::DEFAULT_T_COST),
+            read(PARALLELISM_VAR, Params::DEFAULT_P_COST),
+            None,
+        )
+        .expect("Niepoprawne parametry Argon2 w konfiguracji")
+    })
+}
+
+fn hasher() -> Argon2<'static> {
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params().clone())
+}
+
+/// Hashuje hasło z nową solą. Operacja jest kosztowna - wołać w `spawn_blocking`.
+pub fn hash(password: &str) -> Result<String, password_hash::Error> {
+    let salt = SaltString::generate(&mut OsRng);
+    Ok(hasher()
+        .hash_password(password.as_bytes(), &salt)?
+        .to_string())
+}
+
+/// Weryfikuje hasło. Parametry bierzemy z zapisanego hasha, nie z konfiguracji.
+pub fn verify(password: &str, phc: &str) -> Result<bool, password_hash::Error> {
+    let parsed = PasswordHash::new(phc)?;
+    match hasher().verify_password(password.as_bytes(), &parsed) {
+        Ok(()) => Ok(true),
+        Err(password_hash::Error::Password) => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// Hash, z którym porównujemy hasło, gdy konta o podanej nazwie nie ma.
+/// Ma aktualne parametry, więc odpowiedź trwa tyle samo co dla istniejącego
+/// konta i czas logowania nie zdradza, które nazwy są zajęte.
+pub fn dummy_hash() -> &'static str {
+    DUMMY_HASH.get_or_init(|| hash("brak-konta").expect("Nie udało się policzyć hasha zastępczego"))
+}
+
+/// Czy hash jest słabszy niż obecna konfiguracja (inny algorytm,
+/// starsza wersja albo niższy koszt pamięci, czasu lub równoległości).
+pub fn needs_rehash(phc: &str) -> bool {
+    let Ok(parsed) = PasswordHash::new(phc) else {
+        return true;
+    };
+    if Algorithm::try_from(parsed.algorithm) != Ok(Algorithm::Argon2id)
+        || parsed.version != Some(Version::V0x13.into())
+    {
+        return true;
+    }
+    let Ok(stored) = Params::try_from(&parsed) else {
+        return true;
+    };
+
+    let current = params();
+    stored.m_cost() < current.m_cost()
+        || stored.t_cost() < current.t_cost()
+        || stored.p_cost() < current.p_cost()
+}
+
+/// Krótki opis parametrów hasha, np. `argon2id v19 m=19456 t=2 p=1`.
+pub fn describe(phc: &str) -> String {
+    let Ok(parsed) = PasswordHash::new(phc) else {
+        return "nieczytelny hash".to_string();
+    };
+    match Params::try_from(&parsed) {
+        Ok(p) => format!(
+            "{} v{} m={} t={} p={}",
+            parsed.algorithm,
+            parsed.version.unwrap_or_default(),
+            p.m_cost(),
+            p.t_cost(),
+            p.p_cost()
+        ),
+        Err(_) => parsed.algorithm.to_string(),
+    }
+}
@@ -0,0 +1,73 @@
+// src/presence.rs
+
+// Obecność modelek. Panel kamerki wysyła co `HEARTBEAT_INTERVAL_SECS` sygnał,
+// a ten task co chwilę gasi status online kont, od których sygnał nie przyszedł
+// w czasie ustawionym w ustawieniach serwisu. Trwająca transmisja (także pokaz
+// prywatny, który przy tym rozliczamy) modelki, która zniknęła bez jej
+// zakończenia, kończy się razem ze statusem online.
+
+use crate::models::private_show::{PrivateShow, PrivateShowEndReason};
+use crate::models::{erika::Erika, stream_session::StreamSession};
+use crate::{app_state::AppState, events::CreatorEvent};
+use std::time::Duration;
+use tracing::{error, info};
+use uuid::Uuid;
+
+/// Co ile sekund panel kamerki wysyła sygnał obecności.
+pub const HEARTBEAT_INTERVAL_SECS: u64 = 30;
+
+/// Co ile sprawdzamy, kto przestał wysyłać sygnał.
+const SWEEP_INTERVAL: Duration = Duration::from_secs(HEARTBEAT_INTERVAL_SECS);
+
+/// Uruchamia task w tle. Wywoływane raz przy starcie serwera.
+pub fn spawn_sweeper(state: AppState) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(SWEEP_INTERVAL);
+        loop {
+            interval.tick().await;
+            let timeout_secs = state.settings().presence_timeout_secs();
+            match Erika::expire_presence(timeout_secs, &state.db).await {
+                Ok(expired) if expired.is_empty() => {}
+                Ok(expired) => {
+                    info!(
+                        "Oznaczono {} nieaktywnych modelek jako offline",
+                        expired.len()
+                    );
+                    for erika_id in expired {
+                        state.publish(CreatorEvent::Presence {
+                            erika_id,
+                            is_online: false,
+                        });
+                        end_private_show(&state, erika_id).await;
+                        if let Err(e) = StreamSession::end_live(erika_id, &state.db).await {
+                            error!(
+                                "Nie udało się zakończyć transmisji modelki {}: {}",
+                                erika_id, e
+                            );
+                        }
+                    }
+                }
+                Err(e) => error!("Nie udało się wygasić statusu online: {}", e),
+            }
+        }
+    });
+}
+
+async fn end_private_show(state: &AppState, erika_id: Uuid) {
+    let commission_percent = state.settings().commission_percent;
+    match PrivateShow::end_for_erika(
+        erika_id,
+        PrivateShowEndReason::CreatorOffline,
+        commission_percent,
+        &state.db,
+    )
+    .await
+    {
+        Ok(Some(show_id)) => state.publish(CreatorEvent::PrivateShow { erika_id, show_id }),
+        Ok(None) => {}
+        Err(e) => error!(
+            "Nie udało się zakończyć pokazu prywatnego modelki {}: {}",
+            erika_id, e
+        ),
+    }
+}
@@ -0,0 +1,45 @@
+// src/retention.rs
+
+// Okres przechowywania dokumentów. Po usunięciu konta dokumenty weryfikacji
+// tożsamości i zgody osób współwystępujących zostają zastrzeżone (bez dostępu
+// z panelu) na `verification::RETENTION_YEARS` lat. Raz na dobę usuwamy te, którym ten
+// okres minął - razem z plikami na dysku.
+
+use crate::app_state::AppState;
+use crate::models::{co_performer::CoPerformer, verification::IdentityVerification};
+use std::time::Duration;
+use tokio::fs;
+use tracing::{error, info, warn};
+
+/// Co ile sprawdzamy, czy minął okres przechowywania.
+const PURGE_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Uruchamia task w tle. Wywoływane raz przy starcie serwera.
+pub fn spawn_purger(state: AppState) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(PURGE_INTERVAL);
+        loop {
+            interval.tick().await;
+            match IdentityVerification::purge_expired(&state.db).await {
+                Ok(files) => remove_files(files).await,
+                Err(e) => error!("Nie udało się usunąć przeterminowanych weryfikacji: {}", e),
+            }
+            match CoPerformer::purge_expired(&state.db).await {
+                Ok(files) => remove_files(files).await,
+                Err(e) => error!("Nie udało się usunąć przeterminowanych zgód: {}", e),
+            }
+        }
+    });
+}
+
+async fn remove_files(files: Vec<String>) {
+    if files.is_empty() {
+        return;
+    }
+    info!("Usuwam {} plików po okresie przechowywania", files.len());
+    for path in files {
+        if let Err(e) = fs::remove_file(&path).await {
+            warn!("Nie udało się usunąć pliku {}: {}", path, e);
+        }
+    }
+}
@@ -2,36 +2,241 @@
 
 use crate::{
     app_state::AppState,
-    handlers::{admin_handlers, erika_handlers, gallery_handlers},
+    handlers::{
+        admin_gallery_handlers, admin_handlers, announcement_handlers, audit_handlers,
+        category_handlers, co_performer_handlers, erika_handlers, gallery_handlers,
+        impersonation_handlers, metrics_handlers, moderation_handlers, notice_handlers,
+        password_reset_handlers, privacy_handlers, private_show_handlers, report_handlers,
+        security_handlers, settings_handlers, staff_handlers, stream_handlers, tip_handlers,
+        upload_handlers, verification_handlers,
+    },
     middleware,
+    models::{permission::Permission, site_settings::MAX_UPLOAD_CEILING_MB},
 };
 
 use axum::{
     Router, // Dodajemy `post`
+    extract::DefaultBodyLimit,
     middleware as axum_middleware,
     routing::{get, post},
 };
-use tower_http::services::ServeDir;
 
 pub fn create_router(app_state: AppState) -> Router {
-    // Grupujemy ścieżki admina i nakładamy na nie nasz middleware
-    let admin_routes = Router::new()
-        .route("/", get(admin_handlers::admin_dashboard))
+    // Panel admina: każda grupa tras wymaga jednego uprawnienia,
+    // a całość - bycia członkiem zespołu (admin lub moderator)
+    let content_routes = Router::new()
+        .route(
+            "/moderation",
+            get(moderation_handlers::show_moderation_queue),
+        )
+        .route("/moderation/bulk", post(moderation_handlers::bulk_moderate))
+        .route(
+            "/moderation/{photo_id}/approve",
+            post(moderation_handlers::approve_photo),
+        )
+        .route(
+            "/moderation/{photo_id}/reject",
+            post(moderation_handlers::reject_photo),
+        )
+        .route("/reports", get(report_handlers::show_reports))
+        .route("/reports/{report_id}", get(report_handlers::show_report))
+        .route(
+            "/reports/{report_id}/decide",
+            post(report_handlers::decide_report),
+        );
+
+    let creator_approval_routes = Router::new()
+        .route(
+            "/erika/{erika_id}/status",
+            post(admin_handlers::change_erika_status),
+        )
+        .route(
+            "/verifications",
+            get(verification_handlers::show_verification_queue),
+        )
+        .route(
+            "/verifications/{verification_id}",
+            get(verification_handlers::show_verification_review),
+        )
+        .route(
+            "/verifications/{verification_id}/files/{file}",
+            get(verification_handlers::show_verification_file),
+        )
+        .route(
+            "/verifications/{verification_id}/approve",
+            post(verification_handlers::approve_verification),
+        )
+        .route(
+            "/verifications/{verification_id}/reject",
+            post(verification_handlers::reject_verification),
+        )
+        .route(
+            "/co-performers",
+            get(co_performer_handlers::show_co_performer_queue),
+        )
+        .route(
+            "/co-performers/export",
+            get(co_performer_handlers::export_compliance_records),
+        )
+        .route(
+            "/co-performers/{co_performer_id}",
+            get(co_performer_handlers::show_co_performer_review),
+        )
+        .route(
+            "/co-performers/{co_performer_id}/files/{file}",
+            get(co_performer_handlers::show_co_performer_file),
+        )
+        .route(
+            "/co-performers/{co_performer_id}/approve",
+            post(co_performer_handlers::approve_co_performer),
+        )
+        .route(
+            "/co-performers/{co_performer_id}/reject",
+            post(co_performer_handlers::reject_co_performer),
+        );
+
+    let profile_routes = Router::new()
+        .route("/creators", get(admin_handlers::show_creators))
         .route(
             "/erika/{erika_id}",
             get(admin_handlers::show_edit_erika_form).post(admin_handlers::update_erika_by_admin),
         )
         .route(
-            "/erika/{erika_id}/approve",
-            post(admin_handlers::approve_erika),
+            "/erika/{erika_id}/logout-all",
+            post(admin_handlers::force_logout_erika),
         )
+        // Edycja galerii i usuwanie zdjęć to zmiany w treściach modelki, nie moderacja
+        .route(
+            "/galleries/{gallery_id}",
+            get(admin_gallery_handlers::show_gallery).post(admin_gallery_handlers::update_gallery),
+        )
+        .route(
+            "/galleries/{gallery_id}/unpublish",
+            post(admin_gallery_handlers::unpublish_gallery),
+        )
+        .route(
+            "/galleries/{gallery_id}/photos/{photo_id}",
+            get(admin_gallery_handlers::get_photo_partial),
+        )
+        .route(
+            "/galleries/{gallery_id}/photos/{photo_id}/delete-confirm",
+            get(admin_gallery_handlers::confirm_delete_photo),
+        )
+        .route(
+            "/galleries/{gallery_id}/photos/{photo_id}/delete",
+            post(admin_gallery_handlers::delete_photo),
+        );
+
+    let settings_routes = Router::new()
+        .route(
+            "/categories",
+            get(category_handlers::show_categories).post(category_handlers::create_category),
+        )
+        .route(
+            "/categories/{category_id}",
+            get(category_handlers::show_edit_category).post(category_handlers::update_category),
+        )
+        .route(
+            "/categories/{category_id}/delete",
+            post(category_handlers::delete_category),
+        )
+        .route(
+            "/settings",
+            get(settings_handlers::show_settings).post(settings_handlers::update_settings),
+        );
+
+    let staff_routes = Router::new().route(
+        "/staff",
+        get(staff_handlers::show_staff).post(staff_handlers::assign_role),
+    );
+
+    let payout_routes = Router::new().route(
+        "/erika/{erika_id}/wallet/credit",
+        post(admin_handlers::credit_wallet),
+    );
+
+    let audit_routes = Router::new().route("/audit", get(audit_handlers::show_audit_log));
+
+    let impersonation_routes = Router::new().route(
+        "/erika/{erika_id}/impersonate",
+        post(impersonation_handlers::start_impersonation),
+    );
+
+    let announcement_routes = Router::new()
+        .route(
+            "/announcements",
+            get(announcement_handlers::show_announcements)
+                .post(announcement_handlers::create_announcement),
+        )
+        .route(
+            "/announcements/{announcement_id}/delete",
+            post(announcement_handlers::delete_announcement),
+        )
+        .route(
+            "/erika/{erika_id}/notices",
+            get(notice_handlers::show_erika_notices).post(notice_handlers::send_notice),
+        );
+
+    let metrics_routes = Router::new().route("/metrics.csv", get(metrics_handlers::export_metrics));
+
+    // Stronę główną widzi każdy członek zespołu; wskaźniki tylko z `ViewMetrics`
+    let admin_routes = Router::new()
+        .route("/", get(metrics_handlers::show_dashboard))
+        .merge(guarded(
+            Permission::ModerateContent,
+            content_routes,
+            &app_state,
+        ))
+        .merge(guarded(
+            Permission::ApproveCreators,
+            creator_approval_routes,
+            &app_state,
+        ))
+        .merge(guarded(
+            Permission::EditProfiles,
+            profile_routes,
+            &app_state,
+        ))
+        .merge(guarded(
+            Permission::ManagePayouts,
+            payout_routes,
+            &app_state,
+        ))
+        .merge(guarded(
+            Permission::ManageSettings,
+            settings_routes,
+            &app_state,
+        ))
+        .merge(guarded(Permission::ManageStaff, staff_routes, &app_state))
+        .merge(guarded(Permission::ViewAuditLog, audit_routes, &app_state))
+        .merge(guarded(Permission::ViewMetrics, metrics_routes, &app_state))
+        .merge(guarded(
+            Permission::Impersonate,
+            impersonation_routes,
+            &app_state,
+        ))
+        .merge(guarded(
+            Permission::SendAnnouncements,
+            announcement_routes,
+            &app_state,
+        ))
         .route_layer(axum_middleware::from_fn_with_state(
             app_state.clone(),
-            middleware::require_admin,
+            middleware::require_staff,
         ));
 
     Router::new()
         .route("/", get(erika_handlers::homepage))
+        .route("/events/creators", get(erika_handlers::homepage_events))
+        .route("/events/tips/{erika_id}", get(tip_handlers::tip_events))
+        .route(
+            "/events/private-shows/{show_id}",
+            get(private_show_handlers::show_events),
+        )
+        .route(
+            "/events/private-requests",
+            get(private_show_handlers::request_events),
+        )
         .route(
             "/register",
             get(erika_handlers::show_register_form).post(erika_handlers::register_erika),
@@ -40,6 +245,11 @@ pub fn create_router(app_state: AppState) -> Router {
             "/login",
             get(erika_handlers::show_login_form).post(erika_handlers::login_erika),
         )
+        .route(
+            "/reset-password/{token}",
+            get(password_reset_handlers::show_reset_form)
+                .post(password_reset_handlers::reset_password),
+        )
         .route(
             "/panel",
             get(erika_handlers::erika_panel).post(erika_handlers::update_erika_profile),
@@ -66,20 +276,186 @@ pub fn create_router(app_state: AppState) -> Router {
         )
         .route(
             "/panel/galleries/{gallery_id}/upload",
-            post(gallery_handlers::upload_photo),
+            // Właściwy limit jest w ustawieniach serwisu i sprawdza go handler
+            post(gallery_handlers::upload_photo).layer(DefaultBodyLimit::max(
+                MAX_UPLOAD_CEILING_MB as usize * 1024 * 1024,
+            )),
+        )
+        .route(
+            "/panel/galleries/{gallery_id}/publish",
+            post(gallery_handlers::publish_gallery),
+        )
+        .route(
+            "/panel/galleries/{gallery_id}/unpublish",
+            post(gallery_handlers::unpublish_gallery),
+        )
+        .route(
+            "/panel/galleries/{gallery_id}/co-performers",
+            get(co_performer_handlers::show_co_performers_page)
+                .post(co_performer_handlers::add_co_performer)
+                .layer(DefaultBodyLimit::max(
+                    co_performer_handlers::MAX_UPLOAD_BYTES,
+                )),
+        )
+        .route(
+            "/panel/galleries/{gallery_id}/co-performers/{co_performer_id}/delete",
+            post(co_performer_handlers::remove_co_performer),
         )
         .route("/erika/{username}", get(erika_handlers::show_erika_profile))
+        .route(
+            "/erika/{username}/schedule.ics",
+            get(stream_handlers::schedule_feed),
+        )
         .route(
             "/pay/gallery/{gallery_id}",
             get(erika_handlers::initiate_gallery_payment),
         )
+        .route("/pay/tip/{username}", post(tip_handlers::send_tip))
+        .route(
+            "/pay/private/{username}",
+            post(private_show_handlers::request_show),
+        )
+        .route(
+            "/private-shows/{show_id}",
+            get(private_show_handlers::show_private_show),
+        )
+        .route(
+            "/private-shows/{show_id}/cancel",
+            post(private_show_handlers::cancel_show),
+        )
+        .route(
+            "/private-shows/{show_id}/end",
+            post(private_show_handlers::end_show_by_viewer),
+        )
+        .route(
+            "/private-shows/{show_id}/presence",
+            post(private_show_handlers::viewer_heartbeat),
+        )
+        .route(
+            "/report/{kind}/{target_id}",
+            get(report_handlers::show_report_form).post(report_handlers::submit_report),
+        )
         .route("/logout", post(erika_handlers::logout))
-        .route("/panel/stream", get(erika_handlers::show_stream_panel))
+        .route(
+            "/impersonation/stop",
+            post(impersonation_handlers::stop_impersonation),
+        )
+        .route("/panel/stream", get(stream_handlers::show_stream_panel))
+        .route(
+            "/panel/stream/schedule",
+            post(stream_handlers::schedule_stream),
+        )
+        .route(
+            "/panel/stream/start",
+            post(stream_handlers::start_stream_now),
+        )
+        .route("/panel/stream/end", post(stream_handlers::end_stream))
+        .route(
+            "/panel/stream/{session_id}/start",
+            post(stream_handlers::start_scheduled_stream),
+        )
+        .route(
+            "/panel/stream/{session_id}/delete",
+            post(stream_handlers::cancel_stream),
+        )
+        .route("/panel/stream/menu", post(tip_handlers::add_menu_item))
+        .route(
+            "/panel/stream/menu/{item_id}/delete",
+            post(tip_handlers::delete_menu_item),
+        )
+        .route("/panel/stream/goal", post(tip_handlers::set_goal))
+        .route("/panel/stream/goal/delete", post(tip_handlers::clear_goal))
+        .route(
+            "/panel/stream/private-rate",
+            post(private_show_handlers::set_private_rate),
+        )
+        .route(
+            "/panel/stream/private-rate/delete",
+            post(private_show_handlers::clear_private_rate),
+        )
+        .route(
+            "/panel/private/{show_id}/accept",
+            post(private_show_handlers::accept_show),
+        )
+        .route(
+            "/panel/private/{show_id}/decline",
+            post(private_show_handlers::decline_show),
+        )
+        .route(
+            "/panel/security",
+            get(security_handlers::show_security_page),
+        )
+        .route(
+            "/panel/security/sessions/{session_id}/revoke",
+            post(security_handlers::revoke_session),
+        )
+        .route(
+            "/panel/security/sessions/revoke-others",
+            post(security_handlers::revoke_other_sessions),
+        )
+        .route(
+            "/panel/verification",
+            get(verification_handlers::show_verification_page)
+                .post(verification_handlers::submit_verification)
+                .layer(DefaultBodyLimit::max(
+                    verification_handlers::MAX_UPLOAD_BYTES,
+                )),
+        )
+        .route(
+            "/panel/notices",
+            get(report_handlers::show_moderation_notices),
+        )
+        .route("/panel/messages", get(notice_handlers::show_notices))
+        .route(
+            "/panel/messages/{notice_id}",
+            get(notice_handlers::show_notice),
+        )
+        .route("/panel/privacy", get(privacy_handlers::show_privacy_page))
+        .route(
+            "/panel/privacy/export",
+            post(privacy_handlers::request_export),
+        )
+        .route(
+            "/panel/privacy/export/{job_id}",
+            get(privacy_handlers::download_export),
+        )
+        .route(
+            "/panel/privacy/delete",
+            post(privacy_handlers::request_deletion),
+        )
         .route(
             "/panel/status-toggle",
-            post(erika_handlers::toggle_online_status),
+            post(erika_handlers::toggle_appear_offline),
         )
+        .route("/panel/presence", post(erika_handlers::presence_heartbeat))
         .nest("/admin", admin_routes)
-        .nest_service("/uploads", ServeDir::new("uploads"))
+        // Tylko strony - pliki z `/uploads` dokładamy niżej, bez ogłoszeń
+        .layer(axum_middleware::from_fn_with_state(
+            app_state.clone(),
+            middleware::announcements,
+        ))
+        .route("/uploads/{file_name}", get(upload_handlers::serve_upload))
+        // Kolejność ma znaczenie: `site_settings` jest na zewnątrz, więc tryb
+        // konserwacji sprawdza admina, a nie konto oglądane w podglądzie
+        .layer(axum_middleware::from_fn_with_state(
+            app_state.clone(),
+            middleware::impersonation,
+        ))
+        .layer(axum_middleware::from_fn_with_state(
+            app_state.clone(),
+            middleware::site_settings,
+        ))
         .with_state(app_state)
 }
+
+/// Nakłada na grupę tras wymóg uprawnienia.
+fn guarded(
+    permission: Permission,
+    routes: Router<AppState>,
+    app_state: &AppState,
+) -> Router<AppState> {
+    routes.route_layer(axum_middleware::from_fn_with_state(
+        (app_state.clone(), permission),
+        middleware::require_permission,
+    ))
+}
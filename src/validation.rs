@@ -0,0 +1,433 @@
+// src/validation.rs
+
+// Walidacja danych z formularzy. Każdy payload zwraca listę błędów
+// przypisanych do pól, a handler renderuje formularz ponownie
+// z komunikatami pod polami i wcześniej wpisanymi wartościami.
+// Tu są wspólne reguły pól i limity; implementacje `Validate` leżą
+// obok payloadów w modułach handlerów.
+
+use bigdecimal::BigDecimal;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+pub const USERNAME_MIN_LEN: usize = 3;
+pub const USERNAME_MAX_LEN: usize = 30;
+pub const EMAIL_MAX_LEN: usize = 255;
+pub const PASSWORD_MIN_LEN: usize = 10;
+pub const PASSWORD_MAX_LEN: usize = 128;
+pub const BIO_MAX_LEN: usize = 500;
+pub const CATEGORY_NAME_MAX_LEN: usize = 60;
+pub const CATEGORY_SLUG_MAX_LEN: usize = 60;
+pub const HEADLINE_MAX_LEN: usize = 120;
+pub const BANNER_MAX_LEN: usize = 300;
+pub const MAINTENANCE_MESSAGE_MAX_LEN: usize = 500;
+pub const ANNOUNCEMENT_MAX_LEN: usize = 300;
+pub const NOTICE_SUBJECT_MAX_LEN: usize = 120;
+pub const NOTICE_BODY_MAX_LEN: usize = 2000;
+pub const STREAM_TITLE_MAX_LEN: usize = 120;
+pub const STREAM_MIN_MINUTES: i32 = 15;
+pub const STREAM_MAX_MINUTES: i32 = 12 * 60;
+pub const TIP_LABEL_MAX_LEN: usize = 60;
+pub const TIP_MESSAGE_MAX_LEN: usize = 200;
+pub const TIP_MIN_PLN: i64 = 1;
+pub const TIP_MAX_PLN: i64 = 10_000;
+pub const GOAL_MAX_PLN: i64 = 100_000;
+pub const PRIVATE_RATE_MAX_PLN: i64 = 1_000;
+
+/// Błędy walidacji przypisane do nazw pól formularza.
+#[derive(Debug, Default)]
+pub struct FormErrors(BTreeMap<&'static str, String>);
+
+impl FormErrors {
+    /// Dodaje błąd do pola. Zostaje pierwszy komunikat - zwykle najważniejszy.
+    pub fn add(&mut self, field: &'static str, message: impl Into<String>) {
+        self.0.entry(field).or_insert_with(|| message.into());
+    }
+
+    pub fn get(&self, field: &str) -> Option<&str> {
+        self.0.get(field).map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn into_result(self) -> Result<(), FormErrors> {
+        if self.is_empty() { Ok(()) } else { Err(self) }
+    }
+}
+
+/// Kwota w złotówkach z pola formularza ("12,50" albo "12.50"),
+/// najwyżej dwa miejsca po przecinku. `None`, jeśli to nie jest kwota.
+pub fn parse_pln(value: &str) -> Option<BigDecimal> {
+    let amount = BigDecimal::from_str(&value.replace(',', ".")).ok()?;
+    (amount.fractional_digit_count() <= 2).then_some(amount)
+}
+
+/// Kwota z zakresu `min..=max` złotych.
+pub fn pln_in_range(value: &str, min: i64, max: i64) -> bool {
+    parse_pln(value)
+        .is_some_and(|amount| amount >= BigDecimal::from(min) && amount <= BigDecimal::from(max))
+}
+
+pub trait Validate {
+    /// Przycina białe znaki i ujednolica wartości przed walidacją.
+    fn normalize(&mut self);
+    fn validate(&self) -> Result<(), FormErrors>;
+}
+
+/// Zamienia nazwę na slug do URL-a, np. "Całe Ciało" -> "cale-cialo".
+pub fn slugify(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.to_lowercase().chars() {
+        let c = match c {
+            'ą' => 'a',
+            'ć' => 'c',
+            'ę' => 'e',
+            'ł' => 'l',
+            'ń' => 'n',
+            'ó' => 'o',
+            'ś' => 's',
+            'ź' | 'ż' => 'z',
+            c => c,
+        };
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Nazwa trafia do adresu `/erika/{username}`, więc dopuszczamy tylko
+/// znaki bezpieczne w URL-u.
+pub fn validate_username(username: &str, errors: &mut FormErrors) {
+    let len = username.chars().count();
+    if len == 0 {
+        errors.add("username", "Nazwa użytkownika jest wymagana.");
+    } else if !(USERNAME_MIN_LEN..=USERNAME_MAX_LEN).contains(&len) {
+        errors.add(
+            "username",
+            format!(
+                "Nazwa użytkownika musi mieć od {} do {} znaków.",
+                USERNAME_MIN_LEN, USERNAME_MAX_LEN
+            ),
+        );
+    } else if !username
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+    {
+        errors.add(
+            "username",
+            "Dozwolone są tylko litery (bez polskich znaków), cyfry oraz _ - .",
+        );
+    } else if !username.starts_with(|c: char| c.is_ascii_alphanumeric()) {
+        errors.add(
+            "username",
+            "Nazwa użytkownika musi zaczynać się od litery lub cyfry.",
+        );
+    }
+}
+
+pub fn validate_email(email: &str, errors: &mut FormErrors) {
+    if email.is_empty() {
+        errors.add("email", "Email jest wymagany.");
+        return;
+    }
+    if email.len() > EMAIL_MAX_LEN {
+        errors.add("email", "Adres email jest za długi.");
+        return;
+    }
+
+    // Celowo prosta reguła: coś@domena.tld, bez spacji
+    let valid = match email.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+                && !email.chars().any(char::is_whitespace)
+        }
+        None => false,
+    };
+    if !valid {
+        errors.add("email", "Podaj poprawny adres email.");
+    }
+}
+
+pub fn validate_password(password: &str, username: &str, errors: &mut FormErrors) {
+    let len = password.chars().count();
+    if len < PASSWORD_MIN_LEN {
+        errors.add(
+            "password",
+            format!("Hasło musi mieć co najmniej {} znaków.", PASSWORD_MIN_LEN),
+        );
+    } else if len > PASSWORD_MAX_LEN {
+        errors.add(
+            "password",
+            format!("Hasło może mieć najwyżej {} znaków.", PASSWORD_MAX_LEN),
+        );
+    } else if !password.chars().any(char::is_alphabetic)
+        || !password.chars().any(|c| c.is_ascii_digit())
+    {
+        errors.add("password", "Hasło musi zawierać litery i cyfry.");
+    } else if !username.is_empty() && password.to_lowercase().contains(&username.to_lowercase()) {
+        errors.add("password", "Hasło nie może zawierać nazwy użytkownika.");
+    }
+}
+
+pub fn validate_bio(bio: &str, errors: &mut FormErrors) {
+    if bio.chars().count() > BIO_MAX_LEN {
+        errors.add(
+            "bio",
+            format!("Bio może mieć najwyżej {} znaków.", BIO_MAX_LEN),
+        );
+    }
+}
+
+/// Zamienia naruszenie ograniczenia UNIQUE z bazy na błąd konkretnego pola.
+/// Dzięki temu zajęta nazwa to komunikat w formularzu, a nie błąd 500.
+pub fn unique_violation(err: &sqlx::Error) -> Option<FormErrors> {
+    let db_err = err.as_database_error()?;
+    if !db_err.is_unique_violation() {
+        return None;
+    }
+
+    let mut errors = FormErrors::default();
+    match db_err.constraint() {
+        Some("erikas_username_key" | "erikas_username_lower_key") => {
+            errors.add("username", "Ta nazwa użytkownika jest już zajęta.")
+        }
+        Some("erikas_email_key" | "erikas_email_lower_key") => {
+            errors.add("email", "Konto z tym adresem email już istnieje.")
+        }
+        Some("categories_slug_key") => errors.add("slug", "Kategoria z tym slugiem już istnieje."),
+        Some("categories_name_key") => errors.add("name", "Kategoria o tej nazwie już istnieje."),
+        _ => return None,
+    }
+    Some(errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_pln_accepts_comma_and_dot() {
+        assert_eq!(
+            parse_pln("12,50"),
+            Some(BigDecimal::from_str("12.50").unwrap())
+        );
+        assert_eq!(
+            parse_pln("12.5"),
+            Some(BigDecimal::from_str("12.5").unwrap())
+        );
+        assert_eq!(parse_pln("7"), Some(BigDecimal::from(7)));
+    }
+
+    #[test]
+    fn parse_pln_rejects_fractions_of_a_grosz_and_garbage() {
+        assert_eq!(parse_pln("1.005"), None);
+        assert_eq!(parse_pln("1,999"), None);
+        assert_eq!(parse_pln(""), None);
+        assert_eq!(parse_pln("dziesięć"), None);
+        assert_eq!(parse_pln("1.2.3"), None);
+    }
+
+    #[test]
+    fn pln_in_range_includes_both_bounds() {
+        assert!(pln_in_range("1", TIP_MIN_PLN, TIP_MAX_PLN));
+        assert!(pln_in_range("10000,00", TIP_MIN_PLN, TIP_MAX_PLN));
+        assert!(!pln_in_range("0,99", TIP_MIN_PLN, TIP_MAX_PLN));
+        assert!(!pln_in_range("10000.01", TIP_MIN_PLN, TIP_MAX_PLN));
+        assert!(!pln_in_range("-5", TIP_MIN_PLN, TIP_MAX_PLN));
+        assert!(!pln_in_range("5.001", TIP_MIN_PLN, TIP_MAX_PLN));
+    }
+
+    fn username_error(username: &str) -> Option<String> {
+        let mut errors = FormErrors::default();
+        validate_username(username, &mut errors);
+        errors.get("username").map(str::to_string)
+    }
+
+    fn email_error(email: &str) -> Option<String> {
+        let mut errors = FormErrors::default();
+        validate_email(email, &mut errors);
+        errors.get("email").map(str::to_string)
+    }
+
+    fn password_error(password: &str, username: &str) -> Option<String> {
+        let mut errors = FormErrors::default();
+        validate_password(password, username, &mut errors);
+        errors.get("password").map(str::to_string)
+    }
+
+    #[test]
+    fn username_length_bounds_are_inclusive() {
+        assert_eq!(username_error(&"a".repeat(USERNAME_MIN_LEN)), None);
+        assert_eq!(username_error(&"a".repeat(USERNAME_MAX_LEN)), None);
+        assert!(
+            username_error(&"a".repeat(USERNAME_MIN_LEN - 1))
+                .unwrap()
+                .contains("od 3 do 30")
+        );
+        assert!(
+            username_error(&"a".repeat(USERNAME_MAX_LEN + 1))
+                .unwrap()
+                .contains("od 3 do 30")
+        );
+        assert_eq!(
+            username_error("").as_deref(),
+            Some("Nazwa użytkownika jest wymagana.")
+        );
+    }
+
+    #[test]
+    fn username_allows_only_url_safe_characters() {
+        assert_eq!(username_error("anna_k-92.x"), None);
+        // Polskie litery i spacje trafiłyby do adresu profilu
+        assert!(
+            username_error("Łucja")
+                .unwrap()
+                .contains("bez polskich znaków")
+        );
+        assert!(
+            username_error("zażółć")
+                .unwrap()
+                .contains("bez polskich znaków")
+        );
+        assert!(
+            username_error("anna k")
+                .unwrap()
+                .contains("bez polskich znaków")
+        );
+        assert!(
+            username_error("_anna")
+                .unwrap()
+                .contains("zaczynać się od litery")
+        );
+        assert!(
+            username_error(".anna")
+                .unwrap()
+                .contains("zaczynać się od litery")
+        );
+    }
+
+    #[test]
+    fn username_length_counts_characters_not_bytes() {
+        // 2 znaki, ale 4 bajty - błąd długości, a nie znaków
+        assert!(username_error("źż").unwrap().contains("od 3 do 30"));
+    }
+
+    #[test]
+    fn email_accepts_simple_addresses() {
+        assert_eq!(email_error("anna@example.com"), None);
+        assert_eq!(email_error("anna.k+test@poczta.example.pl"), None);
+        assert_eq!(email_error("żaneta@przykład.pl"), None);
+    }
+
+    #[test]
+    fn email_rejects_malformed_addresses() {
+        assert_eq!(email_error("").as_deref(), Some("Email jest wymagany."));
+        for email in [
+            "anna",
+            "@example.com",
+            "anna@example",
+            "anna@.example.com",
+            "anna@example.com.",
+            "anna@@example.com",
+            "anna k@example.com",
+        ] {
+            assert_eq!(
+                email_error(email).as_deref(),
+                Some("Podaj poprawny adres email."),
+                "{}",
+                email
+            );
+        }
+    }
+
+    #[test]
+    fn email_length_limit_is_in_bytes() {
+        let domain = "@example.com";
+        let fits = format!("{}{}", "a".repeat(EMAIL_MAX_LEN - domain.len()), domain);
+        assert_eq!(email_error(&fits), None);
+        let too_long = format!("a{}", fits);
+        assert_eq!(
+            email_error(&too_long).as_deref(),
+            Some("Adres email jest za długi.")
+        );
+        // Polskie znaki zajmują po dwa bajty
+        let polish = format!("{}{}", "ż".repeat(EMAIL_MAX_LEN / 2), domain);
+        assert_eq!(
+            email_error(&polish).as_deref(),
+            Some("Adres email jest za długi.")
+        );
+    }
+
+    #[test]
+    fn password_length_bounds_are_inclusive() {
+        let password = |len: usize| format!("1{}", "a".repeat(len - 1));
+        assert_eq!(password_error(&password(PASSWORD_MIN_LEN), "anna"), None);
+        assert_eq!(password_error(&password(PASSWORD_MAX_LEN), "anna"), None);
+        assert!(
+            password_error(&password(PASSWORD_MIN_LEN - 1), "anna")
+                .unwrap()
+                .contains("co najmniej")
+        );
+        assert!(
+            password_error(&password(PASSWORD_MAX_LEN + 1), "anna")
+                .unwrap()
+                .contains("najwyżej")
+        );
+    }
+
+    #[test]
+    fn password_length_counts_characters_not_bytes() {
+        assert_eq!(password_error("zażółćgęś1", "anna"), None);
+        // 9 znaków, choć ponad 10 bajtów
+        assert!(
+            password_error("zażółćgę1", "anna")
+                .unwrap()
+                .contains("co najmniej")
+        );
+    }
+
+    #[test]
+    fn password_needs_letters_and_digits() {
+        let message = Some("Hasło musi zawierać litery i cyfry.");
+        assert_eq!(password_error("1234567890", "anna").as_deref(), message);
+        assert_eq!(password_error("abcdefghij", "anna").as_deref(), message);
+        // Polskie litery też są literami
+        assert_eq!(password_error("ąęółżźćń12", "anna"), None);
+    }
+
+    #[test]
+    fn password_must_not_contain_username() {
+        let message = Some("Hasło nie może zawierać nazwy użytkownika.");
+        assert_eq!(password_error("xxAnna12345", "anna").as_deref(), message);
+        assert_eq!(password_error("xxanna12345", "ANNA").as_deref(), message);
+        assert_eq!(password_error("xxanna12345", ""), None);
+    }
+
+    #[test]
+    fn slugify_transliterates_polish_characters() {
+        assert_eq!(slugify("Całe Ciało"), "cale-cialo");
+        assert_eq!(slugify("Zażółć gęślą jaźń"), "zazolc-gesla-jazn");
+        assert_eq!(
+            slugify("ŻÓŁW Ćma Ślimak Źdźbło Ńa"),
+            "zolw-cma-slimak-zdzblo-na"
+        );
+    }
+
+    #[test]
+    fn slugify_collapses_and_trims_separators() {
+        assert_eq!(slugify("  --Ala & Kot--  "), "ala-kot");
+        assert_eq!(slugify("Top 10"), "top-10");
+        assert_eq!(slugify("a...b"), "a-b");
+        assert_eq!(slugify("!!!"), "");
+        assert_eq!(slugify(""), "");
+    }
+}